@@ -1,24 +1,46 @@
 // AlgoTrade OS - Tauri Commands
 // Frontend ile iletişim kuran komutlar
 
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::sync::OnceLock;
+use std::sync::{Arc, OnceLock};
 use tokio::sync::RwLock;
-use crate::models::{RiskCalculation, UserSettings};
+use crate::core::klines::MAX_CANDLES;
+use crate::core::{alarms, balance, correlation, daily_pnl, drawdown, notifier, positions, price_feed, scheduler, AppState, DrawingGeometry};
+use crate::models::{
+    Alarm, AlarmCondition, AlarmMode, AlarmRule, AssetGroup, Basket, CorrelationMatrix, DailyPnl, DrawdownStatus, DrawingAlert, DrawingTrigger, EquityPoint, IndicatorPoint, IndicatorSource, IndicatorSpec, KillSwitch, ModuleActivation, PositionPlan,
+    RiskCalculation, RiskSnapshot, SizingModel, StopMove, TakeProfitLevel, TakeProfitTarget, TimeLimit, TimeStop,
+    TradeCosts, TradeStats, TradingLockout, UserSettings, VolumeProfile, VolumeProfileSpec,
+};
+use crate::modules::batch_trading::{self, BasketOrder, LegMarket};
 use crate::modules::risk_calculator::RiskCalculator;
+use crate::modules::config::{self as module_config, ConfigField, ModuleConfig};
+use crate::modules::{ModuleEntry, ModuleInfo, ModuleRegistry};
 use crate::i18n::{Language, t, current_language};
-use crate::exchange::bybit::{BybitClient, WalletBalance, TickerInfo, InstrumentInfo, Kline, MarketCategory, AllInstruments};
+use crate::indicators;
+use crate::exchange::bybit::{MAKER_FEE_RATE, SPOT_FEE_RATE, TAKER_FEE_RATE, BybitClient, WalletBalance, TickerInfo, InstrumentInfo, Kline, MarketCategory, AllInstruments};
 use crate::db::{self, Drawing, DrawingGroup};
 
 // Global exchange client
 static EXCHANGE_CLIENT: OnceLock<RwLock<Option<BybitClient>>> = OnceLock::new();
 static CONNECTION_STATUS: OnceLock<RwLock<ConnectionState>> = OnceLock::new();
 
-// Module states
-static MODULE_STATES: OnceLock<RwLock<std::collections::HashMap<String, bool>>> = OnceLock::new();
+// Module registry
+static MODULE_REGISTRY: OnceLock<ModuleRegistry> = OnceLock::new();
 
-fn get_module_states() -> &'static RwLock<std::collections::HashMap<String, bool>> {
-    MODULE_STATES.get_or_init(|| RwLock::new(std::collections::HashMap::new()))
+// Shared application state (positions, live prices, events)
+static APP_STATE: OnceLock<Arc<AppState>> = OnceLock::new();
+
+fn get_registry() -> &'static ModuleRegistry {
+    MODULE_REGISTRY.get_or_init(|| {
+        let registry = ModuleRegistry::new();
+        crate::modules::register_builtin_modules(&registry);
+        registry
+    })
+}
+
+fn get_app_state() -> Arc<AppState> {
+    APP_STATE.get_or_init(|| Arc::new(AppState::new())).clone()
 }
 
 fn get_client_lock() -> &'static RwLock<Option<BybitClient>> {
@@ -37,24 +59,280 @@ pub struct CalculateRiskRequest {
     pub entry_price: f64,
     pub stop_price: f64,
     pub take_profit_price: Option<f64>,
+    /// "linear" / "inverse" / "spot" (verilmezse sembolden çıkarılır)
+    #[serde(default)]
+    pub category: Option<String>,
+    /// Verilirse ve bağlıysa bakım marjı kademeleri ve fonlama oranı borsadan alınır
+    #[serde(default)]
+    pub symbol: Option<String>,
+    #[serde(default)]
+    pub leverage: Option<f64>,
+    /// Giriş limit emirle yapılır (maker komisyonu)
+    #[serde(default)]
+    pub limit_entry: bool,
+    /// Pozisyonun tutulması beklenen fonlama aralığı sayısı
+    #[serde(default)]
+    pub funding_intervals: u32,
+    /// Bu emrin boyutlandırma modeli (verilmezse ayarlardaki varsayılan)
+    #[serde(default)]
+    pub sizing: Option<SizingModel>,
+}
+
+/// Boyutlandırma istatistikleri için bakılan son kapanmış işlem sayısı
+const SIZING_HISTORY_TRADES: u32 = 100;
+
+/// Modelin ihtiyaç duyduğu işlem geçmişi istatistikleri
+async fn sizing_stats(model: &SizingModel) -> TradeStats {
+    if !matches!(model, SizingModel::Kelly { .. } | SizingModel::EquityCurve { .. }) {
+        return TradeStats::default();
+    }
+    match db::get_trade_outcomes(SIZING_HISTORY_TRADES).await {
+        Ok(outcomes) => RiskCalculator::trade_stats(&outcomes),
+        Err(e) => {
+            tracing::warn!("İşlem geçmişi okunamadı: {}", e);
+            TradeStats::default()
+        }
+    }
+}
+
+/// Volatilite hedefli modelde sembolün ATR'si
+async fn sizing_atr(client: &BybitClient, model: &SizingModel, symbol: &str, category: MarketCategory) -> Option<f64> {
+    let SizingModel::VolatilityTarget { interval, period, .. } = model else {
+        return None;
+    };
+    // Wilder yumuşatması için periyodun birkaç katı mum
+    let limit = (*period as u32).saturating_mul(3).clamp(*period as u32 + 1, 1000);
+    match client.get_klines(symbol, category, interval, limit).await {
+        Ok(mut klines) => {
+            klines.sort_by_key(|k| k.timestamp);
+            indicators::atr(&klines, *period)
+        }
+        Err(e) => {
+            tracing::warn!("{} ATR için mumlar alınamadı: {}", symbol, e);
+            None
+        }
+    }
 }
 
-/// Risk hesaplama komutu
+/// Risk hesaplama komutu (tepeden düşüş kademesindeki boyut çarpanı uygulanır)
 #[tauri::command]
-pub fn calculate_risk(request: CalculateRiskRequest) -> RiskCalculation {
-    RiskCalculator::calculate_position_size(
+pub async fn calculate_risk(request: CalculateRiskRequest) -> RiskCalculation {
+    let state = get_app_state();
+    let sizing_factor = state.drawdown.sizing_factor().await;
+    let model = match request.sizing.clone() {
+        Some(model) => model,
+        None => state.settings.read().await.sizing_model(None),
+    };
+    let category = match (&request.category, &request.symbol) {
+        (Some(category), _) => parse_category(category),
+        (None, Some(symbol)) => MarketCategory::for_symbol(symbol),
+        (None, None) => MarketCategory::Linear,
+    };
+    let (entry_fee_rate, exit_fee_rate) = match category {
+        MarketCategory::Spot => (SPOT_FEE_RATE, SPOT_FEE_RATE),
+        _ if request.limit_entry => (MAKER_FEE_RATE, TAKER_FEE_RATE),
+        _ => (TAKER_FEE_RATE, TAKER_FEE_RATE),
+    };
+    let mut costs = TradeCosts {
+        leverage: request.leverage.unwrap_or(1.0),
+        entry_fee_rate,
+        exit_fee_rate,
+        funding_intervals: request.funding_intervals,
+        ..TradeCosts::default()
+    };
+
+    // Kademeler alınamazsa varsayılan bakım marjı oranıyla devam edilir
+    let mut atr = None;
+    if let Some(symbol) = &request.symbol {
+        let lock = get_client_lock().read().await;
+        if let Some(client) = lock.as_ref() {
+            atr = sizing_atr(client, &model, symbol, category).await;
+            if category != MarketCategory::Spot {
+                let (tiers, ticker) = tokio::join!(
+                    client.get_risk_limits(category, symbol),
+                    client.get_ticker(symbol, category)
+                );
+                match tiers {
+                    Ok(tiers) => costs.margin_tiers = tiers,
+                    Err(e) => tracing::warn!("{} risk limiti kademeleri alınamadı: {}", symbol, e),
+                }
+                if let Ok(ticker) = ticker {
+                    costs.funding_rate = ticker.funding_rate;
+                }
+            }
+        }
+    }
+
+    let stats = sizing_stats(&model).await;
+    let mut sizing = RiskCalculator::decide_sizing(&model, request.risk_percent, atr, &stats);
+    sizing.scale(sizing_factor, "sizing.drawdown_factor");
+    let mut calculation = RiskCalculator::calculate_position_size(
+        category,
         request.account_balance,
-        request.risk_percent,
         request.entry_price,
         request.stop_price,
         request.take_profit_price,
-    )
+        &costs,
+        &sizing,
+    );
+    for step in &mut calculation.derivation {
+        step.label = t(&step.label);
+    }
+    if calculation.liquidation_before_stop {
+        tracing::warn!(
+            "Tasfiye fiyatı ({:?}) stoptan ({}) önce: {}x kaldıraç bu stop için fazla",
+            calculation.liquidation_price, request.stop_price, calculation.leverage
+        );
+    }
+    calculation
+}
+
+/// Kademeli kâr al hesaplama isteği
+#[derive(Debug, Deserialize)]
+pub struct CalculateTakeProfitsRequest {
+    pub symbol: String,
+    pub entry_price: f64,
+    pub stop_price: f64,
+    pub quantity: f64,
+    pub levels: Vec<TakeProfitLevel>,
+}
+
+/// Kademeli kâr al komutu - fiyat/miktarlar sembolün lot adımlarına yuvarlanır
+#[tauri::command]
+pub async fn calculate_take_profits(request: CalculateTakeProfitsRequest) -> Result<Vec<TakeProfitTarget>, String> {
+    validate_take_profits(&request.levels)?;
+
+    let category = MarketCategory::for_symbol(&request.symbol);
+    let lock = get_client_lock().read().await;
+    let lot = match lock.as_ref() {
+        Some(client) => client.get_lot_size(category, &request.symbol).await,
+        // Bağlantı yoksa public API kullan
+        None => BybitClient::new(String::new(), String::new(), false)
+            .get_lot_size(category, &request.symbol)
+            .await,
+    }
+    .map_err(|e| e.to_string())?;
+
+    Ok(RiskCalculator::calculate_take_profit_ladder(
+        request.entry_price,
+        request.stop_price,
+        request.quantity,
+        &request.levels,
+        &lot,
+    ))
+}
+
+/// Kâr al kademelerini doğrula
+fn validate_take_profits(levels: &[TakeProfitLevel]) -> Result<(), String> {
+    if levels.iter().any(|l| l.r_multiple <= 0.0 || l.percent <= 0.0) {
+        return Err("Kâr al kademelerinin R katı ve yüzdesi pozitif olmalı".to_string());
+    }
+    let total: f64 = levels.iter().map(|l| l.percent).sum();
+    if total > 100.0 + 1e-9 {
+        return Err(format!("Kâr al yüzdeleri toplamı %100'ü aşıyor: %{}", total));
+    }
+    Ok(())
+}
+
+/// Risk izleyicinin son görünümü (pozisyon riski, büyüklük, marjin kullanımı)
+#[tauri::command]
+pub async fn get_risk_snapshot() -> Result<RiskSnapshot, String> {
+    let entry = get_registry().get("risk_monitor")
+        .ok_or("Risk izleyici modülü kayıtlı değil")?;
+    if entry.activation() != ModuleActivation::Active {
+        return Err("Risk izleyici modülü aktif değil".to_string());
+    }
+    get_app_state().risk_snapshot.read().await.clone()
+        .ok_or_else(|| "Risk görünümü henüz hesaplanmadı".to_string())
+}
+
+/// Semboller arası getiri korelasyonları (boşsa açık pozisyonların sembolleri)
+///
+/// Risk kapısıyla aynı aralık ve pencere kullanılır; veri önbellekteki kapanmış mumlardan gelir.
+#[tauri::command]
+pub async fn get_correlation_matrix(symbols: Vec<String>) -> CorrelationMatrix {
+    let state = get_app_state();
+    let mut symbols = if symbols.is_empty() {
+        state.positions.read().await.iter().map(|p| p.symbol.clone()).collect()
+    } else {
+        symbols
+    };
+    symbols.sort();
+    symbols.dedup();
+    let limits = state.settings.read().await.risk_limits.clone();
+    correlation::correlation_matrix(&state.klines, &symbols, &limits.correlation_interval, limits.correlation_lookback, Utc::now())
 }
 
+/// Kullanıcı ayarlarının saklandığı anahtar
+const SETTINGS_KEY: &str = "user_settings";
+
 /// Kullanıcı ayarlarını al
 #[tauri::command]
-pub fn get_settings() -> UserSettings {
-    UserSettings::default()
+pub async fn get_settings() -> UserSettings {
+    get_app_state().settings.read().await.clone()
+}
+
+/// Kullanıcı ayarlarını kaydet (risk kapısı yeni sınırları bir sonraki emirde uygular)
+#[tauri::command]
+pub async fn save_settings(settings: UserSettings) -> Result<UserSettings, String> {
+    let limits = &settings.risk_limits;
+    let percents = [
+        settings.default_risk_percent,
+        settings.max_daily_loss,
+        limits.max_trade_risk_pct,
+        limits.max_open_risk_pct,
+        limits.max_symbol_exposure_pct,
+    ];
+    if percents.iter().any(|p| *p <= 0.0) || limits.max_leverage <= 0.0 || limits.max_positions == 0 {
+        return Err("Risk sınırları pozitif olmalı".to_string());
+    }
+    let drawdown = &settings.drawdown_limits;
+    if !(drawdown.warn_pct > 0.0
+        && drawdown.warn_pct <= drawdown.reduce_size_pct
+        && drawdown.reduce_size_pct <= drawdown.kill_switch_pct
+        && drawdown.kill_switch_pct < 100.0)
+    {
+        return Err("Tepeden düşüş eşikleri artan sırada ve %100'ün altında olmalı".to_string());
+    }
+    if limits.max_correlated_risk_pct <= 0.0 || limits.max_var_pct <= 0.0 {
+        return Err("Korelasyon ve VaR sınırları pozitif olmalı".to_string());
+    }
+    if correlation::bars_per_day(&limits.correlation_interval, Utc::now()).is_none() {
+        return Err(format!("Geçersiz korelasyon aralığı: {}", limits.correlation_interval));
+    }
+    if limits.correlation_lookback < correlation::MIN_OBSERVATIONS {
+        return Err(format!("Korelasyon penceresi en az {} mum olmalı", correlation::MIN_OBSERVATIONS));
+    }
+    // Pencereye bir önceki kapanış ve açık mum eklenerek borsadan doldurulur
+    if limits.correlation_lookback + 2 > MAX_CANDLES {
+        return Err(format!("Korelasyon penceresi en fazla {} mum olabilir", MAX_CANDLES - 2));
+    }
+    if settings.notifications.max_per_minute == 0 {
+        return Err("Bildirim hız sınırı pozitif olmalı".to_string());
+    }
+    if let Some(webhook) = settings.notifications.webhooks.iter()
+        .find(|w| !(w.url.starts_with("https://") || w.url.starts_with("http://")))
+    {
+        return Err(format!("Geçersiz webhook adresi: {}", webhook.url));
+    }
+    settings.default_sizing.validate()?;
+    for model in settings.module_sizing.values() {
+        model.validate()?;
+    }
+
+    let json = serde_json::to_string(&settings).map_err(|e| e.to_string())?;
+    db::save_setting(SETTINGS_KEY, &json).await?;
+    *get_app_state().settings.write().await = settings.clone();
+    Ok(settings)
+}
+
+/// Kayıtlı kullanıcı ayarlarını uygulama durumuna yükle
+async fn load_settings() -> Result<(), String> {
+    if let Some(json) = db::get_setting(SETTINGS_KEY).await? {
+        let settings: UserSettings = serde_json::from_str(&json).map_err(|e| e.to_string())?;
+        *get_app_state().settings.write().await = settings;
+    }
+    Ok(())
 }
 
 /// Uygulama versiyonu
@@ -63,95 +341,674 @@ pub fn get_version() -> String {
     env!("CARGO_PKG_VERSION").to_string()
 }
 
-/// Modül listesi
-#[derive(Debug, Serialize)]
-pub struct ModuleInfo {
-    pub id: String,
-    pub name: String,
-    pub description: String,
-    pub is_active: bool,
-    pub can_trade: bool,
-}
-
-/// Mevcut modülleri listele
+/// Mevcut modülleri listele (kayıtlı modüllerden, kayıt sırasıyla)
 #[tauri::command]
 pub async fn list_modules() -> Vec<ModuleInfo> {
-    let states = get_module_states().read().await;
-
-    vec![
-        // === Grafik Göstergeleri ===
-        ModuleInfo {
-            id: "sma_analyzer".into(),
-            name: t("module.sma_analyzer.name"),
-            description: t("module.sma_analyzer.description"),
-            is_active: *states.get("sma_analyzer").unwrap_or(&false),
-            can_trade: false,
-        },
-        ModuleInfo {
-            id: "vwap_analyzer".into(),
-            name: t("module.vwap_analyzer.name"),
-            description: t("module.vwap_analyzer.description"),
-            is_active: *states.get("vwap_analyzer").unwrap_or(&false),
-            can_trade: false,
-        },
-        ModuleInfo {
-            id: "anchored_vwap".into(),
-            name: t("module.anchored_vwap.name"),
-            description: t("module.anchored_vwap.description"),
-            is_active: *states.get("anchored_vwap").unwrap_or(&false),
-            can_trade: false,
-        },
-        // === Risk ve İzleme ===
-        ModuleInfo {
-            id: "risk_monitor".into(),
-            name: t("module.risk_monitor.name"),
-            description: t("module.risk_monitor.description"),
-            is_active: *states.get("risk_monitor").unwrap_or(&false),
-            can_trade: false,
-        },
-        // === İşlem Araçları ===
-        ModuleInfo {
-            id: "stop_loss".into(),
-            name: t("module.stop_loss.name"),
-            description: t("module.stop_loss.description"),
-            is_active: *states.get("stop_loss").unwrap_or(&false),
-            can_trade: true,
-        },
-        ModuleInfo {
-            id: "batch_trading".into(),
-            name: t("module.batch_trading.name"),
-            description: t("module.batch_trading.description"),
-            is_active: *states.get("batch_trading").unwrap_or(&false),
-            can_trade: true,
-        },
-        // === Strateji Oluşturucu (En Altta Sabit) ===
-        ModuleInfo {
-            id: "strategy_builder".into(),
-            name: t("module.strategy_builder.name"),
-            description: t("module.strategy_builder.description"),
-            is_active: *states.get("strategy_builder").unwrap_or(&false),
-            can_trade: false,
-        },
-    ]
+    get_registry().list().await
 }
 
 /// Modül durumunu değiştir
+///
+/// Onay bekleyen bir işlem modülü, canlı pozisyonlarla mutabakat yapılmadan aktifleşmez.
 #[tauri::command]
 pub async fn toggle_module(module_id: String, active: bool) -> Result<bool, String> {
     tracing::info!("Modül {} durumu: {}", module_id, active);
 
-    let mut states = get_module_states().write().await;
-    states.insert(module_id, active);
+    let entry = get_registry().get(&module_id)
+        .ok_or_else(|| format!("Bilinmeyen modül: {}", module_id))?;
+    set_module_activation(&get_app_state(), &entry, active).await
+}
+
+/// Modülü aç/kapat, onay bekleyen işlem modülünde önce pozisyon mutabakatı yap
+///
+/// Etkinleştirme, modülün istediği izinlerin kullanıcı tarafından onayıdır ve kaydedilir.
+async fn set_module_activation(state: &AppState, entry: &ModuleEntry, active: bool) -> Result<bool, String> {
+    if active && entry.activation() == ModuleActivation::NeedsConfirmation && entry.can_trade() {
+        reconcile_positions(state).await?;
+    }
+    if active {
+        db::save_module_grants(entry.id(), entry.permissions()).await?;
+    }
+
+    let activation = if active { ModuleActivation::Active } else { ModuleActivation::Inactive };
+    db::save_module_state(entry.id(), activation).await?;
+    entry.set_activation(activation).await;
+    state.set_module_active(entry.id(), active).await;
 
     Ok(active)
 }
 
+/// Modül sistemini başlat (uygulama açılışında)
+///
+/// Kayıtlı ayarlar ve aktivasyon durumları geri yüklenir. Analiz modülleri kaldığı
+/// yerden devam eder; emir gönderebilen modüller kullanıcı onayı ve pozisyon
+/// mutabakatı bekleyen durumda döner.
+pub async fn init_modules() {
+    let registry = get_registry();
+
+    for entry in registry.entries() {
+        let config = load_module_config(entry.id(), entry.schema()).await.unwrap_or_else(|e| {
+            tracing::warn!("Modül {} ayarları yüklenemedi: {}", entry.id(), e);
+            module_config::defaults(entry.schema())
+        });
+        if let Err(e) = entry.apply_config(&serde_json::Value::Object(config)).await {
+            tracing::warn!("Modül {} ayarları uygulanamadı: {}", entry.id(), e);
+        }
+    }
+
+    if let Err(e) = load_settings().await {
+        tracing::warn!("Kullanıcı ayarları yüklenemedi: {}", e);
+    }
+    if let Err(e) = get_app_state().daily_pnl.load().await {
+        tracing::warn!("Günlük kayıp kilidi yüklenemedi: {}", e);
+    }
+    if let Err(e) = get_app_state().drawdown.load().await {
+        tracing::warn!("Tepeden düşüş durumu yüklenemedi: {}", e);
+    }
+    if let Err(e) = alarms::load(&get_app_state()).await {
+        tracing::warn!("Alarmlar yüklenemedi: {}", e);
+    }
+    if let Err(e) = load_position_plans(&get_app_state()).await {
+        tracing::warn!("Pozisyon planları yüklenemedi: {}", e);
+    }
+    match db::get_baskets(true).await {
+        Ok(baskets) => get_app_state()
+            .baskets
+            .write()
+            .await
+            .extend(baskets.into_iter().map(|b| (b.id.clone(), b))),
+        Err(e) => tracing::warn!("Sepetler yüklenemedi: {}", e),
+    }
+
+    let saved = db::get_module_states().await.unwrap_or_else(|e| {
+        tracing::warn!("Modül durumları yüklenemedi: {}", e);
+        Vec::new()
+    });
+    registry.restore_activations(saved).await;
+
+    registry.start(get_app_state());
+    scheduler::spawn_clock(get_app_state());
+    scheduler::spawn_time_sync(get_app_state());
+    drawdown::spawn_equity_watch(get_app_state());
+    positions::spawn_position_sync(get_app_state());
+    balance::spawn_balance_poll(get_app_state());
+    alarms::spawn_alarm_engine(get_app_state());
+    price_feed::spawn_price_feed(get_app_state(), || async { get_status_lock().read().await.is_demo });
+    notifier::spawn_notifier(get_app_state());
+    tracing::info!("{} modül başlatıldı", registry.entries().len());
+}
+
+/// Arayüz olayları ve masaüstü bildirimleri için uygulama tutamacını kaydet
+pub fn set_app_handle(app: tauri::AppHandle) {
+    get_app_state().notifier.set_app_handle(app);
+}
+
+/// Canlı borsa pozisyonlarını uygulama durumuna yükle
+async fn reconcile_positions(state: &AppState) -> Result<usize, String> {
+    let executor = state.executor().await
+        .ok_or("Bağlantı yok. İşlem modülü sürdürülmeden önce pozisyonlar doğrulanmalı.")?;
+    let positions = executor.open_positions().await
+        .map_err(|e| format!("Pozisyonlar alınamadı: {}", e))?;
+
+    let count = positions.len();
+    *state.positions.write().await = positions;
+
+    tracing::info!("Pozisyon mutabakatı tamamlandı: {} açık pozisyon", count);
+    Ok(count)
+}
+
 /// Sağlık kontrolü
 #[tauri::command]
 pub fn health_check() -> bool {
     true
 }
 
+// ==================== Module Config Commands ====================
+
+/// Modül ayarları ve şeması
+#[derive(Debug, Serialize)]
+pub struct ModuleConfigInfo {
+    pub module_id: String,
+    pub schema: Vec<ConfigField>,
+    pub values: ModuleConfig,
+}
+
+/// Veritabanındaki ayarları şemaya göre doğrula (yoksa varsayılanlar)
+async fn load_module_config(module_id: &str, schema: &[ConfigField]) -> Result<ModuleConfig, String> {
+    match db::get_module_config(module_id).await? {
+        Some(raw) => {
+            let value: serde_json::Value = serde_json::from_str(&raw)
+                .map_err(|e| format!("Kayıtlı ayarlar okunamadı: {}", e))?;
+            module_config::validate(schema, &value).or_else(|e| {
+                // Şema değiştiyse eski kayıt yerine varsayılanlar kullanılır
+                tracing::warn!("Modül {} kayıtlı ayarları geçersiz: {}", module_id, e);
+                Ok(module_config::defaults(schema))
+            })
+        }
+        None => Ok(module_config::defaults(schema)),
+    }
+}
+
+/// Modül ayarlarını ve şemasını al
+#[tauri::command]
+pub async fn get_module_config(module_id: String) -> Result<ModuleConfigInfo, String> {
+    let entry = get_registry().get(&module_id)
+        .ok_or_else(|| format!("Bilinmeyen modül: {}", module_id))?;
+
+    Ok(ModuleConfigInfo {
+        schema: entry.schema().iter().map(ConfigField::localized).collect(),
+        values: entry.get_config().await,
+        module_id,
+    })
+}
+
+/// Modül ayarlarını doğrula, çalışan modüle uygula ve kaydet
+#[tauri::command]
+pub async fn set_module_config(module_id: String, config: serde_json::Value) -> Result<ModuleConfigInfo, String> {
+    let entry = get_registry().get(&module_id)
+        .ok_or_else(|| format!("Bilinmeyen modül: {}", module_id))?;
+
+    let validated = entry.apply_config(&config).await.map_err(|e| e.to_string())?;
+
+    let raw = serde_json::to_string(&validated).map_err(|e| e.to_string())?;
+    db::save_module_config(&module_id, &raw).await?;
+    tracing::info!("Modül {} ayarları kaydedildi", module_id);
+
+    Ok(ModuleConfigInfo {
+        schema: entry.schema().iter().map(ConfigField::localized).collect(),
+        values: entry.get_config().await,
+        module_id,
+    })
+}
+
+// ==================== Position Plan Commands ====================
+
+/// Kayıtlı pozisyon planlarını uygulama durumuna yükle
+async fn load_position_plans(state: &AppState) -> Result<(), String> {
+    let mut plans = state.position_plans.write().await;
+    for (position_id, raw) in db::get_position_plans().await? {
+        match serde_json::from_str::<PositionPlan>(&raw) {
+            Ok(plan) => {
+                plans.insert(position_id, plan);
+            }
+            Err(e) => tracing::warn!("Pozisyon {} planı okunamadı: {}", position_id, e),
+        }
+    }
+    Ok(())
+}
+
+/// Pozisyonun planını al (tanımlı değilse boş plan)
+#[tauri::command]
+pub async fn get_position_plan(position_id: String) -> PositionPlan {
+    get_app_state().position_plan(&position_id).await
+}
+
+/// Pozisyonun planını kaydet (stop-loss modülü bir sonraki tick'te uygular)
+#[tauri::command]
+pub async fn set_position_plan(position_id: String, plan: PositionPlan) -> Result<PositionPlan, String> {
+    validate_position_plan(&plan)?;
+
+    let raw = serde_json::to_string(&plan).map_err(|e| e.to_string())?;
+    db::save_position_plan(&position_id, &raw).await?;
+
+    get_app_state()
+        .position_plans
+        .write()
+        .await
+        .insert(position_id.clone(), plan.clone());
+
+    tracing::info!("Pozisyon {} planı kaydedildi", position_id);
+    Ok(plan)
+}
+
+/// Plan kurallarını doğrula
+fn validate_position_plan(plan: &PositionPlan) -> Result<(), String> {
+    validate_take_profits(&plan.take_profits)?;
+
+    if let Some(TimeStop { limit: TimeLimit::Candles { interval, .. }, .. }) = &plan.time_stop {
+        if scheduler::candle_open(interval, Utc::now()).is_none() {
+            return Err(format!("Geçersiz mum aralığı: {}", interval));
+        }
+    }
+    if let Some(rule) = &plan.volatility_stop {
+        if scheduler::candle_open(&rule.interval, Utc::now()).is_none() {
+            return Err(format!("Geçersiz mum aralığı: {}", rule.interval));
+        }
+        if rule.period == 0 || rule.multiplier <= 0.0 {
+            return Err("Volatilite stopunun periyodu ve katsayısı pozitif olmalı".to_string());
+        }
+    }
+    Ok(())
+}
+
+/// Pozisyonun stop hareket geçmişi
+#[tauri::command]
+pub async fn get_stop_moves(position_id: String) -> Result<Vec<StopMove>, String> {
+    db::get_stop_moves(&position_id).await
+}
+
+// ==================== Batch Trading Commands ====================
+
+/// Varlık gruplarını listele
+#[tauri::command]
+pub async fn get_asset_groups() -> Result<Vec<AssetGroup>, String> {
+    db::get_asset_groups().await
+}
+
+/// Varlık grubunu kaydet (yeni veya güncelleme)
+#[tauri::command]
+pub async fn save_asset_group(group: AssetGroup) -> Result<AssetGroup, String> {
+    if group.name.trim().is_empty() {
+        return Err("Grup adı boş olamaz".to_string());
+    }
+    if group.symbols.is_empty() {
+        return Err("Grupta en az bir parite olmalı".to_string());
+    }
+    let mut seen = std::collections::HashSet::new();
+    let symbols: Vec<String> = group.symbols
+        .iter()
+        .map(|s| s.trim().to_uppercase())
+        .filter(|s| seen.insert(s.clone()))
+        .collect();
+
+    db::save_asset_group(AssetGroup { symbols, ..group }).await
+}
+
+/// Varlık grubunu sil
+#[tauri::command]
+pub async fn delete_asset_group(id: String) -> Result<bool, String> {
+    db::delete_asset_group(&id).await
+}
+
+/// Toplu işlem - grubun risk bütçesini dağıt, tüm emirleri aynı anda gönder
+#[tauri::command]
+pub async fn execute_basket(mut order: BasketOrder) -> Result<Basket, String> {
+    let entry = get_registry().get("batch_trading")
+        .ok_or("Toplu işlem modülü kayıtlı değil")?;
+    if entry.activation() != ModuleActivation::Active {
+        return Err("Toplu işlem modülü aktif değil".to_string());
+    }
+
+    let group = db::get_asset_groups().await?
+        .into_iter()
+        .find(|g| g.id == order.group_id)
+        .ok_or_else(|| format!("Varlık grubu bulunamadı: {}", order.group_id))?;
+
+    let state = get_app_state();
+    let executor = state.executor().await.ok_or("Bağlantı yok")?;
+    let lock = get_client_lock().read().await;
+    let client = lock.as_ref().ok_or("Bağlantı yok")?;
+
+    let settings = state.settings.read().await.clone();
+    let model = match order.sizing.clone() {
+        Some(model) => model,
+        None => settings.sizing_model(Some("batch_trading")),
+    };
+    model.validate()?;
+
+    let balance = client.get_wallet_balance().await.map_err(|e| e.to_string())?;
+    state.drawdown.record(balance.total_equity, &settings.drawdown_limits, Utc::now()).await;
+
+    // Kelly / varlık eğrisi risk bütçesini hesap varlığına göre yeniden belirler
+    if matches!(model, SizingModel::Kelly { .. } | SizingModel::EquityCurve { .. }) && balance.total_equity > 0.0 {
+        let base_percent = order.risk_amount / balance.total_equity * 100.0;
+        let decision = RiskCalculator::decide_sizing(&model, base_percent, None, &sizing_stats(&model).await);
+        order.risk_amount = balance.total_equity * decision.risk_percent / 100.0;
+    }
+    // Tepeden düşüş kademesi pozisyon boyutlarını küçültebilir
+    let scale = state.drawdown.sizing_factor().await;
+    order.risk_amount *= scale;
+
+    // Doğrulama: risk bütçesi kullanılabilir bakiyeyi aşamaz
+    if order.risk_amount > balance.available_balance {
+        return Err(format!(
+            "Risk bütçesi kullanılabilir bakiyeyi aşıyor: {} > {}",
+            order.risk_amount, balance.available_balance
+        ));
+    }
+
+    // Tüm paritelerin fiyat, hacim ve lot bilgisi aynı anda
+    let model = &model;
+    let markets = futures::future::join_all(group.symbols.iter().map(|symbol| async move {
+        let category = MarketCategory::for_symbol(symbol);
+        let (ticker, lot, atr) = tokio::join!(
+            client.get_ticker(symbol, category),
+            client.get_lot_size(category, symbol),
+            sizing_atr(client, model, symbol, category)
+        );
+        let ticker = ticker.map_err(|e| format!("{}: {}", symbol, e))?;
+        Ok::<_, String>(LegMarket {
+            symbol: symbol.clone(),
+            price: ticker.last_price,
+            turnover_24h: ticker.turnover_24h,
+            high_24h: ticker.high_price_24h,
+            low_24h: ticker.low_price_24h,
+            lot: lot.map_err(|e| format!("{}: {}", symbol, e))?,
+            atr,
+        })
+    }))
+    .await
+    .into_iter()
+    .collect::<Result<Vec<_>, String>>()?;
+
+    let legs = batch_trading::plan_legs(&group, &order, &markets, model, scale)?;
+    let basket = batch_trading::execute_basket(executor.as_ref(), &group, order.side, legs, state.scheduler.now())
+        .await
+        .map_err(|e| e.to_string())?;
+
+    // Emirler gönderildi - kayıt başarısız olsa da sepet izlenmeye devam eder
+    if let Err(e) = db::save_basket(&basket).await {
+        tracing::warn!("Sepet kaydedilemedi: {}", e);
+    }
+    state.baskets.write().await.insert(basket.id.clone(), basket.clone());
+
+    tracing::info!("Sepet gönderildi: {} ({} bacak)", basket.name, basket.legs.len());
+    Ok(basket)
+}
+
+/// Sepetleri listele (en yeni önce)
+#[tauri::command]
+pub async fn get_baskets(active_only: Option<bool>) -> Result<Vec<Basket>, String> {
+    db::get_baskets(active_only.unwrap_or(false)).await
+}
+
+// ==================== Daily PnL Commands ====================
+
+/// Günün kâr/zararı ve kayıp kilidi durumu
+#[derive(Debug, Serialize)]
+pub struct DailyPnlStatus {
+    pub pnl: DailyPnl,
+    /// Açık pozisyonların gerçekleşmemiş kâr/zararı (bağlantı yoksa None)
+    pub unrealized_pnl: Option<f64>,
+    pub lockout: Option<TradingLockout>,
+    /// Kilidin en erken kaldırılabileceği an
+    pub override_available_at: Option<DateTime<Utc>>,
+}
+
+/// Günün kâr/zararı - bağlıysa borsadan eşlenir ve kayıp limiti uygulanır
+#[tauri::command]
+pub async fn get_daily_pnl() -> Result<DailyPnlStatus, String> {
+    let state = get_app_state();
+    let settings = state.settings.read().await.clone();
+    let timezone = settings.trading_day_timezone;
+    let now = Utc::now();
+
+    let (pnl, unrealized_pnl) = match state.executor().await {
+        Some(executor) => {
+            let (pnl, balance) = tokio::join!(
+                state.daily_pnl.sync(executor.as_ref(), timezone, now),
+                executor.account_balance()
+            );
+            let (pnl, balance) = (pnl?, balance.map_err(|e| e.to_string())?);
+            state.daily_pnl
+                .enforce(pnl.net_pnl + balance.unrealized_pnl, balance.equity, settings.max_daily_loss, timezone, now)
+                .await;
+            (pnl, Some(balance.unrealized_pnl))
+        }
+        None => (daily_pnl::daily_pnl(daily_pnl::trading_day(now, timezone), timezone).await?, None),
+    };
+
+    let lockout = state.daily_pnl.lockout(now).await;
+    let override_available_at = lockout.as_ref()
+        .filter(|l| daily_pnl::is_blocking(l, now))
+        .map(|l| daily_pnl::override_available_at(l, settings.lockout_override_cooldown_minutes));
+    Ok(DailyPnlStatus { pnl, unrealized_pnl, lockout, override_available_at })
+}
+
+/// Son işlem günlerinin kâr/zararı (en yeni önce)
+#[tauri::command]
+pub async fn get_daily_pnl_history(days: u32) -> Result<Vec<DailyPnl>, String> {
+    let timezone = get_app_state().settings.read().await.trading_day_timezone;
+    let today = daily_pnl::trading_day(Utc::now(), timezone);
+
+    let mut history = Vec::new();
+    for back in 0..i64::from(days.clamp(1, 365)) {
+        history.push(daily_pnl::daily_pnl(today - chrono::Duration::days(back), timezone).await?);
+    }
+    Ok(history)
+}
+
+/// Günlük kayıp kilidini kaldır (kilitlendikten sonra bekleme süresi dolmalı)
+#[tauri::command]
+pub async fn override_trading_lockout() -> Result<TradingLockout, String> {
+    let state = get_app_state();
+    let cooldown = state.settings.read().await.lockout_override_cooldown_minutes;
+    state.daily_pnl.override_lockout(cooldown, Utc::now()).await
+}
+
+// ==================== Drawdown Commands ====================
+
+/// Tepeden düşüş durumu ve acil durdurma
+#[tauri::command]
+pub async fn get_drawdown_status() -> DrawdownStatus {
+    get_app_state().drawdown.status().await
+}
+
+/// Son günlerin varlık eğrisi (eskiden yeniye)
+#[tauri::command]
+pub async fn get_equity_curve(days: u32) -> Result<Vec<EquityPoint>, String> {
+    let since = Utc::now() - chrono::Duration::days(i64::from(days.clamp(1, 365)));
+    db::get_equity_curve(since).await
+}
+
+/// Kapanan işlemlerin boyutlandırma istatistikleri (Kelly ve varlık eğrisi modelleri bunları kullanır)
+#[tauri::command]
+pub async fn get_trade_stats() -> Result<TradeStats, String> {
+    let outcomes = db::get_trade_outcomes(SIZING_HISTORY_TRADES).await?;
+    Ok(RiskCalculator::trade_stats(&outcomes))
+}
+
+/// Acil durdurmayı elle devreye al - yeni pozisyon açan tüm emirler reddedilir
+#[tauri::command]
+pub async fn engage_kill_switch() -> KillSwitch {
+    get_app_state().drawdown.engage_kill_switch(Utc::now()).await
+}
+
+/// Acil durdurmayı kaldır (tepe güncel varlıktan yeniden başlar)
+#[tauri::command]
+pub async fn reset_kill_switch() -> Result<DrawdownStatus, String> {
+    get_app_state().drawdown.reset_kill_switch(Utc::now()).await
+}
+
+// ==================== Alarm Commands ====================
+
+/// Alarmları listele
+#[tauri::command]
+pub async fn get_alarms() -> Vec<Alarm> {
+    get_app_state().alarms.read().await.clone()
+}
+
+/// Alarmı kaydet (yeni veya güncelleme); motor bir sonraki tick'te veya kural aralığının
+/// mum kapanışında değerlendirir
+#[tauri::command]
+pub async fn save_alarm(mut alarm: Alarm) -> Result<Alarm, String> {
+    alarm.symbol = alarm.symbol.trim().to_uppercase();
+    if alarm.symbol.is_empty() {
+        return Err("Alarm sembolü boş olamaz".to_string());
+    }
+    match alarm.condition {
+        AlarmCondition::PriceAbove | AlarmCondition::PriceBelow if alarm.target_price <= 0.0 => {
+            return Err("Alarm fiyatı pozitif olmalı".to_string());
+        }
+        AlarmCondition::Rule(ref rule) => validate_alarm_rule(rule)?,
+        AlarmCondition::Drawing(ref alert) => validate_drawing_alert(&alarm.symbol, alert).await?,
+        _ => {}
+    }
+    if alarm.mode == (AlarmMode::Cooldown { seconds: 0 }) {
+        return Err("Bekleme süresi pozitif olmalı".to_string());
+    }
+    if alarm.id.is_empty() {
+        alarm.id = format!("alarm-{}-{:08x}", Utc::now().timestamp_millis(), rand::random::<u32>());
+        alarm.created_at = Utc::now();
+    }
+
+    db::save_alarm(&alarm).await?;
+    let state = get_app_state();
+    let mut alarms = state.alarms.write().await;
+    match alarms.iter_mut().find(|a| a.id == alarm.id) {
+        Some(existing) => *existing = alarm.clone(),
+        None => alarms.push(alarm.clone()),
+    }
+    alarms::schedule_rules(&state, &alarms);
+    drop(alarms);
+    alarms::refresh_drawings(&state).await;
+    Ok(alarm)
+}
+
+/// Çizim alarmı doğrulaması (çizim alarmın sembolünde olmalı ve tetikleyiciyi desteklemeli)
+async fn validate_drawing_alert(symbol: &str, alert: &DrawingAlert) -> Result<(), String> {
+    let drawing = db::get_drawing(&alert.drawing_id).await?
+        .ok_or_else(|| format!("Çizim bulunamadı: {}", alert.drawing_id))?;
+    if drawing.symbol.to_uppercase() != symbol {
+        return Err(format!("Çizim {} sembolüne ait", drawing.symbol));
+    }
+    if let DrawingTrigger::FibLevel { level } = alert.trigger {
+        if !level.is_finite() {
+            return Err("Geçersiz fib seviyesi".to_string());
+        }
+    }
+    if !DrawingGeometry::from_drawing(&drawing)?.supports(&alert.trigger) {
+        return Err(format!("{} çizimi bu tetikleyiciyi desteklemiyor", drawing.drawing_type));
+    }
+    Ok(())
+}
+
+/// Kural alarmı doğrulaması
+fn validate_alarm_rule(rule: &AlarmRule) -> Result<(), String> {
+    if scheduler::candle_open(&rule.interval, Utc::now()).is_none() {
+        return Err(format!("Geçersiz kural aralığı: {}", rule.interval));
+    }
+    if rule.conditions.is_empty() {
+        return Err("Kural en az bir koşul içermeli".to_string());
+    }
+    if rule.confirmations == 0 {
+        return Err("Onay sayısı en az 1 olmalı".to_string());
+    }
+    let invalid_period = rule.conditions.iter()
+        .flat_map(|c| [&c.left, &c.right])
+        .any(|s| matches!(s, IndicatorSource::Sma { period } | IndicatorSource::Rsi { period } if *period == 0));
+    if invalid_period {
+        return Err("İndikatör periyodu pozitif olmalı".to_string());
+    }
+    Ok(())
+}
+
+/// Alarmı aç/kapat
+#[tauri::command]
+pub async fn set_alarm_active(id: String, active: bool) -> Result<Alarm, String> {
+    let state = get_app_state();
+    let alarm = {
+        let mut alarms = state.alarms.write().await;
+        let alarm = alarms.iter_mut().find(|a| a.id == id)
+            .ok_or_else(|| format!("Alarm bulunamadı: {}", id))?;
+        alarm.is_active = active;
+        let alarm = alarm.clone();
+        alarms::schedule_rules(&state, &alarms);
+        alarm
+    };
+    db::save_alarm(&alarm).await?;
+    Ok(alarm)
+}
+
+/// Alarmı sil
+#[tauri::command]
+pub async fn delete_alarm(id: String) -> Result<bool, String> {
+    let state = get_app_state();
+    {
+        let mut alarms = state.alarms.write().await;
+        alarms.retain(|a| a.id != id);
+        alarms::schedule_rules(&state, &alarms);
+    }
+    alarms::refresh_drawings(&state).await;
+    db::delete_alarm(&id).await
+}
+
+// ==================== Indicator Commands ====================
+
+/// Önbellek yetersizken gösterge için çekilen mum sayısı
+const INDICATOR_KLINES: u32 = 1000;
+
+/// Önbellekteki mumlar
+///
+/// En az `required` mum yoksa veya seri `since` (ms) anına kadar uzanmıyorsa borsadan çekilip önbelleğe
+/// eklenir. Borsanın verdiği en fazla mum da `since`'e ulaşamıyorsa hata döner; yarım aralıktan
+/// hesaplanmış değer döndürülmez.
+async fn cached_klines(symbol: &str, interval: &str, required: usize, since: Option<i64>) -> Result<Vec<Kline>, String> {
+    let covered = |klines: &[Kline]| {
+        klines.len() >= required && since.is_none_or(|since| klines.first().is_some_and(|k| k.timestamp <= since))
+    };
+    let state = get_app_state();
+    let klines = state.klines.get(symbol, interval);
+    if covered(&klines) {
+        return Ok(klines);
+    }
+    let client = BybitClient::new(String::new(), String::new(), false);
+    let fetched = client.get_klines(symbol, MarketCategory::for_symbol(symbol), interval, INDICATOR_KLINES)
+        .await
+        .map_err(|e| e.to_string())?;
+    state.klines.merge(symbol, interval, &fetched);
+    let klines = state.klines.get(symbol, interval);
+    // Borsa istenenden az mum verdiyse daha eski veri yoktur (yeni listelenmiş sembol)
+    if covered(&klines) || fetched.len() < INDICATOR_KLINES as usize {
+        Ok(klines)
+    } else {
+        Err(format!("Aralığın başı son {} mumun dışında, daha büyük bir mum aralığı seçin", INDICATOR_KLINES))
+    }
+}
+
+/// Göstergeyi mum önbelleği üzerinden hesapla (modüller ve alarmlarla aynı değerler; fiyat grafiği
+/// kendi katmanlarını ön yüzde hesaplar)
+#[tauri::command]
+pub async fn compute_indicator(symbol: String, interval: String, indicator: IndicatorSpec) -> Result<Vec<IndicatorPoint>, String> {
+    validate_indicator(&indicator)?;
+    let since = indicators::coverage_start(&indicator, Utc::now());
+    let klines = cached_klines(&symbol, &interval, indicators::lookback(&indicator), since).await?;
+    Ok(indicators::compute(&indicator, &klines))
+}
+
+/// Mum önbelleğinden hacim profili (POC, VAH, VAL)
+#[tauri::command]
+pub async fn get_volume_profile(symbol: String, interval: String, spec: VolumeProfileSpec) -> Result<VolumeProfile, String> {
+    if !(spec.value_area_pct > 0.0 && spec.value_area_pct <= 100.0) {
+        return Err("Değer alanı yüzdesi 0-100 arasında olmalı".to_string());
+    }
+    if spec.bucket_size.is_some_and(|size| !(size.is_finite() && size > 0.0)) {
+        return Err("Kova boyutu pozitif olmalı".to_string());
+    }
+    let now = Utc::now();
+    let (start, _) = indicators::range_bounds(&spec.range, now);
+    let klines = cached_klines(&symbol, &interval, 1, Some(start)).await?;
+    indicators::volume_profile(&klines, &spec, now)
+}
+
+/// Gösterge parametrelerinin doğrulaması
+fn validate_indicator(indicator: &IndicatorSpec) -> Result<(), String> {
+    let (periods, multiplier) = match indicator {
+        IndicatorSpec::Vwap { band_multiplier, .. } => (vec![], *band_multiplier),
+        IndicatorSpec::Sma { period }
+        | IndicatorSpec::Ema { period }
+        | IndicatorSpec::Rsi { period }
+        | IndicatorSpec::Atr { period } => (vec![*period], 0.0),
+        IndicatorSpec::Bollinger { period, multiplier } => (vec![*period], *multiplier),
+        IndicatorSpec::Macd { fast, slow, signal } => {
+            if fast >= slow {
+                return Err("MACD hızlı periyodu yavaş periyottan küçük olmalı".to_string());
+            }
+            (vec![*fast, *slow, *signal], 0.0)
+        }
+    };
+    if periods.contains(&0) {
+        return Err("Gösterge periyodu pozitif olmalı".to_string());
+    }
+    if !multiplier.is_finite() || multiplier < 0.0 {
+        return Err("Bant çarpanı negatif olamaz".to_string());
+    }
+    if indicators::lookback(indicator) > INDICATOR_KLINES as usize {
+        return Err(format!("Gösterge en fazla {} mum geriye bakabilir", INDICATOR_KLINES));
+    }
+    Ok(())
+}
+
 // ==================== i18n Commands ====================
 
 /// Dil bilgisi
@@ -232,6 +1089,15 @@ pub async fn connect_exchange(credentials: ApiCredentials) -> Result<ConnectionS
     // Bağlantıyı test et
     match client.test_connection().await {
         Ok(true) => {
+            // Zamanlayıcıyı borsa saatine senkronize et
+            match client.get_server_time().await {
+                Ok(server_time) => get_app_state().scheduler.sync_exchange_time(server_time),
+                Err(e) => tracing::warn!("Borsa saati alınamadı: {}", e),
+            }
+
+            // Modüllerin emir göndermesi için yürütücüyü kaydet
+            get_app_state().set_executor(Some(Arc::new(client.clone()))).await;
+
             // Client'ı kaydet
             {
                 let mut lock = get_client_lock().write().await;
@@ -270,6 +1136,7 @@ pub async fn disconnect_exchange() -> Result<ConnectionState, String> {
         let mut lock = get_client_lock().write().await;
         *lock = None;
     }
+    get_app_state().set_executor(None).await;
 
     let state = ConnectionState {
         is_connected: false,
@@ -299,8 +1166,12 @@ pub async fn get_wallet_balance() -> Result<WalletBalance, String> {
     let lock = get_client_lock().read().await;
     let client = lock.as_ref().ok_or("Bağlantı yok. Önce API ile bağlanın.")?;
 
-    client.get_wallet_balance().await
-        .map_err(|e| e.to_string())
+    let balance = client.get_wallet_balance().await
+        .map_err(|e| e.to_string())?;
+    let state = get_app_state();
+    let limits = state.settings.read().await.drawdown_limits.clone();
+    state.drawdown.record(balance.total_equity, &limits, Utc::now()).await;
+    Ok(balance)
 }
 
 /// Ticker bilgisini al - kategori destekli
@@ -352,7 +1223,11 @@ pub async fn get_all_instruments() -> Result<AllInstruments, String> {
 pub async fn get_klines(symbol: String, category: Option<String>, interval: String, limit: u32) -> Result<Vec<Kline>, String> {
     let cat = parse_category(&category.unwrap_or_else(|| "linear".to_string()));
     let client = BybitClient::new(String::new(), String::new(), false);
-    client.get_klines(&symbol, cat, &interval, limit).await.map_err(|e| e.to_string())
+    let klines = client.get_klines(&symbol, cat, &interval, limit).await.map_err(|e| e.to_string())?;
+
+    // Modüller (iz süren stop vb.) için önbelleğe al
+    get_app_state().klines.merge(&symbol, &interval, &klines);
+    Ok(klines)
 }
 
 /// Tüm tarihsel kline verilerini al (başlangıçtan bugüne)
@@ -366,9 +1241,12 @@ pub async fn get_all_klines(
 ) -> Result<Vec<Kline>, String> {
     let cat = parse_category(&category.unwrap_or_else(|| "linear".to_string()));
     let client = BybitClient::new(String::new(), String::new(), false);
-    client.get_all_klines(&symbol, cat, &interval, start_time, end_time)
+    let klines = client.get_all_klines(&symbol, cat, &interval, start_time, end_time)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?;
+
+    get_app_state().klines.merge(&symbol, &interval, &klines);
+    Ok(klines)
 }
 
 /// Kategori string'ini parse et
@@ -439,7 +1317,9 @@ pub async fn save_drawing(request: SaveDrawingRequest) -> Result<Drawing, String
         group_id: request.group_id,
     };
 
-    db::save_drawing(drawing).await
+    let drawing = db::save_drawing(drawing).await?;
+    alarms::refresh_drawings(&get_app_state()).await;
+    Ok(drawing)
 }
 
 /// Get all drawings for a symbol and interval
@@ -451,13 +1331,17 @@ pub async fn get_drawings(symbol: String, interval: String) -> Result<Vec<Drawin
 /// Delete a drawing by ID
 #[tauri::command]
 pub async fn delete_drawing(id: String) -> Result<bool, String> {
-    db::delete_drawing(&id).await
+    let deleted = db::delete_drawing(&id).await?;
+    alarms::refresh_drawings(&get_app_state()).await;
+    Ok(deleted)
 }
 
 /// Clear all drawings for a symbol and interval
 #[tauri::command]
 pub async fn clear_drawings(symbol: String, interval: String) -> Result<u64, String> {
-    db::clear_drawings(&symbol, &interval).await
+    let cleared = db::clear_drawings(&symbol, &interval).await?;
+    alarms::refresh_drawings(&get_app_state()).await;
+    Ok(cleared)
 }
 
 /// Get all drawings for a symbol (all intervals)
@@ -547,3 +1431,66 @@ pub async fn toggle_group_visibility(group_id: String, visible: bool) -> Result<
 pub async fn move_drawing_to_group(drawing_id: String, group_id: Option<String>) -> Result<(), String> {
     db::move_drawing_to_group(&drawing_id, group_id.as_deref()).await
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exchange::mock::MockExecutor;
+    use crate::models::{Position, PositionSide};
+    use crate::modules::{risk_monitor::RiskMonitorModule, stop_loss::StopLossModule};
+
+    #[tokio::test]
+    async fn test_restored_trading_module_reconciles_before_activation() {
+        db::init_test_database();
+        let state = AppState::new();
+        let registry = ModuleRegistry::new();
+        registry.register(StopLossModule::new());
+        registry.register(RiskMonitorModule::new());
+
+        registry
+            .restore_activations(vec![
+                ("stop_loss".into(), ModuleActivation::Active),
+                ("risk_monitor".into(), ModuleActivation::Active),
+                ("removed_module".into(), ModuleActivation::Active),
+            ])
+            .await;
+        let stop_loss = registry.get("stop_loss").unwrap();
+        assert_eq!(stop_loss.activation(), ModuleActivation::NeedsConfirmation);
+        assert_eq!(registry.get("risk_monitor").unwrap().activation(), ModuleActivation::Active);
+
+        // Bağlantı yokken pozisyonlar doğrulanamaz, modül onay beklemeye devam eder
+        assert!(set_module_activation(&state, &stop_loss, true).await.is_err());
+        assert_eq!(stop_loss.activation(), ModuleActivation::NeedsConfirmation);
+
+        let executor = Arc::new(MockExecutor::default());
+        executor.positions.lock().unwrap().push(Position {
+            id: "BTCUSDT-Buy".into(),
+            symbol: "BTCUSDT".into(),
+            side: PositionSide::Long,
+            entry_price: 100.0,
+            quantity: 1.0,
+            stop_loss: Some(95.0),
+            take_profit: None,
+            created_at: Utc::now(),
+        });
+        state.positions.write().await.push(Position {
+            id: "stale".into(),
+            symbol: "ETHUSDT".into(),
+            side: PositionSide::Short,
+            entry_price: 10.0,
+            quantity: 1.0,
+            stop_loss: None,
+            take_profit: None,
+            created_at: Utc::now(),
+        });
+        state.set_executor(Some(executor)).await;
+
+        assert_eq!(set_module_activation(&state, &stop_loss, true).await, Ok(true));
+        assert_eq!(stop_loss.activation(), ModuleActivation::Active);
+        assert!(state.is_module_active("stop_loss").await);
+        let ids: Vec<String> = state.positions.read().await.iter().map(|p| p.id.clone()).collect();
+        assert_eq!(ids, vec!["BTCUSDT-Buy".to_string()]);
+        let saved = db::get_module_states().await.unwrap();
+        assert!(saved.contains(&("stop_loss".to_string(), ModuleActivation::Active)));
+    }
+}
@@ -0,0 +1,650 @@
+// AlgoTrade OS - Alarm Engine
+// Aktif alarmları her fiyat tick'inde değerlendirir, tetiklenenleri kaydedip AlarmTriggered yayınlar
+//
+// Alarmlar seviye karşılaştırmasıyla değil kesişle tetiklenir: fiyat zaten seviyenin üstündeyken
+// kurulan PriceAbove alarmı, fiyat önce altına inip tekrar yukarı kesene kadar beklemede kalır.
+// Sembolün ilk tick'i sadece önceki fiyat olarak kaydedilir.
+//
+// Kural alarmları (AlarmCondition::Rule) tick'lerde değil, seçilen aralıkta mum kapanışında
+// değerlendirilir: koşullar AND/OR ile birleştirilir ve kural art arda N kapanışta sağlanınca tetiklenir.
+//
+// Çizim alarmları (AlarmCondition::Drawing) bağlı çizimin geometrisini tick zamanına izdüşürür;
+// geometriler alarmlar veya çizimler değiştikçe `refresh_drawings` ile yenilenir.
+//
+// Seans VWAP'ı ve hacim profili sembol başına bir kez hesaplanır (`SessionCache`) ve yalnızca seans
+// serisinde yeni mum açıldığında yenilenir. Önbellekte seansı baştan kapsayan seri yoksa seans
+// 5 dakikalık mumlarla borsadan doldurulur.
+
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+use std::sync::Arc;
+use crate::db;
+use crate::exchange::bybit::Kline;
+use crate::indicators;
+use crate::models::{
+    Alarm, AlarmCondition, AlarmMode, AlarmRule, Comparison, IndicatorSource, RuleCombinator, RuleCondition,
+    StandardTick, VolumeProfile, VolumeProfileSpec, VwapAnchor,
+};
+use super::drawings::DrawingGeometry;
+use super::klines::{self, KlineCache};
+use super::scheduler::ScheduleSpec;
+use super::{AppEvent, AppState};
+
+/// Seans VWAP'ı ve hacim profili için denenen mum aralıkları (en ince önce)
+const SESSION_INTERVALS: [&str; 4] = ["1", "5", "15", "60"];
+
+/// Seans önbellekte yoksa doldurulan aralık ve dakika karşılığı (günlük seans 288 mum)
+const SESSION_BACKFILL_INTERVAL: &str = "5";
+const SESSION_BACKFILL_MINUTES: i64 = 5;
+
+/// Başarısız seans doldurmasının tekrar denenmesinden önce beklenen süre
+const SESSION_BACKFILL_RETRY: Duration = Duration::minutes(1);
+
+/// Kural alarmlarının mum kapanışı zamanlayıcılarının sahibi
+pub const SCHEDULE_OWNER: &str = "alarms";
+
+/// Kural için önbellek yetersizse borsadan doldurulan mum sayısı
+const RULE_KLINES: usize = 200;
+
+/// UTC gününün milisaniyesi
+const DAY_MS: i64 = 86_400_000;
+
+/// Fiyat `previous` -> `current` hareketinde seviyeyi koşulun yönünde kesti mi?
+pub fn crossed(condition: &AlarmCondition, previous: f64, current: f64, level: f64) -> bool {
+    let up = previous < level && current >= level;
+    let down = previous > level && current <= level;
+    match condition {
+        AlarmCondition::PriceAbove => up,
+        AlarmCondition::PriceBelow => down,
+        AlarmCondition::CrossVwap | AlarmCondition::CrossVal | AlarmCondition::CrossVah => up || down,
+        AlarmCondition::Rule(_) | AlarmCondition::Drawing(_) => false,
+    }
+}
+
+/// Alarm şu an tetiklenebilir mi? (aktif ve bekleme süresi dolmuş)
+pub fn is_armed(alarm: &Alarm, now: DateTime<Utc>) -> bool {
+    if !alarm.is_active {
+        return false;
+    }
+    match (&alarm.mode, alarm.last_triggered_at) {
+        (AlarmMode::Cooldown { seconds }, Some(last)) => {
+            now - last >= Duration::seconds(i64::try_from(*seconds).unwrap_or(i64::MAX))
+        }
+        _ => true,
+    }
+}
+
+/// Tetiklenen alarmı güncelle (tek seferlik alarm pasifleşir)
+pub fn fire(alarm: &mut Alarm, now: DateTime<Utc>) {
+    alarm.last_triggered_at = Some(now);
+    alarm.trigger_count += 1;
+    if alarm.mode == AlarmMode::OneShot {
+        alarm.is_active = false;
+    }
+}
+
+/// Sembolün UTC seansı için hesaplanmış seviyeler
+#[derive(Debug, Clone)]
+pub struct SessionLevels {
+    /// Seans başlangıcı, kullanılan aralık ve son mumun açılışı (değişince yeniden hesaplanır)
+    key: (i64, &'static str, i64),
+    pub vwap: Option<f64>,
+    /// Varsayılan ayarlarla (%70 değer alanı, otomatik kova) seans hacim profili
+    pub profile: Option<VolumeProfile>,
+}
+
+/// Sembol başına seans seviyeleri ve seans doldurma denemeleri
+#[derive(Debug, Default)]
+pub struct SessionCache {
+    levels: HashMap<String, SessionLevels>,
+    backfills: HashMap<String, DateTime<Utc>>,
+}
+
+impl SessionCache {
+    /// Sembolün seans seviyeleri (önbellekte seansı kapsayan seri yoksa None)
+    pub fn levels(&mut self, klines: &KlineCache, symbol: &str, now: DateTime<Utc>) -> Option<&SessionLevels> {
+        let start = session_start(now)?;
+        let (interval, last) = session_interval(klines, symbol, start)?;
+        let key = (start, interval, last);
+        if self.levels.get(symbol).is_none_or(|levels| levels.key != key) {
+            let session: Vec<Kline> = klines.get(symbol, interval).into_iter().filter(|k| k.timestamp >= start).collect();
+            let levels = SessionLevels {
+                key,
+                vwap: indicators::vwap_since(&session, start),
+                profile: indicators::volume_profile(&session, &VolumeProfileSpec::default(), now).ok(),
+            };
+            self.levels.insert(symbol.to_string(), levels);
+        }
+        self.levels.get(symbol)
+    }
+
+    /// Seans doldurması denenmeli mi? (deneme kaydedilir, başarısızsa bir dakika sonra tekrar)
+    pub fn backfill_due(&mut self, symbol: &str, now: DateTime<Utc>) -> bool {
+        let due = self.backfills.get(symbol).is_none_or(|last| now - *last >= SESSION_BACKFILL_RETRY);
+        if due {
+            self.backfills.insert(symbol.to_string(), now);
+        }
+        due
+    }
+}
+
+/// Alarmın izlediği seviye (hesaplanamıyorsa None)
+///
+/// VWAP, VAL ve VAH `session` seviyelerinden gelir.
+pub fn alarm_level(alarm: &Alarm, session: Option<&SessionLevels>) -> Option<f64> {
+    match alarm.condition {
+        AlarmCondition::PriceAbove | AlarmCondition::PriceBelow => Some(alarm.target_price),
+        AlarmCondition::CrossVwap => session?.vwap,
+        AlarmCondition::CrossVal => Some(session?.profile.as_ref()?.val),
+        AlarmCondition::CrossVah => Some(session?.profile.as_ref()?.vah),
+        AlarmCondition::Rule(_) | AlarmCondition::Drawing(_) => None,
+    }
+}
+
+/// Alarm seans seviyelerini mi izliyor?
+fn uses_session(alarm: &Alarm) -> bool {
+    matches!(alarm.condition, AlarmCondition::CrossVwap | AlarmCondition::CrossVal | AlarmCondition::CrossVah)
+}
+
+/// Kaynağın son mumdaki değeri (mumlar eskiden yeniye, veri yetersizse None)
+pub fn source_value(source: &IndicatorSource, klines: &[Kline]) -> Option<f64> {
+    let last = klines.last()?;
+    match source {
+        IndicatorSource::Close => Some(last.close),
+        IndicatorSource::Value { value } => Some(*value),
+        IndicatorSource::Vwap => indicators::vwap_since(klines, last.timestamp - last.timestamp.rem_euclid(DAY_MS)),
+        IndicatorSource::AnchoredVwap { anchor } => indicators::vwap_since(klines, anchor.timestamp_millis()),
+        IndicatorSource::Sma { period } => indicators::sma(klines, *period),
+        IndicatorSource::Rsi { period } => indicators::rsi(klines, *period),
+        IndicatorSource::Poc | IndicatorSource::Vah | IndicatorSource::Val => {
+            let now = DateTime::from_timestamp_millis(last.timestamp)?;
+            let profile = indicators::volume_profile(klines, &VolumeProfileSpec::default(), now).ok()?;
+            Some(match source {
+                IndicatorSource::Poc => profile.poc,
+                IndicatorSource::Vah => profile.vah,
+                _ => profile.val,
+            })
+        }
+    }
+}
+
+/// Koşul son mumda sağlanıyor mu? (veri yetersizse hayır)
+pub fn condition_met(condition: &RuleCondition, klines: &[Kline]) -> bool {
+    let values = |klines: &[Kline]| {
+        Some((source_value(&condition.left, klines)?, source_value(&condition.right, klines)?))
+    };
+    let Some((left, right)) = values(klines) else {
+        return false;
+    };
+    let previous = || values(&klines[..klines.len() - 1]);
+    match condition.comparison {
+        Comparison::Above => left > right,
+        Comparison::Below => left < right,
+        Comparison::CrossesAbove => left > right && previous().is_some_and(|(l, r)| l <= r),
+        Comparison::CrossesBelow => left < right && previous().is_some_and(|(l, r)| l >= r),
+    }
+}
+
+/// Kural son mumda sağlanıyor mu?
+pub fn rule_met(rule: &AlarmRule, klines: &[Kline]) -> bool {
+    let mut results = rule.conditions.iter().map(|c| condition_met(c, klines));
+    match rule.combinator {
+        RuleCombinator::All => !rule.conditions.is_empty() && results.all(|met| met),
+        RuleCombinator::Any => results.any(|met| met),
+    }
+}
+
+/// Kural değerlendirmesinin kapanmış mumlardan beklediği kapsam
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct KlineNeeds {
+    /// En az mum sayısı
+    count: usize,
+    /// Mumların geriye uzanması gereken an (ms; seans/çapalı VWAP ve profil)
+    start: Option<i64>,
+}
+
+impl KlineNeeds {
+    fn merge(self, other: KlineNeeds) -> KlineNeeds {
+        KlineNeeds {
+            count: self.count.max(other.count),
+            start: match (self.start, other.start) {
+                (Some(a), Some(b)) => Some(a.min(b)),
+                (a, b) => a.or(b),
+            },
+        }
+    }
+
+    /// Kapanmış mumlar yeterli mi? (RULE_KLINES mum varsa doldurma daha eskiye uzanamaz)
+    fn covered_by(&self, klines: &[Kline]) -> bool {
+        klines.len() >= self.count.min(RULE_KLINES)
+            && self.start.is_none_or(|start| {
+                klines.len() >= RULE_KLINES || klines.first().is_some_and(|k| k.timestamp <= start)
+            })
+    }
+}
+
+/// Kaynağın son mumdaki değeri için gereken mumlar (`session`: son mumun seans başlangıcı)
+fn source_needs(source: &IndicatorSource, session: Option<i64>) -> KlineNeeds {
+    match source {
+        IndicatorSource::Close | IndicatorSource::Value { .. } => KlineNeeds { count: 1, start: None },
+        IndicatorSource::Sma { period } => KlineNeeds { count: *period, start: None },
+        IndicatorSource::Rsi { period } => KlineNeeds { count: period + 1, start: None },
+        IndicatorSource::Vwap | IndicatorSource::Poc | IndicatorSource::Vah | IndicatorSource::Val => {
+            KlineNeeds { count: 1, start: session }
+        }
+        IndicatorSource::AnchoredVwap { anchor } => KlineNeeds { count: 1, start: Some(anchor.timestamp_millis()) },
+    }
+}
+
+/// `close_time` kapanışında kuralın ihtiyaç duyduğu kapanmış mumlar (kesişim bir önceki mumu da ister)
+fn rule_needs(rule: &AlarmRule, close_time: DateTime<Utc>) -> KlineNeeds {
+    let session = session_start(close_time - Duration::milliseconds(1));
+    rule.conditions.iter().fold(KlineNeeds::default(), |needs, condition| {
+        let mut condition_needs = source_needs(&condition.left, session).merge(source_needs(&condition.right, session));
+        if matches!(condition.comparison, Comparison::CrossesAbove | Comparison::CrossesBelow) {
+            condition_needs.count += 1;
+        }
+        needs.merge(condition_needs)
+    })
+}
+
+/// Kapanan mumu sembolün o aralıktaki kural alarmlarına uygula, tetiklenenleri döndür
+///
+/// `streaks`: alarm id -> kuralın art arda sağlandığı kapanış sayısı (tetiklenince sıfırlanır)
+pub fn evaluate_close(
+    alarms: &mut [Alarm],
+    streaks: &mut HashMap<String, u32>,
+    symbol: &str,
+    interval: &str,
+    klines: &[Kline],
+    close_time: DateTime<Utc>,
+) -> Vec<Alarm> {
+    let mut triggered = Vec::new();
+    for alarm in alarms.iter_mut().filter(|a| a.symbol == symbol && a.is_active) {
+        let (met, confirmations) = match &alarm.condition {
+            AlarmCondition::Rule(rule) if rule.interval == interval => (rule_met(rule, klines), rule.confirmations),
+            _ => continue,
+        };
+        let streak = streaks.entry(alarm.id.clone()).or_default();
+        *streak = if met { *streak + 1 } else { 0 };
+        if *streak >= confirmations.max(1) && is_armed(alarm, close_time) {
+            *streak = 0;
+            fire(alarm, close_time);
+            triggered.push(alarm.clone());
+        }
+    }
+    triggered
+}
+
+/// Aktif kural alarmları için mum kapanışı zamanlayıcılarını yeniden kur
+pub fn schedule_rules(state: &AppState, alarms: &[Alarm]) {
+    state.scheduler.remove_owner(SCHEDULE_OWNER);
+    for alarm in alarms.iter().filter(|a| a.is_active) {
+        if let AlarmCondition::Rule(rule) = &alarm.condition {
+            if let Err(e) = state.scheduler.add(SCHEDULE_OWNER, ScheduleSpec::candle_close(&alarm.symbol, &rule.interval)) {
+                tracing::warn!("Alarm {} zamanlanamadı: {}", alarm.id, e);
+            }
+        }
+    }
+}
+
+/// Kapanmış mumları döndür; takip edilen seri kuralların ihtiyacını karşılamıyorsa önce borsadan doldur
+///
+/// Seri bir kez doldurulup takibe alındıktan sonra canlı tick'lerle güncel kalır; her kapanışta ağ isteği yapılmaz.
+async fn closed_klines(state: &AppState, symbol: &str, interval: &str, close_time: DateTime<Utc>, needs: KlineNeeds) -> Vec<Kline> {
+    let cached = state.klines.closed(symbol, interval, close_time);
+    if needs.covered_by(&cached) {
+        return cached;
+    }
+    if let Err(e) = klines::backfill(&state.klines, symbol, interval, RULE_KLINES).await {
+        tracing::warn!("{} {} mumları alınamadı, önbellek kullanılıyor: {}", symbol, interval, e);
+    }
+    state.klines.closed(symbol, interval, close_time)
+}
+
+/// UTC seansının başlangıcı (ms; compute_indicator'ın seans VWAP'ı ile aynı periyot)
+fn session_start(now: DateTime<Utc>) -> Option<i64> {
+    indicators::vwap_period_start(&VwapAnchor::Session, now)
+}
+
+/// Önbellekte seansı baştan kapsayan en ince aralık ve son mumunun açılışı
+///
+/// İlk mum seans başlangıcında veya öncesinde olmalı; aksi halde VWAP ve profil seansın bir kısmını görür.
+fn session_interval(klines: &KlineCache, symbol: &str, start: i64) -> Option<(&'static str, i64)> {
+    SESSION_INTERVALS.iter().find_map(|&interval| {
+        let (first, last) = klines.span(symbol, interval)?;
+        (first <= start && last >= start).then_some((interval, last))
+    })
+}
+
+/// Seansı 5 dakikalık mumlarla borsadan doldur (seri takibe alınır, sonra canlı tick'lerle güncellenir)
+fn spawn_session_backfill(state: Arc<AppState>, symbol: String, now: DateTime<Utc>) {
+    let Some(start) = session_start(now) else {
+        return;
+    };
+    let candles = ((now.timestamp_millis() - start) / (SESSION_BACKFILL_MINUTES * 60_000) + 1) as usize;
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = klines::backfill(&state.klines, &symbol, SESSION_BACKFILL_INTERVAL, candles).await {
+            tracing::warn!("{} seans mumları alınamadı: {}", symbol, e);
+        }
+    });
+}
+
+/// Tick'i sembolün alarmlarına uygula, tetiklenenleri döndür
+pub fn evaluate_tick(
+    alarms: &mut [Alarm],
+    previous: f64,
+    tick: &StandardTick,
+    level: impl Fn(&Alarm) -> Option<f64>,
+) -> Vec<Alarm> {
+    let mut triggered = Vec::new();
+    for alarm in alarms.iter_mut().filter(|a| a.symbol == tick.symbol) {
+        if !is_armed(alarm, tick.timestamp) {
+            continue;
+        }
+        let Some(level) = level(alarm) else {
+            continue;
+        };
+        if crossed(&alarm.condition, previous, tick.price, level) {
+            fire(alarm, tick.timestamp);
+            triggered.push(alarm.clone());
+        }
+    }
+    triggered
+}
+
+/// Tick'i sembolün çizim alarmlarına uygula, tetiklenenleri döndür
+///
+/// `geometries`: drawing_id -> geometri (çizimi silinmiş alarmlar beklemede kalır)
+pub fn evaluate_drawings(
+    alarms: &mut [Alarm],
+    previous: f64,
+    tick: &StandardTick,
+    geometries: &HashMap<String, DrawingGeometry>,
+) -> Vec<Alarm> {
+    let mut triggered = Vec::new();
+    for alarm in alarms.iter_mut().filter(|a| a.symbol == tick.symbol) {
+        let AlarmCondition::Drawing(alert) = &alarm.condition else {
+            continue;
+        };
+        let Some(geometry) = geometries.get(&alert.drawing_id) else {
+            continue;
+        };
+        if is_armed(alarm, tick.timestamp)
+            && geometry.triggered(&alert.trigger, previous, tick.price, tick.timestamp.timestamp_millis())
+        {
+            fire(alarm, tick.timestamp);
+            triggered.push(alarm.clone());
+        }
+    }
+    triggered
+}
+
+/// Çizim alarmlarının izlediği geometrileri veritabanından yeniden oku
+pub async fn refresh_drawings(state: &AppState) {
+    let drawing_ids: Vec<String> = state.alarms.read().await.iter()
+        .filter_map(|a| match &a.condition {
+            AlarmCondition::Drawing(alert) => Some(alert.drawing_id.clone()),
+            _ => None,
+        })
+        .collect();
+
+    let mut geometries = HashMap::new();
+    for id in drawing_ids {
+        match db::get_drawing(&id).await {
+            Ok(Some(drawing)) => match DrawingGeometry::from_drawing(&drawing) {
+                Ok(geometry) => {
+                    geometries.insert(id, geometry);
+                }
+                Err(e) => tracing::warn!("Çizim {} alarm için okunamadı: {}", id, e),
+            },
+            Ok(None) => tracing::warn!("Alarmın çizimi bulunamadı: {}", id),
+            Err(e) => tracing::warn!("Çizim {} yüklenemedi: {}", id, e),
+        }
+    }
+    *state.drawing_geometries.write().await = geometries;
+}
+
+/// Kayıtlı alarmları uygulama durumuna yükle
+pub async fn load(state: &AppState) -> Result<usize, String> {
+    let alarms = db::get_alarms().await?;
+    let count = alarms.len();
+    schedule_rules(state, &alarms);
+    *state.alarms.write().await = alarms;
+    refresh_drawings(state).await;
+    Ok(count)
+}
+
+/// Fiyat tick'lerinde seviye ve çizim alarmlarını, mum kapanışlarında kural alarmlarını değerlendir
+pub fn spawn_alarm_engine(state: Arc<AppState>) {
+    let subscription = state.dispatcher.subscribe("alarms");
+    tauri::async_runtime::spawn(async move {
+        let mut last_prices: HashMap<String, f64> = HashMap::new();
+        let mut streaks: HashMap<String, u32> = HashMap::new();
+        let mut sessions = SessionCache::default();
+        while let Some(event) = subscription.recv().await {
+            let triggered = match event {
+                AppEvent::PriceUpdated(tick) => {
+                    let Some(previous) = last_prices.insert(tick.symbol.clone(), tick.price) else {
+                        continue;
+                    };
+                    let mut alarms = state.alarms.write().await;
+                    if !alarms.iter().any(|a| a.is_active && a.symbol == tick.symbol) {
+                        continue;
+                    }
+                    let watches_session = alarms.iter().any(|a| a.is_active && a.symbol == tick.symbol && uses_session(a));
+                    if watches_session
+                        && sessions.levels(&state.klines, &tick.symbol, tick.timestamp).is_none()
+                        && sessions.backfill_due(&tick.symbol, tick.timestamp)
+                    {
+                        spawn_session_backfill(state.clone(), tick.symbol.clone(), tick.timestamp);
+                    }
+                    let session = if watches_session {
+                        sessions.levels(&state.klines, &tick.symbol, tick.timestamp)
+                    } else {
+                        None
+                    };
+                    let mut triggered = evaluate_tick(&mut alarms, previous, &tick, |alarm| alarm_level(alarm, session));
+                    let geometries = state.drawing_geometries.read().await;
+                    triggered.extend(evaluate_drawings(&mut alarms, previous, &tick, &geometries));
+                    triggered
+                }
+                AppEvent::CandleClosed { module_id, symbol, interval, close_time } if module_id == SCHEDULE_OWNER => {
+                    let needs = state
+                        .alarms
+                        .read()
+                        .await
+                        .iter()
+                        .filter(|a| a.is_active && a.symbol == symbol)
+                        .filter_map(|a| match &a.condition {
+                            AlarmCondition::Rule(rule) if rule.interval == interval => Some(rule_needs(rule, close_time)),
+                            _ => None,
+                        })
+                        .fold(KlineNeeds::default(), KlineNeeds::merge);
+                    let klines = closed_klines(&state, &symbol, &interval, close_time, needs).await;
+                    let mut alarms = state.alarms.write().await;
+                    evaluate_close(&mut alarms, &mut streaks, &symbol, &interval, &klines, close_time)
+                }
+                _ => continue,
+            };
+
+            for alarm in triggered {
+                tracing::info!("Alarm tetiklendi: {} {:?}", alarm.symbol, alarm.condition);
+                if let Err(e) = db::save_alarm(&alarm).await {
+                    tracing::warn!("Alarm durumu kaydedilemedi: {}", e);
+                }
+                let _ = state.dispatcher.publish(AppEvent::AlarmTriggered { alarm_id: alarm.id });
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::NotificationType;
+    use crate::testing;
+
+    fn at(secs: i64) -> DateTime<Utc> {
+        DateTime::from_timestamp(secs, 0).unwrap()
+    }
+
+    fn alarm(condition: AlarmCondition, mode: AlarmMode) -> Alarm {
+        Alarm {
+            id: "a".into(),
+            symbol: "BTCUSDT".into(),
+            condition,
+            target_price: 100.0,
+            is_active: true,
+            notification_type: NotificationType::Visual,
+            mode,
+            created_at: at(0),
+            last_triggered_at: None,
+            trigger_count: 0,
+        }
+    }
+
+    fn tick(secs: i64, price: f64) -> StandardTick {
+        StandardTick { volume: 0.0, timestamp: at(secs), ..testing::tick("BTCUSDT", price) }
+    }
+
+    #[test]
+    fn test_cross_detection() {
+        // Seviyenin üstünde beklemek tetiklemez, kesiş tetikler
+        assert!(!crossed(&AlarmCondition::PriceAbove, 101.0, 102.0, 100.0));
+        assert!(crossed(&AlarmCondition::PriceAbove, 99.0, 100.0, 100.0));
+        assert!(!crossed(&AlarmCondition::PriceAbove, 101.0, 99.0, 100.0));
+        assert!(crossed(&AlarmCondition::PriceBelow, 101.0, 99.0, 100.0));
+        assert!(crossed(&AlarmCondition::CrossVwap, 101.0, 99.0, 100.0));
+        assert!(crossed(&AlarmCondition::CrossVwap, 99.0, 101.0, 100.0));
+        assert!(!crossed(&AlarmCondition::CrossVwap, 100.0, 100.0, 100.0));
+    }
+
+    #[test]
+    fn test_modes() {
+        let level = |a: &Alarm| Some(a.target_price);
+        let mut alarms = vec![
+            alarm(AlarmCondition::PriceAbove, AlarmMode::OneShot),
+            alarm(AlarmCondition::PriceAbove, AlarmMode::Rearm),
+            alarm(AlarmCondition::PriceAbove, AlarmMode::Cooldown { seconds: 60 }),
+        ];
+
+        assert_eq!(evaluate_tick(&mut alarms, 99.0, &tick(0, 101.0), level).len(), 3);
+        assert!(!alarms[0].is_active);
+        // Fiyat geri inip tekrar kesiyor (30 sn sonra)
+        assert!(evaluate_tick(&mut alarms, 101.0, &tick(10, 98.0), level).is_empty());
+        let again = evaluate_tick(&mut alarms, 98.0, &tick(30, 100.5), level);
+        assert_eq!(again.iter().map(|a| a.mode.clone()).collect::<Vec<_>>(), vec![AlarmMode::Rearm]);
+        // Bekleme süresi doldu
+        let later = evaluate_tick(&mut alarms, 99.0, &tick(61, 100.5), level);
+        assert_eq!(later.len(), 2);
+        assert_eq!(alarms[1].trigger_count, 3);
+        assert_eq!(alarms[2].trigger_count, 2);
+        assert_eq!(alarms[2].last_triggered_at, Some(at(61)));
+    }
+
+    #[test]
+    fn test_rule_confirmations_and_combinators() {
+        let klines: Vec<Kline> = [10.0, 10.0, 10.0, 12.0, 13.0]
+            .iter()
+            .enumerate()
+            .map(|(i, &close)| Kline { timestamp: i as i64 * 60_000, open: close, high: close, low: close, close, volume: 1.0 })
+            .collect();
+        let cond = |left, comparison, right| RuleCondition { left, comparison, right };
+        let above_sma = cond(IndicatorSource::Close, Comparison::Above, IndicatorSource::Sma { period: 3 });
+        let crosses_11 = cond(IndicatorSource::Close, Comparison::CrossesAbove, IndicatorSource::Value { value: 11.0 });
+        let above_20 = cond(IndicatorSource::Close, Comparison::Above, IndicatorSource::Value { value: 20.0 });
+
+        // Kesişim sadece ilk kapanışta (12), üstünde kalmak kesişim değil
+        assert!(condition_met(&crosses_11, &klines[..4]));
+        assert!(!condition_met(&crosses_11, &klines));
+        let rule = |combinator, conditions, confirmations| AlarmRule { interval: "1".into(), combinator, conditions, confirmations };
+        assert!(!rule_met(&rule(RuleCombinator::All, vec![above_sma.clone(), above_20.clone()], 1), &klines));
+        assert!(rule_met(&rule(RuleCombinator::Any, vec![above_sma.clone(), above_20], 1), &klines));
+        assert!(!rule_met(&rule(RuleCombinator::All, vec![], 1), &klines));
+
+        // 2 onay: ilk kapanışta beklenir, ikincide tetiklenir
+        let mut alarms = vec![alarm(AlarmCondition::Rule(rule(RuleCombinator::All, vec![above_sma], 2)), AlarmMode::Rearm)];
+        let mut streaks = HashMap::new();
+        assert!(evaluate_close(&mut alarms, &mut streaks, "BTCUSDT", "1", &klines[..4], at(240)).is_empty());
+        assert!(evaluate_close(&mut alarms, &mut streaks, "BTCUSDT", "5", &klines, at(300)).is_empty());
+        assert_eq!(evaluate_close(&mut alarms, &mut streaks, "BTCUSDT", "1", &klines, at(300)).len(), 1);
+        assert_eq!(streaks["a"], 0);
+    }
+
+    #[test]
+    fn test_rule_kline_needs() {
+        const DAY: i64 = 86_400;
+        let cond = |left, comparison, right| RuleCondition { left, comparison, right };
+        let rule = |conditions| AlarmRule { interval: "60".into(), combinator: RuleCombinator::Any, conditions, confirmations: 1 };
+        let close_time = at(DAY + 3 * 3600);
+
+        // SMA 20 kesişimi bir önceki mumu da ister; RSI 14 ise 15 mum
+        let sma = rule(vec![
+            cond(IndicatorSource::Close, Comparison::CrossesAbove, IndicatorSource::Sma { period: 20 }),
+            cond(IndicatorSource::Rsi { period: 14 }, Comparison::Above, IndicatorSource::Value { value: 70.0 }),
+        ]);
+        let needs = rule_needs(&sma, close_time);
+        assert_eq!(needs, KlineNeeds { count: 21, start: None });
+
+        let candles = |from: i64, count: i64| -> Vec<Kline> {
+            (0..count)
+                .map(|i| Kline { timestamp: (from + i * 3600) * 1000, open: 1.0, high: 1.0, low: 1.0, close: 1.0, volume: 1.0 })
+                .collect()
+        };
+        assert!(!needs.covered_by(&candles(0, 20)));
+        assert!(needs.covered_by(&candles(0, 21)));
+
+        // Seans VWAP'ı kapanan mumun seansının başından ister
+        let vwap = rule(vec![cond(IndicatorSource::Close, Comparison::Above, IndicatorSource::Vwap)]);
+        let needs = rule_needs(&vwap, close_time);
+        assert_eq!(needs, KlineNeeds { count: 1, start: Some(DAY * 1000) });
+        assert!(!needs.covered_by(&candles(DAY + 3600, 2)));
+        assert!(needs.covered_by(&candles(DAY - 3600, 4)));
+        // Doldurmanın ulaşabileceğinden eski başlangıç yeniden istek yaptırmaz
+        let anchored = KlineNeeds { count: 1, start: Some(0) };
+        assert!(anchored.covered_by(&candles(DAY, RULE_KLINES as i64)));
+    }
+
+    #[test]
+    fn test_session_levels() {
+        const DAY: i64 = 86_400;
+        let candle = |secs: i64, price: f64, volume: f64| Kline {
+            timestamp: secs * 1000,
+            open: price,
+            high: price,
+            low: price,
+            close: price,
+            volume,
+        };
+        let cache = KlineCache::new();
+        let mut sessions = SessionCache::default();
+        let now = at(DAY + 1200);
+
+        // 1 dk serisi seansın ortasından başlıyor: seansı kapsamaz
+        cache.merge("BTCUSDT", "1", &[candle(DAY + 600, 50.0, 10.0)]);
+        assert!(sessions.levels(&cache, "BTCUSDT", now).is_none());
+        assert!(sessions.backfill_due("BTCUSDT", now));
+        assert!(!sessions.backfill_due("BTCUSDT", at(DAY + 1230)));
+        assert!(sessions.backfill_due("BTCUSDT", at(DAY + 1260)));
+
+        // 5 dk serisi önceki seanstan başlıyor; önceki seansın mumu hesaba katılmaz
+        cache.merge("BTCUSDT", "5", &[candle(DAY - 300, 10.0, 100.0), candle(DAY, 20.0, 1.0), candle(DAY + 900, 30.0, 1.0)]);
+        let levels = sessions.levels(&cache, "BTCUSDT", now).unwrap();
+        assert_eq!(levels.vwap, Some(25.0));
+        let profile = levels.profile.clone().unwrap();
+        assert_eq!((profile.start, profile.total_volume), (DAY * 1000, 2.0));
+        let mut vwap_alarm = alarm(AlarmCondition::CrossVwap, AlarmMode::Rearm);
+        assert_eq!(alarm_level(&vwap_alarm, Some(levels)), Some(25.0));
+        vwap_alarm.condition = AlarmCondition::CrossVah;
+        assert_eq!(alarm_level(&vwap_alarm, Some(levels)), Some(profile.vah));
+        assert_eq!(alarm_level(&vwap_alarm, None), None);
+
+        // Aynı mum içinde seviyeler yeniden hesaplanmaz, yeni mum açılınca hesaplanır
+        cache.merge("BTCUSDT", "5", &[candle(DAY + 900, 30.0, 3.0)]);
+        assert_eq!(sessions.levels(&cache, "BTCUSDT", now).unwrap().vwap, Some(25.0));
+        cache.merge("BTCUSDT", "5", &[candle(DAY + 1200, 40.0, 4.0)]);
+        // (20 + 30 * 3 + 40 * 4) / 8
+        assert_eq!(sessions.levels(&cache, "BTCUSDT", now).unwrap().vwap, Some(33.75));
+    }
+}
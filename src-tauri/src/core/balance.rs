@@ -0,0 +1,77 @@
+// AlgoTrade OS - Balance Poll
+// Hesap varlığını bağlı borsadan periyodik olarak okur ve BalanceChanged olarak yayınlar
+//
+// Sadece değişen varlık yayınlanır; drawdown izleyicisi ve on_balance_change dinleyen modüller
+// bu olaydan beslenir. Bağlantı yokken veya istek başarısız olduğunda sonraki turda tekrar denenir.
+
+use std::sync::Arc;
+use std::time::Duration;
+use crate::exchange::ExecutionError;
+use super::{AppEvent, AppState};
+
+/// Hesap varlığının yayınlandığı coin
+pub const EQUITY_COIN: &str = "USDT";
+
+/// Borsadan bakiye okuma aralığı
+const POLL_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Bakiyeyi oku, önceki yayından farklıysa BalanceChanged yayınla (bağlantı yoksa bir şey yapmaz)
+pub async fn poll(state: &AppState, last: &mut Option<f64>) -> Result<(), ExecutionError> {
+    let Some(executor) = state.executor().await else {
+        return Ok(());
+    };
+    let equity = executor.account_balance().await?.equity;
+    if !equity.is_finite() || *last == Some(equity) {
+        return Ok(());
+    }
+    *last = Some(equity);
+    let _ = state.dispatcher.publish(AppEvent::BalanceChanged {
+        symbol: EQUITY_COIN.to_string(),
+        balance: equity,
+    });
+    Ok(())
+}
+
+/// Bakiye sorgusunu arka planda başlat
+pub fn spawn_balance_poll(state: Arc<AppState>) {
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(POLL_INTERVAL);
+        let mut last = None;
+        loop {
+            interval.tick().await;
+            if let Err(e) = poll(&state, &mut last).await {
+                tracing::warn!("Hesap bakiyesi okunamadı: {}", e);
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exchange::mock::MockExecutor;
+
+    #[tokio::test]
+    async fn test_poll_publishes_changed_equity() {
+        let state = AppState::new();
+        let subscription = state.dispatcher.subscribe("test");
+        let mut last = None;
+        // Bağlantı yok: yayın yok
+        poll(&state, &mut last).await.unwrap();
+
+        let mock = Arc::new(MockExecutor::with_equity(1000.0));
+        state.set_executor(Some(mock.clone())).await;
+        poll(&state, &mut last).await.unwrap();
+        poll(&state, &mut last).await.unwrap();
+        mock.balance.lock().unwrap().equity = 950.0;
+        poll(&state, &mut last).await.unwrap();
+
+        let balances: Vec<f64> = std::iter::from_fn(|| subscription.try_recv())
+            .filter_map(|e| match e {
+                AppEvent::BalanceChanged { symbol, balance } if symbol == EQUITY_COIN => Some(balance),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(balances, vec![1000.0, 950.0]);
+    }
+}
@@ -0,0 +1,282 @@
+// AlgoTrade OS - Portfolio Correlation
+// Önbellekteki mumlardan semboller arası getiri korelasyonu, korelasyonlu risk ve portföy VaR'ı
+//
+// Aynı yöndeki yüksek korelasyonlu pozisyonlar tek bir büyük bahis gibi davranır
+// (BTC, ETH ve SOL'de %1'lik üç long ~ %3'lük tek işlem). Korelasyonlu risk, işaretli
+// pozisyon risklerinin korelasyon matrisiyle birleştirilmesidir: sqrt(Σ rᵢ rⱼ ρᵢⱼ).
+// Verisi eksik sembol çiftleri en kötü durum kabul edilir: aynı yönde tam korelasyon,
+// ters yönde korelasyonsuz (hedge varsayılmaz).
+
+use chrono::{DateTime, Utc};
+use crate::exchange::bybit::Kline;
+use crate::models::CorrelationMatrix;
+use super::klines::KlineCache;
+use super::scheduler::{candle_open, next_candle_close};
+
+/// Korelasyon ve oynaklık için gereken en az ortak getiri
+pub const MIN_OBSERVATIONS: usize = 20;
+
+/// Tek taraflı %95 güven düzeyinin z değeri
+const VAR_Z_95: f64 = 1.645;
+
+/// Portföydeki işaretli pozisyon (long pozitif, short negatif)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Exposure {
+    pub symbol: String,
+    /// Stop'a kadar risk
+    pub risk: f64,
+    /// Pozisyon büyüklüğü
+    pub value: f64,
+}
+
+/// Ardışık kapanışların logaritmik getirileri (getirinin ait olduğu mumun açılışıyla)
+pub fn log_returns(klines: &[Kline]) -> Vec<(i64, f64)> {
+    klines
+        .windows(2)
+        .filter(|w| w[0].close > 0.0 && w[1].close > 0.0)
+        .map(|w| (w[1].timestamp, (w[1].close / w[0].close).ln()))
+        .collect()
+}
+
+/// Getirilerin standart sapması
+pub fn volatility(returns: &[(i64, f64)]) -> Option<f64> {
+    if returns.len() < MIN_OBSERVATIONS {
+        return None;
+    }
+    let n = returns.len() as f64;
+    let mean = returns.iter().map(|(_, r)| r).sum::<f64>() / n;
+    let variance = returns.iter().map(|(_, r)| (r - mean).powi(2)).sum::<f64>() / (n - 1.0);
+    Some(variance.sqrt())
+}
+
+/// Aynı mumlara düşen getirilerin Pearson korelasyonu
+pub fn correlation(a: &[(i64, f64)], b: &[(i64, f64)]) -> Option<f64> {
+    let pairs: Vec<(f64, f64)> = a
+        .iter()
+        .filter_map(|(ts, x)| b.binary_search_by_key(ts, |(t, _)| *t).ok().map(|i| (*x, b[i].1)))
+        .collect();
+    if pairs.len() < MIN_OBSERVATIONS {
+        return None;
+    }
+
+    let n = pairs.len() as f64;
+    let mean_x = pairs.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = pairs.iter().map(|(_, y)| y).sum::<f64>() / n;
+    let (mut cov, mut var_x, mut var_y) = (0.0, 0.0, 0.0);
+    for (x, y) in &pairs {
+        cov += (x - mean_x) * (y - mean_y);
+        var_x += (x - mean_x).powi(2);
+        var_y += (y - mean_y).powi(2);
+    }
+    if var_x <= 0.0 || var_y <= 0.0 {
+        return None;
+    }
+    Some((cov / (var_x * var_y).sqrt()).clamp(-1.0, 1.0))
+}
+
+/// Sembollerin son `lookback` getirisinden korelasyon matrisi
+pub fn correlation_matrix(
+    cache: &KlineCache,
+    symbols: &[String],
+    interval: &str,
+    lookback: usize,
+    now: DateTime<Utc>,
+) -> CorrelationMatrix {
+    let returns: Vec<Vec<(i64, f64)>> = symbols
+        .iter()
+        .map(|symbol| {
+            let mut returns = log_returns(&cache.closed(symbol, interval, now));
+            let excess = returns.len().saturating_sub(lookback);
+            returns.drain(..excess);
+            returns
+        })
+        .collect();
+
+    let values = returns
+        .iter()
+        .enumerate()
+        .map(|(i, a)| {
+            returns
+                .iter()
+                .enumerate()
+                .map(|(j, b)| if i == j { Some(1.0) } else { correlation(a, b) })
+                .collect()
+        })
+        .collect();
+
+    CorrelationMatrix {
+        interval: interval.to_string(),
+        symbols: symbols.to_vec(),
+        values,
+        volatilities: returns.iter().map(|r| volatility(r)).collect(),
+        updated_at: now,
+    }
+}
+
+/// Bir günde kaç mum olduğu (geçersiz aralıkta None)
+pub fn bars_per_day(interval: &str, now: DateTime<Utc>) -> Option<f64> {
+    let open = candle_open(interval, now)?;
+    let close = next_candle_close(interval, now)?;
+    let seconds = (close - open).num_seconds();
+    (seconds > 0).then(|| 86_400.0 / seconds as f64)
+}
+
+/// İki sembolün korelasyonu (aynı sembol 1, veri yoksa None)
+fn pair(matrix: &CorrelationMatrix, a: &str, b: &str) -> Option<f64> {
+    if a == b {
+        return Some(1.0);
+    }
+    let i = matrix.symbols.iter().position(|s| s == a)?;
+    let j = matrix.symbols.iter().position(|s| s == b)?;
+    matrix.values.get(i)?.get(j).copied().flatten()
+}
+
+/// Sembolün mum başına getiri oynaklığı
+fn symbol_volatility(matrix: &CorrelationMatrix, symbol: &str) -> Option<f64> {
+    let i = matrix.symbols.iter().position(|s| s == symbol)?;
+    matrix.volatilities.get(i).copied().flatten()
+}
+
+/// wₐ·w_b·ρ (korelasyon bilinmiyorsa en kötü durum)
+fn cross(matrix: &CorrelationMatrix, a: (&str, f64), b: (&str, f64)) -> f64 {
+    let product = a.1 * b.1;
+    let rho = pair(matrix, a.0, b.0).unwrap_or(if product > 0.0 { 1.0 } else { 0.0 });
+    product * rho
+}
+
+/// Yeni pozisyon `k` ile ölçeklendiğinde Σ wᵢ wⱼ ρᵢⱼ = a + 2bk + ck² katsayıları
+fn quadratic(matrix: &CorrelationMatrix, existing: &[(&str, f64)], new: (&str, f64)) -> (f64, f64, f64) {
+    let a = existing
+        .iter()
+        .flat_map(|x| existing.iter().map(move |y| (*x, *y)))
+        .map(|(x, y)| cross(matrix, x, y))
+        .sum();
+    let b = existing.iter().map(|x| cross(matrix, *x, new)).sum();
+    (a, b, new.1 * new.1)
+}
+
+/// Pozisyonların korelasyonla birleştirilmiş riski
+pub fn correlated_risk(exposures: &[Exposure], matrix: &CorrelationMatrix) -> f64 {
+    let weights: Vec<(&str, f64)> = exposures.iter().map(|e| (e.symbol.as_str(), e.risk)).collect();
+    let (a, _, _) = quadratic(matrix, &weights, ("", 0.0));
+    a.max(0.0).sqrt()
+}
+
+/// Pozisyonların VaR hesabındaki ağırlıkları (oynaklığı bilinmeyen sembol varsa None)
+fn var_weights<'a>(exposures: &'a [Exposure], matrix: &CorrelationMatrix) -> Option<Vec<(&'a str, f64)>> {
+    exposures
+        .iter()
+        .map(|e| symbol_volatility(matrix, &e.symbol).map(|sigma| (e.symbol.as_str(), e.value * sigma)))
+        .collect()
+}
+
+/// Parametrik portföy VaR'ı (%95, `horizon_bars` mum ufkunda)
+///
+/// Oynaklığı hesaplanamayan sembol varsa None.
+pub fn portfolio_var(exposures: &[Exposure], matrix: &CorrelationMatrix, horizon_bars: f64) -> Option<f64> {
+    let weights = var_weights(exposures, matrix)?;
+    let (a, _, _) = quadratic(matrix, &weights, ("", 0.0));
+    Some(VAR_Z_95 * a.max(0.0).sqrt() * horizon_bars.sqrt())
+}
+
+/// Yeni pozisyonun sınır içinde kalan en büyük çarpanı (0..=1)
+///
+/// Yeni pozisyon birleşik değeri artırmıyorsa (hedge) 1; hiçbir çarpan sınıra sığmıyorsa None.
+fn max_scale((a, b, c): (f64, f64, f64), cap: f64) -> Option<f64> {
+    let cap_sq = cap * cap;
+    let full = a + 2.0 * b + c;
+    if full <= cap_sq || full <= a {
+        return Some(1.0);
+    }
+    let discriminant = b * b - c * (a - cap_sq);
+    if c <= 0.0 || discriminant < 0.0 {
+        return None;
+    }
+    let k = (-b + discriminant.sqrt()) / c;
+    (k > 0.0).then_some(k.min(1.0))
+}
+
+/// Korelasyonlu risk sınırına sığan en büyük çarpan
+pub fn risk_scale(existing: &[Exposure], new: &Exposure, matrix: &CorrelationMatrix, cap: f64) -> Option<f64> {
+    let weights: Vec<(&str, f64)> = existing.iter().map(|e| (e.symbol.as_str(), e.risk)).collect();
+    max_scale(quadratic(matrix, &weights, (&new.symbol, new.risk)), cap)
+}
+
+/// VaR sınırına sığan en büyük çarpan (VaR hesaplanamıyorsa 1)
+pub fn var_scale(
+    existing: &[Exposure],
+    new: &Exposure,
+    matrix: &CorrelationMatrix,
+    horizon_bars: f64,
+    cap: f64,
+) -> Option<f64> {
+    let Some(weights) = var_weights(existing, matrix) else {
+        return Some(1.0);
+    };
+    let Some(sigma) = symbol_volatility(matrix, &new.symbol) else {
+        return Some(1.0);
+    };
+    let cap = cap / (VAR_Z_95 * horizon_bars.sqrt());
+    max_scale(quadratic(matrix, &weights, (&new.symbol, new.value * sigma)), cap)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn series(closes: &[f64]) -> Vec<Kline> {
+        closes
+            .iter()
+            .enumerate()
+            .map(|(i, &close)| Kline { timestamp: i as i64 * 60_000, open: close, high: close, low: close, close, volume: 1.0 })
+            .collect()
+    }
+
+    fn exposure(symbol: &str, risk: f64) -> Exposure {
+        Exposure { symbol: symbol.into(), risk, value: risk * 10.0 }
+    }
+
+    #[test]
+    fn test_correlation_and_var() {
+        // Zikzak fiyat: B, A ile aynı, C ters yönde hareket ediyor
+        let a: Vec<f64> = (0..40).map(|i| 100.0 + if i % 2 == 0 { 0.0 } else { 1.0 + i as f64 * 0.01 }).collect();
+        let b: Vec<f64> = a.iter().map(|p| p * 2.0).collect();
+        let c: Vec<f64> = a.iter().map(|p| 20_000.0 / p).collect();
+        let cache = KlineCache::new();
+        cache.merge("A", "1", &series(&a));
+        cache.merge("B", "1", &series(&b));
+        cache.merge("C", "1", &series(&c));
+        cache.merge("D", "1", &series(&a[..10]));
+
+        let symbols: Vec<String> = ["A", "B", "C", "D"].iter().map(|s| s.to_string()).collect();
+        let now = DateTime::from_timestamp(3600, 0).unwrap();
+        let matrix = correlation_matrix(&cache, &symbols, "1", 100, now);
+        assert!((matrix.values[0][1].unwrap() - 1.0).abs() < 1e-9);
+        assert!((matrix.values[0][2].unwrap() + 1.0).abs() < 1e-9);
+        // Yetersiz veri
+        assert_eq!(matrix.values[0][3], None);
+        assert_eq!(matrix.volatilities[3], None);
+
+        // Tam korelasyonlu iki long: riskler toplanır
+        let longs = [exposure("A", 10.0), exposure("B", 10.0)];
+        assert!((correlated_risk(&longs, &matrix) - 20.0).abs() < 1e-9);
+        // Ters korelasyonlu long + long birbirini dengeler
+        assert!(correlated_risk(&[exposure("A", 10.0), exposure("C", 10.0)], &matrix) < 1e-6);
+        // Verisi olmayan aynı yönlü çift: en kötü durum
+        assert!((correlated_risk(&[exposure("A", 10.0), exposure("D", 5.0)], &matrix) - 15.0).abs() < 1e-9);
+
+        // 20 mevcut + 20 yeni > 30: yeni pozisyon 10/20'ye küçülür
+        assert_eq!(risk_scale(&longs[..1], &longs[1], &matrix, 30.0), Some(1.0));
+        assert_eq!(risk_scale(&[exposure("A", 20.0)], &exposure("B", 20.0), &matrix, 30.0), Some(0.5));
+        // Sınır zaten aşılmış ve yeni pozisyon riski artırıyor
+        assert_eq!(risk_scale(&[exposure("A", 40.0)], &exposure("B", 10.0), &matrix, 30.0), None);
+        // Hedge her zaman kabul
+        assert_eq!(risk_scale(&[exposure("A", 40.0)], &exposure("C", 10.0), &matrix, 30.0), Some(1.0));
+
+        let var = portfolio_var(&longs, &matrix, 4.0).unwrap();
+        let sigma = matrix.volatilities[0].unwrap();
+        assert!((var - VAR_Z_95 * 200.0 * sigma * 2.0).abs() < 1e-9);
+        assert_eq!(portfolio_var(&[exposure("D", 1.0)], &matrix, 4.0), None);
+        assert_eq!(bars_per_day("60", now), Some(24.0));
+    }
+}
@@ -0,0 +1,246 @@
+// AlgoTrade OS - Daily PnL Tracker
+// İşlem günü bazında gerçekleşen kâr/zarar takibi ve günlük kayıp kilidi
+//
+// Borsadaki gerçekleşmeler (dolum + komisyon) SQLite'a yazılır, günün kâr/zararı bunlardan toplanır.
+// Günlük kayıp limiti aşılınca sonraki işlem gününe kadar yeni girişler kilitlenir; kilit
+// veritabanında saklandığından yeniden başlatmada kalkmaz ve ancak bekleme süresinden sonra
+// kullanıcı tarafından kaldırılabilir.
+//
+// İşlem günü ayarlardaki IANA saat diliminin (örn: Europe/Istanbul) gece yarısında sıfırlanır;
+// yaz saati geçişlerinde sıfırlama yerel gece yarısında kalır.
+
+use chrono::{DateTime, Duration, LocalResult, NaiveDate, NaiveTime, Offset, TimeZone, Utc};
+use chrono_tz::Tz;
+use tokio::sync::RwLock;
+use crate::db;
+use crate::exchange::executor::OrderExecutor;
+use crate::models::{DailyPnl, TradingLockout};
+use crate::modules::risk_calculator::RiskCalculator;
+
+/// Kilidin saklandığı ayar anahtarı
+const LOCKOUT_KEY: &str = "trading_lockout";
+
+/// Borsanın gerçekleşme geçmişi için izin verdiği en uzun aralık
+const MAX_BACKFILL_DAYS: i64 = 7;
+
+/// Zamanın `timezone` saat dilimindeki işlem günü
+pub fn trading_day(time: DateTime<Utc>, timezone: Tz) -> NaiveDate {
+    time.with_timezone(&timezone).date_naive()
+}
+
+/// İşlem gününün başladığı an (yerel gece yarısı)
+///
+/// Gece yarısı yaz saati geçişiyle atlanırsa gün geçiş anında başlar; iki kez yaşanırsa ilki alınır.
+pub fn session_start(day: NaiveDate, timezone: Tz) -> DateTime<Utc> {
+    let midnight = day.and_time(NaiveTime::MIN);
+    match timezone.from_local_datetime(&midnight) {
+        LocalResult::Single(start) | LocalResult::Ambiguous(start, _) => start.with_timezone(&Utc),
+        LocalResult::None => {
+            // Geçişten önceki fark ile gece yarısı, atlanan aralığın başına (geçiş anına) denk gelir
+            let before = timezone.offset_from_utc_datetime(&(midnight - Duration::days(1))).fix();
+            midnight.and_utc() - Duration::seconds(i64::from(before.local_minus_utc()))
+        }
+    }
+}
+
+/// Günlük kâr/zarar limiti aştıysa kaybın hesap varlığına oranı (%)
+pub fn limit_breach(daily_pnl: f64, equity: f64, max_daily_loss: f64) -> Option<f64> {
+    if RiskCalculator::check_daily_loss_limit(daily_pnl, max_daily_loss, equity) {
+        return None;
+    }
+    Some(if equity > 0.0 { -daily_pnl / equity * 100.0 } else { f64::INFINITY })
+}
+
+/// Kilit şu an yeni girişleri engelliyor mu?
+pub fn is_blocking(lockout: &TradingLockout, now: DateTime<Utc>) -> bool {
+    lockout.overridden_at.is_none() && now < lockout.until
+}
+
+/// Kilidin en erken kaldırılabileceği an
+pub fn override_available_at(lockout: &TradingLockout, cooldown_minutes: u32) -> DateTime<Utc> {
+    lockout.locked_at + Duration::minutes(i64::from(cooldown_minutes))
+}
+
+/// Günlük kâr/zarar takibi ve kayıp kilidi
+#[derive(Default)]
+pub struct DailyPnlTracker {
+    /// Güncel işlem gününün kilidi (süresi dolunca temizlenir)
+    lockout: RwLock<Option<TradingLockout>>,
+}
+
+impl DailyPnlTracker {
+    /// Kayıtlı kilidi yükle (uygulama açılışında)
+    pub async fn load(&self) -> Result<(), String> {
+        if let Some(json) = db::get_setting(LOCKOUT_KEY).await? {
+            let lockout: TradingLockout = serde_json::from_str(&json).map_err(|e| e.to_string())?;
+            *self.lockout.write().await = Some(lockout);
+        }
+        Ok(())
+    }
+
+    /// Güncel kilit (süresi dolmuşsa silinir)
+    pub async fn lockout(&self, now: DateTime<Utc>) -> Option<TradingLockout> {
+        let mut lockout = self.lockout.write().await;
+        if lockout.as_ref().is_some_and(|l| now >= l.until) {
+            *lockout = None;
+            if let Err(e) = db::delete_setting(LOCKOUT_KEY).await {
+                tracing::warn!("Süresi dolan kilit silinemedi: {}", e);
+            }
+            tracing::info!("Günlük kayıp kilidi yeni işlem gününde kalktı");
+        }
+        lockout.clone()
+    }
+
+    /// Yeni girişleri engelleyen kilit
+    pub async fn active_lockout(&self, now: DateTime<Utc>) -> Option<TradingLockout> {
+        self.lockout(now).await.filter(|l| is_blocking(l, now))
+    }
+
+    /// Yeni gerçekleşmeleri borsadan al, kaydet ve günün kâr/zararını döndür
+    pub async fn sync(
+        &self,
+        executor: &dyn OrderExecutor,
+        timezone: Tz,
+        now: DateTime<Utc>,
+    ) -> Result<DailyPnl, String> {
+        let day = trading_day(now, timezone);
+        // Son kayıttan itibaren al (uygulama kapalıyken kaçanlar dahil)
+        let since = db::get_last_execution_time()
+            .await?
+            .map(|last| last.max(now - Duration::days(MAX_BACKFILL_DAYS)))
+            .unwrap_or_else(|| session_start(day, timezone));
+
+        let executions = executor.executions(since).await.map_err(|e| e.to_string())?;
+        let inserted = db::save_executions(&executions).await?;
+        if inserted > 0 {
+            tracing::debug!("{} yeni gerçekleşme kaydedildi", inserted);
+        }
+        daily_pnl(day, timezone).await
+    }
+
+    /// Günlük kayıp limitini uygula, aşıldıysa sonraki işlem gününe kadar kilitle
+    ///
+    /// `daily_pnl`: gerçekleşen + gerçekleşmemiş kâr/zarar. Bugünkü kilit kaldırıldıysa tekrar kilitlenmez.
+    pub async fn enforce(
+        &self,
+        daily_pnl: f64,
+        equity: f64,
+        max_daily_loss: f64,
+        timezone: Tz,
+        now: DateTime<Utc>,
+    ) -> Option<TradingLockout> {
+        if let Some(lockout) = self.lockout(now).await {
+            return is_blocking(&lockout, now).then_some(lockout);
+        }
+        let loss_percent = limit_breach(daily_pnl, equity, max_daily_loss)?;
+
+        let day = trading_day(now, timezone);
+        let lockout = TradingLockout {
+            trading_day: day,
+            locked_at: now,
+            until: session_start(day + Duration::days(1), timezone),
+            loss_percent,
+            limit_percent: max_daily_loss,
+            overridden_at: None,
+        };
+        tracing::warn!(
+            "Günlük kayıp limiti aşıldı (%{:.2} / %{:.2}), yeni girişler {} tarihine kadar kilitlendi",
+            loss_percent, max_daily_loss, lockout.until
+        );
+        self.store(&lockout).await;
+        *self.lockout.write().await = Some(lockout.clone());
+        Some(lockout)
+    }
+
+    /// Bekleme süresi dolduysa kilidi kaldır (bu işlem günü için)
+    pub async fn override_lockout(&self, cooldown_minutes: u32, now: DateTime<Utc>) -> Result<TradingLockout, String> {
+        let mut lockout = self.lockout(now).await.filter(|l| is_blocking(l, now))
+            .ok_or("Aktif günlük kayıp kilidi yok")?;
+        let available_at = override_available_at(&lockout, cooldown_minutes);
+        if now < available_at {
+            return Err(format!("Kilit {} tarihinden önce kaldırılamaz", available_at));
+        }
+
+        lockout.overridden_at = Some(now);
+        tracing::warn!("Günlük kayıp kilidi kullanıcı tarafından kaldırıldı");
+        self.store(&lockout).await;
+        *self.lockout.write().await = Some(lockout.clone());
+        Ok(lockout)
+    }
+
+    /// Kilidi veritabanına yaz (yazılamazsa bellekte geçerli kalır)
+    async fn store(&self, lockout: &TradingLockout) {
+        let result = match serde_json::to_string(lockout) {
+            Ok(json) => db::save_setting(LOCKOUT_KEY, &json).await,
+            Err(e) => Err(e.to_string()),
+        };
+        if let Err(e) = result {
+            tracing::error!("Günlük kayıp kilidi kaydedilemedi: {}", e);
+        }
+    }
+}
+
+/// Kayıtlı gerçekleşmelerden işlem gününün kâr/zararı
+pub async fn daily_pnl(day: NaiveDate, timezone: Tz) -> Result<DailyPnl, String> {
+    let start = session_start(day, timezone);
+    let end = session_start(day + Duration::days(1), timezone);
+    let (realized_pnl, fees, executions) = db::get_execution_totals(start, end).await?;
+    Ok(DailyPnl {
+        trading_day: day,
+        realized_pnl,
+        fees,
+        net_pnl: realized_pnl - fees,
+        executions,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(secs: i64) -> DateTime<Utc> {
+        DateTime::from_timestamp(secs, 0).unwrap()
+    }
+
+    #[test]
+    fn test_trading_day_boundaries() {
+        // 1970-01-01 22:00 UTC, İstanbul'da (UTC+2) ertesi gün
+        let time = at(22 * 3600);
+        assert_eq!(trading_day(time, Tz::UTC), NaiveDate::from_ymd_opt(1970, 1, 1).unwrap());
+        let day = trading_day(time, Tz::Europe__Istanbul);
+        assert_eq!(day, NaiveDate::from_ymd_opt(1970, 1, 2).unwrap());
+        assert_eq!(session_start(day, Tz::Europe__Istanbul), at(22 * 3600));
+
+        // Yaz saati: New York'ta sıfırlama kışın 05:00, yazın 04:00 UTC'de
+        let date = |m, d| NaiveDate::from_ymd_opt(2024, m, d).unwrap();
+        let new_york = Tz::America__New_York;
+        let utc = |m, d, h| date(m, d).and_hms_opt(h, 0, 0).unwrap().and_utc();
+        assert_eq!(session_start(date(3, 9), new_york), utc(3, 9, 5));
+        assert_eq!(session_start(date(3, 11), new_york), utc(3, 11, 4));
+        assert_eq!(trading_day(utc(3, 11, 3), new_york), date(3, 10));
+        // Gece yarısı atlanan günde (Havana, 00:00 -> 01:00) gün geçiş anında başlar
+        assert_eq!(session_start(date(3, 10), Tz::America__Havana), utc(3, 10, 5));
+
+        assert_eq!(limit_breach(-40.0, 1000.0, 5.0), None);
+        assert_eq!(limit_breach(-60.0, 1000.0, 5.0), Some(6.0));
+        assert_eq!(limit_breach(80.0, 1000.0, 5.0), None);
+    }
+
+    #[test]
+    fn test_lockout_cooldown() {
+        let lockout = TradingLockout {
+            trading_day: NaiveDate::from_ymd_opt(1970, 1, 1).unwrap(),
+            locked_at: at(3600),
+            until: at(86_400),
+            loss_percent: 6.0,
+            limit_percent: 5.0,
+            overridden_at: None,
+        };
+        assert!(is_blocking(&lockout, at(7200)));
+        assert!(!is_blocking(&lockout, at(86_400)));
+        assert_eq!(override_available_at(&lockout, 60), at(7200));
+
+        let overridden = TradingLockout { overridden_at: Some(at(7200)), ..lockout };
+        assert!(!is_blocking(&overridden, at(7300)));
+    }
+}
@@ -1,8 +1,20 @@
 // AlgoTrade OS - Event Dispatcher
 // Borsa verilerini modüllere dağıtan Pub/Sub sistemi
+//
+// Olaylar iki şeritten akar:
+// - Conflated: fiyat tick'leri sembol başına birleştirilir, yavaş abone sadece son fiyatı görür
+// - Lossless: emir, pozisyon ve alarm olayları abone başına kayıpsız kuyrukta bekler
+//
+// Kayıpsız kuyruk sınırlıdır: dolduğunda yeni olaylar o abone için atlanır, abone geride
+// işaretlenir ve atlanan olaylar sayılır. Takılan tek abone süreç belleğini büyütemez.
 
-use tokio::sync::broadcast;
-use crate::models::StandardTick;
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tokio::sync::Notify;
+use crate::models::{RiskWarning, StandardTick, StopMove};
 
 /// Sistem içindeki olaylar
 #[derive(Debug, Clone)]
@@ -19,41 +31,437 @@ pub enum AppEvent {
     AlarmTriggered { alarm_id: String },
     /// Modül durumu değişti
     ModuleStateChanged { module_id: String, is_active: bool },
+    /// Pozisyonun stopu taşındı
+    StopMoved(StopMove),
+    /// Risk limitine yaklaşıldı (değer eşiği her aştığında bir kez)
+    RiskWarning(RiskWarning),
+    /// Modülün zamanlayıcısı tetiklendi (borsa saatine göre)
+    TimerFired { module_id: String, timer_id: String, fired_at: DateTime<Utc> },
+    /// Modülün takip ettiği mum kapandı
+    CandleClosed { module_id: String, symbol: String, interval: String, close_time: DateTime<Utc> },
+    /// Abone kayıpsız kuyrukta geride kaldı (sadece ilgili aboneye teslim edilir)
+    SubscriberLagged { subscriber: String, pending: usize },
+}
+
+/// Olayın teslim şeridi
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventLane {
+    /// Son değer geçerli - yeni tick aynı sembolün bekleyen tick'inin üzerine yazar
+    Conflated,
+    /// Kayıpsız - her olay sırayla teslim edilir
+    Lossless,
+}
+
+impl AppEvent {
+    /// Olayın hangi şeritten teslim edileceği
+    pub fn lane(&self) -> EventLane {
+        match self {
+            AppEvent::PriceUpdated(_) => EventLane::Conflated,
+            _ => EventLane::Lossless,
+        }
+    }
+}
+
+/// Abone başına teslim metrikleri
+#[derive(Debug, Clone, Serialize)]
+pub struct SubscriberMetrics {
+    pub subscriber: String,
+    /// Kayıpsız kuyrukta bekleyen olay sayısı
+    pub pending_events: usize,
+    /// Teslim bekleyen (birleştirilmiş) tick sayısı
+    pub pending_ticks: usize,
+    /// Teslim edilen toplam olay
+    pub delivered: u64,
+    /// Üzerine yazılarak atlanan tick sayısı
+    pub conflated_ticks: u64,
+    /// Kayıpsız kuyruğun ulaştığı en yüksek derinlik
+    pub max_queue_depth: usize,
+    /// Kuyruk dolu olduğu için atlanan olay sayısı
+    pub dropped_events: u64,
+    /// Kaç kez gecikme eşiği aşıldı
+    pub lag_events: u64,
+    /// Şu an geride mi?
+    pub is_lagging: bool,
+}
+
+/// Sembol başına son tick (varış sırası korunur)
+#[derive(Default)]
+struct ConflatedTicks {
+    order: VecDeque<String>,
+    latest: HashMap<String, StandardTick>,
+}
+
+/// Abone iç durumu - dispatcher ve Subscription arasında paylaşılır
+struct SubscriberInner {
+    name: String,
+    lag_threshold: usize,
+    capacity: usize,
+    ticks: Mutex<ConflatedTicks>,
+    queue: Mutex<VecDeque<AppEvent>>,
+    notify: Notify,
+    closed: AtomicBool,
+    lagging: AtomicBool,
+    delivered: AtomicU64,
+    conflated_ticks: AtomicU64,
+    max_queue_depth: AtomicUsize,
+    lag_events: AtomicU64,
+    overflowing: AtomicBool,
+    dropped_events: AtomicU64,
+}
+
+impl SubscriberInner {
+    fn push(&self, event: AppEvent) {
+        match event {
+            AppEvent::PriceUpdated(tick) => {
+                let mut ticks = self.ticks.lock().unwrap();
+                let symbol = tick.symbol.clone();
+                if ticks.latest.insert(symbol.clone(), tick).is_some() {
+                    self.conflated_ticks.fetch_add(1, Ordering::Relaxed);
+                } else {
+                    ticks.order.push_back(symbol);
+                }
+            }
+            event => {
+                let mut queue = self.queue.lock().unwrap();
+                // Kuyruk dolu: olay bu abone için atlanır (kuyruk yarı eşiğe inene kadar tek uyarı)
+                if queue.len() >= self.capacity {
+                    self.dropped_events.fetch_add(1, Ordering::Relaxed);
+                    self.lagging.store(true, Ordering::Relaxed);
+                    if !self.overflowing.swap(true, Ordering::Relaxed) {
+                        tracing::error!(
+                            "Abone kuyruğu doldu, olaylar atlanıyor: {} ({} olay bekliyor)",
+                            self.name,
+                            queue.len()
+                        );
+                    }
+                    return;
+                }
+                queue.push_back(event);
+                let depth = queue.len();
+                self.max_queue_depth.fetch_max(depth, Ordering::Relaxed);
+
+                // Eşik aşıldıysa aboneyi bir kez uyar (kuyruk boşalınca yeniden kurulur)
+                if depth > self.lag_threshold && !self.lagging.swap(true, Ordering::Relaxed) {
+                    self.lag_events.fetch_add(1, Ordering::Relaxed);
+                    tracing::warn!(
+                        "Abone geride kaldı: {} ({} olay bekliyor)",
+                        self.name,
+                        depth
+                    );
+                    queue.push_front(AppEvent::SubscriberLagged {
+                        subscriber: self.name.clone(),
+                        pending: depth,
+                    });
+                }
+            }
+        }
+        self.notify.notify_one();
+    }
+
+    fn try_pop(&self) -> Option<AppEvent> {
+        // Kritik olaylar tick'lerden önce teslim edilir
+        {
+            let mut queue = self.queue.lock().unwrap();
+            if let Some(event) = queue.pop_front() {
+                if queue.len() <= self.lag_threshold / 2 {
+                    self.lagging.store(false, Ordering::Relaxed);
+                    self.overflowing.store(false, Ordering::Relaxed);
+                }
+                self.delivered.fetch_add(1, Ordering::Relaxed);
+                return Some(event);
+            }
+        }
+
+        let mut ticks = self.ticks.lock().unwrap();
+        while let Some(symbol) = ticks.order.pop_front() {
+            if let Some(tick) = ticks.latest.remove(&symbol) {
+                self.delivered.fetch_add(1, Ordering::Relaxed);
+                return Some(AppEvent::PriceUpdated(tick));
+            }
+        }
+        None
+    }
+
+    fn metrics(&self) -> SubscriberMetrics {
+        SubscriberMetrics {
+            subscriber: self.name.clone(),
+            pending_events: self.queue.lock().unwrap().len(),
+            pending_ticks: self.ticks.lock().unwrap().latest.len(),
+            delivered: self.delivered.load(Ordering::Relaxed),
+            conflated_ticks: self.conflated_ticks.load(Ordering::Relaxed),
+            max_queue_depth: self.max_queue_depth.load(Ordering::Relaxed),
+            dropped_events: self.dropped_events.load(Ordering::Relaxed),
+            lag_events: self.lag_events.load(Ordering::Relaxed),
+            is_lagging: self.lagging.load(Ordering::Relaxed),
+        }
+    }
+
+    fn close(&self) {
+        self.closed.store(true, Ordering::Relaxed);
+        self.notify.notify_one();
+    }
+}
+
+/// Olay aboneliği - her modül kendi aboneliğinden okur
+pub struct Subscription {
+    inner: Arc<SubscriberInner>,
+}
+
+impl Subscription {
+    /// Sıradaki olayı bekle (dispatcher kapanırsa None)
+    pub async fn recv(&self) -> Option<AppEvent> {
+        loop {
+            if let Some(event) = self.inner.try_pop() {
+                return Some(event);
+            }
+            if self.inner.closed.load(Ordering::Relaxed) {
+                return None;
+            }
+            self.inner.notify.notified().await;
+        }
+    }
+
+    /// Beklemeden olay al
+    pub fn try_recv(&self) -> Option<AppEvent> {
+        self.inner.try_pop()
+    }
+
+    /// Bu aboneliğin metrikleri
+    pub fn metrics(&self) -> SubscriberMetrics {
+        self.inner.metrics()
+    }
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        self.inner.closed.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Dispatcher iç durumu - son kopya düşünce abonelere kapanış bildirilir
+struct DispatcherInner {
+    lag_threshold: usize,
+    capacity: usize,
+    subscribers: Mutex<Vec<Arc<SubscriberInner>>>,
+}
+
+impl Drop for DispatcherInner {
+    fn drop(&mut self) {
+        if let Ok(subscribers) = self.subscribers.lock() {
+            for subscriber in subscribers.iter() {
+                subscriber.close();
+            }
+        }
+    }
 }
 
 /// Olay dağıtıcı - tüm modüller buradan veri alır
 pub struct EventDispatcher {
-    sender: broadcast::Sender<AppEvent>,
+    inner: Arc<DispatcherInner>,
 }
 
 impl EventDispatcher {
     /// Yeni bir dispatcher oluştur
-    pub fn new(capacity: usize) -> Self {
-        let (sender, _) = broadcast::channel(capacity);
-        Self { sender }
+    ///
+    /// `lag_threshold`: kayıpsız kuyrukta bu kadar olay birikince abone "geride" sayılır
+    /// `capacity`: abone başına kayıpsız kuyruğun üst sınırı (aşan olaylar atlanır)
+    pub fn new(lag_threshold: usize, capacity: usize) -> Self {
+        Self {
+            inner: Arc::new(DispatcherInner {
+                lag_threshold,
+                capacity: capacity.max(lag_threshold + 1),
+                subscribers: Mutex::new(Vec::new()),
+            }),
+        }
     }
 
-    /// Olay yayınla
-    pub fn publish(&self, event: AppEvent) -> Result<usize, broadcast::error::SendError<AppEvent>> {
-        self.sender.send(event)
+    /// Olay yayınla - olayı alan abone sayısını döndürür
+    pub fn publish(&self, event: AppEvent) -> usize {
+        let mut subscribers = self.inner.subscribers.lock().unwrap();
+        subscribers.retain(|s| !s.closed.load(Ordering::Relaxed));
+
+        for subscriber in subscribers.iter() {
+            subscriber.push(event.clone());
+        }
+        subscribers.len()
     }
 
     /// Olaylara abone ol
-    pub fn subscribe(&self) -> broadcast::Receiver<AppEvent> {
-        self.sender.subscribe()
+    pub fn subscribe(&self, name: &str) -> Subscription {
+        let inner = Arc::new(SubscriberInner {
+            name: name.to_string(),
+            lag_threshold: self.inner.lag_threshold,
+            capacity: self.inner.capacity,
+            ticks: Mutex::new(ConflatedTicks::default()),
+            queue: Mutex::new(VecDeque::new()),
+            notify: Notify::new(),
+            closed: AtomicBool::new(false),
+            lagging: AtomicBool::new(false),
+            delivered: AtomicU64::new(0),
+            conflated_ticks: AtomicU64::new(0),
+            max_queue_depth: AtomicUsize::new(0),
+            lag_events: AtomicU64::new(0),
+            overflowing: AtomicBool::new(false),
+            dropped_events: AtomicU64::new(0),
+        });
+
+        self.inner.subscribers.lock().unwrap().push(inner.clone());
+        Subscription { inner }
+    }
+
+    /// Tüm abonelerin metrikleri
+    pub fn metrics(&self) -> Vec<SubscriberMetrics> {
+        self.inner
+            .subscribers
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|s| !s.closed.load(Ordering::Relaxed))
+            .map(|s| s.metrics())
+            .collect()
     }
 }
 
 impl Default for EventDispatcher {
     fn default() -> Self {
-        Self::new(1024) // Varsayılan kapasite
+        Self::new(1024, 16 * 1024) // Varsayılan gecikme eşiği ve kuyruk sınırı
     }
 }
 
 impl Clone for EventDispatcher {
     fn clone(&self) -> Self {
         Self {
-            sender: self.sender.clone(),
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing;
+
+    fn tick(symbol: &str, price: f64) -> AppEvent {
+        AppEvent::PriceUpdated(testing::tick(symbol, price))
+    }
+
+    #[tokio::test]
+    async fn test_ticks_are_conflated() {
+        let dispatcher = EventDispatcher::default();
+        let sub = dispatcher.subscribe("test");
+
+        dispatcher.publish(tick("BTCUSDT", 100.0));
+        dispatcher.publish(tick("ETHUSDT", 10.0));
+        dispatcher.publish(tick("BTCUSDT", 101.0));
+        dispatcher.publish(tick("BTCUSDT", 102.0));
+
+        match sub.recv().await {
+            Some(AppEvent::PriceUpdated(t)) => {
+                assert_eq!(t.symbol, "BTCUSDT");
+                assert_eq!(t.price, 102.0);
+            }
+            other => panic!("beklenmeyen olay: {:?}", other),
+        }
+        assert!(matches!(sub.recv().await, Some(AppEvent::PriceUpdated(t)) if t.symbol == "ETHUSDT"));
+        assert!(sub.try_recv().is_none());
+        assert_eq!(sub.metrics().conflated_ticks, 2);
+    }
+
+    #[tokio::test]
+    async fn test_critical_events_are_lossless_and_first() {
+        let dispatcher = EventDispatcher::default();
+        let sub = dispatcher.subscribe("test");
+
+        dispatcher.publish(tick("BTCUSDT", 100.0));
+        for i in 0..100 {
+            dispatcher.publish(AppEvent::PositionClosed {
+                position_id: i.to_string(),
+                pnl: 0.0,
+            });
         }
+
+        for i in 0..100 {
+            match sub.recv().await {
+                Some(AppEvent::PositionClosed { position_id, .. }) => {
+                    assert_eq!(position_id, i.to_string())
+                }
+                other => panic!("beklenmeyen olay: {:?}", other),
+            }
+        }
+        assert!(matches!(sub.recv().await, Some(AppEvent::PriceUpdated(_))));
+    }
+
+    #[tokio::test]
+    async fn test_lagging_subscriber_is_notified() {
+        let dispatcher = EventDispatcher::new(4, 64);
+        let slow = dispatcher.subscribe("slow");
+
+        for i in 0..10 {
+            dispatcher.publish(AppEvent::AlarmTriggered {
+                alarm_id: i.to_string(),
+            });
+        }
+
+        let metrics = slow.metrics();
+        assert!(metrics.is_lagging);
+        assert_eq!(metrics.lag_events, 1);
+
+        // Uyarı kuyruğun başına eklenir, ardından hiçbir olay kaybolmaz
+        assert!(matches!(
+            slow.recv().await,
+            Some(AppEvent::SubscriberLagged { pending: 5, .. })
+        ));
+        let mut received = 0;
+        while let Some(event) = slow.try_recv() {
+            assert!(matches!(event, AppEvent::AlarmTriggered { .. }));
+            received += 1;
+        }
+        assert_eq!(received, 10);
+        assert!(!slow.metrics().is_lagging);
+    }
+
+    #[tokio::test]
+    async fn test_full_queue_drops_new_events_for_that_subscriber() {
+        let dispatcher = EventDispatcher::new(4, 8);
+        let stalled = dispatcher.subscribe("stalled");
+        let healthy = dispatcher.subscribe("healthy");
+
+        for i in 0..20 {
+            dispatcher.publish(AppEvent::AlarmTriggered {
+                alarm_id: i.to_string(),
+            });
+            while healthy.try_recv().is_some() {}
+        }
+
+        // Kuyruk sınırda kalır: lag uyarısı + ilk 7 olay, kalanlar atlanır
+        let metrics = stalled.metrics();
+        assert_eq!(metrics.pending_events, 8);
+        assert_eq!(metrics.dropped_events, 13);
+        assert!(metrics.is_lagging);
+        assert_eq!(healthy.metrics().dropped_events, 0);
+
+        assert!(matches!(stalled.recv().await, Some(AppEvent::SubscriberLagged { .. })));
+        let ids: Vec<String> = std::iter::from_fn(|| stalled.try_recv())
+            .map(|e| match e {
+                AppEvent::AlarmTriggered { alarm_id } => alarm_id,
+                other => panic!("beklenmeyen olay: {:?}", other),
+            })
+            .collect();
+        assert_eq!(ids, (0..7).map(|i| i.to_string()).collect::<Vec<_>>());
+
+        // Boşalan kuyruk yeniden olay kabul eder
+        dispatcher.publish(AppEvent::AlarmTriggered { alarm_id: "20".into() });
+        assert!(matches!(stalled.try_recv(), Some(AppEvent::AlarmTriggered { alarm_id }) if alarm_id == "20"));
+        assert!(!stalled.metrics().is_lagging);
+    }
+
+    #[tokio::test]
+    async fn test_dropped_subscription_is_pruned() {
+        let dispatcher = EventDispatcher::default();
+        let sub = dispatcher.subscribe("temp");
+        assert_eq!(dispatcher.publish(tick("BTCUSDT", 1.0)), 1);
+        drop(sub);
+        assert_eq!(dispatcher.publish(tick("BTCUSDT", 1.0)), 0);
     }
 }
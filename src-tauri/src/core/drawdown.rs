@@ -0,0 +1,277 @@
+// AlgoTrade OS - Drawdown Circuit Breaker
+// Hesap varlığı eğrisi, tepeden düşüş takibi ve kademeli koruma
+//
+// Varlık cüzdan güncellemelerinden (bakiye sorgusu, risk kapısı, risk izleyici) ve
+// core::balance'ın periyodik olarak yayınladığı BalanceChanged olaylarından kaydedilir.
+// Tepeden düşüş eşikleri aşıldıkça:
+// uyarı yayınlanır -> pozisyon boyutları yarıya iner -> acil durdurma devreye girer.
+// Acil durdurma kapanmaz; kullanıcı sıfırlayınca tepe güncel varlığa çekilir.
+
+use chrono::{DateTime, Duration, Utc};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use crate::db;
+use crate::models::{DrawdownLimits, DrawdownStatus, DrawdownTier, EquityPoint, KillSwitch, RiskWarning, RiskWarningKind};
+use super::balance::EQUITY_COIN;
+use super::{AppEvent, AppState, EventDispatcher};
+
+/// Durumun saklandığı ayar anahtarı
+const STATUS_KEY: &str = "drawdown_status";
+
+/// Varlık değişmediğinde eğriye en sık yazma aralığı
+const MIN_POINT_INTERVAL_SECS: i64 = 60;
+
+/// Kademe uyarılarının i18n anahtarı
+const WARNING_KEY: &str = "warning.drawdown";
+
+/// Yarım boyut kademesindeki boyut çarpanı
+const REDUCED_SIZE_FACTOR: f64 = 0.5;
+
+/// Tepeden düşüş (%)
+pub fn drawdown_percent(peak: f64, equity: f64) -> f64 {
+    if peak > 0.0 {
+        ((peak - equity) / peak * 100.0).max(0.0)
+    } else {
+        0.0
+    }
+}
+
+/// Düşüşün denk geldiği kademe
+pub fn tier_for(drawdown: f64, limits: &DrawdownLimits) -> DrawdownTier {
+    if drawdown >= limits.kill_switch_pct {
+        DrawdownTier::Halted
+    } else if drawdown >= limits.reduce_size_pct {
+        DrawdownTier::ReducedSize
+    } else if drawdown >= limits.warn_pct {
+        DrawdownTier::Warning
+    } else {
+        DrawdownTier::Normal
+    }
+}
+
+/// Yeni varlık değerini duruma uygula, kademe yükseldiyse yeni kademeyi döndür
+///
+/// Acil durdurma devredeyken kademe Halted kalır.
+pub fn apply_equity(
+    status: &mut DrawdownStatus,
+    equity: f64,
+    limits: &DrawdownLimits,
+    now: DateTime<Utc>,
+) -> Option<DrawdownTier> {
+    if equity > status.peak {
+        status.peak = equity;
+        status.peak_at = Some(now);
+    }
+    status.equity = equity;
+    status.drawdown_pct = drawdown_percent(status.peak, equity);
+    status.max_drawdown_pct = status.max_drawdown_pct.max(status.drawdown_pct);
+    status.updated_at = Some(now);
+
+    let previous = status.tier;
+    let tier = tier_for(status.drawdown_pct, limits);
+    if tier == DrawdownTier::Halted && status.kill_switch.is_none() {
+        status.kill_switch = Some(KillSwitch {
+            engaged_at: now,
+            reason: "drawdown".to_string(),
+            drawdown_pct: status.drawdown_pct,
+        });
+    }
+    status.tier = if status.kill_switch.is_some() { DrawdownTier::Halted } else { tier };
+    (status.tier > previous).then_some(status.tier)
+}
+
+#[derive(Default)]
+struct TrackerState {
+    status: DrawdownStatus,
+    /// Eğriye son yazılan nokta
+    last_point: Option<EquityPoint>,
+}
+
+/// Tepeden düşüş takibi ve acil durdurma anahtarı
+pub struct DrawdownTracker {
+    state: RwLock<TrackerState>,
+    dispatcher: EventDispatcher,
+}
+
+impl DrawdownTracker {
+    pub fn new(dispatcher: EventDispatcher) -> Self {
+        Self {
+            state: RwLock::new(TrackerState::default()),
+            dispatcher,
+        }
+    }
+
+    /// Kayıtlı durumu yükle (uygulama açılışında)
+    pub async fn load(&self) -> Result<(), String> {
+        if let Some(json) = db::get_setting(STATUS_KEY).await? {
+            let status: DrawdownStatus = serde_json::from_str(&json).map_err(|e| e.to_string())?;
+            self.state.write().await.status = status;
+        }
+        Ok(())
+    }
+
+    pub async fn status(&self) -> DrawdownStatus {
+        self.state.read().await.status.clone()
+    }
+
+    /// Devredeki acil durdurma
+    pub async fn kill_switch(&self) -> Option<KillSwitch> {
+        self.state.read().await.status.kill_switch.clone()
+    }
+
+    /// Pozisyon boyutlarına uygulanacak çarpan
+    pub async fn sizing_factor(&self) -> f64 {
+        if self.state.read().await.status.tier >= DrawdownTier::ReducedSize {
+            REDUCED_SIZE_FACTOR
+        } else {
+            1.0
+        }
+    }
+
+    /// Hesap varlığını kaydet ve eşikleri uygula
+    pub async fn record(&self, equity: f64, limits: &DrawdownLimits, now: DateTime<Utc>) {
+        if !equity.is_finite() || equity <= 0.0 {
+            return;
+        }
+
+        let (escalated, point, status) = {
+            let mut state = self.state.write().await;
+            let escalated = apply_equity(&mut state.status, equity, limits, now);
+            let due = state.last_point.as_ref().is_none_or(|last| {
+                last.equity != equity || now - last.recorded_at >= Duration::seconds(MIN_POINT_INTERVAL_SECS)
+            });
+            let point = due.then_some(EquityPoint { equity, recorded_at: now });
+            if let Some(point) = &point {
+                state.last_point = Some(point.clone());
+            }
+            (escalated, point, state.status.clone())
+        };
+
+        if let Some(point) = &point {
+            if let Err(e) = db::save_equity_point(point).await {
+                tracing::warn!("Varlık noktası kaydedilemedi: {}", e);
+            }
+        }
+        if point.is_some() || escalated.is_some() {
+            self.store(&status).await;
+        }
+
+        if let Some(tier) = escalated {
+            let limit = match tier {
+                DrawdownTier::Halted => limits.kill_switch_pct,
+                DrawdownTier::ReducedSize => limits.reduce_size_pct,
+                _ => limits.warn_pct,
+            };
+            match tier {
+                DrawdownTier::Halted => tracing::error!(
+                    "Tepeden düşüş %{:.2}: acil durdurma devreye girdi, yeni pozisyonlar engellendi",
+                    status.drawdown_pct
+                ),
+                _ => tracing::warn!("Tepeden düşüş %{:.2}: {:?} kademesine geçildi", status.drawdown_pct, tier),
+            }
+            let _ = self.dispatcher.publish(AppEvent::RiskWarning(RiskWarning {
+                kind: RiskWarningKind::Drawdown,
+                message_key: WARNING_KEY.to_string(),
+                value: status.drawdown_pct,
+                limit,
+                raised_at: now,
+            }));
+        }
+    }
+
+    /// Acil durdurmayı elle devreye al
+    pub async fn engage_kill_switch(&self, now: DateTime<Utc>) -> KillSwitch {
+        let status = {
+            let mut state = self.state.write().await;
+            let drawdown_pct = state.status.drawdown_pct;
+            state.status.kill_switch.get_or_insert_with(|| KillSwitch {
+                engaged_at: now,
+                reason: "manual".to_string(),
+                drawdown_pct,
+            });
+            state.status.tier = DrawdownTier::Halted;
+            state.status.clone()
+        };
+        tracing::error!("Acil durdurma elle devreye alındı");
+        self.store(&status).await;
+        status.kill_switch.expect("kill switch set above")
+    }
+
+    /// Acil durdurmayı kaldır, tepeyi güncel varlığa çek
+    pub async fn reset_kill_switch(&self, now: DateTime<Utc>) -> Result<DrawdownStatus, String> {
+        let status = {
+            let mut state = self.state.write().await;
+            if state.status.kill_switch.take().is_none() {
+                return Err("Acil durdurma devrede değil".to_string());
+            }
+            let status = &mut state.status;
+            status.peak = status.equity;
+            status.peak_at = Some(now);
+            status.drawdown_pct = 0.0;
+            status.tier = DrawdownTier::Normal;
+            status.clone()
+        };
+        tracing::warn!("Acil durdurma kullanıcı tarafından kaldırıldı");
+        self.store(&status).await;
+        Ok(status)
+    }
+
+    /// Durumu veritabanına yaz (yazılamazsa bellekte geçerli kalır)
+    async fn store(&self, status: &DrawdownStatus) {
+        let result = match serde_json::to_string(status) {
+            Ok(json) => db::save_setting(STATUS_KEY, &json).await,
+            Err(e) => Err(e.to_string()),
+        };
+        if let Err(e) = result {
+            tracing::error!("Tepeden düşüş durumu kaydedilemedi: {}", e);
+        }
+    }
+}
+
+/// BalanceChanged olaylarındaki hesap varlığını eğriye işle
+pub fn spawn_equity_watch(state: Arc<AppState>) {
+    let subscription = state.dispatcher.subscribe("drawdown");
+    tauri::async_runtime::spawn(async move {
+        while let Some(event) = subscription.recv().await {
+            if let AppEvent::BalanceChanged { symbol, balance } = event {
+                if symbol == EQUITY_COIN {
+                    let limits = state.settings.read().await.drawdown_limits.clone();
+                    state.drawdown.record(balance, &limits, Utc::now()).await;
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(secs: i64) -> DateTime<Utc> {
+        DateTime::from_timestamp(secs, 0).unwrap()
+    }
+
+    #[test]
+    fn test_tiers_escalate_and_latch() {
+        let limits = DrawdownLimits::default();
+        let mut status = DrawdownStatus::default();
+
+        assert_eq!(apply_equity(&mut status, 1000.0, &limits, at(0)), None);
+        assert_eq!(apply_equity(&mut status, 1100.0, &limits, at(1)), None);
+        assert_eq!(status.peak, 1100.0);
+        // %5 düşüş
+        assert_eq!(apply_equity(&mut status, 1045.0, &limits, at(2)), Some(DrawdownTier::Warning));
+        assert_eq!(apply_equity(&mut status, 1040.0, &limits, at(3)), None);
+        assert_eq!(apply_equity(&mut status, 979.0, &limits, at(4)), Some(DrawdownTier::ReducedSize));
+        // Toparlanınca kademe iner
+        assert_eq!(apply_equity(&mut status, 1080.0, &limits, at(5)), None);
+        assert_eq!(status.tier, DrawdownTier::Normal);
+        assert!((status.max_drawdown_pct - 11.0).abs() < 1e-9);
+
+        assert_eq!(apply_equity(&mut status, 930.0, &limits, at(6)), Some(DrawdownTier::Halted));
+        assert_eq!(status.kill_switch.as_ref().map(|k| k.reason.as_str()), Some("drawdown"));
+        // Acil durdurma toparlanmada kalkmaz
+        assert_eq!(apply_equity(&mut status, 1100.0, &limits, at(7)), None);
+        assert_eq!(status.tier, DrawdownTier::Halted);
+    }
+}
@@ -0,0 +1,213 @@
+// AlgoTrade OS - Drawing Geometry
+// Kayıtlı grafik çizimlerinin noktalarını zamana izdüşürür (çizim alarmları için)
+//
+// Noktalar grafikteki gibi zaman-fiyat doğrusu olarak yorumlanır: trend çizgisi sadece iki nokta
+// arasında (stilde uzatılmadıysa), ışın ikinci noktadan sonra sonsuza kadar devam eder. Paralel
+// kanalın ikinci sınırı üçüncü noktanın ilk noktaya fiyat farkı kadar kaydırılmış halidir.
+// Fib seviyeleri yatay olduğundan çizimin sağ ucundan sonra da izlenir.
+
+use serde_json::Value;
+use crate::db::{Drawing, DrawingPoint};
+use crate::models::DrawingTrigger;
+
+/// Bu değerin altındaki zaman damgaları saniye kabul edilir (eski kayıtlar saniye, grafik ms yazar)
+const SECONDS_THRESHOLD: i64 = 10_000_000_000;
+
+/// İki noktadan geçen zaman-fiyat doğrusu
+#[derive(Debug, Clone, PartialEq)]
+pub struct Segment {
+    /// Başlangıç zamanı (ms) ve fiyatı
+    pub start: (i64, f64),
+    pub end: (i64, f64),
+    pub extend_left: bool,
+    pub extend_right: bool,
+}
+
+impl Segment {
+    /// `time` anında (ms) çizginin fiyatı (çizgi o anda yoksa None)
+    pub fn price_at(&self, time: i64) -> Option<f64> {
+        let ((t0, p0), (t1, p1)) = if self.start.0 <= self.end.0 {
+            (self.start, self.end)
+        } else {
+            (self.end, self.start)
+        };
+        if t0 == t1 || (time < t0 && !self.extend_left) || (time > t1 && !self.extend_right) {
+            return None;
+        }
+        Some(p0 + (p1 - p0) * (time - t0) as f64 / (t1 - t0) as f64)
+    }
+}
+
+/// Alarmların izleyebildiği çizim geometrisi
+#[derive(Debug, Clone, PartialEq)]
+pub enum DrawingGeometry {
+    /// trendline, ray
+    Line(Segment),
+    Horizontal { price: f64 },
+    /// Ana çizgi ve `offset` kadar kaydırılmış paralel sınır
+    Channel { line: Segment, offset: f64 },
+    /// 0 seviyesi `start`, 1 seviyesi `end` fiyatı
+    FibRetracement { start: f64, end: f64 },
+}
+
+impl DrawingGeometry {
+    /// Kayıtlı çizimden geometri çıkar
+    pub fn from_drawing(drawing: &Drawing) -> Result<Self, String> {
+        let points: Vec<DrawingPoint> = serde_json::from_str(&drawing.points)
+            .map_err(|e| format!("Çizim noktaları okunamadı: {}", e))?;
+        let style: Value = serde_json::from_str(&drawing.style).unwrap_or(Value::Null);
+        let flag = |key: &str| style.get(key).and_then(Value::as_bool).unwrap_or(false);
+        let required = match drawing.drawing_type.as_str() {
+            "horizontal" => 1,
+            "trendline" | "ray" | "fib_retracement" => 2,
+            "parallel_channel" => 3,
+            other => return Err(format!("Alarm desteklemeyen çizim tipi: {}", other)),
+        };
+        if points.len() < required {
+            return Err(format!("{} için en az {} nokta gerekli", drawing.drawing_type, required));
+        }
+
+        let segment = |extend_right| Segment {
+            start: (to_millis(points[0].time), points[0].price),
+            end: (to_millis(points[1].time), points[1].price),
+            extend_left: flag("extendLeft"),
+            extend_right,
+        };
+        Ok(match drawing.drawing_type.as_str() {
+            "horizontal" => Self::Horizontal { price: points[0].price },
+            "trendline" => Self::Line(segment(flag("extendRight"))),
+            "ray" => Self::Line(segment(true)),
+            "parallel_channel" => Self::Channel { line: segment(flag("extendRight")), offset: points[2].price - points[0].price },
+            _ => Self::FibRetracement { start: points[0].price, end: points[1].price },
+        })
+    }
+
+    /// Tetikleyici bu çizim tipinde kullanılabilir mi?
+    pub fn supports(&self, trigger: &DrawingTrigger) -> bool {
+        matches!(
+            (self, trigger),
+            (Self::Line(_) | Self::Horizontal { .. } | Self::Channel { .. }, DrawingTrigger::Cross)
+                | (Self::Channel { .. }, DrawingTrigger::ExitChannel)
+                | (Self::FibRetracement { .. }, DrawingTrigger::FibLevel { .. })
+        )
+    }
+
+    /// `time` anında (ms) çizgilerin fiyatları
+    pub fn lines_at(&self, time: i64) -> Vec<f64> {
+        match self {
+            Self::Line(segment) => segment.price_at(time).into_iter().collect(),
+            Self::Horizontal { price } => vec![*price],
+            Self::Channel { line, offset } => line.price_at(time).map(|p| vec![p, p + offset]).unwrap_or_default(),
+            Self::FibRetracement { .. } => Vec::new(),
+        }
+    }
+
+    /// Fiyat `previous` -> `current` hareketinde `time` anında (ms) tetikleyici sağlandı mı?
+    ///
+    /// Tick'ler arası çizgi eğimi ihmal edilir, iki fiyat da `time` anındaki çizgiyle karşılaştırılır.
+    pub fn triggered(&self, trigger: &DrawingTrigger, previous: f64, current: f64, time: i64) -> bool {
+        if !self.supports(trigger) {
+            return false;
+        }
+        match (self, trigger) {
+            (Self::Channel { line, offset }, DrawingTrigger::ExitChannel) => {
+                let Some(price) = line.price_at(time) else {
+                    return false;
+                };
+                let (low, high) = (price.min(price + offset), price.max(price + offset));
+                let inside = |p: f64| (low..=high).contains(&p);
+                inside(previous) && !inside(current)
+            }
+            (Self::FibRetracement { start, end }, DrawingTrigger::FibLevel { level }) => {
+                crosses(previous, current, start + (end - start) * level)
+            }
+            _ => self.lines_at(time).into_iter().any(|line| crosses(previous, current, line)),
+        }
+    }
+}
+
+/// Saniye veya ms zaman damgasını ms'ye çevir
+fn to_millis(time: i64) -> i64 {
+    if time.abs() < SECONDS_THRESHOLD {
+        time * 1000
+    } else {
+        time
+    }
+}
+
+/// Fiyat seviyeye iki yönden birinde ulaştı/kesti mi?
+fn crosses(previous: f64, current: f64, level: f64) -> bool {
+    (previous < level && current >= level) || (previous > level && current <= level)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn drawing(drawing_type: &str, points: &str, style: &str) -> Drawing {
+        Drawing {
+            id: "d".into(),
+            symbol: "BTCUSDT".into(),
+            interval: "60".into(),
+            drawing_type: drawing_type.into(),
+            points: points.into(),
+            style: style.into(),
+            visible: true,
+            locked: false,
+            created_at: 0,
+            updated_at: 0,
+            name: None,
+            group_id: None,
+        }
+    }
+
+    #[test]
+    fn test_projection() {
+        // T'de 100, T+1 sn'de 200; saniye ile kaydedilmiş noktalar aynı doğruyu verir
+        const T: i64 = 1_700_000_000_000;
+        let points = r#"[{"time":1700000000000,"price":100},{"time":1700000001000,"price":200}]"#;
+        let trend = DrawingGeometry::from_drawing(&drawing("trendline", points, "{}")).unwrap();
+        assert_eq!(trend.lines_at(T + 500), vec![150.0]);
+        assert!(trend.lines_at(T + 1500).is_empty());
+        let seconds = r#"[{"time":1700000000,"price":100},{"time":1700000001,"price":200}]"#;
+        assert_eq!(DrawingGeometry::from_drawing(&drawing("trendline", seconds, "{}")).unwrap(), trend);
+
+        let ray = DrawingGeometry::from_drawing(&drawing("ray", points, "{}")).unwrap();
+        assert_eq!(ray.lines_at(T + 2000), vec![300.0]);
+        assert!(ray.triggered(&DrawingTrigger::Cross, 310.0, 299.0, T + 2000));
+        assert!(!ray.triggered(&DrawingTrigger::ExitChannel, 310.0, 299.0, T + 2000));
+        let extended = DrawingGeometry::from_drawing(&drawing("trendline", points, r#"{"extendRight":true}"#)).unwrap();
+        assert_eq!(extended, ray);
+
+        assert!(DrawingGeometry::from_drawing(&drawing("rectangle", points, "{}")).is_err());
+        assert!(DrawingGeometry::from_drawing(&drawing("parallel_channel", points, "{}")).is_err());
+    }
+
+    #[test]
+    fn test_channel_and_fib_triggers() {
+        // Kanal: alt çizgi 100, üst sınır 20 yukarıda, sağa uzatılmış
+        let channel = DrawingGeometry::from_drawing(&drawing(
+            "parallel_channel",
+            r#"[{"time":0,"price":100},{"time":1000,"price":100},{"time":500,"price":120}]"#,
+            r#"{"extendRight":true}"#,
+        ))
+        .unwrap();
+        assert!(!channel.triggered(&DrawingTrigger::ExitChannel, 110.0, 119.0, 5_000_000));
+        assert!(channel.triggered(&DrawingTrigger::ExitChannel, 110.0, 121.0, 5_000_000));
+        assert!(channel.triggered(&DrawingTrigger::ExitChannel, 101.0, 99.0, 5_000_000));
+        // Dışarıda kalmak tekrar tetiklemez
+        assert!(!channel.triggered(&DrawingTrigger::ExitChannel, 121.0, 125.0, 5_000_000));
+
+        let fib = DrawingGeometry::from_drawing(&drawing(
+            "fib_retracement",
+            r#"[{"time":0,"price":200},{"time":1000,"price":100}]"#,
+            "{}",
+        ))
+        .unwrap();
+        // 0.618 seviyesi: 200 - 100 * 0.618 = 138.2, çizimin sağından sonra da izlenir
+        let level = DrawingTrigger::FibLevel { level: 0.618 };
+        assert!(fib.triggered(&level, 140.0, 138.0, 9_000_000));
+        assert!(!fib.triggered(&level, 140.0, 139.0, 9_000_000));
+        assert!(!fib.triggered(&DrawingTrigger::Cross, 140.0, 100.0, 9_000_000));
+    }
+}
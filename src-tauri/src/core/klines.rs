@@ -0,0 +1,184 @@
+// AlgoTrade OS - Kline Cache
+// Grafik için çekilen mumları saklar ve canlı tick'lerle günceller
+// Modüller (iz süren stop, alarmlar) borsaya tekrar istek atmadan mum verisine erişir
+// Grafikte açılmamış bir aralığa ihtiyaç duyan kural seriyi `backfill` ile doldurur;
+// takibe alınan seri sonrasında canlı tick'lerle güncellenir
+
+use std::collections::HashMap;
+use std::sync::RwLock;
+use chrono::{DateTime, Utc};
+use crate::core::scheduler::candle_open;
+use crate::exchange::bybit::{BybitClient, Kline, MarketCategory};
+use crate::models::StandardTick;
+
+/// Sembol/aralık başına tutulan en fazla mum (borsadan tek istekte alınabilen sayı)
+pub const MAX_CANDLES: usize = 1000;
+
+/// Sembol + aralık başına artan zaman sıralı mum serileri
+#[derive(Default)]
+pub struct KlineCache {
+    series: RwLock<HashMap<(String, String), Vec<Kline>>>,
+}
+
+impl KlineCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Borsadan gelen mumları ekle (sıra fark etmez, aynı açılış zamanındaki mum güncellenir)
+    pub fn merge(&self, symbol: &str, interval: &str, klines: &[Kline]) {
+        let mut series = self.series.write().unwrap();
+        let candles = series
+            .entry((symbol.to_string(), interval.to_string()))
+            .or_default();
+
+        for kline in klines {
+            match candles.binary_search_by_key(&kline.timestamp, |k| k.timestamp) {
+                Ok(i) => candles[i] = kline.clone(),
+                Err(i) => candles.insert(i, kline.clone()),
+            }
+        }
+
+        if candles.len() > MAX_CANDLES {
+            let excess = candles.len() - MAX_CANDLES;
+            candles.drain(..excess);
+        }
+    }
+
+    /// Seriyi takibe al (boşsa canlı tick'lerle oluşmaya başlar)
+    pub fn track(&self, symbol: &str, interval: &str) {
+        self.series
+            .write()
+            .unwrap()
+            .entry((symbol.to_string(), interval.to_string()))
+            .or_default();
+    }
+
+    /// Serideki mum sayısı
+    pub fn count(&self, symbol: &str, interval: &str) -> usize {
+        self.series
+            .read()
+            .unwrap()
+            .get(&(symbol.to_string(), interval.to_string()))
+            .map_or(0, Vec::len)
+    }
+
+    /// Serinin ilk ve son mumunun açılış zamanı (seri boşsa None)
+    pub fn span(&self, symbol: &str, interval: &str) -> Option<(i64, i64)> {
+        let series = self.series.read().unwrap();
+        let candles = series.get(&(symbol.to_string(), interval.to_string()))?;
+        Some((candles.first()?.timestamp, candles.last()?.timestamp))
+    }
+
+    /// Tick ile sembolün takip edilen tüm serilerinin son mumunu güncelle / yeni mum aç
+    pub fn apply_tick(&self, tick: &StandardTick) {
+        let mut series = self.series.write().unwrap();
+        for ((symbol, interval), candles) in series.iter_mut() {
+            if *symbol != tick.symbol {
+                continue;
+            }
+            let Some(open) = candle_open(interval, tick.timestamp) else {
+                continue;
+            };
+            let open_ms = open.timestamp_millis();
+
+            match candles.last_mut() {
+                Some(last) if last.timestamp == open_ms => {
+                    last.high = last.high.max(tick.price);
+                    last.low = last.low.min(tick.price);
+                    last.close = tick.price;
+                    last.volume += tick.volume;
+                }
+                Some(last) if last.timestamp > open_ms => {} // Eski tick
+                _ => {
+                    candles.push(Kline {
+                        timestamp: open_ms,
+                        open: tick.price,
+                        high: tick.price,
+                        low: tick.price,
+                        close: tick.price,
+                        volume: tick.volume,
+                    });
+                    if candles.len() > MAX_CANDLES {
+                        candles.remove(0);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Serinin tüm mumları (eskiden yeniye)
+    pub fn get(&self, symbol: &str, interval: &str) -> Vec<Kline> {
+        self.series
+            .read()
+            .unwrap()
+            .get(&(symbol.to_string(), interval.to_string()))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Sadece kapanmış mumlar (`now` anında açık olan mum hariç)
+    pub fn closed(&self, symbol: &str, interval: &str, now: DateTime<Utc>) -> Vec<Kline> {
+        let mut candles = self.get(symbol, interval);
+        if let Some(open) = candle_open(interval, now) {
+            candles.retain(|k| k.timestamp < open.timestamp_millis());
+        }
+        candles
+    }
+}
+
+/// Seriyi takibe al ve borsanın son `limit` mumuyla doldur (herkese açık uç nokta)
+pub async fn backfill(cache: &KlineCache, symbol: &str, interval: &str, limit: usize) -> Result<(), String> {
+    cache.track(symbol, interval);
+    let client = BybitClient::new(String::new(), String::new(), false);
+    let limit = limit.min(MAX_CANDLES) as u32;
+    let klines = client
+        .get_klines(symbol, MarketCategory::for_symbol(symbol), interval, limit)
+        .await
+        .map_err(|e| e.to_string())?;
+    cache.merge(symbol, interval, &klines);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Exchange;
+
+    fn kline(timestamp: i64, close: f64) -> Kline {
+        Kline { timestamp, open: close, high: close, low: close, close, volume: 1.0 }
+    }
+
+    #[test]
+    fn test_merge_and_live_ticks() {
+        let cache = KlineCache::new();
+        // Bybit en yeni mumu önce döndürür
+        cache.merge("BTCUSDT", "1", &[kline(120_000, 12.0), kline(60_000, 11.0), kline(0, 10.0)]);
+        cache.merge("BTCUSDT", "1", &[kline(120_000, 13.0)]);
+        assert_eq!(cache.get("BTCUSDT", "1").iter().map(|k| k.close).collect::<Vec<_>>(), vec![10.0, 11.0, 13.0]);
+
+        let tick = |secs: i64, price: f64| StandardTick {
+            symbol: "BTCUSDT".into(),
+            price,
+            volume: 2.0,
+            timestamp: DateTime::from_timestamp(secs, 0).unwrap(),
+            exchange: Exchange::Bybit,
+        };
+        cache.apply_tick(&tick(150, 15.0)); // Açık mumu güncelle
+        cache.apply_tick(&tick(185, 9.0)); // Yeni mum
+        let candles = cache.get("BTCUSDT", "1");
+        assert_eq!(candles.len(), 4);
+        assert_eq!((candles[2].high, candles[2].close, candles[2].volume), (15.0, 15.0, 3.0));
+        assert_eq!((candles[3].timestamp, candles[3].open), (180_000, 9.0));
+
+        let closed = cache.closed("BTCUSDT", "1", DateTime::from_timestamp(200, 0).unwrap());
+        assert_eq!(closed.len(), 3);
+
+        // Takibe alınan boş seri tick'lerle oluşur
+        cache.apply_tick(&tick(185, 9.0));
+        assert_eq!(cache.count("BTCUSDT", "5"), 0);
+        cache.track("BTCUSDT", "5");
+        cache.apply_tick(&tick(190, 9.5));
+        assert_eq!(cache.count("BTCUSDT", "5"), 1);
+    }
+}
@@ -3,6 +3,25 @@
 
 pub mod dispatcher;
 pub mod state;
+pub mod scheduler;
+pub mod klines;
+pub mod correlation;
+pub mod drawings;
+pub mod alarms;
+pub mod notifier;
+pub mod daily_pnl;
+pub mod drawdown;
+pub mod risk_gate;
+pub mod positions;
+pub mod balance;
+pub mod price_feed;
 
-pub use dispatcher::EventDispatcher;
+pub use dispatcher::{EventDispatcher, AppEvent, Subscription};
 pub use state::AppState;
+pub use scheduler::{Scheduler, ScheduleSpec};
+pub use klines::KlineCache;
+pub use drawings::DrawingGeometry;
+pub use daily_pnl::DailyPnlTracker;
+pub use drawdown::DrawdownTracker;
+pub use notifier::Notifier;
+pub use risk_gate::RiskGate;
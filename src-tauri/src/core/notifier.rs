@@ -0,0 +1,490 @@
+// AlgoTrade OS - Notification Dispatcher
+// Tetiklenen alarmları ve risk uyarılarını kullanıcının seçtiği kanallara iletir
+//
+// - Visual / Sound: arayüze "notification" olayı (Sound'da arayüz sesli uyarı çalar)
+// - OsNative: Tauri bildirim eklentisiyle masaüstü bildirimi
+// - Webhook: HTTP POST (genel JSON, Telegram veya Discord şablonu); zamanı gelen webhook'lar aynı
+//   anda gönderilir. Ağ hatası, 5xx, 408 ve 429 alan gönderimler kuyrukta bekler ve artan aralıklarla
+//   yeniden denenir; diğer 4xx yanıtlar (hatalı URL, token, şablon) yeniden denenmeden düşürülür
+// Her kanalın kendi dakikalık sınırı vardır; sınırı aşan bildirim o kanala gönderilmez.
+
+use chrono::{DateTime, Duration, Utc};
+use futures::future::join_all;
+use reqwest::StatusCode;
+use serde_json::{json, Value};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex, OnceLock};
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_notification::NotificationExt;
+use tokio::sync::Notify;
+use crate::i18n::t;
+use crate::models::{
+    Alarm, AlarmCondition, Notification, NotificationKind, NotificationSettings, NotificationType, RiskWarning,
+    WebhookFormat, WebhookTarget,
+};
+use super::{AppEvent, AppState};
+
+/// Arayüze gönderilen olayın adı
+const EVENT_NAME: &str = "notification";
+
+/// Bir webhook gönderiminin en fazla deneme sayısı
+const MAX_WEBHOOK_ATTEMPTS: u32 = 5;
+
+/// İlk yeniden denemeden önceki bekleme (her denemede iki katına çıkar)
+const RETRY_BASE_SECS: i64 = 5;
+
+const WEBHOOK_TIMEOUT_SECS: u64 = 10;
+
+/// Kuyruk boşken webhook işçisinin en uzun bekleme süresi
+const IDLE_WAIT_SECS: i64 = 30;
+
+/// Hız sınırının penceresi
+const RATE_WINDOW_SECS: i64 = 60;
+
+/// `attempts` başarısız denemeden sonraki bekleme
+pub fn retry_delay(attempts: u32) -> Duration {
+    Duration::seconds(RETRY_BASE_SECS << attempts.saturating_sub(1).min(10))
+}
+
+/// Webhook gövdesi
+pub fn webhook_payload(format: &WebhookFormat, notification: &Notification) -> Value {
+    match format {
+        WebhookFormat::Generic => serde_json::to_value(notification).unwrap_or(Value::Null),
+        WebhookFormat::Telegram { chat_id } => json!({
+            "chat_id": chat_id,
+            "text": format!("{}\n{}", notification.title, notification.message),
+        }),
+        WebhookFormat::Discord => json!({
+            "content": format!("**{}**\n{}", notification.title, notification.message),
+        }),
+    }
+}
+
+/// Alarm bildirimi (`price`: tetikleyen fiyat)
+pub fn alarm_notification(alarm: &Alarm, price: Option<f64>, now: DateTime<Utc>) -> Notification {
+    let condition = t(match alarm.condition {
+        AlarmCondition::PriceAbove => "alarm.condition.price_above",
+        AlarmCondition::PriceBelow => "alarm.condition.price_below",
+        AlarmCondition::CrossVwap => "alarm.condition.cross_vwap",
+        AlarmCondition::CrossVal => "alarm.condition.cross_val",
+        AlarmCondition::CrossVah => "alarm.condition.cross_vah",
+        AlarmCondition::Rule(_) => "alarm.condition.rule",
+        AlarmCondition::Drawing(_) => "alarm.condition.drawing",
+    });
+    let message = match (&alarm.condition, price) {
+        (AlarmCondition::PriceAbove | AlarmCondition::PriceBelow, Some(price)) => {
+            format!("{} {} ({})", condition, alarm.target_price, price)
+        }
+        (_, Some(price)) => format!("{} ({})", condition, price),
+        (_, None) => condition,
+    };
+    Notification {
+        kind: NotificationKind::Alarm,
+        source_id: Some(alarm.id.clone()),
+        symbol: Some(alarm.symbol.clone()),
+        title: format!("{}: {}", t("notification.alarm_triggered"), alarm.symbol),
+        message,
+        sound: alarm.notification_type == NotificationType::Sound,
+        created_at: now,
+    }
+}
+
+/// Risk uyarısı bildirimi
+pub fn risk_notification(warning: &RiskWarning) -> Notification {
+    Notification {
+        kind: NotificationKind::RiskWarning,
+        source_id: None,
+        symbol: None,
+        title: t("notification.risk_warning"),
+        message: format!("{} (%{:.2} / %{:.2})", t(&warning.message_key), warning.value, warning.limit),
+        sound: false,
+        created_at: warning.raised_at,
+    }
+}
+
+/// Kanal başına kayan pencereli hız sınırı
+#[derive(Default)]
+pub struct RateLimiter {
+    sent: HashMap<NotificationType, VecDeque<DateTime<Utc>>>,
+}
+
+impl RateLimiter {
+    /// Kanala şimdi gönderilebilir mi? (izin verilirse sayılır)
+    pub fn allow(&mut self, channel: NotificationType, max_per_minute: u32, now: DateTime<Utc>) -> bool {
+        let sent = self.sent.entry(channel).or_default();
+        while sent.front().is_some_and(|t| now - *t >= Duration::seconds(RATE_WINDOW_SECS)) {
+            sent.pop_front();
+        }
+        if sent.len() >= max_per_minute as usize {
+            return false;
+        }
+        sent.push_back(now);
+        true
+    }
+}
+
+/// Webhook gönderim hatası
+enum PostError {
+    /// Ağ hatası, zaman aşımı, 5xx, 408, 429 - yeniden denenir
+    Retryable(String),
+    /// Diğer 4xx - istek hatalı, yeniden denemek sonucu değiştirmez
+    Rejected(String),
+}
+
+/// Gönderilmeyi bekleyen webhook
+struct PendingWebhook {
+    target: WebhookTarget,
+    payload: Value,
+    attempts: u32,
+    next_attempt: DateTime<Utc>,
+}
+
+/// Bildirim dağıtıcısı
+pub struct Notifier {
+    app: OnceLock<AppHandle>,
+    limiter: Mutex<RateLimiter>,
+    queue: Mutex<VecDeque<PendingWebhook>>,
+    wake: Notify,
+    http: reqwest::Client,
+}
+
+impl Default for Notifier {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Notifier {
+    pub fn new() -> Self {
+        Self {
+            app: OnceLock::new(),
+            limiter: Mutex::new(RateLimiter::default()),
+            queue: Mutex::new(VecDeque::new()),
+            wake: Notify::new(),
+            http: reqwest::Client::new(),
+        }
+    }
+
+    /// Arayüz olayları ve masaüstü bildirimleri için uygulama tutamacı (Tauri kurulumunda)
+    pub fn set_app_handle(&self, app: AppHandle) {
+        let _ = self.app.set(app);
+    }
+
+    /// Bildirimi kanallara ilet (webhook'lar kuyruğa alınır)
+    pub fn send(
+        &self,
+        notification: &Notification,
+        channels: &[NotificationType],
+        settings: &NotificationSettings,
+        now: DateTime<Utc>,
+    ) {
+        let wants = |channel: NotificationType| channels.contains(&channel);
+        let allow = |channel: NotificationType| {
+            let allowed = self.limiter.lock().unwrap().allow(channel, settings.max_per_minute, now);
+            if !allowed {
+                tracing::warn!("{:?} kanalının hız sınırı aşıldı, bildirim gönderilmedi", channel);
+            }
+            allowed
+        };
+
+        // Sesli uyarı görsel bildirimi de içerir
+        let ui_channel = if wants(NotificationType::Sound) {
+            Some(NotificationType::Sound)
+        } else if wants(NotificationType::Visual) {
+            Some(NotificationType::Visual)
+        } else {
+            None
+        };
+        if let Some(channel) = ui_channel {
+            if allow(channel) {
+                let payload = Notification { sound: channel == NotificationType::Sound, ..notification.clone() };
+                self.emit(&payload);
+            }
+        }
+        if wants(NotificationType::OsNative) && allow(NotificationType::OsNative) {
+            self.show_desktop(notification);
+        }
+        if wants(NotificationType::Webhook) && allow(NotificationType::Webhook) {
+            let mut queue = self.queue.lock().unwrap();
+            for target in settings.webhooks.iter().filter(|w| w.enabled) {
+                queue.push_back(PendingWebhook {
+                    target: target.clone(),
+                    payload: webhook_payload(&target.format, notification),
+                    attempts: 0,
+                    next_attempt: now,
+                });
+            }
+            self.wake.notify_one();
+        }
+    }
+
+    fn emit(&self, notification: &Notification) {
+        let Some(app) = self.app.get() else {
+            tracing::debug!("Arayüz bağlı değil, bildirim olayı gönderilmedi");
+            return;
+        };
+        if let Err(e) = app.emit(EVENT_NAME, notification.clone()) {
+            tracing::warn!("Bildirim olayı gönderilemedi: {}", e);
+        }
+    }
+
+    fn show_desktop(&self, notification: &Notification) {
+        let Some(app) = self.app.get() else {
+            tracing::debug!("Arayüz bağlı değil, masaüstü bildirimi gösterilmedi");
+            return;
+        };
+        let result = app
+            .notification()
+            .builder()
+            .title(&notification.title)
+            .body(&notification.message)
+            .show();
+        if let Err(e) = result {
+            tracing::warn!("Masaüstü bildirimi gösterilemedi: {}", e);
+        }
+    }
+
+    /// Kuyrukta bekleyen webhook sayısı
+    pub fn pending_webhooks(&self) -> usize {
+        self.queue.lock().unwrap().len()
+    }
+
+    /// Zamanı gelen webhook'ları gönder, başarısızları yeniden kuyruğa al; gönderilen sayısını döndür
+    pub async fn deliver_due(&self, now: DateTime<Utc>) -> usize {
+        let due = {
+            let mut queue = self.queue.lock().unwrap();
+            let (due, waiting): (VecDeque<_>, VecDeque<_>) = queue.drain(..).partition(|p| p.next_attempt <= now);
+            *queue = waiting;
+            due
+        };
+
+        let results = join_all(due.into_iter().map(|pending| async move {
+            let result = self.post(&pending).await;
+            (pending, result)
+        }))
+        .await;
+
+        let mut delivered = 0;
+        for (mut pending, result) in results {
+            match result {
+                Ok(()) => delivered += 1,
+                Err(PostError::Rejected(e)) => {
+                    tracing::error!("Webhook {} reddedildi, yeniden denenmeyecek: {}", pending.target.id, e);
+                }
+                Err(PostError::Retryable(e)) => {
+                    pending.attempts += 1;
+                    if pending.attempts >= MAX_WEBHOOK_ATTEMPTS {
+                        tracing::error!(
+                            "Webhook {} {} denemeden sonra gönderilemedi: {}",
+                            pending.target.id, pending.attempts, e
+                        );
+                        continue;
+                    }
+                    tracing::warn!("Webhook {} gönderilemedi ({}. deneme): {}", pending.target.id, pending.attempts, e);
+                    pending.next_attempt = now + retry_delay(pending.attempts);
+                    self.queue.lock().unwrap().push_back(pending);
+                }
+            }
+        }
+        delivered
+    }
+
+    async fn post(&self, pending: &PendingWebhook) -> Result<(), PostError> {
+        let status = self
+            .http
+            .post(&pending.target.url)
+            .json(&pending.payload)
+            .timeout(std::time::Duration::from_secs(WEBHOOK_TIMEOUT_SECS))
+            .send()
+            .await
+            .map_err(|e| PostError::Retryable(e.to_string()))?
+            .status();
+        if status.is_success() {
+            return Ok(());
+        }
+        let retryable = !status.is_client_error()
+            || status == StatusCode::REQUEST_TIMEOUT
+            || status == StatusCode::TOO_MANY_REQUESTS;
+        let message = format!("HTTP {}", status);
+        Err(if retryable { PostError::Retryable(message) } else { PostError::Rejected(message) })
+    }
+
+    /// Sıradaki yeniden denemeye kalan süre
+    fn next_wait(&self, now: DateTime<Utc>) -> Duration {
+        self.queue
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|p| p.next_attempt - now)
+            .min()
+            .unwrap_or(Duration::seconds(IDLE_WAIT_SECS))
+            .clamp(Duration::zero(), Duration::seconds(IDLE_WAIT_SECS))
+    }
+}
+
+/// Alarm ve risk uyarısı olaylarını kanallara yönlendir, webhook kuyruğunu işle
+pub fn spawn_notifier(state: Arc<AppState>) {
+    let subscription = state.dispatcher.subscribe("notifier");
+    let router = state.clone();
+    tauri::async_runtime::spawn(async move {
+        while let Some(event) = subscription.recv().await {
+            let settings = router.settings.read().await.notifications.clone();
+            let now = Utc::now();
+            match event {
+                AppEvent::AlarmTriggered { alarm_id } => {
+                    let alarm = router.alarms.read().await.iter().find(|a| a.id == alarm_id).cloned();
+                    let Some(alarm) = alarm else {
+                        continue;
+                    };
+                    let price = router.get_price(&alarm.symbol).await.map(|tick| tick.price);
+                    let notification = alarm_notification(&alarm, price, now);
+                    router.notifier.send(&notification, &[alarm.notification_type], &settings, now);
+                }
+                AppEvent::RiskWarning(warning) => {
+                    let notification = risk_notification(&warning);
+                    router.notifier.send(&notification, &settings.risk_warning_channels, &settings, now);
+                }
+                _ => {}
+            }
+        }
+    });
+
+    tauri::async_runtime::spawn(async move {
+        loop {
+            state.notifier.deliver_due(Utc::now()).await;
+            let wait = state.notifier.next_wait(Utc::now()).to_std().unwrap_or_default();
+            tokio::select! {
+                _ = tokio::time::sleep(wait.max(std::time::Duration::from_millis(100))) => {}
+                _ = state.notifier.wake.notified() => {}
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    fn at(secs: i64) -> DateTime<Utc> {
+        DateTime::from_timestamp(secs, 0).unwrap()
+    }
+
+    fn notification() -> Notification {
+        Notification {
+            kind: NotificationKind::Alarm,
+            source_id: Some("a".into()),
+            symbol: Some("BTCUSDT".into()),
+            title: "Alarm".into(),
+            message: "Price above 100".into(),
+            sound: false,
+            created_at: at(0),
+        }
+    }
+
+    /// Gelen istek gövdelerini kaydeden, istekleri sırayla `statuses` ile (sonra 200) yanıtlayan yerel webhook alıcısı
+    async fn receiver(statuses: Vec<&'static str>) -> (String, Arc<Mutex<Vec<Value>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let bodies = Arc::new(Mutex::new(Vec::new()));
+        let received = bodies.clone();
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut request = Vec::new();
+                let mut buf = [0u8; 4096];
+                let body = loop {
+                    let n = socket.read(&mut buf).await.unwrap();
+                    request.extend_from_slice(&buf[..n]);
+                    let text = String::from_utf8_lossy(&request).to_string();
+                    if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                        let length = head
+                            .lines()
+                            .find_map(|l| l.to_ascii_lowercase().strip_prefix("content-length:").map(|v| v.trim().parse::<usize>().unwrap()))
+                            .unwrap_or(0);
+                        if body.len() >= length || n == 0 {
+                            break body.to_string();
+                        }
+                    }
+                };
+                let status = {
+                    let mut bodies = received.lock().unwrap();
+                    bodies.push(serde_json::from_str(&body).unwrap());
+                    statuses.get(bodies.len() - 1).copied().unwrap_or("200 OK")
+                };
+                let response = format!("HTTP/1.1 {}\r\ncontent-length: 0\r\nconnection: close\r\n\r\n", status);
+                socket.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+        (url, bodies)
+    }
+
+    #[test]
+    fn test_rate_limit_per_channel() {
+        let mut limiter = RateLimiter::default();
+        assert!(limiter.allow(NotificationType::Webhook, 2, at(0)));
+        assert!(limiter.allow(NotificationType::Webhook, 2, at(10)));
+        assert!(!limiter.allow(NotificationType::Webhook, 2, at(20)));
+        // Diğer kanal etkilenmez
+        assert!(limiter.allow(NotificationType::OsNative, 2, at(20)));
+        // Pencere kayınca ilk gönderim düşer
+        assert!(limiter.allow(NotificationType::Webhook, 2, at(60)));
+
+        let telegram = webhook_payload(&WebhookFormat::Telegram { chat_id: "42".into() }, &notification());
+        assert_eq!(telegram, json!({ "chat_id": "42", "text": "Alarm\nPrice above 100" }));
+        assert_eq!(webhook_payload(&WebhookFormat::Generic, &notification())["kind"], "alarm");
+        assert_eq!(retry_delay(1), Duration::seconds(5));
+        assert_eq!(retry_delay(3), Duration::seconds(20));
+    }
+
+    #[tokio::test]
+    async fn test_webhook_retry() {
+        let (url, bodies) = receiver(vec!["500 Internal Server Error"]).await;
+        let notifier = Notifier::new();
+        let settings = NotificationSettings {
+            webhooks: vec![
+                WebhookTarget { id: "discord".into(), url, format: WebhookFormat::Discord, enabled: true },
+                WebhookTarget { id: "off".into(), url: "http://127.0.0.1:1/".into(), format: WebhookFormat::Generic, enabled: false },
+            ],
+            ..NotificationSettings::default()
+        };
+
+        notifier.send(&notification(), &[NotificationType::Webhook], &settings, at(0));
+        assert_eq!(notifier.pending_webhooks(), 1);
+        // İlk deneme 500 alır, kuyrukta bekler
+        assert_eq!(notifier.deliver_due(at(0)).await, 0);
+        assert_eq!(notifier.pending_webhooks(), 1);
+        assert_eq!(notifier.deliver_due(at(1)).await, 0);
+        assert_eq!(bodies.lock().unwrap().len(), 1);
+
+        assert_eq!(notifier.deliver_due(at(0) + retry_delay(1)).await, 1);
+        assert_eq!(notifier.pending_webhooks(), 0);
+        let bodies = bodies.lock().unwrap();
+        assert_eq!(bodies.len(), 2);
+        assert_eq!(bodies[1], json!({ "content": "**Alarm**\nPrice above 100" }));
+    }
+
+    #[tokio::test]
+    async fn test_client_errors_are_dropped_without_retry() {
+        let (rejecting, rejected) = receiver(vec!["404 Not Found"]).await;
+        let (limited, limited_bodies) = receiver(vec!["429 Too Many Requests"]).await;
+        let notifier = Notifier::new();
+        let settings = NotificationSettings {
+            webhooks: vec![
+                WebhookTarget { id: "bad-token".into(), url: rejecting, format: WebhookFormat::Generic, enabled: true },
+                WebhookTarget { id: "limited".into(), url: limited, format: WebhookFormat::Generic, enabled: true },
+            ],
+            ..NotificationSettings::default()
+        };
+
+        notifier.send(&notification(), &[NotificationType::Webhook], &settings, at(0));
+        assert_eq!(notifier.deliver_due(at(0)).await, 0);
+        // 404 düşürüldü, 429 yeniden denenecek
+        assert_eq!(notifier.pending_webhooks(), 1);
+        assert_eq!(notifier.deliver_due(at(0) + retry_delay(1)).await, 1);
+        assert_eq!(notifier.pending_webhooks(), 0);
+        assert_eq!(rejected.lock().unwrap().len(), 1);
+        assert_eq!(limited_bodies.lock().unwrap().len(), 2);
+    }
+}
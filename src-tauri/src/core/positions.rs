@@ -0,0 +1,147 @@
+// AlgoTrade OS - Position Sync
+// Açık pozisyonları bağlı borsadan periyodik olarak okur ve uygulama durumuna yazar
+//
+// Borsada yeni görülen pozisyon için PositionOpened, kaybolan pozisyon için PositionClosed
+// yayınlanır; kâr/zarar, pozisyon açıldığından beri sembolde gerçekleşen işlemlerden hesaplanır.
+// Aynı kimlikte (sembol + pozisyon indeksi) açılış zamanı değişen pozisyon kapanıp yeniden açılmış sayılır.
+// Kapanan pozisyonlar, olayı işleyen modüller bulabilsin diye son CLOSED_HISTORY kadarı saklanır.
+
+use std::sync::Arc;
+use std::time::Duration;
+use crate::exchange::{ExecutionError, OrderExecutor};
+use crate::models::Position;
+use super::{AppEvent, AppState};
+
+/// Borsadan pozisyon okuma aralığı
+const SYNC_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Saklanan en fazla kapanmış pozisyon
+const CLOSED_HISTORY: usize = 100;
+
+/// Pozisyonları borsayla eşitle ve açılış/kapanış olaylarını yayınla (bağlantı yoksa bir şey yapmaz)
+pub async fn sync(state: &AppState) -> Result<(), ExecutionError> {
+    let Some(executor) = state.executor().await else {
+        return Ok(());
+    };
+    let fetched = executor.open_positions().await?;
+    let previous = std::mem::replace(&mut *state.positions.write().await, fetched.clone());
+
+    let same = |a: &Position, b: &Position| a.id == b.id && a.created_at == b.created_at;
+    let closed: Vec<Position> = previous
+        .iter()
+        .filter(|p| !fetched.iter().any(|f| same(p, f)))
+        .cloned()
+        .collect();
+    let opened: Vec<&Position> = fetched
+        .iter()
+        .filter(|f| !previous.iter().any(|p| same(p, f)))
+        .collect();
+
+    for position in closed {
+        let pnl = realized_pnl(executor.as_ref(), &position).await;
+        tracing::info!("Pozisyon kapandı: {} | PnL: {:.2}", position.id, pnl);
+        let position_id = position.id.clone();
+        {
+            let mut history = state.closed_positions.write().await;
+            history.push_front(position);
+            history.truncate(CLOSED_HISTORY);
+        }
+        state.dispatcher.publish(AppEvent::PositionClosed { position_id, pnl });
+    }
+    for position in opened {
+        tracing::info!("Pozisyon açıldı: {} {:?} {}", position.symbol, position.side, position.quantity);
+        state.dispatcher.publish(AppEvent::PositionOpened { position_id: position.id.clone() });
+    }
+    Ok(())
+}
+
+/// Pozisyon açıldığından beri sembolde gerçekleşen kâr/zarar (komisyon düşülmüş)
+async fn realized_pnl(executor: &dyn OrderExecutor, position: &Position) -> f64 {
+    match executor.executions(position.created_at).await {
+        Ok(executions) => executions
+            .iter()
+            .filter(|e| e.symbol == position.symbol)
+            .map(|e| e.realized_pnl - e.fee)
+            .sum(),
+        Err(e) => {
+            tracing::warn!("Kapanan pozisyonun işlemleri alınamadı ({}): {}", position.id, e);
+            0.0
+        }
+    }
+}
+
+/// Pozisyonları arka planda periyodik olarak eşitle
+pub fn spawn_position_sync(state: Arc<AppState>) {
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(SYNC_INTERVAL);
+        loop {
+            interval.tick().await;
+            if let Err(e) = sync(&state).await {
+                tracing::warn!("Pozisyonlar borsayla eşitlenemedi: {}", e);
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration as ChronoDuration, Utc};
+    use crate::exchange::mock::MockExecutor;
+    use crate::models::{Execution, OrderSide};
+    use crate::testing;
+
+    fn position(id: &str, opened_ago_secs: i64) -> Position {
+        Position {
+            id: id.into(),
+            created_at: Utc::now() - ChronoDuration::seconds(opened_ago_secs),
+            ..testing::position(id.split('-').next().unwrap())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sync_publishes_opened_and_closed() {
+        let state = AppState::new();
+        let subscription = state.dispatcher.subscribe("test");
+        let executor = Arc::new(MockExecutor::default());
+        state.set_executor(Some(executor.clone())).await;
+
+        executor.positions.lock().unwrap().extend([position("BTCUSDT-0", 60), position("ETHUSDT-0", 60)]);
+        sync(&state).await.unwrap();
+        for expected in ["BTCUSDT-0", "ETHUSDT-0"] {
+            assert!(matches!(subscription.try_recv(), Some(AppEvent::PositionOpened { position_id }) if position_id == expected));
+        }
+        // Değişiklik yoksa olay yok
+        sync(&state).await.unwrap();
+        assert!(subscription.try_recv().is_none());
+
+        // BTC kapandı (+12 kâr, 2 komisyon), ETH aynı kimlikle yeniden açıldı
+        executor.executions.lock().unwrap().push(Execution {
+            id: "e1".into(),
+            order_id: "o1".into(),
+            symbol: "BTCUSDT".into(),
+            side: OrderSide::Sell,
+            price: 112.0,
+            quantity: 1.0,
+            fee: 2.0,
+            realized_pnl: 12.0,
+            executed_at: Utc::now(),
+        });
+        *executor.positions.lock().unwrap() = vec![position("ETHUSDT-0", 1)];
+        sync(&state).await.unwrap();
+
+        let mut closed = Vec::new();
+        let mut opened = Vec::new();
+        while let Some(event) = subscription.try_recv() {
+            match event {
+                AppEvent::PositionClosed { position_id, pnl } => closed.push((position_id, pnl)),
+                AppEvent::PositionOpened { position_id } => opened.push(position_id),
+                other => panic!("beklenmeyen olay: {:?}", other),
+            }
+        }
+        assert_eq!(closed, vec![("BTCUSDT-0".to_string(), 10.0), ("ETHUSDT-0".to_string(), 0.0)]);
+        assert_eq!(opened, vec!["ETHUSDT-0".to_string()]);
+        assert_eq!(state.positions.read().await.len(), 1);
+        assert_eq!(state.closed_positions.read().await.len(), 2);
+    }
+}
@@ -0,0 +1,310 @@
+// AlgoTrade OS - Price Feed
+// Bybit herkese açık ticker WebSocket akışından canlı fiyatları okur ve AppState'e işler
+//
+// Akış sadece fiyatı gereken sembollere abone olur: aktif alarmlar, açık pozisyonlar, izlenen
+// sepetler ve modüllerin mum kapanışı zamanlayıcıları. Liste periyodik olarak yenilenir; değişen
+// semboller açık bağlantıda abone edilir / abonelikten çıkarılır. Her kategori (linear, inverse)
+// kendi bağlantısını kullanır, bağlantı koparsa beklenip yeniden kurulur.
+// Gelen her fiyat state.update_price ile PriceUpdated olarak yayınlanır (alarmlar, modüller, mumlar).
+//
+// Ticker 24 saatlik hacim verir; tick hacmi ardışık mesajlar arasındaki artıştır (kayan pencere
+// nedeniyle azalırsa sıfır sayılır).
+
+use chrono::{DateTime, Utc};
+use futures::{SinkExt, StreamExt};
+use serde::Deserialize;
+use std::collections::{BTreeSet, HashMap};
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio_tungstenite::tungstenite::Message;
+use crate::exchange::bybit::{public_ws_url, MarketCategory};
+use crate::i18n::t;
+use crate::models::{BasketStatus, Exchange, StandardTick};
+use super::AppState;
+
+/// İzlenen sembollerin yenilenme aralığı
+const WATCH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Bybit bağlantıyı canlı tutmak için 20 sn'de bir ping bekler
+const PING_INTERVAL: Duration = Duration::from_secs(20);
+
+/// Kopan bağlantıyı yeniden kurmadan önce bekleme
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// Tek abonelik isteğindeki en fazla konu
+const SUBSCRIBE_BATCH: usize = 10;
+
+/// Kategori bağlantısının istenen durumu: (testnet, semboller)
+type FeedTarget = (bool, BTreeSet<String>);
+
+#[derive(Deserialize)]
+struct TickerMessage {
+    topic: String,
+    /// Mesaj zamanı (ms)
+    ts: i64,
+    data: TickerData,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TickerData {
+    symbol: String,
+    /// Delta mesajlarında sadece değişen alanlar gelir
+    #[serde(default)]
+    last_price: Option<String>,
+    #[serde(default)]
+    volume24h: Option<String>,
+}
+
+/// Canlı fiyatı gereken semboller
+pub async fn watched_symbols(state: &AppState) -> BTreeSet<String> {
+    let mut symbols: BTreeSet<String> = state
+        .alarms
+        .read()
+        .await
+        .iter()
+        .filter(|a| a.is_active)
+        .map(|a| a.symbol.clone())
+        .collect();
+    symbols.extend(state.positions.read().await.iter().map(|p| p.symbol.clone()));
+    symbols.extend(
+        state
+            .baskets
+            .read()
+            .await
+            .values()
+            .filter(|b| b.status != BasketStatus::Closed)
+            .flat_map(|b| b.legs.iter().map(|l| l.symbol.clone())),
+    );
+    symbols.extend(state.scheduler.candle_symbols());
+    symbols
+}
+
+/// Ticker mesajını işle, fiyat varsa yayınla (`volumes`: sembolün son 24 saatlik hacmi)
+///
+/// Abonelik yanıtları, pong ve fiyat içermeyen delta mesajları yok sayılır.
+pub async fn handle_message(state: &AppState, text: &str, volumes: &mut HashMap<String, f64>) -> bool {
+    let Ok(message) = serde_json::from_str::<TickerMessage>(text) else {
+        return false;
+    };
+    if !message.topic.starts_with("tickers.") {
+        return false;
+    }
+    let data = message.data;
+    let Some(price) = data.last_price.and_then(|p| p.parse::<f64>().ok()) else {
+        return false;
+    };
+    // Fiyatsız mesajların hacmi bir sonraki tick'e eklenir
+    let volume = match data.volume24h.and_then(|v| v.parse::<f64>().ok()) {
+        Some(total) => volumes.insert(data.symbol.clone(), total).map_or(0.0, |previous| (total - previous).max(0.0)),
+        None => 0.0,
+    };
+    let timestamp = DateTime::from_timestamp_millis(message.ts).unwrap_or_else(Utc::now);
+    state
+        .update_price(StandardTick { symbol: data.symbol, price, volume, timestamp, exchange: Exchange::Bybit })
+        .await;
+    true
+}
+
+/// Fiyat akışını başlat (`testnet`: bağlı hesabın ağı, her yenilemede sorulur)
+pub fn spawn_price_feed<F, Fut>(state: Arc<AppState>, testnet: F)
+where
+    F: Fn() -> Fut + Send + 'static,
+    Fut: Future<Output = bool> + Send,
+{
+    tauri::async_runtime::spawn(async move {
+        let categories = [MarketCategory::Linear, MarketCategory::Inverse];
+        let targets: Vec<watch::Sender<FeedTarget>> = categories
+            .iter()
+            .map(|&category| {
+                let (sender, receiver) = watch::channel((false, BTreeSet::new()));
+                tauri::async_runtime::spawn(run_category(state.clone(), category, receiver));
+                sender
+            })
+            .collect();
+
+        let mut interval = tokio::time::interval(WATCH_INTERVAL);
+        loop {
+            interval.tick().await;
+            let symbols = watched_symbols(&state).await;
+            let testnet = testnet().await;
+            for (category, target) in categories.iter().zip(&targets) {
+                let wanted: BTreeSet<String> = symbols
+                    .iter()
+                    .filter(|s| MarketCategory::for_symbol(s) == *category)
+                    .cloned()
+                    .collect();
+                target.send_if_modified(|current| {
+                    let next = (testnet, wanted);
+                    let changed = *current != next;
+                    *current = next;
+                    changed
+                });
+            }
+        }
+    });
+}
+
+/// Kategorinin bağlantısını yönet: sembol varken bağlı kal, koparsa yeniden bağlan
+async fn run_category(state: Arc<AppState>, category: MarketCategory, mut target: watch::Receiver<FeedTarget>) {
+    loop {
+        let (testnet, symbols) = target.borrow_and_update().clone();
+        if symbols.is_empty() {
+            if target.changed().await.is_err() {
+                return;
+            }
+            continue;
+        }
+        if let Err(e) = stream(&state, category, testnet, symbols, &mut target).await {
+            tracing::warn!("{} ({}): {}", t("log.websocket_disconnected"), category.as_str(), e);
+            tokio::time::sleep(RECONNECT_DELAY).await;
+        }
+    }
+}
+
+/// Bağlan ve tick'leri işle; ağ değişince veya sembol kalmayınca bağlantıyı kapatıp döner
+async fn stream(
+    state: &AppState,
+    category: MarketCategory,
+    testnet: bool,
+    mut subscribed: BTreeSet<String>,
+    target: &mut watch::Receiver<FeedTarget>,
+) -> Result<(), String> {
+    let (mut ws, _) = tokio_tungstenite::connect_async(public_ws_url(category, testnet))
+        .await
+        .map_err(|e| e.to_string())?;
+    tracing::info!("{} ({}, {} sembol)", t("log.websocket_connected"), category.as_str(), subscribed.len());
+    for request in subscription_requests("subscribe", &subscribed) {
+        ws.send(Message::Text(request)).await.map_err(|e| e.to_string())?;
+    }
+
+    let mut volumes = HashMap::new();
+    let mut ping = tokio::time::interval(PING_INTERVAL);
+    loop {
+        tokio::select! {
+            message = ws.next() => match message {
+                Some(Ok(Message::Text(text))) => {
+                    handle_message(state, &text, &mut volumes).await;
+                }
+                Some(Ok(Message::Close(_))) | None => return Err("bağlantı kapandı".to_string()),
+                Some(Ok(_)) => {}
+                Some(Err(e)) => return Err(e.to_string()),
+            },
+            _ = ping.tick() => {
+                ws.send(Message::Text(r#"{"op":"ping"}"#.to_string())).await.map_err(|e| e.to_string())?;
+            }
+            changed = target.changed() => {
+                let (next_testnet, symbols) = match changed {
+                    Ok(()) => target.borrow_and_update().clone(),
+                    Err(_) => (testnet, BTreeSet::new()),
+                };
+                if next_testnet != testnet || symbols.is_empty() {
+                    let _ = ws.close(None).await;
+                    return Ok(());
+                }
+                let removed: BTreeSet<String> = subscribed.difference(&symbols).cloned().collect();
+                let added: BTreeSet<String> = symbols.difference(&subscribed).cloned().collect();
+                let requests = subscription_requests("unsubscribe", &removed)
+                    .into_iter()
+                    .chain(subscription_requests("subscribe", &added));
+                for request in requests {
+                    ws.send(Message::Text(request)).await.map_err(|e| e.to_string())?;
+                }
+                for symbol in &removed {
+                    volumes.remove(symbol);
+                }
+                subscribed = symbols;
+            }
+        }
+    }
+}
+
+/// Sembollerin ticker konuları için abonelik istekleri (Bybit istek başına konu sınırı)
+fn subscription_requests(op: &str, symbols: &BTreeSet<String>) -> Vec<String> {
+    let topics: Vec<String> = symbols.iter().map(|s| format!("tickers.{}", s)).collect();
+    topics
+        .chunks(SUBSCRIBE_BATCH)
+        .map(|args| serde_json::json!({ "op": op, "args": args }).to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{alarms, AppEvent};
+    use crate::models::{Alarm, AlarmCondition, AlarmMode, NotificationType};
+
+    fn ticker(kind: &str, price: Option<&str>, volume: &str, ts: i64) -> String {
+        let mut data = serde_json::json!({ "symbol": "FEEDUSDT", "volume24h": volume });
+        if let Some(price) = price {
+            data["lastPrice"] = price.into();
+        }
+        serde_json::json!({ "topic": "tickers.FEEDUSDT", "type": kind, "ts": ts, "data": data }).to_string()
+    }
+
+    #[test]
+    fn test_subscription_requests_are_batched() {
+        let symbols: BTreeSet<String> = (0..12).map(|i| format!("S{:02}USDT", i)).collect();
+        let requests = subscription_requests("subscribe", &symbols);
+        assert_eq!(requests.len(), 2);
+        assert!(requests[0].contains("tickers.S00USDT") && requests[1].contains("tickers.S11USDT"));
+        assert_eq!(public_ws_url(MarketCategory::Inverse, false), "wss://stream.bytick.com/v5/public/inverse");
+    }
+
+    #[tokio::test]
+    async fn test_ticker_messages_drive_alarm_engine() {
+        crate::db::init_test_database();
+        let state = Arc::new(AppState::new());
+        state.alarms.write().await.push(Alarm {
+            id: "feed-alarm".into(),
+            symbol: "FEEDUSDT".into(),
+            condition: AlarmCondition::PriceAbove,
+            target_price: 100.0,
+            is_active: true,
+            notification_type: NotificationType::Visual,
+            mode: AlarmMode::OneShot,
+            created_at: Utc::now(),
+            last_triggered_at: None,
+            trigger_count: 0,
+        });
+        assert!(watched_symbols(&state).await.contains("FEEDUSDT"));
+        let subscription = state.dispatcher.subscribe("test");
+        alarms::spawn_alarm_engine(state.clone());
+
+        let mut volumes = HashMap::new();
+        let now = Utc::now().timestamp_millis();
+        assert!(!handle_message(&state, r#"{"success":true,"op":"subscribe"}"#, &mut volumes).await);
+        assert!(handle_message(&state, &ticker("snapshot", Some("99.5"), "1000", now), &mut volumes).await);
+        // Tick'ler sembol başına birleştirilir: motor ilk fiyatı önceki fiyat olarak alana kadar bekle
+        let delivered = || {
+            state.dispatcher.metrics().iter().find(|m| m.subscriber == "alarms").map_or(0, |m| m.delivered)
+        };
+        tokio::time::timeout(Duration::from_secs(1), async {
+            while delivered() == 0 {
+                tokio::time::sleep(Duration::from_millis(1)).await;
+            }
+        })
+        .await
+        .expect("alarm motoru ilk tick'i almadı");
+        // Fiyatsız delta yayınlanmaz
+        assert!(!handle_message(&state, &ticker("delta", None, "1002", now + 100), &mut volumes).await);
+        assert!(handle_message(&state, &ticker("delta", Some("100.5"), "1005", now + 200), &mut volumes).await);
+        let tick = state.get_price("FEEDUSDT").await.unwrap();
+        assert_eq!((tick.price, tick.volume), (100.5, 5.0));
+
+        let alarm_id = tokio::time::timeout(Duration::from_secs(1), async {
+            loop {
+                match subscription.recv().await {
+                    Some(AppEvent::AlarmTriggered { alarm_id }) => return alarm_id,
+                    Some(_) => {}
+                    None => panic!("dağıtıcı kapandı"),
+                }
+            }
+        })
+        .await
+        .expect("alarm tetiklenmedi");
+        assert_eq!(alarm_id, "feed-alarm");
+    }
+}
@@ -0,0 +1,829 @@
+// AlgoTrade OS - Pre-Trade Risk Gate
+// İşlem açılmadan önceki son onay mercii (roadmap/RISK_MANAGEMENT_SERVICE.md)
+//
+// AppState'e verilen her emir yürütücüsü bu kapıyla sarılır; komutlar ve modüller kapıyı atlayamaz.
+// Günlük kayıp limiti aşılınca DailyPnlTracker sonraki işlem gününe kadar yeni girişleri kilitler;
+// acil durdurma devredeyken tüm girişler reddedilir, yarım boyut kademesinde işlem riski sınırı yarıya iner.
+// Korelasyonlu risk veya portföy VaR sınırını aşan emirler sınıra sığacak kadar küçültülür
+// (ayarda kapalıysa ya da emrin çok küçük bir kısmı kalıyorsa reddedilir).
+// Sadece pozisyon açan/büyüten emirler denetlenir: reduce-only emirler, iptaller ve stop
+// güncellemeleri doğrudan borsaya gider (kapatma hiçbir zaman engellenmez). Ters yöndeki açık
+// pozisyonun miktarına kadar olan emir de azaltma sayılır; pozisyonu çeviren emrin sadece
+// fazlası denetlenir, fazlası reddedilirse kapatan kısmı reduce-only olarak gönderilir.
+// Birlikte gönderilen emirler (sepet bacakları) aynı kurallarla bölünür, açan kısımları tek hesap
+// görünümünde sırayla onaylanır; onaylanan bacak sonraki bacakların pozisyon sayısı ve risk
+// toplamlarına eklenir.
+// Korelasyon matrisinin serileri (emrin ve açık pozisyonların sembolleri, korelasyon aralığı)
+// önbellekte pencereye yetmiyorsa borsadan doldurulup takibe alınır.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::future::join_all;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use crate::exchange::bybit::{Kline, MarketCategory};
+use crate::exchange::executor::{
+    closing_side, AccountBalance, ExecutionError, LotSize, OpenOrder, OrderExecutor, OrderRequest,
+};
+use crate::i18n::t;
+use crate::models::{
+    CorrelationMatrix, Execution, KillSwitch, OrderSide, Position, PositionSide, RiskLimits, StandardTick,
+    TradingLockout, UserSettings,
+};
+use crate::modules::risk_calculator::RiskCalculator;
+use super::correlation::{self, Exposure};
+use super::daily_pnl::DailyPnlTracker;
+use super::drawdown::DrawdownTracker;
+use super::klines::{KlineCache, MAX_CANDLES};
+
+/// Emrin en az bu oranı sınıra sığmıyorsa küçültmek yerine reddedilir
+const MIN_CORRELATION_SCALE: f64 = 0.25;
+
+/// Kapının denetlediği kural
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RiskRule {
+    KillSwitch,
+    DailyLoss,
+    TradeRisk,
+    OpenRisk,
+    MaxPositions,
+    Leverage,
+    SymbolExposure,
+    CorrelatedRisk,
+    PortfolioVar,
+}
+
+impl RiskRule {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RiskRule::KillSwitch => "kill_switch",
+            RiskRule::DailyLoss => "daily_loss",
+            RiskRule::TradeRisk => "trade_risk",
+            RiskRule::OpenRisk => "open_risk",
+            RiskRule::MaxPositions => "max_positions",
+            RiskRule::Leverage => "leverage",
+            RiskRule::SymbolExposure => "symbol_exposure",
+            RiskRule::CorrelatedRisk => "correlated_risk",
+            RiskRule::PortfolioVar => "portfolio_var",
+        }
+    }
+}
+
+/// Reddedilen emrin nedeni
+#[derive(Debug, Clone, PartialEq)]
+pub struct RiskRejection {
+    pub rule: RiskRule,
+    pub value: f64,
+    pub limit: f64,
+}
+
+impl RiskRejection {
+    /// Kullanıcıya gösterilen mesajın i18n anahtarı
+    pub fn message_key(&self) -> &'static str {
+        match self.rule {
+            RiskRule::KillSwitch => "error.kill_switch_engaged",
+            RiskRule::DailyLoss => "error.daily_loss_limit",
+            _ => "error.risk_limit_exceeded",
+        }
+    }
+}
+
+impl std::fmt::Display for RiskRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({}: {:.2} / {:.2})", t(self.message_key()), self.rule.as_str(), self.value, self.limit)
+    }
+}
+
+/// Korelasyon sınırı nedeniyle küçültülmesi gereken emir
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScaleDown {
+    /// Emir miktarının çarpanı (0..1)
+    pub factor: f64,
+    /// Emrin tamamı için aşılan sınır
+    pub rejection: RiskRejection,
+}
+
+/// Emir anındaki hesap durumu
+pub struct AccountView<'a> {
+    pub balance: AccountBalance,
+    pub positions: &'a [Position],
+    /// Sembol -> son fiyat (eksikse giriş fiyatı kullanılır)
+    pub prices: &'a HashMap<String, f64>,
+}
+
+/// Pozisyon açan emri sınırlara göre denetle (günlük kayıp kilidi ayrıca uygulanır)
+///
+/// `price`: emrin tahmini dolum fiyatı
+pub fn evaluate(
+    request: &OrderRequest,
+    price: f64,
+    account: &AccountView,
+    limits: &RiskLimits,
+) -> Result<(), RiskRejection> {
+    let equity = account.balance.equity;
+    let percent = |value: f64| if equity > 0.0 { value / equity * 100.0 } else { f64::INFINITY };
+    let check = |rule: RiskRule, value: f64, limit: f64| {
+        if value > limit {
+            Err(RiskRejection { rule, value, limit })
+        } else {
+            Ok(())
+        }
+    };
+
+    let notional = order_notional(request, price);
+    let trade_risk = trade_risk(request, price);
+    check(RiskRule::TradeRisk, percent(trade_risk), limits.max_trade_risk_pct)?;
+
+    let position_price = |p: &Position| account.prices.get(&p.symbol).copied().unwrap_or(p.entry_price);
+    let open_risk: f64 = account
+        .positions
+        .iter()
+        .map(|p| RiskCalculator::calculate_open_risk(p, position_price(p)))
+        .sum();
+    check(RiskRule::OpenRisk, percent(open_risk + trade_risk), limits.max_open_risk_pct)?;
+
+    let side = match request.side {
+        OrderSide::Buy => PositionSide::Long,
+        OrderSide::Sell => PositionSide::Short,
+    };
+    let adds_position = !account.positions.iter().any(|p| p.symbol == request.symbol && p.side == side);
+    if adds_position {
+        // Aynı sembol ve yöndeki kayıtlar (onay bekleyen eklemeler dahil) tek pozisyondur
+        let open_positions = account
+            .positions
+            .iter()
+            .enumerate()
+            .filter(|(i, p)| !account.positions[..*i].iter().any(|q| q.symbol == p.symbol && q.side == p.side))
+            .count();
+        check(
+            RiskRule::MaxPositions,
+            (open_positions + 1) as f64,
+            f64::from(limits.max_positions),
+        )?;
+    }
+
+    let position_notional = |p: &Position| RiskCalculator::notional(MarketCategory::for_symbol(&p.symbol), position_price(p), p.quantity);
+    let gross: f64 = account.positions.iter().map(position_notional).sum();
+    let leverage = if equity > 0.0 { (gross + notional) / equity } else { f64::INFINITY };
+    check(RiskRule::Leverage, leverage, limits.max_leverage)?;
+
+    let symbol_exposure: f64 = account
+        .positions
+        .iter()
+        .filter(|p| p.symbol == request.symbol)
+        .map(position_notional)
+        .sum();
+    check(RiskRule::SymbolExposure, percent(symbol_exposure + notional), limits.max_symbol_exposure_pct)
+}
+
+/// Emrin quote cinsinden büyüklüğü
+fn order_notional(request: &OrderRequest, price: f64) -> f64 {
+    RiskCalculator::notional(MarketCategory::for_symbol(&request.symbol), price, request.quantity)
+}
+
+/// İşlem riski (stopsuz emrin tüm büyüklüğü risk sayılır)
+fn trade_risk(request: &OrderRequest, price: f64) -> f64 {
+    let category = MarketCategory::for_symbol(&request.symbol);
+    request
+        .stop_loss
+        .map(|stop| RiskCalculator::stop_loss_amount(category, price, stop, request.quantity))
+        .unwrap_or_else(|| order_notional(request, price))
+}
+
+/// Emri korelasyonlu risk ve portföy VaR sınırlarına göre denetle
+///
+/// `horizon_bars`: 1 günlük VaR ufkundaki mum sayısı (None ise VaR denetlenmez).
+/// Sınırı aşan emir için sığan çarpanı döndürür; sığmıyorsa veya küçültme kapalıysa reddeder.
+pub fn correlation_check(
+    request: &OrderRequest,
+    price: f64,
+    account: &AccountView,
+    matrix: &CorrelationMatrix,
+    limits: &RiskLimits,
+    horizon_bars: Option<f64>,
+) -> Result<Option<ScaleDown>, RiskRejection> {
+    let equity = account.balance.equity;
+    let percent = |value: f64| if equity > 0.0 { value / equity * 100.0 } else { f64::INFINITY };
+    let cap = |limit: f64| equity.max(0.0) * limit / 100.0;
+
+    let existing: Vec<Exposure> = account
+        .positions
+        .iter()
+        .map(|p| {
+            let price = account.prices.get(&p.symbol).copied().unwrap_or(p.entry_price);
+            let sign = if p.side == PositionSide::Long { 1.0 } else { -1.0 };
+            Exposure {
+                symbol: p.symbol.clone(),
+                risk: sign * RiskCalculator::calculate_open_risk(p, price),
+                value: sign * RiskCalculator::notional(MarketCategory::for_symbol(&p.symbol), price, p.quantity),
+            }
+        })
+        .collect();
+    let sign = if request.side == OrderSide::Buy { 1.0 } else { -1.0 };
+    let new = Exposure {
+        symbol: request.symbol.clone(),
+        risk: sign * trade_risk(request, price),
+        value: sign * order_notional(request, price),
+    };
+    let mut all = existing.clone();
+    all.push(new.clone());
+
+    let mut scaled: Option<ScaleDown> = None;
+    let mut limit_to = |factor: Option<f64>, rule: RiskRule, value: f64, limit: f64| {
+        let rejection = RiskRejection { rule, value, limit };
+        match factor {
+            Some(factor) if factor >= 1.0 => Ok(()),
+            Some(factor) if factor >= MIN_CORRELATION_SCALE && limits.scale_correlated_orders => {
+                if scaled.as_ref().is_none_or(|s| factor < s.factor) {
+                    scaled = Some(ScaleDown { factor, rejection });
+                }
+                Ok(())
+            }
+            _ => Err(rejection),
+        }
+    };
+
+    limit_to(
+        correlation::risk_scale(&existing, &new, matrix, cap(limits.max_correlated_risk_pct)),
+        RiskRule::CorrelatedRisk,
+        percent(correlation::correlated_risk(&all, matrix)),
+        limits.max_correlated_risk_pct,
+    )?;
+    if let Some(horizon) = horizon_bars {
+        if let Some(var) = correlation::portfolio_var(&all, matrix, horizon) {
+            limit_to(
+                correlation::var_scale(&existing, &new, matrix, horizon, cap(limits.max_var_pct)),
+                RiskRule::PortfolioVar,
+                percent(var),
+                limits.max_var_pct,
+            )?;
+        }
+    }
+    Ok(scaled)
+}
+
+/// Risk kapısı - emir yürütücüsünü sarar, pozisyon açan emirleri sınırlara göre denetler
+pub struct RiskGate {
+    inner: Arc<dyn OrderExecutor>,
+    settings: Arc<RwLock<UserSettings>>,
+    prices: Arc<RwLock<HashMap<String, StandardTick>>>,
+    daily_pnl: Arc<DailyPnlTracker>,
+    drawdown: Arc<DrawdownTracker>,
+    klines: Arc<KlineCache>,
+}
+
+impl RiskGate {
+    pub fn new(
+        inner: Arc<dyn OrderExecutor>,
+        settings: Arc<RwLock<UserSettings>>,
+        prices: Arc<RwLock<HashMap<String, StandardTick>>>,
+        daily_pnl: Arc<DailyPnlTracker>,
+        drawdown: Arc<DrawdownTracker>,
+        klines: Arc<KlineCache>,
+    ) -> Self {
+        Self { inner, settings, prices, daily_pnl, drawdown, klines }
+    }
+
+    /// Emri güncel hesap durumuyla denetle, açılabilecek miktarı döndür
+    async fn approve(&self, request: &OrderRequest) -> Result<f64, ExecutionError> {
+        let snapshot = self.snapshot().await.map_err(|rejection| reject(request, rejection))??;
+        self.check(request, &snapshot, &snapshot.positions).await.map(|(_, quantity)| quantity)
+    }
+
+    /// Birlikte gönderilen emirleri tek hesap görünümünde sırayla denetle
+    ///
+    /// Onaylanan her emir sonraki emirler için açık pozisyon sayılır; böylece bacaklar pozisyon
+    /// sayısı, açık risk, korelasyonlu risk ve VaR sınırlarını birlikte aşamaz.
+    pub async fn approve_batch(&self, requests: &[OrderRequest]) -> Vec<Result<f64, ExecutionError>> {
+        let snapshot = match self.snapshot().await {
+            Ok(Ok(snapshot)) => snapshot,
+            Ok(Err(e)) => return requests.iter().map(|_| Err(e.clone())).collect(),
+            Err(rejection) => return requests.iter().map(|r| Err(reject(r, rejection.clone()))).collect(),
+        };
+        let mut positions = snapshot.positions.clone();
+        let mut results = Vec::with_capacity(requests.len());
+        for request in requests {
+            let result = self.check(request, &snapshot, &positions).await;
+            if let Ok((price, quantity)) = &result {
+                positions.push(pending_position(request, *price, *quantity, snapshot.now));
+            }
+            results.push(result.map(|(_, quantity)| quantity));
+        }
+        results
+    }
+
+    /// Acil durdurma ve günlük kayıp kilidini denetle, hesap durumunu oku
+    ///
+    /// Dış hata: yeni girişler engelli; iç hata: borsa isteği başarısız.
+    async fn snapshot(&self) -> Result<Result<GateSnapshot, ExecutionError>, RiskRejection> {
+        let prices: HashMap<String, f64> = self
+            .prices
+            .read()
+            .await
+            .iter()
+            .map(|(symbol, tick)| (symbol.clone(), tick.price))
+            .collect();
+        let settings = self.settings.read().await.clone();
+        let timezone = settings.trading_day_timezone;
+        let now = Utc::now();
+
+        if let Some(kill_switch) = self.drawdown.kill_switch().await {
+            return Err(kill_switch_rejection(&kill_switch, &settings));
+        }
+        if let Some(lockout) = self.daily_pnl.active_lockout(now).await {
+            return Err(lockout_rejection(&lockout));
+        }
+
+        let (balance, positions, daily) = tokio::join!(
+            self.inner.account_balance(),
+            self.inner.open_positions(),
+            self.daily_pnl.sync(self.inner.as_ref(), timezone, now)
+        );
+        let balance = match balance {
+            Ok(balance) => balance,
+            Err(e) => return Ok(Err(e)),
+        };
+        let daily = match daily {
+            Ok(daily) => daily,
+            Err(e) => return Ok(Err(ExecutionError::Exchange(e))),
+        };
+        // Güncel varlık acil durdurmayı tetiklemiş olabilir
+        self.drawdown.record(balance.equity, &settings.drawdown_limits, now).await;
+        if let Some(kill_switch) = self.drawdown.kill_switch().await {
+            return Err(kill_switch_rejection(&kill_switch, &settings));
+        }
+        if let Some(lockout) = self
+            .daily_pnl
+            .enforce(daily.net_pnl + balance.unrealized_pnl, balance.equity, settings.max_daily_loss, timezone, now)
+            .await
+        {
+            return Err(lockout_rejection(&lockout));
+        }
+
+        let positions = match positions {
+            Ok(positions) => positions,
+            Err(e) => return Ok(Err(e)),
+        };
+        let limits = RiskLimits {
+            max_trade_risk_pct: settings.risk_limits.max_trade_risk_pct * self.drawdown.sizing_factor().await,
+            ..settings.risk_limits.clone()
+        };
+        Ok(Ok(GateSnapshot { prices, balance, positions, limits, now }))
+    }
+
+    /// Emri verilen pozisyonlarla sınırlara göre denetle, (tahmini fiyat, açılabilecek miktar) döndür
+    async fn check(
+        &self,
+        request: &OrderRequest,
+        snapshot: &GateSnapshot,
+        positions: &[Position],
+    ) -> Result<(f64, f64), ExecutionError> {
+        let price = match request.price.or(request.trigger_price).or_else(|| snapshot.prices.get(&request.symbol).copied()) {
+            Some(price) => price,
+            None => self.inner.last_price(&request.symbol).await?,
+        };
+
+        let limits = &snapshot.limits;
+        let account = AccountView { balance: snapshot.balance, positions, prices: &snapshot.prices };
+        evaluate(request, price, &account, limits).map_err(|rejection| reject(request, rejection))?;
+
+        let mut symbols: Vec<String> = positions.iter().map(|p| p.symbol.clone()).collect();
+        symbols.push(request.symbol.clone());
+        symbols.sort();
+        symbols.dedup();
+        let interval = &limits.correlation_interval;
+        let now = snapshot.now;
+        self.load_correlation_klines(&symbols, interval, limits.correlation_lookback).await;
+        let matrix = correlation::correlation_matrix(&self.klines, &symbols, interval, limits.correlation_lookback, now);
+        let horizon = correlation::bars_per_day(interval, now);
+        let Some(scale) = correlation_check(request, price, &account, &matrix, limits, horizon)
+            .map_err(|rejection| reject(request, rejection))?
+        else {
+            return Ok((price, request.quantity));
+        };
+
+        let lot = self.inner.lot_size(&request.symbol).await?;
+        let quantity = lot.round_quantity(request.quantity * scale.factor);
+        if quantity <= 0.0 || quantity < lot.min_qty {
+            return Err(reject(request, scale.rejection));
+        }
+        tracing::warn!(
+            "Risk kapısı emri küçülttü: {} {} -> {} - {}",
+            request.symbol, request.quantity, quantity, scale.rejection
+        );
+        Ok((price, quantity))
+    }
+}
+
+impl RiskGate {
+    /// Korelasyon penceresine yetmeyen serileri borsadan doldur ve takibe al
+    ///
+    /// Alınamayan seri için korelasyon bilinmez sayılır (aynı yöndeki pozisyonlar tam korelasyonlu).
+    async fn load_correlation_klines(&self, symbols: &[String], interval: &str, lookback: usize) {
+        // Getiri için bir önceki kapanış ve borsanın döndürdüğü açık mum
+        let required = (lookback + 2).min(MAX_CANDLES);
+        let missing = symbols.iter().filter(|symbol| self.klines.count(symbol, interval) < required);
+        join_all(missing.map(|symbol| async move {
+            match self.inner.klines(symbol, interval, required).await {
+                Ok(klines) => {
+                    self.klines.track(symbol, interval);
+                    self.klines.merge(symbol, interval, &klines);
+                }
+                Err(e) => tracing::warn!("{} {} korelasyon mumları alınamadı: {}", symbol, interval, e),
+            }
+        }))
+        .await;
+    }
+}
+
+/// Emrin ters yöndeki açık pozisyonları kapatan miktarı (en fazla emir miktarı)
+///
+/// Kapatılan miktar `positions`tan düşülür; aynı toplu istekteki iki emir aynı pozisyonu iki kez kapatamaz.
+fn take_closing(positions: &mut [Position], request: &OrderRequest) -> f64 {
+    let mut remaining = request.quantity;
+    for position in positions
+        .iter_mut()
+        .filter(|p| p.symbol == request.symbol && closing_side(&p.side) == request.side)
+        .filter(|p| request.position_id.as_ref().is_none_or(|id| *id == p.id))
+    {
+        let closed = position.quantity.min(remaining);
+        position.quantity -= closed;
+        remaining -= closed;
+    }
+    request.quantity - remaining
+}
+
+/// Emrin pozisyon açan/büyüten kısmı (reduce-only veya tamamen azaltan emirde None)
+fn opening_part(request: &OrderRequest, closing: f64) -> Option<OrderRequest> {
+    (!request.reduce_only && closing < request.quantity)
+        .then(|| OrderRequest { quantity: request.quantity - closing, ..request.clone() })
+}
+
+/// Açan kısmın onayına göre borsaya gidecek emir
+///
+/// Küçültülen emir kapatan kısımla birlikte gönderilir; açan kısım reddedilirse sadece kapatan kısım
+/// reduce-only olarak gider.
+fn approved_order(
+    request: &OrderRequest,
+    closing: f64,
+    opening: &OrderRequest,
+    approval: Result<f64, ExecutionError>,
+) -> Result<OrderRequest, ExecutionError> {
+    match approval {
+        Ok(quantity) if quantity < opening.quantity => Ok(OrderRequest { quantity: closing + quantity, ..request.clone() }),
+        Ok(_) => Ok(request.clone()),
+        Err(e) if closing > 0.0 => {
+            tracing::warn!(
+                "Pozisyonu çeviren emrin sadece kapatan kısmı gönderiliyor: {} {} - {}",
+                request.symbol, closing, e
+            );
+            Ok(OrderRequest { quantity: closing, reduce_only: true, ..request.clone() })
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Kapının bir denetim için okuduğu hesap durumu
+struct GateSnapshot {
+    prices: HashMap<String, f64>,
+    balance: AccountBalance,
+    positions: Vec<Position>,
+    /// Tepeden düşüş kademesi uygulanmış sınırlar
+    limits: RiskLimits,
+    now: DateTime<Utc>,
+}
+
+/// Aynı toplu istekte onaylanmış emrin sonraki emirler için açık pozisyon karşılığı
+fn pending_position(request: &OrderRequest, price: f64, quantity: f64, now: DateTime<Utc>) -> Position {
+    Position {
+        id: format!("pending-{}", request.symbol),
+        symbol: request.symbol.clone(),
+        side: match request.side {
+            OrderSide::Buy => PositionSide::Long,
+            OrderSide::Sell => PositionSide::Short,
+        },
+        entry_price: price,
+        quantity,
+        stop_loss: request.stop_loss,
+        take_profit: None,
+        created_at: now,
+    }
+}
+
+/// Günlük kayıp kilidinin ret nedeni
+fn lockout_rejection(lockout: &TradingLockout) -> RiskRejection {
+    RiskRejection { rule: RiskRule::DailyLoss, value: lockout.loss_percent, limit: lockout.limit_percent }
+}
+
+/// Acil durdurmanın ret nedeni
+fn kill_switch_rejection(kill_switch: &KillSwitch, settings: &UserSettings) -> RiskRejection {
+    RiskRejection {
+        rule: RiskRule::KillSwitch,
+        value: kill_switch.drawdown_pct,
+        limit: settings.drawdown_limits.kill_switch_pct,
+    }
+}
+
+/// Reddi kaydet ve yürütme hatasına çevir
+fn reject(request: &OrderRequest, rejection: RiskRejection) -> ExecutionError {
+    tracing::warn!("Risk kapısı emri reddetti: {} {} - {}", request.symbol, request.quantity, rejection);
+    ExecutionError::RiskRejected(rejection.to_string())
+}
+
+#[async_trait]
+impl OrderExecutor for RiskGate {
+    async fn place_order(&self, request: &OrderRequest) -> Result<String, ExecutionError> {
+        if request.reduce_only {
+            return self.inner.place_order(request).await;
+        }
+        let mut positions = self.inner.open_positions().await?;
+        let closing = take_closing(&mut positions, request);
+        let Some(opening) = opening_part(request, closing) else {
+            return self.inner.place_order(request).await;
+        };
+        let approval = self.approve(&opening).await;
+        let order = approved_order(request, closing, &opening, approval)?;
+        self.inner.place_order(&order).await
+    }
+
+    async fn place_orders(&self, requests: &[OrderRequest]) -> Vec<Result<String, ExecutionError>> {
+        let mut positions = match self.inner.open_positions().await {
+            Ok(positions) => positions,
+            Err(e) => return requests.iter().map(|_| Err(e.clone())).collect(),
+        };
+        let splits: Vec<(f64, Option<OrderRequest>)> = requests
+            .iter()
+            .map(|request| {
+                let closing = take_closing(&mut positions, request);
+                (closing, opening_part(request, closing))
+            })
+            .collect();
+
+        // Sadece açan kısımlar birlikte denetlenir; kapatan ve reduce-only bacaklar doğrudan gider
+        let openings: Vec<OrderRequest> = splits.iter().filter_map(|(_, opening)| opening.clone()).collect();
+        let mut approvals = self.approve_batch(&openings).await.into_iter();
+        let orders: Vec<Result<OrderRequest, ExecutionError>> = requests
+            .iter()
+            .zip(splits)
+            .map(|(request, (closing, opening))| match opening {
+                Some(opening) => {
+                    let approval = approvals.next().expect("approve_batch her emir için sonuç döndürür");
+                    approved_order(request, closing, &opening, approval)
+                }
+                None => Ok(request.clone()),
+            })
+            .collect();
+        join_all(orders.into_iter().map(|order| async move { self.inner.place_order(&order?).await })).await
+    }
+
+    async fn cancel_order(&self, symbol: &str, order_id: &str) -> Result<(), ExecutionError> {
+        self.inner.cancel_order(symbol, order_id).await
+    }
+
+    async fn open_orders(&self, symbol: &str) -> Result<Vec<OpenOrder>, ExecutionError> {
+        self.inner.open_orders(symbol).await
+    }
+
+    async fn set_stop_loss(&self, position: &Position, stop_price: f64) -> Result<(), ExecutionError> {
+        self.inner.set_stop_loss(position, stop_price).await
+    }
+
+    async fn lot_size(&self, symbol: &str) -> Result<LotSize, ExecutionError> {
+        self.inner.lot_size(symbol).await
+    }
+
+    async fn open_positions(&self) -> Result<Vec<Position>, ExecutionError> {
+        self.inner.open_positions().await
+    }
+
+    async fn account_balance(&self) -> Result<AccountBalance, ExecutionError> {
+        self.inner.account_balance().await
+    }
+
+    async fn last_price(&self, symbol: &str) -> Result<f64, ExecutionError> {
+        self.inner.last_price(symbol).await
+    }
+
+    async fn klines(&self, symbol: &str, interval: &str, limit: usize) -> Result<Vec<Kline>, ExecutionError> {
+        self.inner.klines(symbol, interval, limit).await
+    }
+
+    async fn executions(&self, since: DateTime<Utc>) -> Result<Vec<Execution>, ExecutionError> {
+        self.inner.executions(since).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::AppState;
+    use crate::exchange::mock::MockExecutor;
+    use crate::models::OrderType;
+    use crate::testing;
+
+    fn position(symbol: &str, side: PositionSide, quantity: f64, stop: f64) -> Position {
+        Position { side, quantity, stop_loss: Some(stop), ..testing::position(symbol) }
+    }
+
+    fn order(symbol: &str, quantity: f64, stop: f64) -> OrderRequest {
+        OrderRequest {
+            symbol: symbol.into(),
+            side: OrderSide::Buy,
+            order_type: OrderType::Market,
+            quantity,
+            price: None,
+            trigger_price: None,
+            reduce_only: false,
+            stop_loss: Some(stop),
+            position_id: None,
+        }
+    }
+
+    #[test]
+    fn test_guardrails() {
+        let positions = vec![position("BTCUSDT", PositionSide::Long, 10.0, 98.0)]; // Risk 20
+        let prices = HashMap::new();
+        let balance = AccountBalance { equity: 1000.0, available: 1000.0, ..Default::default() };
+        let account = AccountView { balance, positions: &positions, prices: &prices };
+        let limits = RiskLimits { max_positions: 1, ..RiskLimits::default() };
+        let rule = |request: &OrderRequest, account: &AccountView| {
+            evaluate(request, 100.0, account, &limits).err().map(|r| r.rule)
+        };
+
+        // Aynı yönde mevcut pozisyona ekleme: pozisyon sayısı artmaz
+        assert_eq!(rule(&order("BTCUSDT", 5.0, 97.0), &account), None);
+        // Tek işlem riski %2'yi aşıyor: 5 * 5 = 25
+        assert_eq!(rule(&order("BTCUSDT", 5.0, 95.0), &account), Some(RiskRule::TradeRisk));
+        // Stopsuz emir: tüm büyüklük risk
+        assert_eq!(rule(&OrderRequest { stop_loss: None, ..order("BTCUSDT", 1.0, 0.0) }, &account), Some(RiskRule::TradeRisk));
+        assert_eq!(rule(&order("ETHUSDT", 1.0, 99.0), &account), Some(RiskRule::MaxPositions));
+        // Kaldıraç: (1000 + 9500) / 1000 > 10
+        assert_eq!(rule(&order("BTCUSDT", 95.0, 99.9), &account), Some(RiskRule::Leverage));
+        assert_eq!(lockout_rejection(&TradingLockout {
+            trading_day: Utc::now().date_naive(),
+            locked_at: Utc::now(),
+            until: Utc::now(),
+            loss_percent: 6.0,
+            limit_percent: 5.0,
+            overridden_at: None,
+        }).message_key(), "error.daily_loss_limit");
+    }
+
+    #[test]
+    fn test_correlated_risk_scales_down() {
+        // Riskleri 20 ve 15 olan iki long; korelasyon verisi yok -> tam korelasyon varsayılır
+        let positions = vec![
+            position("ETHUSDT", PositionSide::Long, 10.0, 98.0),
+            position("SOLUSDT", PositionSide::Long, 5.0, 97.0),
+        ];
+        let prices = HashMap::new();
+        let balance = AccountBalance { equity: 1000.0, available: 1000.0, ..Default::default() };
+        let account = AccountView { balance, positions: &positions, prices: &prices };
+        let matrix = CorrelationMatrix::default();
+        let limits = RiskLimits::default(); // Korelasyonlu risk %4 = 40
+        let check = |request: &OrderRequest, limits: &RiskLimits| {
+            correlation_check(request, 100.0, &account, &matrix, limits, None)
+        };
+
+        assert_eq!(check(&order("BTCUSDT", 2.5, 98.0), &limits), Ok(None));
+        // 35 + 20 > 40: emir 5/20 oranına küçülür
+        let scaled = check(&order("BTCUSDT", 10.0, 98.0), &limits).unwrap().unwrap();
+        assert!((scaled.factor - 0.25).abs() < 1e-9);
+        assert_eq!(scaled.rejection.rule, RiskRule::CorrelatedRisk);
+        // Ters yöndeki short korelasyonsuz sayılır: sqrt(35² + 10²) < 40
+        let short = OrderRequest { side: OrderSide::Sell, ..order("BTCUSDT", 5.0, 102.0) };
+        assert_eq!(check(&short, &limits), Ok(None));
+
+        let strict = RiskLimits { scale_correlated_orders: false, ..RiskLimits::default() };
+        assert_eq!(check(&order("BTCUSDT", 10.0, 98.0), &strict).unwrap_err().rule, RiskRule::CorrelatedRisk);
+        // Sınıra emrin %25'inden azı sığıyor
+        assert!(check(&order("BTCUSDT", 30.0, 98.0), &limits).is_err());
+    }
+
+    /// Risk kapısıyla sarılmış sahte yürütücü (BTCUSDT'de 2 adetlik long pozisyon)
+    async fn gate(equity: f64) -> (AppState, Arc<MockExecutor>, Arc<dyn OrderExecutor>) {
+        crate::db::init_test_database();
+        let state = AppState::new();
+        let mock = Arc::new(MockExecutor::with_equity(equity));
+        mock.positions.lock().unwrap().push(position("BTCUSDT", PositionSide::Long, 2.0, 98.0));
+        state.set_executor(Some(mock.clone())).await;
+        let executor = state.executor().await.unwrap();
+        (state, mock, executor)
+    }
+
+    fn sell(quantity: f64) -> OrderRequest {
+        OrderRequest { side: OrderSide::Sell, stop_loss: None, ..order("BTCUSDT", quantity, 0.0) }
+    }
+
+    #[tokio::test]
+    async fn test_kill_switch_blocks_entries_but_not_reductions() {
+        let (state, mock, executor) = gate(100_000.0).await;
+        state.drawdown.engage_kill_switch(Utc::now()).await;
+
+        let rejected = executor.place_order(&order("ETHUSDT", 1.0, 99.0)).await.unwrap_err();
+        assert!(rejected.to_string().contains("kill_switch"));
+        // Aynı yönde ekleme de yeni risk sayılır
+        assert!(executor.place_order(&order("BTCUSDT", 1.0, 99.0)).await.is_err());
+
+        // Ters yönde pozisyon miktarına kadar: reduce-only işaretsiz de olsa geçer
+        executor.place_order(&sell(2.0)).await.unwrap();
+        // Pozisyonu çeviren emir: fazlası reddedilir, kapatan kısmı gönderilir
+        executor.place_order(&sell(3.0)).await.unwrap();
+        let placed = mock.placed();
+        assert_eq!(placed.len(), 2);
+        assert!(!placed[0].reduce_only && placed[0].quantity == 2.0);
+        assert!(placed[1].reduce_only && placed[1].quantity == 2.0);
+    }
+
+    #[tokio::test]
+    async fn test_daily_loss_lockout_blocks_entries_but_not_reductions() {
+        let (state, mock, executor) = gate(1000.0).await;
+        // Gerçekleşmemiş zarar %6 > %5 günlük limit
+        mock.balance.lock().unwrap().unrealized_pnl = -60.0;
+
+        let rejected = executor.place_order(&order("ETHUSDT", 0.1, 99.0)).await.unwrap_err();
+        assert!(rejected.to_string().contains("daily_loss"));
+        assert!(state.daily_pnl.active_lockout(Utc::now()).await.is_some());
+
+        executor.place_order(&sell(1.0)).await.unwrap();
+        assert_eq!(mock.placed().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_correlated_order_is_scaled_down_before_sending() {
+        let (_state, mock, executor) = gate(1000.0).await;
+        // Mevcut riskler 4 (BTC) + 20 (ETH) + 15 (SOL); korelasyon verisi yok -> tam korelasyon
+        mock.positions.lock().unwrap().extend([
+            position("ETHUSDT", PositionSide::Long, 10.0, 98.0),
+            position("SOLUSDT", PositionSide::Long, 5.0, 97.0),
+        ]);
+
+        // 39 + 4 > 40: emir 1/4 oranına küçülür
+        executor.place_order(&order("XRPUSDT", 2.0, 98.0)).await.unwrap();
+        let placed = mock.placed();
+        assert_eq!(placed.len(), 1);
+        assert!((placed[0].quantity - 0.5).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_correlation_series_are_loaded_when_cache_is_empty() {
+        let (state, mock, executor) = gate(1000.0).await;
+        mock.positions.lock().unwrap().extend([
+            position("ETHUSDT", PositionSide::Long, 10.0, 98.0),
+            position("SOLUSDT", PositionSide::Long, 5.0, 97.0),
+        ]);
+        // Saatlik getiriler: BTC, ETH, SOL aynı (+ + - -), XRP ilişkisiz (+ - + -)
+        let last_open = crate::core::scheduler::candle_open("60", Utc::now()).unwrap().timestamp_millis();
+        let series = |pattern: [f64; 4]| {
+            let mut close = 100.0;
+            (0..170)
+                .map(|i| {
+                    close *= 1.0 + 0.001 * pattern[i % 4];
+                    let timestamp = last_open - (169 - i as i64) * 3_600_000;
+                    Kline { timestamp, open: close, high: close, low: close, close, volume: 1.0 }
+                })
+                .collect::<Vec<_>>()
+        };
+        {
+            let mut klines = mock.klines.lock().unwrap();
+            for symbol in ["BTCUSDT", "ETHUSDT", "SOLUSDT"] {
+                klines.insert(symbol.into(), series([1.0, 1.0, -1.0, -1.0]));
+            }
+            klines.insert("XRPUSDT".into(), series([1.0, -1.0, 1.0, -1.0]));
+        }
+        assert_eq!(state.klines.count("XRPUSDT", "60"), 0);
+
+        // Korelasyon verisiyle XRP tam korelasyonlu sayılmaz: sqrt(39² + 4²) < 40, emir küçültülmez
+        executor.place_order(&order("XRPUSDT", 2.0, 98.0)).await.unwrap();
+        assert_eq!(mock.placed()[0].quantity, 2.0);
+        assert_eq!(state.klines.count("XRPUSDT", "60"), 170);
+        assert_eq!(mock.kline_requests.lock().unwrap().len(), 4);
+
+        // Seriler önbellekte ve takipte: tekrar istenmez
+        executor.place_order(&order("XRPUSDT", 2.0, 98.0)).await.unwrap();
+        assert_eq!(mock.kline_requests.lock().unwrap().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_batch_closing_legs_bypass_limits() {
+        let (state, mock, executor) = gate(100_000.0).await;
+        state.settings.write().await.risk_limits.max_positions = 2;
+
+        // Kapatan bacak yeni pozisyon sayılmaz: BTC long + ETH = 2
+        let results = executor.place_orders(&[sell(2.0), order("ETHUSDT", 1.0, 99.0)]).await;
+        assert!(results.iter().all(Result::is_ok));
+        let placed = mock.placed();
+        assert_eq!((placed[0].quantity, placed[1].quantity), (2.0, 1.0));
+
+        // Acil durdurmada: reduce-only bacak gider, çeviren bacağın kapatan kısmı reduce-only gider,
+        // aynı pozisyonu ikinci kez kapatamayan bacak ve yeni giriş reddedilir
+        state.drawdown.engage_kill_switch(Utc::now()).await;
+        let reduce = OrderRequest { reduce_only: true, ..sell(0.5) };
+        let results = executor.place_orders(&[reduce, sell(3.0), sell(1.0), order("ETHUSDT", 1.0, 99.0)]).await;
+        assert_eq!(results.iter().map(Result::is_ok).collect::<Vec<_>>(), vec![true, true, false, false]);
+        let placed = mock.placed();
+        assert_eq!(placed.len(), 4);
+        assert!(placed[3].reduce_only && placed[3].quantity == 1.5);
+    }
+}
@@ -0,0 +1,406 @@
+// AlgoTrade OS - Scheduler
+// Modüllere zamanlayıcı, mum kapanışı ve cron tetiklemeleri sağlar
+//
+// Saat borsa zamanıdır: yerel saat + borsa sunucu saati farkı, tick zaman damgaları da saati ileri
+// taşır. Fark bağlanınca ve sonrasında `TIME_SYNC_INTERVAL` aralığıyla yeniden ölçülür.
+// Tetiklemeler dispatcher üzerinden kayıpsız şeritte, sahibi olan modüle gönderilir.
+
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration as StdDuration;
+use chrono::{DateTime, Datelike, Duration, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use crate::core::{AppEvent, AppState, EventDispatcher};
+use crate::exchange::bybit::BybitClient;
+
+/// Bybit dakika bazlı mum aralıkları
+const MINUTE_INTERVALS: &[i64] = &[1, 3, 5, 15, 30, 60, 120, 240, 360, 720];
+
+/// Borsa saat farkının yeniden ölçülme aralığı (uzun oturumlarda yerel saat kayar)
+const TIME_SYNC_INTERVAL: StdDuration = StdDuration::from_secs(600);
+
+/// Haftalık mumlar Pazartesi 00:00 UTC'de açılır (epoch bir Perşembe)
+const WEEK_ANCHOR_SECS: i64 = 4 * 86_400;
+
+/// Zamanlama türü
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Schedule {
+    /// Sabit aralık (epoch'a hizalı, saniye)
+    Every { seconds: u64 },
+    /// Mum kapanışı (Bybit interval kodu: "1", "5", "60", "D", "W", "M")
+    CandleClose { symbol: String, interval: String },
+    /// Cron ifadesi, UTC (5 alan: dakika saat gün ay haftanın-günü, 6 alan: saniye ile)
+    Cron { expression: String },
+}
+
+/// Modülün istediği zamanlayıcı
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScheduleSpec {
+    /// Modül içinde benzersiz zamanlayıcı kimliği (on_timer'a geri verilir)
+    pub id: String,
+    pub schedule: Schedule,
+}
+
+impl ScheduleSpec {
+    pub fn every(id: &str, seconds: u64) -> Self {
+        Self { id: id.to_string(), schedule: Schedule::Every { seconds } }
+    }
+
+    pub fn candle_close(symbol: &str, interval: &str) -> Self {
+        Self {
+            id: format!("candle:{}:{}", symbol, interval),
+            schedule: Schedule::CandleClose {
+                symbol: symbol.to_string(),
+                interval: interval.to_string(),
+            },
+        }
+    }
+
+    pub fn cron(id: &str, expression: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            schedule: Schedule::Cron { expression: expression.to_string() },
+        }
+    }
+}
+
+/// Kayıtlı iş
+struct Job {
+    owner: String,
+    spec: ScheduleSpec,
+    cron: Option<cron::Schedule>,
+    /// Bir sonraki tetikleme (saat ilk kez ilerleyene kadar None)
+    next_fire: Option<DateTime<Utc>>,
+}
+
+impl Job {
+    /// `after` anından kesinlikle sonraki tetikleme zamanı
+    fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match &self.spec.schedule {
+            Schedule::Every { seconds } => {
+                let step = *seconds as i64;
+                let next = (after.timestamp().div_euclid(step) + 1) * step;
+                Utc.timestamp_opt(next, 0).single()
+            }
+            Schedule::CandleClose { interval, .. } => next_candle_close(interval, after),
+            Schedule::Cron { .. } => self.cron.as_ref()?.after(&after).next(),
+        }
+    }
+
+    fn event(&self, at: DateTime<Utc>) -> AppEvent {
+        match &self.spec.schedule {
+            Schedule::CandleClose { symbol, interval } => AppEvent::CandleClosed {
+                module_id: self.owner.clone(),
+                symbol: symbol.clone(),
+                interval: interval.clone(),
+                close_time: at,
+            },
+            _ => AppEvent::TimerFired {
+                module_id: self.owner.clone(),
+                timer_id: self.spec.id.clone(),
+                fired_at: at,
+            },
+        }
+    }
+}
+
+struct SchedulerInner {
+    /// Saatin ulaştığı son an
+    now: Option<DateTime<Utc>>,
+    /// Borsa saati - yerel saat
+    offset: Duration,
+    jobs: Vec<Job>,
+}
+
+/// Borsa saatiyle çalışan zamanlayıcı
+pub struct Scheduler {
+    dispatcher: EventDispatcher,
+    inner: Mutex<SchedulerInner>,
+}
+
+impl Scheduler {
+    pub fn new(dispatcher: EventDispatcher) -> Self {
+        Self {
+            dispatcher,
+            inner: Mutex::new(SchedulerInner {
+                now: None,
+                offset: Duration::zero(),
+                jobs: Vec::new(),
+            }),
+        }
+    }
+
+    /// Zamanlayıcı ekle (aynı sahip ve kimlikle olan değiştirilir)
+    pub fn add(&self, owner: &str, spec: ScheduleSpec) -> Result<(), String> {
+        let cron = match &spec.schedule {
+            Schedule::Every { seconds } if *seconds == 0 => {
+                return Err("Zamanlayıcı aralığı sıfır olamaz".to_string());
+            }
+            Schedule::CandleClose { interval, .. } => {
+                next_candle_close(interval, Utc::now())
+                    .ok_or_else(|| format!("Geçersiz mum aralığı: {}", interval))?;
+                None
+            }
+            Schedule::Cron { expression } => Some(parse_cron(expression)?),
+            _ => None,
+        };
+
+        let mut inner = self.inner.lock().unwrap();
+        let mut job = Job { owner: owner.to_string(), spec, cron, next_fire: None };
+        job.next_fire = inner.now.and_then(|now| job.next_after(now));
+        inner.jobs.retain(|j| !(j.owner == job.owner && j.spec.id == job.spec.id));
+        inner.jobs.push(job);
+        Ok(())
+    }
+
+    /// Sahibin tüm zamanlayıcılarını kaldır
+    pub fn remove_owner(&self, owner: &str) {
+        self.inner.lock().unwrap().jobs.retain(|j| j.owner != owner);
+    }
+
+    /// Sahibin kayıtlı zamanlayıcıları
+    pub fn schedules_of(&self, owner: &str) -> Vec<ScheduleSpec> {
+        let inner = self.inner.lock().unwrap();
+        inner.jobs.iter().filter(|j| j.owner == owner).map(|j| j.spec.clone()).collect()
+    }
+
+    /// Mum kapanışı zamanlayıcısı olan semboller (tüm sahipler)
+    pub fn candle_symbols(&self) -> Vec<String> {
+        let inner = self.inner.lock().unwrap();
+        inner
+            .jobs
+            .iter()
+            .filter_map(|j| match &j.spec.schedule {
+                Schedule::CandleClose { symbol, .. } => Some(symbol.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Borsa saati
+    pub fn now(&self) -> DateTime<Utc> {
+        Utc::now() + self.inner.lock().unwrap().offset
+    }
+
+    /// Borsa sunucu saatiyle senkronize et
+    pub fn sync_exchange_time(&self, server_time: DateTime<Utc>) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.offset = server_time - Utc::now();
+        tracing::info!("Borsa saat farkı: {} ms", inner.offset.num_milliseconds());
+    }
+
+    /// Borsa olayının zaman damgasını işle (tick) - tetiklenen sayısını döner
+    pub fn observe(&self, exchange_time: DateTime<Utc>) -> usize {
+        self.advance_to(exchange_time)
+    }
+
+    /// Duvar saatine göre ilerle
+    pub fn poll(&self) -> usize {
+        self.advance_to(self.now())
+    }
+
+    /// Saati ileri al ve zamanı gelen işleri tetikle
+    /// Kaçırılan tetiklemeler birleştirilir: bir iş tek ilerlemede en fazla bir kez tetiklenir
+    fn advance_to(&self, time: DateTime<Utc>) -> usize {
+        let mut fired = Vec::new();
+        {
+            let mut inner = self.inner.lock().unwrap();
+            if inner.now.is_some_and(|now| time <= now) {
+                return 0; // Saat geri gitmez
+            }
+            inner.now = Some(time);
+
+            for job in inner.jobs.iter_mut() {
+                match job.next_fire {
+                    Some(due) if due <= time => {
+                        fired.push(job.event(due));
+                        job.next_fire = job.next_after(time);
+                    }
+                    Some(_) => {}
+                    None => job.next_fire = job.next_after(time),
+                }
+            }
+        }
+
+        let count = fired.len();
+        for event in fired {
+            self.dispatcher.publish(event);
+        }
+        count
+    }
+}
+
+/// Saat döngüsünü başlat: tick zaman damgaları ve saniyelik duvar saati saati ilerletir
+pub fn spawn_clock(state: Arc<AppState>) {
+    let subscription = state.dispatcher.subscribe("scheduler");
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(StdDuration::from_secs(1));
+        loop {
+            tokio::select! {
+                event = subscription.recv() => match event {
+                    Some(AppEvent::PriceUpdated(tick)) => {
+                        state.scheduler.observe(tick.timestamp);
+                    }
+                    Some(_) => {}
+                    None => break,
+                },
+                _ = interval.tick() => {
+                    state.scheduler.poll();
+                }
+            }
+        }
+    });
+}
+
+/// Borsa saat farkını periyodik olarak yeniden ölç (herkese açık uç nokta)
+pub fn spawn_time_sync(state: Arc<AppState>) {
+    tauri::async_runtime::spawn(async move {
+        let client = BybitClient::new(String::new(), String::new(), false);
+        let mut interval = tokio::time::interval(TIME_SYNC_INTERVAL);
+        loop {
+            interval.tick().await;
+            match client.get_server_time().await {
+                Ok(server_time) => state.scheduler.sync_exchange_time(server_time),
+                Err(e) => tracing::warn!("Borsa saati alınamadı: {}", e),
+            }
+        }
+    });
+}
+
+/// `after` anından sonraki ilk mum kapanışı (geçersiz aralıkta None)
+pub fn next_candle_close(interval: &str, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let ts = after.timestamp();
+    let aligned = |step: i64, anchor: i64| {
+        let next = ((ts - anchor).div_euclid(step) + 1) * step + anchor;
+        Utc.timestamp_opt(next, 0).single()
+    };
+
+    match interval {
+        "D" => aligned(86_400, 0),
+        "W" => aligned(7 * 86_400, WEEK_ANCHOR_SECS),
+        "M" => {
+            let (year, month) = if after.month() == 12 {
+                (after.year() + 1, 1)
+            } else {
+                (after.year(), after.month() + 1)
+            };
+            Utc.with_ymd_and_hms(year, month, 1, 0, 0, 0).single()
+        }
+        minutes => {
+            let minutes: i64 = minutes.parse().ok()?;
+            if !MINUTE_INTERVALS.contains(&minutes) {
+                return None;
+            }
+            aligned(minutes * 60, 0)
+        }
+    }
+}
+
+/// `time` anını içeren mumun açılış zamanı (geçersiz aralıkta None)
+pub fn candle_open(interval: &str, time: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let ts = time.timestamp();
+    let aligned = |step: i64, anchor: i64| {
+        let open = (ts - anchor).div_euclid(step) * step + anchor;
+        Utc.timestamp_opt(open, 0).single()
+    };
+
+    match interval {
+        "D" => aligned(86_400, 0),
+        "W" => aligned(7 * 86_400, WEEK_ANCHOR_SECS),
+        "M" => Utc.with_ymd_and_hms(time.year(), time.month(), 1, 0, 0, 0).single(),
+        minutes => {
+            let minutes: i64 = minutes.parse().ok()?;
+            if !MINUTE_INTERVALS.contains(&minutes) {
+                return None;
+            }
+            aligned(minutes * 60, 0)
+        }
+    }
+}
+
+/// Cron ifadesini ayrıştır (5 alanlı ifadelere saniye alanı eklenir)
+fn parse_cron(expression: &str) -> Result<cron::Schedule, String> {
+    let normalized = if expression.split_whitespace().count() == 5 {
+        format!("0 {}", expression)
+    } else {
+        expression.to_string()
+    };
+    cron::Schedule::from_str(&normalized)
+        .map_err(|e| format!("Geçersiz cron ifadesi '{}': {}", expression, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    fn drain(subscription: &crate::core::Subscription) -> Vec<AppEvent> {
+        std::iter::from_fn(|| subscription.try_recv()).collect()
+    }
+
+    #[test]
+    fn test_candle_close_boundaries() {
+        let t = at("2024-02-29T13:07:30Z"); // Perşembe
+        assert_eq!(next_candle_close("5", t), Some(at("2024-02-29T13:10:00Z")));
+        assert_eq!(next_candle_close("240", t), Some(at("2024-02-29T16:00:00Z")));
+        assert_eq!(next_candle_close("D", t), Some(at("2024-03-01T00:00:00Z")));
+        assert_eq!(next_candle_close("W", t), Some(at("2024-03-04T00:00:00Z")));
+        assert_eq!(next_candle_close("M", at("2024-12-31T23:59:00Z")), Some(at("2025-01-01T00:00:00Z")));
+        // Tam sınırda: bir sonraki kapanış
+        assert_eq!(next_candle_close("1", at("2024-02-29T13:08:00Z")), Some(at("2024-02-29T13:09:00Z")));
+        assert_eq!(next_candle_close("7", t), None);
+
+        assert_eq!(candle_open("15", t), Some(at("2024-02-29T13:00:00Z")));
+        assert_eq!(candle_open("W", t), Some(at("2024-02-26T00:00:00Z")));
+        assert_eq!(candle_open("M", t), Some(at("2024-02-01T00:00:00Z")));
+    }
+
+    #[test]
+    fn test_clock_fires_from_event_time() {
+        let dispatcher = EventDispatcher::default();
+        let subscription = dispatcher.subscribe("test");
+        let scheduler = Scheduler::new(dispatcher);
+        scheduler.add("stop_loss", ScheduleSpec::candle_close("BTCUSDT", "15")).unwrap();
+        scheduler.add("stop_loss", ScheduleSpec::cron("eod", "0 0 * * *")).unwrap();
+        assert!(scheduler.add("stop_loss", ScheduleSpec::cron("bad", "not cron")).is_err());
+
+        // İlk gözlem saati başlatır, tetiklemez
+        assert_eq!(scheduler.observe(at("2023-06-01T23:40:00Z")), 0);
+        assert_eq!(scheduler.observe(at("2023-06-01T23:44:59Z")), 0);
+
+        assert_eq!(scheduler.observe(at("2023-06-01T23:45:01Z")), 1);
+        // Geri giden zaman yok sayılır
+        assert_eq!(scheduler.observe(at("2023-06-01T23:45:00Z")), 0);
+
+        // Boşluk: birden fazla mum kapanışı tek tetiklemede birleşir, gün sonu da tetiklenir
+        assert_eq!(scheduler.observe(at("2023-06-02T00:31:00Z")), 2);
+
+        let events = drain(&subscription);
+        assert!(matches!(
+            &events[0],
+            AppEvent::CandleClosed { module_id, interval, close_time, .. }
+                if module_id == "stop_loss" && interval == "15" && *close_time == at("2023-06-01T23:45:00Z")
+        ));
+        assert!(events.iter().any(|e| matches!(
+            e,
+            AppEvent::TimerFired { timer_id, fired_at, .. }
+                if timer_id == "eod" && *fired_at == at("2023-06-02T00:00:00Z")
+        )));
+    }
+
+    #[test]
+    fn test_live_clock_uses_exchange_offset() {
+        let scheduler = Scheduler::new(EventDispatcher::default());
+        scheduler.sync_exchange_time(Utc::now() + Duration::hours(1));
+        let drift = scheduler.now() - Utc::now();
+        assert!((drift - Duration::hours(1)).num_seconds().abs() <= 1);
+
+        scheduler.add("risk_monitor", ScheduleSpec::every("heartbeat", 3600)).unwrap();
+        scheduler.remove_owner("risk_monitor");
+        assert!(scheduler.schedules_of("risk_monitor").is_empty());
+    }
+}
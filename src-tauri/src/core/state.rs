@@ -1,43 +1,79 @@
 // AlgoTrade OS - Application State
 // Uygulama durumu ve paylaşılan veriler
 
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::sync::Arc;
 use tokio::sync::RwLock;
-use crate::models::{StandardTick, Alarm, UserSettings, Position};
-use crate::core::EventDispatcher;
+use crate::models::{StandardTick, Alarm, UserSettings, Position, PositionPlan, Basket, RiskSnapshot};
+use crate::core::{DailyPnlTracker, DrawdownTracker, DrawingGeometry, EventDispatcher, KlineCache, Notifier, RiskGate, Scheduler};
+use crate::exchange::OrderExecutor;
 
 /// Uygulama durumu - tüm modüller tarafından paylaşılır
 pub struct AppState {
     /// Olay dağıtıcı
     pub dispatcher: EventDispatcher,
+    /// Borsa saatiyle çalışan zamanlayıcı
+    pub scheduler: Scheduler,
     /// Canlı fiyatlar (symbol -> tick)
     pub live_prices: Arc<RwLock<HashMap<String, StandardTick>>>,
     /// Aktif alarmlar
     pub alarms: Arc<RwLock<Vec<Alarm>>>,
+    /// Çizim alarmlarının izlediği geometriler (drawing_id -> geometri)
+    pub drawing_geometries: Arc<RwLock<HashMap<String, DrawingGeometry>>>,
+    /// Alarm ve risk uyarısı bildirimlerinin kanalları
+    pub notifier: Arc<Notifier>,
     /// Kullanıcı ayarları
     pub settings: Arc<RwLock<UserSettings>>,
     /// Açık pozisyonlar
     pub positions: Arc<RwLock<Vec<Position>>>,
+    /// Son kapanan pozisyonlar, en yenisi önde (PositionClosed olayını işleyen modüller için)
+    pub closed_positions: Arc<RwLock<VecDeque<Position>>>,
+    /// Pozisyon planları (position_id -> plan)
+    pub position_plans: Arc<RwLock<HashMap<String, PositionPlan>>>,
+    /// Takip edilen toplu işlemler (basket_id -> sepet)
+    pub baskets: Arc<RwLock<HashMap<String, Basket>>>,
+    /// Günlük kâr/zarar ve kayıp kilidi
+    pub daily_pnl: Arc<DailyPnlTracker>,
+    /// Varlık eğrisi, tepeden düşüş ve acil durdurma
+    pub drawdown: Arc<DrawdownTracker>,
+    /// Risk izleyicinin son ürettiği görünüm
+    pub risk_snapshot: Arc<RwLock<Option<RiskSnapshot>>>,
+    /// Mum önbelleği (grafik verisi + canlı tick'ler)
+    pub klines: Arc<KlineCache>,
     /// Aktif modüller
     pub active_modules: Arc<RwLock<HashMap<String, bool>>>,
+    /// Bağlı borsanın emir yürütücüsü (bağlantı yoksa None)
+    pub executor: Arc<RwLock<Option<Arc<dyn OrderExecutor>>>>,
 }
 
 impl AppState {
     pub fn new() -> Self {
+        let dispatcher = EventDispatcher::default();
         Self {
-            dispatcher: EventDispatcher::default(),
+            scheduler: Scheduler::new(dispatcher.clone()),
             live_prices: Arc::new(RwLock::new(HashMap::new())),
             alarms: Arc::new(RwLock::new(Vec::new())),
+            drawing_geometries: Arc::new(RwLock::new(HashMap::new())),
+            notifier: Arc::new(Notifier::new()),
             settings: Arc::new(RwLock::new(UserSettings::default())),
             positions: Arc::new(RwLock::new(Vec::new())),
+            closed_positions: Arc::new(RwLock::new(VecDeque::new())),
+            position_plans: Arc::new(RwLock::new(HashMap::new())),
+            baskets: Arc::new(RwLock::new(HashMap::new())),
+            daily_pnl: Arc::new(DailyPnlTracker::default()),
+            drawdown: Arc::new(DrawdownTracker::new(dispatcher.clone())),
+            risk_snapshot: Arc::new(RwLock::new(None)),
+            klines: Arc::new(KlineCache::new()),
             active_modules: Arc::new(RwLock::new(HashMap::new())),
+            executor: Arc::new(RwLock::new(None)),
+            dispatcher,
         }
     }
 
     /// Fiyat güncelle
     pub async fn update_price(&self, tick: StandardTick) {
         let symbol = tick.symbol.clone();
+        self.klines.apply_tick(&tick);
         {
             let mut prices = self.live_prices.write().await;
             prices.insert(symbol, tick.clone());
@@ -51,6 +87,33 @@ impl AppState {
         prices.get(symbol).cloned()
     }
 
+    /// Emir yürütücüsünü ayarla (bağlantı kesilince None)
+    ///
+    /// Yürütücü risk kapısıyla sarılır; hiçbir emir yolu kapıyı atlayamaz.
+    pub async fn set_executor(&self, executor: Option<Arc<dyn OrderExecutor>>) {
+        *self.executor.write().await = executor.map(|inner| {
+            let gate = RiskGate::new(
+                inner,
+                self.settings.clone(),
+                self.live_prices.clone(),
+                self.daily_pnl.clone(),
+                self.drawdown.clone(),
+                self.klines.clone(),
+            );
+            Arc::new(gate) as Arc<dyn OrderExecutor>
+        });
+    }
+
+    /// Bağlı borsanın emir yürütücüsü
+    pub async fn executor(&self) -> Option<Arc<dyn OrderExecutor>> {
+        self.executor.read().await.clone()
+    }
+
+    /// Pozisyonun planı (tanımlı değilse varsayılan)
+    pub async fn position_plan(&self, position_id: &str) -> PositionPlan {
+        self.position_plans.read().await.get(position_id).cloned().unwrap_or_default()
+    }
+
     /// Modül durumunu güncelle
     pub async fn set_module_active(&self, module_id: &str, is_active: bool) {
         {
@@ -6,6 +6,11 @@ use serde::{Deserialize, Serialize};
 use std::sync::OnceLock;
 use tokio::sync::Mutex;
 use std::path::PathBuf;
+use crate::modules::traits::ModulePermission;
+use crate::models::{
+    Alarm, AllocationMethod, AssetGroup, Basket, BasketStatus, EquityPoint, Execution, ModuleActivation, OrderSide, StopMove,
+    StopMoveReason,
+};
 
 // Global database connection
 static DB_CONNECTION: OnceLock<Mutex<Connection>> = OnceLock::new();
@@ -93,7 +98,27 @@ pub fn init_database() -> Result<(), String> {
 
     let conn = Connection::open(&db_path)
         .map_err(|e| format!("Failed to open database: {}", e))?;
+    create_tables(&conn)?;
 
+    // Store connection globally
+    let _ = DB_CONNECTION.set(Mutex::new(conn));
+
+    tracing::info!("Database initialized successfully");
+    Ok(())
+}
+
+/// Initialize a shared in-memory database for tests (first call wins)
+#[cfg(test)]
+pub fn init_test_database() {
+    DB_CONNECTION.get_or_init(|| {
+        let conn = Connection::open_in_memory().expect("in-memory database");
+        create_tables(&conn).expect("test schema");
+        Mutex::new(conn)
+    });
+}
+
+/// Create all tables if they don't exist
+fn create_tables(conn: &Connection) -> Result<(), String> {
     // Create drawings table
     conn.execute(
         "CREATE TABLE IF NOT EXISTS drawings (
@@ -144,10 +169,154 @@ pub fn init_database() -> Result<(), String> {
         [],
     ).map_err(|e| format!("Failed to create groups index: {}", e))?;
 
-    // Store connection globally
-    let _ = DB_CONNECTION.set(Mutex::new(conn));
+    // Create module config table (one JSON document per module)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS module_configs (
+            module_id TEXT PRIMARY KEY,
+            config TEXT NOT NULL,
+            updated_at INTEGER NOT NULL
+        )",
+        [],
+    ).map_err(|e| format!("Failed to create module_configs table: {}", e))?;
+
+    // Create module activation state table
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS module_states (
+            module_id TEXT PRIMARY KEY,
+            activation TEXT NOT NULL,
+            updated_at INTEGER NOT NULL
+        )",
+        [],
+    ).map_err(|e| format!("Failed to create module_states table: {}", e))?;
+
+    // Create module permission grant table (permissions the user approved, JSON array)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS module_grants (
+            module_id TEXT PRIMARY KEY,
+            permissions TEXT NOT NULL,
+            granted_at INTEGER NOT NULL
+        )",
+        [],
+    ).map_err(|e| format!("Failed to create module_grants table: {}", e))?;
+
+    // Create position plan table (one JSON document per open position)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS position_plans (
+            position_id TEXT PRIMARY KEY,
+            plan TEXT NOT NULL,
+            updated_at INTEGER NOT NULL
+        )",
+        [],
+    ).map_err(|e| format!("Failed to create position_plans table: {}", e))?;
+
+    // Create stop move log table
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS stop_moves (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            position_id TEXT NOT NULL,
+            symbol TEXT NOT NULL,
+            from_price REAL,
+            to_price REAL NOT NULL,
+            reason TEXT NOT NULL,
+            market_price REAL NOT NULL,
+            moved_at INTEGER NOT NULL
+        )",
+        [],
+    ).map_err(|e| format!("Failed to create stop_moves table: {}", e))?;
+
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_stop_moves_position
+         ON stop_moves(position_id)",
+        [],
+    ).map_err(|e| format!("Failed to create stop moves index: {}", e))?;
+
+    // Create asset group table (batch trading baskets)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS asset_groups (
+            id TEXT PRIMARY KEY,
+            name TEXT NOT NULL,
+            symbols TEXT NOT NULL,
+            allocation TEXT NOT NULL,
+            linked INTEGER DEFAULT 0,
+            created_at INTEGER NOT NULL,
+            updated_at INTEGER NOT NULL
+        )",
+        [],
+    ).map_err(|e| format!("Failed to create asset_groups table: {}", e))?;
+
+    // Create executed basket table (one JSON document per basket)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS baskets (
+            id TEXT PRIMARY KEY,
+            group_id TEXT NOT NULL,
+            status TEXT NOT NULL,
+            data TEXT NOT NULL,
+            opened_at INTEGER NOT NULL,
+            updated_at INTEGER NOT NULL
+        )",
+        [],
+    ).map_err(|e| format!("Failed to create baskets table: {}", e))?;
+
+    // Create key/value settings table (one JSON document per key)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS app_settings (
+            key TEXT PRIMARY KEY,
+            value TEXT NOT NULL,
+            updated_at INTEGER NOT NULL
+        )",
+        [],
+    ).map_err(|e| format!("Failed to create app_settings table: {}", e))?;
+
+    // Create exchange execution table (realized PnL and fees per fill)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS executions (
+            id TEXT PRIMARY KEY,
+            symbol TEXT NOT NULL,
+            side TEXT NOT NULL,
+            price REAL NOT NULL,
+            quantity REAL NOT NULL,
+            fee REAL NOT NULL,
+            realized_pnl REAL NOT NULL,
+            executed_at INTEGER NOT NULL
+        )",
+        [],
+    ).map_err(|e| format!("Failed to create executions table: {}", e))?;
+
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_executions_time
+         ON executions(executed_at)",
+        [],
+    ).map_err(|e| format!("Failed to create executions index: {}", e))?;
+
+    // Create equity curve table
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS equity_points (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            equity REAL NOT NULL,
+            recorded_at INTEGER NOT NULL
+        )",
+        [],
+    ).map_err(|e| format!("Failed to create equity_points table: {}", e))?;
+
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_equity_points_time
+         ON equity_points(recorded_at)",
+        [],
+    ).map_err(|e| format!("Failed to create equity_points index: {}", e))?;
+
+    // Create alarm table (one JSON document per alarm)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS alarms (
+            id TEXT PRIMARY KEY,
+            symbol TEXT NOT NULL,
+            is_active INTEGER NOT NULL,
+            data TEXT NOT NULL,
+            created_at INTEGER NOT NULL,
+            updated_at INTEGER NOT NULL
+        )",
+        [],
+    ).map_err(|e| format!("Failed to create alarms table: {}", e))?;
 
-    tracing::info!("Database initialized successfully");
     Ok(())
 }
 
@@ -255,6 +424,38 @@ pub async fn get_drawings(symbol: &str, interval: &str) -> Result<Vec<Drawing>,
     Ok(result)
 }
 
+/// Get a single drawing by ID
+pub async fn get_drawing(id: &str) -> Result<Option<Drawing>, String> {
+    let conn = get_connection()?;
+    let conn = conn.lock().await;
+
+    let result = conn.query_row(
+        "SELECT id, symbol, interval, drawing_type, points, style, visible, locked, created_at, updated_at, name, group_id
+         FROM drawings WHERE id = ?",
+        params![id],
+        |row| Ok(Drawing {
+            id: row.get(0)?,
+            symbol: row.get(1)?,
+            interval: row.get(2)?,
+            drawing_type: row.get(3)?,
+            points: row.get(4)?,
+            style: row.get(5)?,
+            visible: row.get::<_, i32>(6)? == 1,
+            locked: row.get::<_, i32>(7)? == 1,
+            created_at: row.get(8)?,
+            updated_at: row.get(9)?,
+            name: row.get(10).ok(),
+            group_id: row.get(11).ok(),
+        }),
+    );
+
+    match result {
+        Ok(drawing) => Ok(Some(drawing)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(format!("Failed to get drawing: {}", e)),
+    }
+}
+
 /// Delete a drawing by ID
 pub async fn delete_drawing(id: &str) -> Result<bool, String> {
     let conn = get_connection()?;
@@ -446,3 +647,602 @@ pub async fn move_drawing_to_group(drawing_id: &str, group_id: Option<&str>) ->
 
     Ok(())
 }
+
+// ============================================
+// MODULE CONFIG OPERATIONS
+// ============================================
+
+/// Get the stored config JSON for a module (None if never saved)
+pub async fn get_module_config(module_id: &str) -> Result<Option<String>, String> {
+    let conn = get_connection()?;
+    let conn = conn.lock().await;
+
+    let result = conn.query_row(
+        "SELECT config FROM module_configs WHERE module_id = ?",
+        params![module_id],
+        |row| row.get::<_, String>(0),
+    );
+
+    match result {
+        Ok(config) => Ok(Some(config)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(format!("Failed to load module config: {}", e)),
+    }
+}
+
+/// Save (insert or replace) the config JSON for a module
+pub async fn save_module_config(module_id: &str, config: &str) -> Result<(), String> {
+    let conn = get_connection()?;
+    let conn = conn.lock().await;
+
+    let now = chrono::Utc::now().timestamp();
+
+    conn.execute(
+        "INSERT INTO module_configs (module_id, config, updated_at) VALUES (?, ?, ?)
+         ON CONFLICT(module_id) DO UPDATE SET config = excluded.config, updated_at = excluded.updated_at",
+        params![module_id, config, now],
+    ).map_err(|e| format!("Failed to save module config: {}", e))?;
+
+    Ok(())
+}
+
+// ============================================
+// MODULE STATE OPERATIONS
+// ============================================
+
+/// Get all persisted module activation states
+pub async fn get_module_states() -> Result<Vec<(String, ModuleActivation)>, String> {
+    let conn = get_connection()?;
+    let conn = conn.lock().await;
+
+    let mut stmt = conn.prepare(
+        "SELECT module_id, activation FROM module_states"
+    ).map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+    let states = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            ModuleActivation::from_code(&row.get::<_, String>(1)?),
+        ))
+    }).map_err(|e| format!("Failed to query module states: {}", e))?;
+
+    let result: Vec<(String, ModuleActivation)> = states
+        .filter_map(|s| s.ok())
+        .collect();
+
+    Ok(result)
+}
+
+/// Save (insert or replace) the activation state of a module
+pub async fn save_module_state(module_id: &str, activation: ModuleActivation) -> Result<(), String> {
+    let conn = get_connection()?;
+    let conn = conn.lock().await;
+
+    let now = chrono::Utc::now().timestamp();
+
+    conn.execute(
+        "INSERT INTO module_states (module_id, activation, updated_at) VALUES (?, ?, ?)
+         ON CONFLICT(module_id) DO UPDATE SET activation = excluded.activation, updated_at = excluded.updated_at",
+        params![module_id, activation.as_str(), now],
+    ).map_err(|e| format!("Failed to save module state: {}", e))?;
+
+    Ok(())
+}
+
+/// Get the permissions the user granted to a module (empty if never granted)
+pub async fn get_module_grants(module_id: &str) -> Result<Vec<ModulePermission>, String> {
+    let conn = get_connection()?;
+    let conn = conn.lock().await;
+
+    let permissions: Option<String> = conn.query_row(
+        "SELECT permissions FROM module_grants WHERE module_id = ?",
+        params![module_id],
+        |row| row.get(0),
+    ).ok();
+
+    match permissions {
+        Some(json) => serde_json::from_str(&json)
+            .map_err(|e| format!("Failed to parse module grants: {}", e)),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// Save the permissions the user granted to a module
+pub async fn save_module_grants(module_id: &str, permissions: &[ModulePermission]) -> Result<(), String> {
+    let conn = get_connection()?;
+    let conn = conn.lock().await;
+
+    let json = serde_json::to_string(permissions)
+        .map_err(|e| format!("Failed to serialize module grants: {}", e))?;
+    let now = chrono::Utc::now().timestamp();
+
+    conn.execute(
+        "INSERT INTO module_grants (module_id, permissions, granted_at) VALUES (?, ?, ?)
+         ON CONFLICT(module_id) DO UPDATE SET permissions = excluded.permissions, granted_at = excluded.granted_at",
+        params![module_id, json, now],
+    ).map_err(|e| format!("Failed to save module grants: {}", e))?;
+
+    Ok(())
+}
+
+// ============================================
+// POSITION PLAN OPERATIONS
+// ============================================
+
+/// Get all stored position plans as (position_id, plan JSON)
+pub async fn get_position_plans() -> Result<Vec<(String, String)>, String> {
+    let conn = get_connection()?;
+    let conn = conn.lock().await;
+
+    let mut stmt = conn.prepare(
+        "SELECT position_id, plan FROM position_plans"
+    ).map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+    let plans = stmt.query_map([], |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+    }).map_err(|e| format!("Failed to query position plans: {}", e))?;
+
+    Ok(plans.filter_map(|p| p.ok()).collect())
+}
+
+/// Save (insert or replace) the plan JSON for a position
+pub async fn save_position_plan(position_id: &str, plan: &str) -> Result<(), String> {
+    let conn = get_connection()?;
+    let conn = conn.lock().await;
+
+    let now = chrono::Utc::now().timestamp();
+
+    conn.execute(
+        "INSERT INTO position_plans (position_id, plan, updated_at) VALUES (?, ?, ?)
+         ON CONFLICT(position_id) DO UPDATE SET plan = excluded.plan, updated_at = excluded.updated_at",
+        params![position_id, plan, now],
+    ).map_err(|e| format!("Failed to save position plan: {}", e))?;
+
+    Ok(())
+}
+
+/// Delete the plan of a position
+pub async fn delete_position_plan(position_id: &str) -> Result<bool, String> {
+    let conn = get_connection()?;
+    let conn = conn.lock().await;
+
+    let rows = conn.execute(
+        "DELETE FROM position_plans WHERE position_id = ?",
+        params![position_id],
+    ).map_err(|e| format!("Failed to delete position plan: {}", e))?;
+
+    Ok(rows > 0)
+}
+
+// ============================================
+// STOP MOVE LOG OPERATIONS
+// ============================================
+
+/// Append a stop move to the log
+pub async fn save_stop_move(stop_move: &StopMove) -> Result<(), String> {
+    let conn = get_connection()?;
+    let conn = conn.lock().await;
+
+    conn.execute(
+        "INSERT INTO stop_moves (position_id, symbol, from_price, to_price, reason, market_price, moved_at)
+         VALUES (?, ?, ?, ?, ?, ?, ?)",
+        params![
+            stop_move.position_id,
+            stop_move.symbol,
+            stop_move.from,
+            stop_move.to,
+            stop_move.reason.as_str(),
+            stop_move.price,
+            stop_move.moved_at.timestamp_millis(),
+        ],
+    ).map_err(|e| format!("Failed to save stop move: {}", e))?;
+
+    Ok(())
+}
+
+/// Get the stop moves of a position (oldest first)
+pub async fn get_stop_moves(position_id: &str) -> Result<Vec<StopMove>, String> {
+    let conn = get_connection()?;
+    let conn = conn.lock().await;
+
+    let mut stmt = conn.prepare(
+        "SELECT position_id, symbol, from_price, to_price, reason, market_price, moved_at
+         FROM stop_moves WHERE position_id = ? ORDER BY id"
+    ).map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+    let moves = stmt.query_map(params![position_id], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, Option<f64>>(2)?,
+            row.get::<_, f64>(3)?,
+            row.get::<_, String>(4)?,
+            row.get::<_, f64>(5)?,
+            row.get::<_, i64>(6)?,
+        ))
+    }).map_err(|e| format!("Failed to query stop moves: {}", e))?;
+
+    let result: Vec<StopMove> = moves
+        .filter_map(|m| m.ok())
+        .filter_map(|(position_id, symbol, from, to, reason, price, moved_at)| {
+            Some(StopMove {
+                position_id,
+                symbol,
+                from,
+                to,
+                reason: StopMoveReason::from_code(&reason)?,
+                price,
+                moved_at: chrono::DateTime::from_timestamp_millis(moved_at)?,
+            })
+        })
+        .collect();
+
+    Ok(result)
+}
+
+// ============================================
+// ASSET GROUP OPERATIONS
+// ============================================
+
+/// Save (insert or update) an asset group
+pub async fn save_asset_group(group: AssetGroup) -> Result<AssetGroup, String> {
+    let conn = get_connection()?;
+    let conn = conn.lock().await;
+
+    let now = chrono::Utc::now().timestamp();
+    let created_at = if group.created_at > 0 { group.created_at } else { now };
+    let symbols = serde_json::to_string(&group.symbols).map_err(|e| e.to_string())?;
+
+    conn.execute(
+        "INSERT INTO asset_groups (id, name, symbols, allocation, linked, created_at, updated_at)
+         VALUES (?, ?, ?, ?, ?, ?, ?)
+         ON CONFLICT(id) DO UPDATE SET
+            name = excluded.name,
+            symbols = excluded.symbols,
+            allocation = excluded.allocation,
+            linked = excluded.linked,
+            updated_at = excluded.updated_at",
+        params![
+            &group.id,
+            &group.name,
+            symbols,
+            group.allocation.as_str(),
+            group.linked as i32,
+            created_at,
+            now,
+        ],
+    ).map_err(|e| format!("Failed to save asset group: {}", e))?;
+
+    Ok(AssetGroup {
+        created_at,
+        ..group
+    })
+}
+
+/// Get all asset groups (oldest first)
+pub async fn get_asset_groups() -> Result<Vec<AssetGroup>, String> {
+    let conn = get_connection()?;
+    let conn = conn.lock().await;
+
+    let mut stmt = conn.prepare(
+        "SELECT id, name, symbols, allocation, linked, created_at
+         FROM asset_groups ORDER BY created_at ASC"
+    ).map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+    let groups = stmt.query_map([], |row| {
+        let symbols: String = row.get(2)?;
+        Ok(AssetGroup {
+            id: row.get(0)?,
+            name: row.get(1)?,
+            symbols: serde_json::from_str(&symbols).unwrap_or_default(),
+            allocation: AllocationMethod::from_code(&row.get::<_, String>(3)?),
+            linked: row.get::<_, i32>(4)? == 1,
+            created_at: row.get(5)?,
+        })
+    }).map_err(|e| format!("Failed to query asset groups: {}", e))?;
+
+    Ok(groups.filter_map(|g| g.ok()).collect())
+}
+
+/// Delete an asset group (executed baskets are kept)
+pub async fn delete_asset_group(id: &str) -> Result<bool, String> {
+    let conn = get_connection()?;
+    let conn = conn.lock().await;
+
+    let rows = conn.execute(
+        "DELETE FROM asset_groups WHERE id = ?",
+        params![id],
+    ).map_err(|e| format!("Failed to delete asset group: {}", e))?;
+
+    Ok(rows > 0)
+}
+
+// ============================================
+// BASKET OPERATIONS
+// ============================================
+
+/// Save (insert or replace) an executed basket
+pub async fn save_basket(basket: &Basket) -> Result<(), String> {
+    let conn = get_connection()?;
+    let conn = conn.lock().await;
+
+    let now = chrono::Utc::now().timestamp();
+    let data = serde_json::to_string(basket).map_err(|e| e.to_string())?;
+    let status = match basket.status {
+        BasketStatus::Open => "open",
+        BasketStatus::Closing => "closing",
+        BasketStatus::Closed => "closed",
+    };
+
+    conn.execute(
+        "INSERT INTO baskets (id, group_id, status, data, opened_at, updated_at) VALUES (?, ?, ?, ?, ?, ?)
+         ON CONFLICT(id) DO UPDATE SET status = excluded.status, data = excluded.data, updated_at = excluded.updated_at",
+        params![
+            &basket.id,
+            &basket.group_id,
+            status,
+            data,
+            basket.opened_at.timestamp_millis(),
+            now,
+        ],
+    ).map_err(|e| format!("Failed to save basket: {}", e))?;
+
+    Ok(())
+}
+
+/// Get baskets (newest first), optionally only the ones still being tracked
+pub async fn get_baskets(active_only: bool) -> Result<Vec<Basket>, String> {
+    let conn = get_connection()?;
+    let conn = conn.lock().await;
+
+    let sql = if active_only {
+        "SELECT data FROM baskets WHERE status != 'closed' ORDER BY opened_at DESC"
+    } else {
+        "SELECT data FROM baskets ORDER BY opened_at DESC"
+    };
+    let mut stmt = conn.prepare(sql)
+        .map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+    let baskets = stmt.query_map([], |row| row.get::<_, String>(0))
+        .map_err(|e| format!("Failed to query baskets: {}", e))?;
+
+    Ok(baskets
+        .filter_map(|b| b.ok())
+        .filter_map(|data| serde_json::from_str(&data).ok())
+        .collect())
+}
+
+// ============================================
+// ALARM OPERATIONS
+// ============================================
+
+/// Save (insert or replace) an alarm
+pub async fn save_alarm(alarm: &Alarm) -> Result<(), String> {
+    let conn = get_connection()?;
+    let conn = conn.lock().await;
+
+    let now = chrono::Utc::now().timestamp();
+    let data = serde_json::to_string(alarm).map_err(|e| e.to_string())?;
+
+    conn.execute(
+        "INSERT INTO alarms (id, symbol, is_active, data, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?)
+         ON CONFLICT(id) DO UPDATE SET
+            symbol = excluded.symbol,
+            is_active = excluded.is_active,
+            data = excluded.data,
+            updated_at = excluded.updated_at",
+        params![
+            &alarm.id,
+            &alarm.symbol,
+            alarm.is_active as i32,
+            data,
+            alarm.created_at.timestamp_millis(),
+            now,
+        ],
+    ).map_err(|e| format!("Failed to save alarm: {}", e))?;
+
+    Ok(())
+}
+
+/// Get all alarms (oldest first)
+pub async fn get_alarms() -> Result<Vec<Alarm>, String> {
+    let conn = get_connection()?;
+    let conn = conn.lock().await;
+
+    let mut stmt = conn.prepare("SELECT data FROM alarms ORDER BY created_at")
+        .map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+    let alarms = stmt.query_map([], |row| row.get::<_, String>(0))
+        .map_err(|e| format!("Failed to query alarms: {}", e))?;
+
+    Ok(alarms
+        .filter_map(|a| a.ok())
+        .filter_map(|data| serde_json::from_str(&data).ok())
+        .collect())
+}
+
+/// Delete an alarm
+pub async fn delete_alarm(id: &str) -> Result<bool, String> {
+    let conn = get_connection()?;
+    let conn = conn.lock().await;
+
+    let rows = conn.execute(
+        "DELETE FROM alarms WHERE id = ?",
+        params![id],
+    ).map_err(|e| format!("Failed to delete alarm: {}", e))?;
+
+    Ok(rows > 0)
+}
+
+// ============================================
+// SETTINGS OPERATIONS
+// ============================================
+
+/// Get a stored setting JSON (None if never saved)
+pub async fn get_setting(key: &str) -> Result<Option<String>, String> {
+    let conn = get_connection()?;
+    let conn = conn.lock().await;
+
+    let result = conn.query_row(
+        "SELECT value FROM app_settings WHERE key = ?",
+        params![key],
+        |row| row.get::<_, String>(0),
+    );
+
+    match result {
+        Ok(value) => Ok(Some(value)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(format!("Failed to load setting: {}", e)),
+    }
+}
+
+/// Save (insert or replace) a setting JSON
+pub async fn save_setting(key: &str, value: &str) -> Result<(), String> {
+    let conn = get_connection()?;
+    let conn = conn.lock().await;
+
+    let now = chrono::Utc::now().timestamp();
+
+    conn.execute(
+        "INSERT INTO app_settings (key, value, updated_at) VALUES (?, ?, ?)
+         ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
+        params![key, value, now],
+    ).map_err(|e| format!("Failed to save setting: {}", e))?;
+
+    Ok(())
+}
+
+/// Delete a stored setting
+pub async fn delete_setting(key: &str) -> Result<(), String> {
+    let conn = get_connection()?;
+    let conn = conn.lock().await;
+
+    conn.execute("DELETE FROM app_settings WHERE key = ?", params![key])
+        .map_err(|e| format!("Failed to delete setting: {}", e))?;
+
+    Ok(())
+}
+
+// ============================================
+// EXECUTION OPERATIONS
+// ============================================
+
+/// Save executions, skipping the ones already stored. Returns the number of new rows.
+pub async fn save_executions(executions: &[Execution]) -> Result<usize, String> {
+    let conn = get_connection()?;
+    let conn = conn.lock().await;
+
+    let mut inserted = 0;
+    for execution in executions {
+        let side = match execution.side {
+            OrderSide::Buy => "buy",
+            OrderSide::Sell => "sell",
+        };
+        inserted += conn.execute(
+            "INSERT OR IGNORE INTO executions (id, symbol, side, price, quantity, fee, realized_pnl, executed_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+            params![
+                &execution.id,
+                &execution.symbol,
+                side,
+                execution.price,
+                execution.quantity,
+                execution.fee,
+                execution.realized_pnl,
+                execution.executed_at.timestamp_millis(),
+            ],
+        ).map_err(|e| format!("Failed to save execution: {}", e))?;
+    }
+
+    Ok(inserted)
+}
+
+/// Sum realized PnL and fees of executions in [start, end). Returns (realized_pnl, fees, count).
+pub async fn get_execution_totals(
+    start: chrono::DateTime<chrono::Utc>,
+    end: chrono::DateTime<chrono::Utc>,
+) -> Result<(f64, f64, u32), String> {
+    let conn = get_connection()?;
+    let conn = conn.lock().await;
+
+    conn.query_row(
+        "SELECT COALESCE(SUM(realized_pnl), 0), COALESCE(SUM(fee), 0), COUNT(*)
+         FROM executions WHERE executed_at >= ? AND executed_at < ?",
+        params![start.timestamp_millis(), end.timestamp_millis()],
+        |row| Ok((row.get::<_, f64>(0)?, row.get::<_, f64>(1)?, row.get::<_, u32>(2)?)),
+    ).map_err(|e| format!("Failed to sum executions: {}", e))
+}
+
+/// Time of the newest stored execution
+pub async fn get_last_execution_time() -> Result<Option<chrono::DateTime<chrono::Utc>>, String> {
+    let conn = get_connection()?;
+    let conn = conn.lock().await;
+
+    let last: Option<i64> = conn.query_row(
+        "SELECT MAX(executed_at) FROM executions",
+        [],
+        |row| row.get(0),
+    ).map_err(|e| format!("Failed to query executions: {}", e))?;
+
+    Ok(last.and_then(chrono::DateTime::from_timestamp_millis))
+}
+
+/// Net results of the most recent closing executions (newest first)
+///
+/// An execution with non-zero realized PnL closes (part of) a position; its fee is deducted.
+pub async fn get_trade_outcomes(limit: u32) -> Result<Vec<f64>, String> {
+    let conn = get_connection()?;
+    let conn = conn.lock().await;
+
+    let mut stmt = conn.prepare(
+        "SELECT realized_pnl - fee FROM executions WHERE realized_pnl != 0
+         ORDER BY executed_at DESC LIMIT ?"
+    ).map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+    let outcomes = stmt.query_map(params![limit], |row| row.get::<_, f64>(0))
+        .map_err(|e| format!("Failed to query trade outcomes: {}", e))?;
+
+    outcomes.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to read trade outcomes: {}", e))
+}
+
+// ============================================
+// EQUITY CURVE OPERATIONS
+// ============================================
+
+/// Append a point to the equity curve
+pub async fn save_equity_point(point: &EquityPoint) -> Result<(), String> {
+    let conn = get_connection()?;
+    let conn = conn.lock().await;
+
+    conn.execute(
+        "INSERT INTO equity_points (equity, recorded_at) VALUES (?, ?)",
+        params![point.equity, point.recorded_at.timestamp_millis()],
+    ).map_err(|e| format!("Failed to save equity point: {}", e))?;
+
+    Ok(())
+}
+
+/// Get the equity curve since the given time (oldest first)
+pub async fn get_equity_curve(since: chrono::DateTime<chrono::Utc>) -> Result<Vec<EquityPoint>, String> {
+    let conn = get_connection()?;
+    let conn = conn.lock().await;
+
+    let mut stmt = conn.prepare(
+        "SELECT equity, recorded_at FROM equity_points WHERE recorded_at >= ? ORDER BY recorded_at"
+    ).map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+    let points = stmt.query_map(params![since.timestamp_millis()], |row| {
+        Ok((row.get::<_, f64>(0)?, row.get::<_, i64>(1)?))
+    }).map_err(|e| format!("Failed to query equity curve: {}", e))?;
+
+    Ok(points
+        .filter_map(|p| p.ok())
+        .filter_map(|(equity, recorded_at)| {
+            Some(EquityPoint {
+                equity,
+                recorded_at: chrono::DateTime::from_timestamp_millis(recorded_at)?,
+            })
+        })
+        .collect())
+}
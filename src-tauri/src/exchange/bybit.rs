@@ -2,9 +2,12 @@
 // Bybit REST API ve WebSocket bağlantısı
 // Spot, Linear Perpetual ve Inverse Perpetual desteği
 
-use serde::{Deserialize, Serialize};
+use async_trait::async_trait;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::collections::HashMap;
-use chrono::Utc;
+use chrono::{DateTime, TimeZone, Utc};
+use crate::models::{Execution, MarginTier, OrderSide, OrderType, Position, PositionSide};
+use super::executor::{AccountBalance, ExecutionError, LotSize, OpenOrder, OrderExecutor, OrderRequest};
 use sha2::Sha256;
 use hmac::{Hmac, Mac};
 
@@ -18,6 +21,18 @@ pub const BYBIT_WS_URL: &str = "wss://stream.bytick.com/v5/public/linear";
 pub const BYBIT_WS_PRIVATE_URL: &str = "wss://stream.bytick.com/v5/private";
 pub const BYBIT_TESTNET_WS_URL: &str = "wss://stream-testnet.bybit.com/v5/public/linear";
 
+/// Kategorinin herkese açık WebSocket akışı
+pub fn public_ws_url(category: MarketCategory, testnet: bool) -> String {
+    let linear = if testnet { BYBIT_TESTNET_WS_URL } else { BYBIT_WS_URL };
+    format!("{}/{}", linear.trim_end_matches("/linear"), category.as_str())
+}
+
+/// Vadeli işlem komisyon oranları (standart hesap)
+pub const TAKER_FEE_RATE: f64 = 0.00055;
+pub const MAKER_FEE_RATE: f64 = 0.0002;
+/// Spot komisyon oranı (maker ve taker aynı)
+pub const SPOT_FEE_RATE: f64 = 0.001;
+
 /// Market kategorisi
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
@@ -37,6 +52,17 @@ impl MarketCategory {
     }
 }
 
+impl MarketCategory {
+    /// Türev sembolünün kategorisi (USDT/USDC/PERP linear, diğerleri inverse)
+    pub fn for_symbol(symbol: &str) -> Self {
+        if symbol.ends_with("USDT") || symbol.ends_with("USDC") || symbol.ends_with("PERP") {
+            MarketCategory::Linear
+        } else {
+            MarketCategory::Inverse
+        }
+    }
+}
+
 impl Default for MarketCategory {
     fn default() -> Self {
         MarketCategory::Linear
@@ -101,6 +127,35 @@ impl BybitClient {
         headers
     }
 
+    /// İmzalı POST isteği (imza JSON gövdesi üzerinden)
+    async fn signed_post<T: DeserializeOwned>(&self, endpoint: &str, payload: &serde_json::Value) -> Result<Option<T>, BybitError> {
+        let url = format!("{}{}", self.base_url(), endpoint);
+        let payload = payload.to_string();
+
+        let headers = self.auth_headers(&payload);
+
+        let mut request = self.client.post(&url).body(payload);
+
+        for (key, value) in headers {
+            request = request.header(&key, value);
+        }
+
+        let response = request.send().await
+            .map_err(|e| BybitError::NetworkError(e.to_string()))?;
+
+        let body = response.text().await
+            .map_err(|e| BybitError::NetworkError(e.to_string()))?;
+
+        let result: BybitResponse<T> = serde_json::from_str(&body)
+            .map_err(|e| BybitError::ParseError(format!("{}: {}", e, body)))?;
+
+        if result.ret_code != 0 {
+            return Err(BybitError::ApiError(result.ret_msg));
+        }
+
+        Ok(result.result)
+    }
+
     /// Cüzdan bakiyesini al
     pub async fn get_wallet_balance(&self) -> Result<WalletBalance, BybitError> {
         let endpoint = "/v5/account/wallet-balance";
@@ -131,9 +186,11 @@ impl BybitClient {
         result.result
             .and_then(|r| r.list.into_iter().next())
             .and_then(|account| {
-                let total_equity: f64 = account.coin.iter()
-                    .filter_map(|c| c.equity.parse::<f64>().ok())
-                    .sum();
+                let total_equity: f64 = account.total_equity.parse().unwrap_or_else(|_| {
+                    account.coin.iter()
+                        .filter_map(|c| c.equity.parse::<f64>().ok())
+                        .sum()
+                });
                 let available: f64 = account.coin.iter()
                     .filter_map(|c| c.available_to_withdraw.parse::<f64>().ok())
                     .sum();
@@ -141,6 +198,13 @@ impl BybitClient {
                 Some(WalletBalance {
                     total_equity,
                     available_balance: available,
+                    initial_margin: account.total_initial_margin.parse().unwrap_or(0.0),
+                    maintenance_margin: account.total_maintenance_margin.parse().unwrap_or(0.0),
+                    unrealized_pnl: account.total_perp_upl.parse().unwrap_or_else(|_| {
+                        account.coin.iter()
+                            .filter_map(|c| c.unrealised_pnl.parse::<f64>().ok())
+                            .sum()
+                    }),
                     coins: account.coin.into_iter().map(|c| CoinBalance {
                         coin: c.coin,
                         equity: c.equity.parse().unwrap_or(0.0),
@@ -181,6 +245,7 @@ impl BybitClient {
                 low_price_24h: t.low_price_24h.parse().unwrap_or(0.0),
                 volume_24h: t.volume_24h.parse().unwrap_or(0.0),
                 turnover_24h: t.turnover_24h.parse().unwrap_or(0.0),
+                funding_rate: t.funding_rate.parse().unwrap_or(0.0),
                 category,
                 max_leverage: 0.0, // Will be enriched later if needed
             })
@@ -215,6 +280,7 @@ impl BybitClient {
                 low_price_24h: t.low_price_24h.parse().unwrap_or(0.0),
                 volume_24h: t.volume_24h.parse().unwrap_or(0.0),
                 turnover_24h: t.turnover_24h.parse().unwrap_or(0.0),
+                funding_rate: t.funding_rate.parse().unwrap_or(0.0),
                 category,
                 max_leverage: 0.0,
             }).collect())
@@ -284,6 +350,76 @@ impl BybitClient {
             .unwrap_or_default())
     }
 
+    /// Sembolün miktar / fiyat adımları (emir yuvarlama için)
+    pub async fn get_lot_size(&self, category: MarketCategory, symbol: &str) -> Result<LotSize, BybitError> {
+        let endpoint = "/v5/market/instruments-info";
+        let params = format!("category={}&symbol={}", category.as_str(), symbol);
+        let url = format!("{}{}?{}", self.base_url(), endpoint, params);
+
+        let response = self.client.get(&url).send().await
+            .map_err(|e| BybitError::NetworkError(e.to_string()))?;
+
+        let body = response.text().await
+            .map_err(|e| BybitError::NetworkError(e.to_string()))?;
+
+        let result: BybitResponse<InstrumentsResult> = serde_json::from_str(&body)
+            .map_err(|e| BybitError::ParseError(format!("{}: {}", e, body)))?;
+
+        if result.ret_code != 0 {
+            return Err(BybitError::ApiError(result.ret_msg));
+        }
+
+        let instrument = result.result
+            .and_then(|r| r.list.into_iter().next())
+            .ok_or_else(|| BybitError::ApiError(format!("Sembol bulunamadı: {}", symbol)))?;
+
+        let parse = |value: &str| value.parse::<f64>().unwrap_or(0.0);
+        let lot = instrument.lot_size_filter.unwrap_or_default();
+        // Spot sembollerde miktar adımı basePrecision alanındadır
+        let qty_step = if lot.qty_step.is_empty() { &lot.base_precision } else { &lot.qty_step };
+        Ok(LotSize {
+            qty_step: parse(qty_step),
+            min_qty: parse(&lot.min_order_qty),
+            tick_size: instrument.price_filter.map(|p| parse(&p.tick_size)).unwrap_or(0.0),
+        })
+    }
+
+    /// Sembolün risk limiti kademeleri (bakım marjı oranları, küçükten büyüğe)
+    pub async fn get_risk_limits(&self, category: MarketCategory, symbol: &str) -> Result<Vec<MarginTier>, BybitError> {
+        let endpoint = "/v5/market/risk-limit";
+        let params = format!("category={}&symbol={}", category.as_str(), symbol);
+        let url = format!("{}{}?{}", self.base_url(), endpoint, params);
+
+        let response = self.client.get(&url).send().await
+            .map_err(|e| BybitError::NetworkError(e.to_string()))?;
+
+        let body = response.text().await
+            .map_err(|e| BybitError::NetworkError(e.to_string()))?;
+
+        let result: BybitResponse<RiskLimitResult> = serde_json::from_str(&body)
+            .map_err(|e| BybitError::ParseError(format!("{}: {}", e, body)))?;
+
+        if result.ret_code != 0 {
+            return Err(BybitError::ApiError(result.ret_msg));
+        }
+
+        let parse = |value: &str| value.parse::<f64>().unwrap_or(0.0);
+        let mut tiers: Vec<MarginTier> = result.result
+            .map(|r| r.list)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|t| t.symbol == symbol)
+            .map(|t| MarginTier {
+                risk_limit_value: parse(&t.risk_limit_value),
+                maintenance_margin_rate: parse(&t.maintenance_margin),
+                mm_deduction: parse(&t.mm_deduction),
+                max_leverage: parse(&t.max_leverage),
+            })
+            .collect();
+        tiers.sort_by(|a, b| a.risk_limit_value.total_cmp(&b.risk_limit_value));
+        Ok(tiers)
+    }
+
     /// Tüm kategorilerden sembolleri al
     pub async fn get_all_instruments(&self) -> Result<AllInstruments, BybitError> {
         let (spot, linear, inverse) = tokio::join!(
@@ -446,6 +582,260 @@ impl BybitClient {
         Ok(all_klines)
     }
 
+    /// Açık pozisyonları al (linear/inverse)
+    pub async fn get_positions(&self, category: MarketCategory) -> Result<Vec<PositionInfo>, BybitError> {
+        let endpoint = "/v5/position/list";
+        let params = match category {
+            MarketCategory::Linear => format!("category={}&settleCoin=USDT", category.as_str()),
+            _ => format!("category={}", category.as_str()),
+        };
+        let url = format!("{}{}?{}", self.base_url(), endpoint, params);
+
+        let headers = self.auth_headers(&params);
+
+        let mut request = self.client.get(&url);
+
+        for (key, value) in headers {
+            request = request.header(&key, value);
+        }
+
+        let response = request.send().await
+            .map_err(|e| BybitError::NetworkError(e.to_string()))?;
+
+        let body = response.text().await
+            .map_err(|e| BybitError::NetworkError(e.to_string()))?;
+
+        let result: BybitResponse<PositionListResult> = serde_json::from_str(&body)
+            .map_err(|e| BybitError::ParseError(format!("{}: {}", e, body)))?;
+
+        if result.ret_code != 0 {
+            return Err(BybitError::ApiError(result.ret_msg));
+        }
+
+        Ok(result.result
+            .map(|r| r.list.into_iter()
+                .filter_map(|p| {
+                    let size: f64 = p.size.parse().unwrap_or(0.0);
+                    if size == 0.0 {
+                        return None; // Boş pozisyon kaydı
+                    }
+                    Some(PositionInfo {
+                        symbol: p.symbol,
+                        side: p.side,
+                        size,
+                        avg_price: p.avg_price.parse().unwrap_or(0.0),
+                        mark_price: p.mark_price.parse().unwrap_or(0.0),
+                        leverage: p.leverage.parse().unwrap_or(1.0),
+                        liq_price: p.liq_price.parse().ok(),
+                        stop_loss: p.stop_loss.parse().ok().filter(|v: &f64| *v > 0.0),
+                        take_profit: p.take_profit.parse().ok().filter(|v: &f64| *v > 0.0),
+                        unrealized_pnl: p.unrealised_pnl.parse().unwrap_or(0.0),
+                        position_idx: p.position_idx,
+                        created_time: p.created_time.parse().unwrap_or(0),
+                        category,
+                    })
+                })
+                .collect())
+            .unwrap_or_default())
+    }
+
+    /// Verilen andan sonraki işlem gerçekleşmeleri (dolumlar, fonlama) - eskiden yeniye
+    pub async fn get_executions(&self, category: MarketCategory, start: DateTime<Utc>) -> Result<Vec<Execution>, BybitError> {
+        let endpoint = "/v5/execution/list";
+        let mut executions = Vec::new();
+        let mut cursor = String::new();
+
+        loop {
+            let mut params = format!(
+                "category={}&startTime={}&limit=100",
+                category.as_str(),
+                start.timestamp_millis()
+            );
+            if !cursor.is_empty() {
+                params.push_str(&format!("&cursor={}", cursor));
+            }
+            let url = format!("{}{}?{}", self.base_url(), endpoint, params);
+
+            let headers = self.auth_headers(&params);
+
+            let mut request = self.client.get(&url);
+
+            for (key, value) in headers {
+                request = request.header(&key, value);
+            }
+
+            let response = request.send().await
+                .map_err(|e| BybitError::NetworkError(e.to_string()))?;
+
+            let body = response.text().await
+                .map_err(|e| BybitError::NetworkError(e.to_string()))?;
+
+            let result: BybitResponse<ExecutionListResult> = serde_json::from_str(&body)
+                .map_err(|e| BybitError::ParseError(format!("{}: {}", e, body)))?;
+
+            if result.ret_code != 0 {
+                return Err(BybitError::ApiError(result.ret_msg));
+            }
+
+            let Some(page) = result.result else { break };
+            executions.extend(page.list.into_iter().map(|e| Execution {
+                id: e.exec_id,
+                order_id: e.order_id,
+                symbol: e.symbol,
+                side: if e.side == "Sell" { OrderSide::Sell } else { OrderSide::Buy },
+                price: e.exec_price.parse().unwrap_or(0.0),
+                quantity: e.exec_qty.parse().unwrap_or(0.0),
+                fee: e.exec_fee.parse().unwrap_or(0.0),
+                realized_pnl: e.exec_pnl.parse().unwrap_or(0.0),
+                executed_at: e.exec_time.parse().ok()
+                    .and_then(|ms| Utc.timestamp_millis_opt(ms).single())
+                    .unwrap_or_else(Utc::now),
+            }));
+
+            // Sonraki sayfa (boş imleç = son sayfa)
+            if page.next_page_cursor.is_empty() {
+                break;
+            }
+            cursor = page.next_page_cursor;
+        }
+
+        executions.sort_by_key(|e| e.executed_at);
+        Ok(executions)
+    }
+
+    /// Emir gönder (conditional emirlerde tetik yönü emir yönünden çıkarılır: Sell düşüşte, Buy yükselişte)
+    pub async fn create_order(&self, category: MarketCategory, request: &OrderRequest, position_idx: i32) -> Result<String, BybitError> {
+        let mut payload = serde_json::json!({
+            "category": category.as_str(),
+            "symbol": request.symbol,
+            "side": order_side_str(&request.side),
+            "orderType": match request.order_type {
+                OrderType::Market | OrderType::StopMarket => "Market",
+                OrderType::Limit | OrderType::StopLimit => "Limit",
+            },
+            "qty": request.quantity.to_string(),
+            "reduceOnly": request.reduce_only,
+            "positionIdx": position_idx,
+        });
+        if let Some(price) = request.price {
+            payload["price"] = price.to_string().into();
+        }
+        if let Some(stop_loss) = request.stop_loss {
+            payload["stopLoss"] = stop_loss.to_string().into();
+            payload["tpslMode"] = "Full".into();
+        }
+        if let Some(trigger) = request.trigger_price {
+            payload["triggerPrice"] = trigger.to_string().into();
+            payload["triggerDirection"] = match request.side {
+                OrderSide::Sell => 2.into(),
+                OrderSide::Buy => 1.into(),
+            };
+        }
+
+        let result: Option<CreateOrderResult> = self.signed_post("/v5/order/create", &payload).await?;
+        result
+            .map(|r| r.order_id)
+            .ok_or_else(|| BybitError::ParseError("No order id".to_string()))
+    }
+
+    /// Bekleyen emri iptal et
+    pub async fn cancel_order_by_id(&self, category: MarketCategory, symbol: &str, order_id: &str) -> Result<(), BybitError> {
+        let payload = serde_json::json!({
+            "category": category.as_str(),
+            "symbol": symbol,
+            "orderId": order_id,
+        });
+        let _: Option<serde_json::Value> = self.signed_post("/v5/order/cancel", &payload).await?;
+        Ok(())
+    }
+
+    /// Sembolün bekleyen emirleri
+    pub async fn get_open_orders(&self, category: MarketCategory, symbol: &str) -> Result<Vec<OpenOrder>, BybitError> {
+        let endpoint = "/v5/order/realtime";
+        let params = format!("category={}&symbol={}", category.as_str(), symbol);
+        let url = format!("{}{}?{}", self.base_url(), endpoint, params);
+
+        let headers = self.auth_headers(&params);
+
+        let mut request = self.client.get(&url);
+
+        for (key, value) in headers {
+            request = request.header(&key, value);
+        }
+
+        let response = request.send().await
+            .map_err(|e| BybitError::NetworkError(e.to_string()))?;
+
+        let body = response.text().await
+            .map_err(|e| BybitError::NetworkError(e.to_string()))?;
+
+        let result: BybitResponse<OpenOrderListResult> = serde_json::from_str(&body)
+            .map_err(|e| BybitError::ParseError(format!("{}: {}", e, body)))?;
+
+        if result.ret_code != 0 {
+            return Err(BybitError::ApiError(result.ret_msg));
+        }
+
+        Ok(result.result
+            .map(|r| r.list.into_iter().map(|o| {
+                let trigger_price = o.trigger_price.parse().ok().filter(|v: &f64| *v > 0.0);
+                OpenOrder {
+                    order_id: o.order_id,
+                    symbol: o.symbol,
+                    side: if o.side == "Sell" { OrderSide::Sell } else { OrderSide::Buy },
+                    order_type: match (o.order_type.as_str(), trigger_price.is_some()) {
+                        ("Market", false) => OrderType::Market,
+                        ("Market", true) => OrderType::StopMarket,
+                        (_, true) => OrderType::StopLimit,
+                        _ => OrderType::Limit,
+                    },
+                    price: o.price.parse().ok().filter(|v: &f64| *v > 0.0),
+                    trigger_price,
+                    quantity: o.qty.parse().unwrap_or(0.0),
+                    reduce_only: o.reduce_only,
+                }
+            }).collect())
+            .unwrap_or_default())
+    }
+
+    /// Pozisyonun stop-loss seviyesini ayarla (tüm pozisyon için)
+    pub async fn set_trading_stop(&self, category: MarketCategory, symbol: &str, position_idx: i32, stop_loss: f64) -> Result<(), BybitError> {
+        let payload = serde_json::json!({
+            "category": category.as_str(),
+            "symbol": symbol,
+            "stopLoss": stop_loss.to_string(),
+            "tpslMode": "Full",
+            "positionIdx": position_idx,
+        });
+        let _: Option<serde_json::Value> = self.signed_post("/v5/position/trading-stop", &payload).await?;
+        Ok(())
+    }
+
+    /// Borsa sunucu saati (zamanlayıcı senkronizasyonu için)
+    pub async fn get_server_time(&self) -> Result<DateTime<Utc>, BybitError> {
+        let endpoint = "/v5/market/time";
+        let url = format!("{}{}", self.base_url(), endpoint);
+
+        let response = self.client.get(&url).send().await
+            .map_err(|e| BybitError::NetworkError(e.to_string()))?;
+
+        let body = response.text().await
+            .map_err(|e| BybitError::NetworkError(e.to_string()))?;
+
+        let result: BybitResponse<ServerTimeResult> = serde_json::from_str(&body)
+            .map_err(|e| BybitError::ParseError(format!("{}: {}", e, body)))?;
+
+        if result.ret_code != 0 {
+            return Err(BybitError::ApiError(result.ret_msg));
+        }
+
+        let nanos: i64 = result.result
+            .and_then(|r| r.time_nano.parse().ok())
+            .ok_or_else(|| BybitError::ParseError("timeNano".to_string()))?;
+
+        Ok(Utc.timestamp_nanos(nanos))
+    }
+
     /// Bağlantı testi
     pub async fn test_connection(&self) -> Result<bool, BybitError> {
         let endpoint = "/v5/market/time";
@@ -458,6 +848,87 @@ impl BybitClient {
     }
 }
 
+#[async_trait]
+impl OrderExecutor for BybitClient {
+    async fn place_order(&self, request: &OrderRequest) -> Result<String, ExecutionError> {
+        let position_idx = request.position_id.as_deref().map(position_idx).unwrap_or(0);
+        Ok(self.create_order(MarketCategory::for_symbol(&request.symbol), request, position_idx).await?)
+    }
+
+    async fn cancel_order(&self, symbol: &str, order_id: &str) -> Result<(), ExecutionError> {
+        Ok(self.cancel_order_by_id(MarketCategory::for_symbol(symbol), symbol, order_id).await?)
+    }
+
+    async fn open_orders(&self, symbol: &str) -> Result<Vec<OpenOrder>, ExecutionError> {
+        Ok(self.get_open_orders(MarketCategory::for_symbol(symbol), symbol).await?)
+    }
+
+    async fn set_stop_loss(&self, position: &Position, stop_price: f64) -> Result<(), ExecutionError> {
+        let category = MarketCategory::for_symbol(&position.symbol);
+        Ok(self.set_trading_stop(category, &position.symbol, position_idx(&position.id), stop_price).await?)
+    }
+
+    async fn lot_size(&self, symbol: &str) -> Result<LotSize, ExecutionError> {
+        Ok(self.get_lot_size(MarketCategory::for_symbol(symbol), symbol).await?)
+    }
+
+    async fn open_positions(&self) -> Result<Vec<Position>, ExecutionError> {
+        let (linear, inverse) = tokio::join!(
+            self.get_positions(MarketCategory::Linear),
+            self.get_positions(MarketCategory::Inverse)
+        );
+        Ok(linear?.iter().chain(inverse?.iter()).map(PositionInfo::to_position).collect())
+    }
+
+    async fn account_balance(&self) -> Result<AccountBalance, ExecutionError> {
+        let wallet = self.get_wallet_balance().await?;
+        Ok(AccountBalance {
+            equity: wallet.total_equity,
+            available: wallet.available_balance,
+            initial_margin: wallet.initial_margin,
+            maintenance_margin: wallet.maintenance_margin,
+            unrealized_pnl: wallet.unrealized_pnl,
+        })
+    }
+
+    async fn last_price(&self, symbol: &str) -> Result<f64, ExecutionError> {
+        Ok(self.get_ticker(symbol, MarketCategory::for_symbol(symbol)).await?.last_price)
+    }
+
+    async fn klines(&self, symbol: &str, interval: &str, limit: usize) -> Result<Vec<Kline>, ExecutionError> {
+        let mut klines = self.get_klines(symbol, MarketCategory::for_symbol(symbol), interval, limit as u32).await?;
+        klines.sort_by_key(|k| k.timestamp);
+        Ok(klines)
+    }
+
+    async fn executions(&self, since: DateTime<Utc>) -> Result<Vec<Execution>, ExecutionError> {
+        // Inverse kâr/zararı coin cinsinden olduğundan sadece linear (USDT) takip edilir
+        Ok(self.get_executions(MarketCategory::Linear, since).await?)
+    }
+}
+
+impl From<BybitError> for ExecutionError {
+    fn from(e: BybitError) -> Self {
+        ExecutionError::Exchange(e.to_string())
+    }
+}
+
+fn order_side_str(side: &OrderSide) -> &'static str {
+    match side {
+        OrderSide::Buy => "Buy",
+        OrderSide::Sell => "Sell",
+    }
+}
+
+/// Pozisyon kimliğindeki (`{symbol}-{positionIdx}`) pozisyon indeksi
+fn position_idx(position_id: &str) -> i32 {
+    position_id
+        .rsplit('-')
+        .next()
+        .and_then(|idx| idx.parse().ok())
+        .unwrap_or(0)
+}
+
 // ==================== Response Types ====================
 
 #[derive(Debug, Deserialize)]
@@ -476,6 +947,15 @@ struct WalletBalanceResult {
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct AccountInfo {
+    /// Hesap düzeyi toplamlar (USD, birleşik hesapta dolu gelir)
+    #[serde(default)]
+    total_equity: String,
+    #[serde(default)]
+    total_initial_margin: String,
+    #[serde(default)]
+    total_maintenance_margin: String,
+    #[serde(default, rename = "totalPerpUPL")]
+    total_perp_upl: String,
     coin: Vec<CoinInfo>,
 }
 
@@ -509,6 +989,8 @@ struct TickerData {
     volume_24h: String,
     #[serde(rename = "turnover24h", default)]
     turnover_24h: String,
+    #[serde(rename = "fundingRate", default)]
+    funding_rate: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -527,6 +1009,10 @@ struct InstrumentData {
     status: String,
     #[serde(default)]
     leverage_filter: Option<LeverageFilter>,
+    #[serde(default)]
+    lot_size_filter: Option<LotSizeFilter>,
+    #[serde(default)]
+    price_filter: Option<PriceFilter>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -538,17 +1024,146 @@ struct LeverageFilter {
     min_leverage: String,
 }
 
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LotSizeFilter {
+    #[serde(default)]
+    qty_step: String,
+    #[serde(default)]
+    base_precision: String,
+    #[serde(default)]
+    min_order_qty: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct RiskLimitResult {
+    list: Vec<RiskLimitData>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RiskLimitData {
+    symbol: String,
+    risk_limit_value: String,
+    maintenance_margin: String,
+    #[serde(default)]
+    mm_deduction: String,
+    #[serde(default)]
+    max_leverage: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PriceFilter {
+    #[serde(default)]
+    tick_size: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CreateOrderResult {
+    order_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenOrderListResult {
+    list: Vec<OpenOrderData>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OpenOrderData {
+    order_id: String,
+    symbol: String,
+    side: String,
+    order_type: String,
+    #[serde(default)]
+    price: String,
+    #[serde(default)]
+    trigger_price: String,
+    #[serde(default)]
+    qty: String,
+    #[serde(default)]
+    reduce_only: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ExecutionListResult {
+    list: Vec<ExecutionData>,
+    #[serde(default)]
+    next_page_cursor: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ExecutionData {
+    exec_id: String,
+    #[serde(default)]
+    order_id: String,
+    symbol: String,
+    side: String,
+    exec_price: String,
+    exec_qty: String,
+    #[serde(default)]
+    exec_fee: String,
+    /// Pozisyon kapatan dolumun kâr/zararı (komisyon hariç)
+    #[serde(default)]
+    exec_pnl: String,
+    exec_time: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ServerTimeResult {
+    time_nano: String,
+}
+
 #[derive(Debug, Deserialize)]
 struct KlineResult {
     list: Vec<(String, String, String, String, String, String, String)>,
 }
 
+#[derive(Debug, Deserialize)]
+struct PositionListResult {
+    list: Vec<PositionData>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PositionData {
+    symbol: String,
+    side: String,
+    size: String,
+    #[serde(default)]
+    avg_price: String,
+    #[serde(default)]
+    mark_price: String,
+    #[serde(default)]
+    leverage: String,
+    #[serde(default)]
+    liq_price: String,
+    #[serde(default)]
+    stop_loss: String,
+    #[serde(default)]
+    take_profit: String,
+    #[serde(default)]
+    unrealised_pnl: String,
+    #[serde(default)]
+    position_idx: i32,
+    #[serde(default)]
+    created_time: String,
+}
+
 // ==================== Public Types ====================
 
 #[derive(Debug, Clone, Serialize)]
 pub struct WalletBalance {
     pub total_equity: f64,
     pub available_balance: f64,
+    pub initial_margin: f64,
+    pub maintenance_margin: f64,
+    pub unrealized_pnl: f64,
     pub coins: Vec<CoinBalance>,
 }
 
@@ -569,6 +1184,8 @@ pub struct TickerInfo {
     pub low_price_24h: f64,
     pub volume_24h: f64,
     pub turnover_24h: f64,
+    /// Güncel fonlama oranı (sadece vadeli)
+    pub funding_rate: f64,
     pub category: MarketCategory,
     #[serde(default)]
     pub max_leverage: f64,
@@ -601,6 +1218,42 @@ pub struct Kline {
     pub volume: f64,
 }
 
+#[derive(Debug, Clone, Serialize)]
+pub struct PositionInfo {
+    pub symbol: String,
+    pub side: String,       // "Buy" | "Sell"
+    pub size: f64,
+    pub avg_price: f64,
+    pub mark_price: f64,
+    pub leverage: f64,
+    pub liq_price: Option<f64>,
+    pub stop_loss: Option<f64>,
+    pub take_profit: Option<f64>,
+    pub unrealized_pnl: f64,
+    pub position_idx: i32,
+    pub created_time: i64,
+    pub category: MarketCategory,
+}
+
+impl PositionInfo {
+    /// Borsa pozisyonunu uygulama modeline dönüştür
+    pub fn to_position(&self) -> Position {
+        Position {
+            id: format!("{}-{}", self.symbol, self.position_idx),
+            symbol: self.symbol.clone(),
+            side: if self.side == "Sell" { PositionSide::Short } else { PositionSide::Long },
+            entry_price: self.avg_price,
+            quantity: self.size,
+            stop_loss: self.stop_loss,
+            take_profit: self.take_profit,
+            created_at: Utc
+                .timestamp_millis_opt(self.created_time)
+                .single()
+                .unwrap_or_else(Utc::now),
+        }
+    }
+}
+
 // ==================== Errors ====================
 
 #[derive(Debug, Clone)]
@@ -0,0 +1,196 @@
+// AlgoTrade OS - Order Executor
+// Modüllerin emir göndermek için kullandığı borsadan bağımsız arayüz
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::future::join_all;
+use serde::{Deserialize, Serialize};
+use crate::exchange::bybit::Kline;
+use crate::models::{Execution, OrderSide, OrderType, Position, PositionSide};
+
+/// Emir talebi
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderRequest {
+    pub symbol: String,
+    pub side: OrderSide,
+    pub order_type: OrderType,
+    pub quantity: f64,
+    /// Limit fiyatı (Limit / StopLimit)
+    pub price: Option<f64>,
+    /// Tetik fiyatı (StopMarket / StopLimit)
+    pub trigger_price: Option<f64>,
+    /// Sadece pozisyonu azaltabilir
+    pub reduce_only: bool,
+    /// Emirle birlikte pozisyona eklenecek stop-loss (açılış emirleri)
+    #[serde(default)]
+    pub stop_loss: Option<f64>,
+    /// Emri açan/kapatan pozisyon (hedge modunda yön seçimi için)
+    pub position_id: Option<String>,
+}
+
+impl OrderRequest {
+    /// Pozisyonu piyasa fiyatından kapatan emir
+    pub fn close_position(position: &Position) -> Self {
+        Self {
+            symbol: position.symbol.clone(),
+            side: closing_side(&position.side),
+            order_type: OrderType::Market,
+            quantity: position.quantity,
+            price: None,
+            trigger_price: None,
+            reduce_only: true,
+            stop_loss: None,
+            position_id: Some(position.id.clone()),
+        }
+    }
+}
+
+/// Borsada bekleyen emir
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpenOrder {
+    pub order_id: String,
+    pub symbol: String,
+    pub side: OrderSide,
+    pub order_type: OrderType,
+    pub price: Option<f64>,
+    pub trigger_price: Option<f64>,
+    pub quantity: f64,
+    pub reduce_only: bool,
+}
+
+/// Sembolün emir miktarı / fiyat adımları
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LotSize {
+    /// Miktar adımı (0 = yuvarlama yok)
+    pub qty_step: f64,
+    /// En küçük emir miktarı
+    pub min_qty: f64,
+    /// Fiyat adımı (0 = yuvarlama yok)
+    pub tick_size: f64,
+}
+
+impl LotSize {
+    /// Yuvarlama yapmayan adımlar (sembol bilgisi alınamadığında)
+    pub const NONE: LotSize = LotSize { qty_step: 0.0, min_qty: 0.0, tick_size: 0.0 };
+
+    /// Miktarı adıma aşağı yuvarla (emir pozisyondan büyük olamaz)
+    pub fn round_quantity(&self, quantity: f64) -> f64 {
+        if self.qty_step <= 0.0 {
+            return quantity;
+        }
+        // Kayan nokta hatası yüzünden tam adımlar bir alta düşmesin
+        let steps = (quantity / self.qty_step + 1e-9).floor();
+        round_to_step_decimals(steps * self.qty_step, self.qty_step)
+    }
+
+    /// Fiyatı en yakın adıma yuvarla
+    pub fn round_price(&self, price: f64) -> f64 {
+        if self.tick_size <= 0.0 {
+            return price;
+        }
+        round_to_step_decimals((price / self.tick_size).round() * self.tick_size, self.tick_size)
+    }
+}
+
+/// Adımın ondalık basamak sayısına yuvarla (0.1 * 3 = 0.30000000000000004 gibi artıkları temizler)
+fn round_to_step_decimals(value: f64, step: f64) -> f64 {
+    let decimals = (-step.log10()).ceil().max(0.0) as i32;
+    let factor = 10f64.powi(decimals);
+    (value * factor).round() / factor
+}
+
+/// Hesap bakiyesi ve marjin kullanımı (kote para birimi)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct AccountBalance {
+    pub equity: f64,
+    pub available: f64,
+    /// Açık pozisyon ve emirler için kilitlenen başlangıç marjini
+    pub initial_margin: f64,
+    pub maintenance_margin: f64,
+    /// Açık pozisyonların gerçekleşmemiş kâr/zararı
+    pub unrealized_pnl: f64,
+}
+
+/// Emir yürütme hatası
+#[derive(Debug, Clone)]
+pub enum ExecutionError {
+    /// Bağlı borsa yok
+    NotConnected,
+    /// Borsa isteği reddetti veya başarısız oldu
+    Exchange(String),
+    /// Risk kapısı emri reddetti (yerelleştirilmiş neden)
+    RiskRejected(String),
+}
+
+impl std::fmt::Display for ExecutionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExecutionError::NotConnected => write!(f, "Not connected to an exchange"),
+            ExecutionError::Exchange(msg) => write!(f, "Exchange error: {}", msg),
+            ExecutionError::RiskRejected(reason) => write!(f, "{}", reason),
+        }
+    }
+}
+
+impl std::error::Error for ExecutionError {}
+
+/// Emir yürütücü - borsa istemcileri uygular, modüller AppState üzerinden kullanır
+#[async_trait]
+pub trait OrderExecutor: Send + Sync {
+    /// Emir gönder, borsa emir kimliğini döner
+    async fn place_order(&self, request: &OrderRequest) -> Result<String, ExecutionError>;
+
+    /// Birlikte açılan emirleri aynı anda gönder (sonuçlar istek sırasında)
+    async fn place_orders(&self, requests: &[OrderRequest]) -> Vec<Result<String, ExecutionError>> {
+        join_all(requests.iter().map(|r| self.place_order(r))).await
+    }
+
+    /// Bekleyen emri iptal et
+    async fn cancel_order(&self, symbol: &str, order_id: &str) -> Result<(), ExecutionError>;
+
+    /// Sembolün bekleyen emirleri
+    async fn open_orders(&self, symbol: &str) -> Result<Vec<OpenOrder>, ExecutionError>;
+
+    /// Pozisyonun borsadaki stop-loss seviyesini değiştir
+    async fn set_stop_loss(&self, position: &Position, stop_price: f64) -> Result<(), ExecutionError>;
+
+    /// Sembolün miktar / fiyat adımları
+    async fn lot_size(&self, symbol: &str) -> Result<LotSize, ExecutionError>;
+
+    /// Borsadaki açık pozisyonlar
+    async fn open_positions(&self) -> Result<Vec<Position>, ExecutionError>;
+
+    /// Hesap bakiyesi ve marjin bilgisi
+    async fn account_balance(&self) -> Result<AccountBalance, ExecutionError>;
+
+    /// Sembolün son işlem fiyatı
+    async fn last_price(&self, symbol: &str) -> Result<f64, ExecutionError>;
+
+    /// Sembolün aralıktaki son `limit` mumu (açık mum dahil, eskiden yeniye)
+    async fn klines(&self, symbol: &str, interval: &str, limit: usize) -> Result<Vec<Kline>, ExecutionError>;
+
+    /// Verilen andan sonraki işlem gerçekleşmeleri (eskiden yeniye)
+    async fn executions(&self, since: DateTime<Utc>) -> Result<Vec<Execution>, ExecutionError>;
+}
+
+/// Pozisyonu kapatan emir yönü
+pub fn closing_side(side: &PositionSide) -> OrderSide {
+    match side {
+        PositionSide::Long => OrderSide::Sell,
+        PositionSide::Short => OrderSide::Buy,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lot_size_rounding() {
+        let lot = LotSize { qty_step: 0.001, min_qty: 0.001, tick_size: 0.1 };
+        assert_eq!(lot.round_quantity(0.3), 0.3);
+        assert_eq!(lot.round_quantity(0.12345), 0.123);
+        assert_eq!(lot.round_price(101.26), 101.3);
+        assert_eq!(LotSize::NONE.round_quantity(0.12345), 0.12345);
+    }
+}
@@ -0,0 +1,168 @@
+// AlgoTrade OS - Mock Executor
+// Testler için borsa yerine geçen, çağrıları kaydeden emir yürütücü
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use crate::exchange::bybit::Kline;
+use crate::models::{Execution, OrderSide, OrderType, Position};
+use super::executor::{AccountBalance, ExecutionError, LotSize, OpenOrder, OrderExecutor, OrderRequest};
+
+/// Durumu testten ayarlanan sahte yürütücü
+pub struct MockExecutor {
+    pub positions: Mutex<Vec<Position>>,
+    pub balance: Mutex<AccountBalance>,
+    pub open: Mutex<Vec<OpenOrder>>,
+    pub executions: Mutex<Vec<Execution>>,
+    /// Gönderilen emirler (gönderim sırasıyla)
+    pub placed: Mutex<Vec<OrderRequest>>,
+    /// Emir, iptal ve stop çağrılarının okunur kaydı ("place Sell 2 @ 120", "cancel order-1", "stop 100.1")
+    pub calls: Mutex<Vec<String>>,
+    pub price: Mutex<f64>,
+    pub lot: Mutex<LotSize>,
+    /// Sembol -> borsanın döndürdüğü mumlar (aralıktan bağımsız)
+    pub klines: Mutex<HashMap<String, Vec<Kline>>>,
+    /// Mum istekleri: (sembol, aralık, limit)
+    pub kline_requests: Mutex<Vec<(String, String, usize)>>,
+}
+
+impl Default for MockExecutor {
+    fn default() -> Self {
+        Self {
+            positions: Mutex::new(Vec::new()),
+            balance: Mutex::new(AccountBalance::default()),
+            open: Mutex::new(Vec::new()),
+            executions: Mutex::new(Vec::new()),
+            placed: Mutex::new(Vec::new()),
+            calls: Mutex::new(Vec::new()),
+            price: Mutex::new(100.0),
+            lot: Mutex::new(LotSize::NONE),
+            klines: Mutex::new(HashMap::new()),
+            kline_requests: Mutex::new(Vec::new()),
+        }
+    }
+}
+
+impl MockExecutor {
+    /// Özsermayesi ayarlanmış yürütücü
+    pub fn with_equity(equity: f64) -> Self {
+        let executor = Self::default();
+        *executor.balance.lock().unwrap() = AccountBalance { equity, available: equity, ..Default::default() };
+        executor
+    }
+
+    pub fn placed(&self) -> Vec<OrderRequest> {
+        self.placed.lock().unwrap().clone()
+    }
+
+    pub fn calls(&self) -> Vec<String> {
+        self.calls.lock().unwrap().clone()
+    }
+
+    /// Borsada bekleyen limit emir ekle
+    pub fn add_open(&self, symbol: &str, order_id: &str, side: OrderSide, price: f64, quantity: f64, reduce_only: bool) {
+        self.open.lock().unwrap().push(OpenOrder {
+            order_id: order_id.into(),
+            symbol: symbol.into(),
+            side,
+            order_type: OrderType::Limit,
+            price: Some(price),
+            trigger_price: None,
+            quantity,
+            reduce_only,
+        });
+    }
+
+    /// Bekleyen emrin `quantity` kadarını doldur (tamamı dolan emir bekleyenlerden kalkar)
+    pub fn fill(&self, order_id: &str, quantity: f64) {
+        let mut open = self.open.lock().unwrap();
+        let Some(index) = open.iter().position(|o| o.order_id == order_id) else {
+            return;
+        };
+        let order = open[index].clone();
+        open[index].quantity -= quantity;
+        if open[index].quantity < 1e-9 {
+            open.remove(index);
+        }
+        let mut executions = self.executions.lock().unwrap();
+        let id = format!("exec-{}", executions.len() + 1);
+        executions.push(Execution {
+            id,
+            order_id: order_id.into(),
+            symbol: order.symbol,
+            side: order.side,
+            price: order.price.unwrap_or_default(),
+            quantity,
+            fee: 0.0,
+            realized_pnl: 0.0,
+            executed_at: Utc::now(),
+        });
+    }
+
+    /// Bekleyen emri borsa tarafında kaldır (kullanıcı veya borsa iptali; kayda geçmez)
+    pub fn drop_open(&self, order_id: &str) {
+        self.open.lock().unwrap().retain(|o| o.order_id != order_id);
+    }
+}
+
+#[async_trait]
+impl OrderExecutor for MockExecutor {
+    async fn place_order(&self, request: &OrderRequest) -> Result<String, ExecutionError> {
+        let order_id = {
+            let mut placed = self.placed.lock().unwrap();
+            placed.push(request.clone());
+            format!("order-{}", placed.len())
+        };
+        // Limit emirler dolana kadar bekleyen emir olarak kalır
+        match request.price {
+            Some(price) => {
+                self.calls.lock().unwrap().push(format!("place {:?} {} @ {}", request.side, request.quantity, price));
+                self.add_open(&request.symbol, &order_id, request.side.clone(), price, request.quantity, request.reduce_only);
+            }
+            None => self.calls.lock().unwrap().push(format!("place {:?} {}", request.side, request.quantity)),
+        }
+        Ok(order_id)
+    }
+
+    async fn cancel_order(&self, _symbol: &str, order_id: &str) -> Result<(), ExecutionError> {
+        self.calls.lock().unwrap().push(format!("cancel {}", order_id));
+        self.drop_open(order_id);
+        Ok(())
+    }
+
+    async fn open_orders(&self, symbol: &str) -> Result<Vec<OpenOrder>, ExecutionError> {
+        Ok(self.open.lock().unwrap().iter().filter(|o| o.symbol == symbol).cloned().collect())
+    }
+
+    async fn set_stop_loss(&self, _position: &Position, stop_price: f64) -> Result<(), ExecutionError> {
+        self.calls.lock().unwrap().push(format!("stop {}", stop_price));
+        Ok(())
+    }
+
+    async fn lot_size(&self, _symbol: &str) -> Result<LotSize, ExecutionError> {
+        Ok(*self.lot.lock().unwrap())
+    }
+
+    async fn open_positions(&self) -> Result<Vec<Position>, ExecutionError> {
+        Ok(self.positions.lock().unwrap().clone())
+    }
+
+    async fn account_balance(&self) -> Result<AccountBalance, ExecutionError> {
+        Ok(*self.balance.lock().unwrap())
+    }
+
+    async fn last_price(&self, _symbol: &str) -> Result<f64, ExecutionError> {
+        Ok(*self.price.lock().unwrap())
+    }
+
+    async fn klines(&self, symbol: &str, interval: &str, limit: usize) -> Result<Vec<Kline>, ExecutionError> {
+        self.kline_requests.lock().unwrap().push((symbol.to_string(), interval.to_string(), limit));
+        let klines = self.klines.lock().unwrap().get(symbol).cloned().unwrap_or_default();
+        Ok(klines[klines.len().saturating_sub(limit)..].to_vec())
+    }
+
+    async fn executions(&self, since: DateTime<Utc>) -> Result<Vec<Execution>, ExecutionError> {
+        Ok(self.executions.lock().unwrap().iter().filter(|e| e.executed_at > since).cloned().collect())
+    }
+}
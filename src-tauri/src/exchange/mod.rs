@@ -2,5 +2,9 @@
 // Borsa bağlantıları ve API yönetimi
 
 pub mod bybit;
+pub mod executor;
+#[cfg(test)]
+pub mod mock;
 
 pub use bybit::BybitClient;
+pub use executor::{AccountBalance, ExecutionError, LotSize, OrderExecutor, OrderRequest};
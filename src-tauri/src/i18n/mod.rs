@@ -71,6 +71,7 @@ impl Translations {
         en.insert("error.invalid_input", "Invalid input. Please check your values.");
         en.insert("error.risk_limit_exceeded", "Risk limit exceeded. Cannot open new position.");
         en.insert("error.daily_loss_limit", "Daily loss limit reached. Trading disabled.");
+        en.insert("error.kill_switch_engaged", "Kill switch engaged. New positions are blocked.");
 
         // Success messages
         en.insert("success.order_placed", "Order placed successfully");
@@ -88,6 +89,39 @@ impl Translations {
         en.insert("warning.volatile_market", "High market volatility detected");
         en.insert("warning.breakeven_triggered", "Breakeven triggered");
         en.insert("warning.stop_loss_hit", "Stop loss hit");
+        en.insert("warning.drawdown", "Account drawdown threshold reached");
+
+        // Notifications
+        en.insert("notification.alarm_triggered", "Alarm triggered");
+        en.insert("notification.risk_warning", "Risk warning");
+        en.insert("alarm.condition.price_above", "Price crossed above");
+        en.insert("alarm.condition.price_below", "Price crossed below");
+        en.insert("alarm.condition.cross_vwap", "Price crossed VWAP");
+        en.insert("alarm.condition.cross_val", "Price crossed VAL");
+        en.insert("alarm.condition.cross_vah", "Price crossed VAH");
+        en.insert("alarm.condition.rule", "Alarm rule conditions met");
+        en.insert("alarm.condition.drawing", "Price reached drawing");
+
+        // Position sizing steps
+        en.insert("sizing.base_risk_percent", "Base risk (%)");
+        en.insert("sizing.atr", "ATR");
+        en.insert("sizing.atr_multiple", "ATR multiple");
+        en.insert("sizing.atr_unavailable", "ATR unavailable, using fixed fractional");
+        en.insert("sizing.trades", "Closed trades");
+        en.insert("sizing.insufficient_history", "Too few trades for Kelly, using fixed fractional (minimum)");
+        en.insert("sizing.win_rate", "Win rate (%)");
+        en.insert("sizing.payoff_ratio", "Payoff ratio");
+        en.insert("sizing.kelly_percent", "Full Kelly (%)");
+        en.insert("sizing.kelly_fraction", "Kelly fraction");
+        en.insert("sizing.notional", "Fixed notional");
+        en.insert("sizing.contracts", "Fixed contracts");
+        en.insert("sizing.losing_streak", "Losing streak");
+        en.insert("sizing.streak_factor", "Streak size factor");
+        en.insert("sizing.risk_percent", "Applied risk (%)");
+        en.insert("sizing.drawdown_factor", "Drawdown size factor");
+        en.insert("sizing.unit_risk", "Risk per unit");
+        en.insert("sizing.risk_amount", "Risk amount");
+        en.insert("sizing.position_size", "Position size");
 
         // Modules
         en.insert("module.stop_loss.name", "Smart Stop-Loss");
@@ -105,6 +139,22 @@ impl Translations {
         en.insert("module.strategy_builder.name", "Strategy Builder");
         en.insert("module.strategy_builder.description", "Create custom trading signals by combining multiple indicators");
 
+        // Module config fields
+        en.insert("module.stop_loss.config.auto_breakeven.label", "Auto Breakeven");
+        en.insert("module.stop_loss.config.auto_breakeven.description", "Move the stop to entry once the trade reaches the threshold");
+        en.insert("module.stop_loss.config.breakeven_threshold.label", "Breakeven Threshold (R)");
+        en.insert("module.stop_loss.config.breakeven_threshold.description", "Profit in R multiples required before moving the stop to entry");
+        en.insert("module.stop_loss.config.breakeven_offset_pct.label", "Breakeven Offset (%)");
+        en.insert("module.stop_loss.config.breakeven_offset_pct.description", "Distance beyond entry for the breakeven stop so fees are covered");
+        en.insert("module.stop_loss.config.min_trail_step_pct.label", "Minimum Trail Step (%)");
+        en.insert("module.stop_loss.config.min_trail_step_pct.description", "Smallest trailing stop move sent to the exchange, as a percent of price");
+        en.insert("module.risk_monitor.config.max_account_risk_pct.label", "Max Account Risk (%)");
+        en.insert("module.risk_monitor.config.max_account_risk_pct.description", "Limit for the total open risk of all positions, as a percent of equity");
+        en.insert("module.risk_monitor.config.max_margin_utilization_pct.label", "Max Margin Utilization (%)");
+        en.insert("module.risk_monitor.config.max_margin_utilization_pct.description", "Limit for initial margin in use, as a percent of equity");
+        en.insert("module.risk_monitor.config.warn_at_pct.label", "Warn At (% of Limit)");
+        en.insert("module.risk_monitor.config.warn_at_pct.description", "Raise a risk warning once a value reaches this share of its limit");
+
         // Trading
         en.insert("trading.buy", "Buy");
         en.insert("trading.sell", "Sell");
@@ -150,6 +200,7 @@ impl Translations {
         tr.insert("error.invalid_input", "Geçersiz giriş. Lütfen değerlerinizi kontrol edin.");
         tr.insert("error.risk_limit_exceeded", "Risk limiti aşıldı. Yeni pozisyon açılamaz.");
         tr.insert("error.daily_loss_limit", "Günlük kayıp limitine ulaşıldı. İşlem devre dışı.");
+        tr.insert("error.kill_switch_engaged", "Acil durdurma devrede. Yeni pozisyon açılamaz.");
 
         // Success messages
         tr.insert("success.order_placed", "Emir başarıyla verildi");
@@ -167,6 +218,39 @@ impl Translations {
         tr.insert("warning.volatile_market", "Yüksek piyasa oynaklığı tespit edildi");
         tr.insert("warning.breakeven_triggered", "Breakeven tetiklendi");
         tr.insert("warning.stop_loss_hit", "Stop loss tetiklendi");
+        tr.insert("warning.drawdown", "Hesap tepeden düşüş eşiğine ulaşıldı");
+
+        // Notifications
+        tr.insert("notification.alarm_triggered", "Alarm tetiklendi");
+        tr.insert("notification.risk_warning", "Risk uyarısı");
+        tr.insert("alarm.condition.price_above", "Fiyat yukarı kesti");
+        tr.insert("alarm.condition.price_below", "Fiyat aşağı kesti");
+        tr.insert("alarm.condition.cross_vwap", "Fiyat VWAP'ı kesti");
+        tr.insert("alarm.condition.cross_val", "Fiyat VAL'ı kesti");
+        tr.insert("alarm.condition.cross_vah", "Fiyat VAH'ı kesti");
+        tr.insert("alarm.condition.rule", "Alarm kuralı koşulları sağlandı");
+        tr.insert("alarm.condition.drawing", "Fiyat çizime ulaştı");
+
+        // Position sizing steps
+        tr.insert("sizing.base_risk_percent", "Temel risk (%)");
+        tr.insert("sizing.atr", "ATR");
+        tr.insert("sizing.atr_multiple", "ATR çarpanı");
+        tr.insert("sizing.atr_unavailable", "ATR yok, sabit oran kullanıldı");
+        tr.insert("sizing.trades", "Kapanan işlem");
+        tr.insert("sizing.insufficient_history", "Kelly için işlem sayısı yetersiz, sabit oran kullanıldı (en az)");
+        tr.insert("sizing.win_rate", "Kazanma oranı (%)");
+        tr.insert("sizing.payoff_ratio", "Ödeme oranı");
+        tr.insert("sizing.kelly_percent", "Tam Kelly (%)");
+        tr.insert("sizing.kelly_fraction", "Kelly kesri");
+        tr.insert("sizing.notional", "Sabit değer");
+        tr.insert("sizing.contracts", "Sabit miktar");
+        tr.insert("sizing.losing_streak", "Art arda kayıp");
+        tr.insert("sizing.streak_factor", "Kayıp serisi çarpanı");
+        tr.insert("sizing.risk_percent", "Uygulanan risk (%)");
+        tr.insert("sizing.drawdown_factor", "Tepeden düşüş çarpanı");
+        tr.insert("sizing.unit_risk", "Birim başına risk");
+        tr.insert("sizing.risk_amount", "Risk miktarı");
+        tr.insert("sizing.position_size", "Pozisyon boyutu");
 
         // Modules
         tr.insert("module.stop_loss.name", "Akıllı Stop-Loss");
@@ -184,6 +268,22 @@ impl Translations {
         tr.insert("module.strategy_builder.name", "Strateji Oluşturucu");
         tr.insert("module.strategy_builder.description", "Birden fazla göstergeyi birleştirerek özel işlem sinyalleri oluşturun");
 
+        // Modül ayar alanları
+        tr.insert("module.stop_loss.config.auto_breakeven.label", "Otomatik Breakeven");
+        tr.insert("module.stop_loss.config.auto_breakeven.description", "İşlem eşiğe ulaştığında stopu girişe taşı");
+        tr.insert("module.stop_loss.config.breakeven_threshold.label", "Breakeven Eşiği (R)");
+        tr.insert("module.stop_loss.config.breakeven_threshold.description", "Stopu girişe taşımak için gereken R cinsinden kâr");
+        tr.insert("module.stop_loss.config.breakeven_offset_pct.label", "Breakeven Payı (%)");
+        tr.insert("module.stop_loss.config.breakeven_offset_pct.description", "Komisyonları karşılamak için breakeven stopunun girişten uzaklığı");
+        tr.insert("module.stop_loss.config.min_trail_step_pct.label", "En Küçük İz Adımı (%)");
+        tr.insert("module.stop_loss.config.min_trail_step_pct.description", "Borsaya gönderilen en küçük iz süren stop hareketi, fiyatın yüzdesi olarak");
+        tr.insert("module.risk_monitor.config.max_account_risk_pct.label", "En Yüksek Hesap Riski (%)");
+        tr.insert("module.risk_monitor.config.max_account_risk_pct.description", "Tüm pozisyonların toplam açık riski için limit, hesap varlığının yüzdesi olarak");
+        tr.insert("module.risk_monitor.config.max_margin_utilization_pct.label", "En Yüksek Marjin Kullanımı (%)");
+        tr.insert("module.risk_monitor.config.max_margin_utilization_pct.description", "Kullanılan başlangıç marjini için limit, hesap varlığının yüzdesi olarak");
+        tr.insert("module.risk_monitor.config.warn_at_pct.label", "Uyarı Eşiği (Limitin %'si)");
+        tr.insert("module.risk_monitor.config.warn_at_pct.description", "Değer limitinin bu oranına ulaşınca risk uyarısı ver");
+
         // Trading
         tr.insert("trading.buy", "Al");
         tr.insert("trading.sell", "Sat");
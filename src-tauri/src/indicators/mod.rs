@@ -0,0 +1,195 @@
+// AlgoTrade OS - Indicators
+// Modüllerin kullandığı teknik gösterge hesaplamaları (mumlar eskiden yeniye sıralı)
+//
+// Her gösterge kapanan mumlarla beslenen akışkan bir yapıdır (`Indicator::update`). Tek değer
+// döndüren fonksiyonlar ve compute_indicator komutunun kullandığı `compute` aynı yapıları çalıştırır;
+// böylece modüller, alarmlar ve komutu kullanan arayüz aynı değerleri görür.
+// Mevcut fiyat grafiği VWAP/SMA'yı kendi (özel aralıklarla birleştirilmiş) mumlarından hesaplar ve
+// bu kapsamın dışındadır.
+
+mod moving_average;
+mod oscillators;
+mod volatility;
+mod vwap;
+mod volume_profile;
+
+pub use moving_average::{Ema, Sma};
+pub use oscillators::{Macd, MacdValue, Rsi};
+pub use volatility::{Atr, Bollinger};
+pub use vwap::{period_start as vwap_period_start, Vwap, VwapValue};
+pub use volume_profile::{range_bounds, volume_profile, VolumeProfileBuilder};
+
+use chrono::{DateTime, Utc};
+use crate::exchange::bybit::Kline;
+use crate::models::{IndicatorPoint, IndicatorSpec, VwapAnchor};
+
+/// Mum mum güncellenen gösterge
+pub trait Indicator {
+    type Output;
+
+    /// Mumu ekle ve güncel değeri döndür (ısınma süresince None)
+    fn update(&mut self, kline: &Kline) -> Option<Self::Output>;
+}
+
+/// Orta çizgi ve simetrik bantlar
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bands {
+    pub middle: f64,
+    pub upper: f64,
+    pub lower: f64,
+}
+
+impl Bands {
+    pub fn around(middle: f64, width: f64) -> Self {
+        Self { middle, upper: middle + width, lower: middle - width }
+    }
+}
+
+/// Göstergeyi tüm seriden geçir, son mumdaki değeri döndür
+pub fn last_value<I: Indicator>(mut indicator: I, klines: &[Kline]) -> Option<I::Output> {
+    klines.iter().map(|k| indicator.update(k)).last().flatten()
+}
+
+/// Göstergenin seri boyunca değerleri (ısınma mumları atlanır)
+pub fn compute(spec: &IndicatorSpec, klines: &[Kline]) -> Vec<IndicatorPoint> {
+    fn series<I: Indicator>(
+        mut indicator: I,
+        klines: &[Kline],
+        point: impl Fn(i64, I::Output) -> IndicatorPoint,
+    ) -> Vec<IndicatorPoint> {
+        klines.iter().filter_map(|k| indicator.update(k).map(|v| point(k.timestamp, v))).collect()
+    }
+    let line = |timestamp, value| IndicatorPoint { timestamp, value, ..Default::default() };
+    let bands = |timestamp, bands: Bands| IndicatorPoint {
+        timestamp,
+        value: bands.middle,
+        upper: Some(bands.upper),
+        lower: Some(bands.lower),
+        ..Default::default()
+    };
+
+    match spec {
+        IndicatorSpec::Vwap { anchor, band_multiplier } => {
+            series(Vwap::new(anchor.clone()), klines, |t, v: VwapValue| bands(t, v.bands(*band_multiplier)))
+        }
+        IndicatorSpec::Sma { period } => series(Sma::new(*period), klines, line),
+        IndicatorSpec::Ema { period } => series(Ema::new(*period), klines, line),
+        IndicatorSpec::Rsi { period } => series(Rsi::new(*period), klines, line),
+        IndicatorSpec::Atr { period } => series(Atr::new(*period), klines, line),
+        IndicatorSpec::Bollinger { period, multiplier } => series(Bollinger::new(*period, *multiplier), klines, bands),
+        IndicatorSpec::Macd { fast, slow, signal } => series(Macd::new(*fast, *slow, *signal), klines, |timestamp, v| {
+            IndicatorPoint {
+                timestamp,
+                value: v.macd,
+                signal: Some(v.signal),
+                histogram: Some(v.histogram),
+                ..Default::default()
+            }
+        }),
+    }
+}
+
+/// Göstergenin ilk değeri için gereken mum sayısı
+pub fn lookback(spec: &IndicatorSpec) -> usize {
+    match spec {
+        IndicatorSpec::Vwap { .. } => 1,
+        IndicatorSpec::Sma { period }
+        | IndicatorSpec::Ema { period }
+        | IndicatorSpec::Atr { period }
+        | IndicatorSpec::Bollinger { period, .. } => *period,
+        IndicatorSpec::Rsi { period } => period + 1,
+        IndicatorSpec::Macd { slow, signal, .. } => slow + signal - 1,
+    }
+}
+
+/// Göstergenin `now` anındaki değeri için mumların geriye uzanması gereken an (ms)
+///
+/// Seans, haftalık, aylık ve çapalı VWAP periyodun başından itibaren birikir; diğerleri için `lookback` yeterlidir.
+pub fn coverage_start(spec: &IndicatorSpec, now: DateTime<Utc>) -> Option<i64> {
+    match spec {
+        IndicatorSpec::Vwap { anchor, .. } => vwap_period_start(anchor, now),
+        _ => None,
+    }
+}
+
+/// Gerçek aralık (True Range)
+pub fn true_range(kline: &Kline, previous_close: Option<f64>) -> f64 {
+    let range = kline.high - kline.low;
+    match previous_close {
+        Some(prev) => range.max((kline.high - prev).abs()).max((kline.low - prev).abs()),
+        None => range,
+    }
+}
+
+/// Wilder ATR - son mumdaki değer (yeterli mum yoksa None)
+pub fn atr(klines: &[Kline], period: usize) -> Option<f64> {
+    last_value(Atr::new(period), klines)
+}
+
+/// Son `n` mumun en yüksek seviyesi
+pub fn highest_high(klines: &[Kline], n: usize) -> Option<f64> {
+    let start = klines.len().checked_sub(n)?;
+    klines[start..].iter().map(|k| k.high).reduce(f64::max)
+}
+
+/// Son `n` mumun en düşük seviyesi
+pub fn lowest_low(klines: &[Kline], n: usize) -> Option<f64> {
+    let start = klines.len().checked_sub(n)?;
+    klines[start..].iter().map(|k| k.low).reduce(f64::min)
+}
+
+/// `start` anından (ms) itibaren hacim ağırlıklı ortalama fiyat (hlc3, hacim yoksa None)
+///
+/// Seans VWAP'ı için seans başlangıcı, anchored VWAP için çapa zamanı verilir.
+pub fn vwap_since(klines: &[Kline], start: i64) -> Option<f64> {
+    let anchor = chrono::DateTime::from_timestamp_millis(start)?;
+    last_value(Vwap::new(VwapAnchor::Anchored(anchor)), klines).map(|v| v.vwap)
+}
+
+/// Son `period` kapanışın basit ortalaması
+pub fn sma(klines: &[Kline], period: usize) -> Option<f64> {
+    last_value(Sma::new(period), klines)
+}
+
+/// Wilder RSI - son mumdaki değer (en az `period + 1` mum gerekir)
+pub fn rsi(klines: &[Kline], period: usize) -> Option<f64> {
+    last_value(Rsi::new(period), klines)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compute_and_lookback() {
+        let klines: Vec<Kline> = [10.0, 11.0, 12.0, 11.0, 13.0, 14.0]
+            .iter()
+            .map(|&close| Kline { timestamp: 0, open: close, high: close, low: close, close, volume: 0.0 })
+            .collect();
+
+        let points = compute(&IndicatorSpec::Sma { period: 5 }, &klines);
+        assert_eq!(points.len(), 2);
+        assert_eq!(points[1].value, sma(&klines, 5).unwrap());
+
+        // İlk değer tam `lookback` mumda üretilir
+        let specs = [
+            IndicatorSpec::Sma { period: 3 },
+            IndicatorSpec::Ema { period: 4 },
+            IndicatorSpec::Rsi { period: 3 },
+            IndicatorSpec::Atr { period: 2 },
+            IndicatorSpec::Bollinger { period: 5, multiplier: 2.0 },
+            IndicatorSpec::Macd { fast: 2, slow: 3, signal: 2 },
+        ];
+        for spec in specs {
+            let needed = lookback(&spec);
+            assert!(compute(&spec, &klines[..needed - 1]).is_empty(), "{:?}", spec);
+            assert_eq!(compute(&spec, &klines[..needed]).len(), 1, "{:?}", spec);
+            assert_eq!(coverage_start(&spec, Utc::now()), None);
+        }
+
+        // Haftalık VWAP haftanın başına kadar mum ister
+        let now = DateTime::from_timestamp(1_704_067_200 + 3 * 86_400 + 60, 0).unwrap();
+        let weekly = IndicatorSpec::Vwap { anchor: VwapAnchor::Week, band_multiplier: 0.0 };
+        assert_eq!(coverage_start(&weekly, now), Some(1_704_067_200_000));
+    }
+}
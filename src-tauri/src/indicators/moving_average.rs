@@ -0,0 +1,106 @@
+// AlgoTrade OS - Moving Averages
+// Kapanış fiyatı üzerinden basit ve üssel hareketli ortalamalar
+
+use std::collections::VecDeque;
+use crate::exchange::bybit::Kline;
+use super::Indicator;
+
+/// Basit hareketli ortalama (son `period` değer)
+#[derive(Debug, Clone)]
+pub struct Sma {
+    period: usize,
+    window: VecDeque<f64>,
+}
+
+impl Sma {
+    pub fn new(period: usize) -> Self {
+        Self { period, window: VecDeque::with_capacity(period + 1) }
+    }
+
+    /// Yeni değeri ekle (pencere dolana kadar None)
+    pub fn update_value(&mut self, value: f64) -> Option<f64> {
+        if self.period == 0 {
+            return None;
+        }
+        self.window.push_back(value);
+        if self.window.len() > self.period {
+            self.window.pop_front();
+        }
+        (self.window.len() == self.period).then(|| self.window.iter().sum::<f64>() / self.period as f64)
+    }
+
+    /// Penceredeki değerler (eskiden yeniye)
+    pub(super) fn window(&self) -> &VecDeque<f64> {
+        &self.window
+    }
+}
+
+impl Indicator for Sma {
+    type Output = f64;
+
+    fn update(&mut self, kline: &Kline) -> Option<f64> {
+        self.update_value(kline.close)
+    }
+}
+
+/// Üssel hareketli ortalama (ilk `period` değerin SMA'sı ile başlar)
+#[derive(Debug, Clone)]
+pub struct Ema {
+    period: usize,
+    seed: Sma,
+    value: Option<f64>,
+}
+
+impl Ema {
+    pub fn new(period: usize) -> Self {
+        Self { period, seed: Sma::new(period), value: None }
+    }
+
+    /// Yeni değeri ekle (ilk `period` değer dolana kadar None)
+    pub fn update_value(&mut self, value: f64) -> Option<f64> {
+        self.value = match self.value {
+            Some(previous) => {
+                let alpha = 2.0 / (self.period as f64 + 1.0);
+                Some(previous + alpha * (value - previous))
+            }
+            None => self.seed.update_value(value),
+        };
+        self.value
+    }
+}
+
+impl Indicator for Ema {
+    type Output = f64;
+
+    fn update(&mut self, kline: &Kline) -> Option<f64> {
+        self.update_value(kline.close)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{compute, last_value, sma};
+    use crate::models::IndicatorSpec;
+
+    fn close(close: f64) -> Kline {
+        Kline { timestamp: 0, open: close, high: close, low: close, close, volume: 0.0 }
+    }
+
+    #[test]
+    fn test_sma_and_ema() {
+        let klines: Vec<Kline> = [10.0, 11.0, 12.0, 11.0, 13.0, 14.0].into_iter().map(close).collect();
+
+        assert_eq!(sma(&klines, 2), Some(13.5));
+        assert_eq!(sma(&klines, 7), None);
+        assert_eq!(last_value(Sma::new(3), &klines[..3]), Some(11.0));
+
+        // EMA(3): SMA(10,11,12) = 11, sonra alpha 0.5
+        let mut ema = Ema::new(3);
+        let values: Vec<Option<f64>> = klines.iter().map(|k| ema.update(k)).collect();
+        assert_eq!(values, vec![None, None, Some(11.0), Some(11.0), Some(12.0), Some(13.0)]);
+
+        let points = compute(&IndicatorSpec::Ema { period: 3 }, &klines);
+        assert_eq!(points.iter().map(|p| p.value).collect::<Vec<_>>(), vec![11.0, 11.0, 12.0, 13.0]);
+    }
+}
@@ -0,0 +1,110 @@
+// AlgoTrade OS - Oscillators
+// RSI ve MACD
+
+use crate::exchange::bybit::Kline;
+use super::{Ema, Indicator};
+
+/// Wilder RSI (ilk `period` değişimin ortalamasıyla başlar, en az `period + 1` mum gerekir)
+#[derive(Debug, Clone)]
+pub struct Rsi {
+    period: usize,
+    previous_close: Option<f64>,
+    changes: usize,
+    gain: f64,
+    loss: f64,
+}
+
+impl Rsi {
+    pub fn new(period: usize) -> Self {
+        Self { period, previous_close: None, changes: 0, gain: 0.0, loss: 0.0 }
+    }
+}
+
+impl Indicator for Rsi {
+    type Output = f64;
+
+    fn update(&mut self, kline: &Kline) -> Option<f64> {
+        let previous = self.previous_close.replace(kline.close)?;
+        if self.period == 0 {
+            return None;
+        }
+        let change = kline.close - previous;
+        let period = self.period as f64;
+        self.changes += 1;
+        if self.changes <= self.period {
+            // Isınma: ilk `period` değişimin toplamı
+            self.gain += change.max(0.0);
+            self.loss += (-change).max(0.0);
+            if self.changes < self.period {
+                return None;
+            }
+            self.gain /= period;
+            self.loss /= period;
+        } else {
+            self.gain = (self.gain * (period - 1.0) + change.max(0.0)) / period;
+            self.loss = (self.loss * (period - 1.0) + (-change).max(0.0)) / period;
+        }
+        Some(if self.loss == 0.0 { 100.0 } else { 100.0 - 100.0 / (1.0 + self.gain / self.loss) })
+    }
+}
+
+/// MACD değeri
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MacdValue {
+    /// Hızlı EMA - yavaş EMA
+    pub macd: f64,
+    pub signal: f64,
+    pub histogram: f64,
+}
+
+/// MACD (sinyal çizgisi dolana kadar None)
+#[derive(Debug, Clone)]
+pub struct Macd {
+    fast: Ema,
+    slow: Ema,
+    signal: Ema,
+}
+
+impl Macd {
+    pub fn new(fast: usize, slow: usize, signal: usize) -> Self {
+        Self { fast: Ema::new(fast), slow: Ema::new(slow), signal: Ema::new(signal) }
+    }
+}
+
+impl Indicator for Macd {
+    type Output = MacdValue;
+
+    fn update(&mut self, kline: &Kline) -> Option<MacdValue> {
+        let fast = self.fast.update(kline);
+        let slow = self.slow.update(kline);
+        let macd = fast? - slow?;
+        let signal = self.signal.update_value(macd)?;
+        Some(MacdValue { macd, signal, histogram: macd - signal })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{last_value, rsi};
+
+    fn close(close: f64) -> Kline {
+        Kline { timestamp: 0, open: close, high: close, low: close, close, volume: 0.0 }
+    }
+
+    #[test]
+    fn test_rsi_and_macd() {
+        let klines: Vec<Kline> = [10.0, 11.0, 14.0, 9.0].into_iter().map(close).collect();
+        // Değişimler +1, +3, -5: ilk 2 ortalama kazanç 2, kayıp 0; sonra kazanç 1, kayıp 2.5
+        let value = rsi(&klines, 2).unwrap();
+        assert!((value - 100.0 * 1.0 / 3.5).abs() < 1e-9);
+        assert_eq!(rsi(&klines[..3], 2), Some(100.0));
+        assert_eq!(rsi(&klines[..2], 2), None);
+
+        // MACD(1, 2, 1): hızlı EMA = kapanış, sinyal = MACD
+        let macd = last_value(Macd::new(1, 2, 1), &klines).unwrap();
+        assert!((macd.macd - (9.0 - last_value(Ema::new(2), &klines).unwrap())).abs() < 1e-12);
+        assert_eq!(macd.histogram, 0.0);
+        assert!(last_value(Macd::new(1, 3, 2), &klines[..3]).is_none());
+    }
+}
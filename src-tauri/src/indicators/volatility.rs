@@ -0,0 +1,102 @@
+// AlgoTrade OS - Volatility Indicators
+// ATR ve Bollinger bantları
+
+use crate::exchange::bybit::Kline;
+use super::{true_range, Bands, Indicator, Sma};
+
+/// Wilder ATR (ilk `period` gerçek aralığın ortalamasıyla başlar)
+#[derive(Debug, Clone)]
+pub struct Atr {
+    period: usize,
+    previous_close: Option<f64>,
+    ranges: usize,
+    value: f64,
+}
+
+impl Atr {
+    pub fn new(period: usize) -> Self {
+        Self { period, previous_close: None, ranges: 0, value: 0.0 }
+    }
+}
+
+impl Indicator for Atr {
+    type Output = f64;
+
+    fn update(&mut self, kline: &Kline) -> Option<f64> {
+        let range = true_range(kline, self.previous_close.replace(kline.close));
+        if self.period == 0 {
+            return None;
+        }
+        let period = self.period as f64;
+        self.ranges += 1;
+        if self.ranges <= self.period {
+            self.value += range;
+            if self.ranges < self.period {
+                return None;
+            }
+            self.value /= period;
+        } else {
+            self.value = (self.value * (period - 1.0) + range) / period;
+        }
+        Some(self.value)
+    }
+}
+
+/// Bollinger bantları: SMA ± `multiplier` x standart sapma (popülasyon)
+#[derive(Debug, Clone)]
+pub struct Bollinger {
+    sma: Sma,
+    multiplier: f64,
+}
+
+impl Bollinger {
+    pub fn new(period: usize, multiplier: f64) -> Self {
+        Self { sma: Sma::new(period), multiplier }
+    }
+}
+
+impl Indicator for Bollinger {
+    type Output = Bands;
+
+    fn update(&mut self, kline: &Kline) -> Option<Bands> {
+        let middle = self.sma.update(kline)?;
+        let window = self.sma.window();
+        let variance = window.iter().map(|v| (v - middle).powi(2)).sum::<f64>() / window.len() as f64;
+        Some(Bands::around(middle, variance.sqrt() * self.multiplier))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{atr, highest_high, last_value, lowest_low};
+
+    fn kline(high: f64, low: f64, close: f64) -> Kline {
+        Kline { timestamp: 0, open: close, high, low, close, volume: 0.0 }
+    }
+
+    #[test]
+    fn test_atr_and_extremes() {
+        let klines = vec![
+            kline(11.0, 9.0, 10.0),  // TR 2
+            kline(12.0, 10.0, 11.0), // TR 2
+            kline(15.0, 12.0, 14.0), // TR 4
+            kline(14.0, 8.0, 9.0),   // TR 6
+        ];
+        // İlk 2 TR ortalaması 2, sonra Wilder: (2+4)/2 = 3, (3+6)/2 = 4.5
+        assert_eq!(atr(&klines, 2), Some(4.5));
+        assert_eq!(atr(&klines, 5), None);
+        assert_eq!(highest_high(&klines, 2), Some(15.0));
+        assert_eq!(lowest_low(&klines, 3), Some(8.0));
+        assert_eq!(lowest_low(&klines, 5), None);
+    }
+
+    #[test]
+    fn test_bollinger_bands() {
+        let klines: Vec<Kline> = [10.0, 11.0, 12.0, 11.0, 13.0, 14.0].iter().map(|&c| kline(c, c, c)).collect();
+        // Bollinger(2, 1): son iki kapanış 13, 14 -> ort 13.5, sapma 0.5
+        let bands = last_value(Bollinger::new(2, 1.0), &klines).unwrap();
+        assert_eq!(bands, Bands { middle: 13.5, upper: 14.0, lower: 13.0 });
+        assert!(last_value(Bollinger::new(7, 1.0), &klines).is_none());
+    }
+}
@@ -0,0 +1,196 @@
+// AlgoTrade OS - Volume Profile
+// Fiyat kovalarına dağıtılmış hacim: POC (en yüksek hacimli kova) ve değer alanı (VAH/VAL)
+//
+// Mumdan kurulurken mumun hacmi düşük-yüksek aralığındaki kovalara eşit dağıtılır; işlem verisiyle
+// kurulurken her işlem kendi fiyatının kovasına eklenir. Değer alanı POC'tan başlayarak her adımda
+// komşu kovalardan hacmi büyük olanın eklenmesiyle istenen yüzdeye ulaşana kadar genişletilir.
+
+use std::collections::BTreeMap;
+use chrono::{DateTime, Utc};
+use crate::exchange::bybit::Kline;
+use crate::models::{ProfileBucket, ProfileRange, VolumeProfile, VolumeProfileSpec};
+
+/// Kova boyutu verilmediğinde fiyat aralığının bölündüğü kova sayısı
+pub const DEFAULT_BUCKETS: f64 = 100.0;
+
+/// Bir profilde izin verilen en fazla kova
+const MAX_BUCKETS: i64 = 10_000;
+
+/// Kova sınırındaki kayan nokta hatası için pay (30.0 / 0.1 = 299.99...)
+const BUCKET_EPSILON: f64 = 1e-9;
+
+/// Hacmi fiyat kovalarında biriktirir
+#[derive(Debug, Clone)]
+pub struct VolumeProfileBuilder {
+    bucket_size: f64,
+    volumes: BTreeMap<i64, f64>,
+    start: Option<i64>,
+    end: Option<i64>,
+}
+
+impl VolumeProfileBuilder {
+    pub fn new(bucket_size: f64) -> Self {
+        Self { bucket_size, volumes: BTreeMap::new(), start: None, end: None }
+    }
+
+    fn bucket(&self, price: f64) -> i64 {
+        (price / self.bucket_size + BUCKET_EPSILON).floor() as i64
+    }
+
+    fn touch(&mut self, timestamp: i64) {
+        self.start = Some(self.start.map_or(timestamp, |s| s.min(timestamp)));
+        self.end = Some(self.end.map_or(timestamp, |e| e.max(timestamp)));
+    }
+
+    /// İşlemi fiyatının kovasına ekle
+    pub fn add_trade(&mut self, timestamp: i64, price: f64, volume: f64) {
+        self.touch(timestamp);
+        *self.volumes.entry(self.bucket(price)).or_default() += volume;
+    }
+
+    /// Mumun hacmini düşük-yüksek aralığındaki kovalara eşit dağıt
+    pub fn add_kline(&mut self, kline: &Kline) {
+        self.touch(kline.timestamp);
+        let (low, high) = (self.bucket(kline.low), self.bucket(kline.high));
+        let share = kline.volume / (high - low + 1) as f64;
+        for bucket in low..=high {
+            *self.volumes.entry(bucket).or_default() += share;
+        }
+    }
+
+    /// POC ve `value_area_pct` yüzdelik değer alanı ile profili oluştur
+    pub fn build(&self, value_area_pct: f64) -> Result<VolumeProfile, String> {
+        let (Some((&first, _)), Some((&last, _))) = (self.volumes.first_key_value(), self.volumes.last_key_value()) else {
+            return Err("Profil için hacim yok".to_string());
+        };
+        if last - first >= MAX_BUCKETS {
+            return Err(too_many_buckets());
+        }
+        let volumes: Vec<f64> = (first..=last).map(|b| self.volumes.get(&b).copied().unwrap_or(0.0)).collect();
+        let total_volume: f64 = volumes.iter().sum();
+        if total_volume <= 0.0 {
+            return Err("Profil için hacim yok".to_string());
+        }
+
+        // Eşit hacimde düşük fiyatlı kova POC olur
+        let poc = volumes.iter().enumerate().fold(0, |best, (i, v)| if *v > volumes[best] { i } else { best });
+        let target = total_volume * value_area_pct / 100.0;
+        let (mut low, mut high, mut area) = (poc, poc, volumes[poc]);
+        while area < target && (low > 0 || high + 1 < volumes.len()) {
+            let below = low.checked_sub(1).map(|i| volumes[i]);
+            let above = volumes.get(high + 1).copied();
+            if above.unwrap_or(-1.0) >= below.unwrap_or(-1.0) {
+                high += 1;
+                area += above.unwrap_or(0.0);
+            } else {
+                low -= 1;
+                area += below.unwrap_or(0.0);
+            }
+        }
+
+        let edge = |i: usize| (first + i as i64) as f64 * self.bucket_size;
+        Ok(VolumeProfile {
+            start: self.start.unwrap_or_default(),
+            end: self.end.unwrap_or_default(),
+            bucket_size: self.bucket_size,
+            value_area_pct,
+            total_volume,
+            poc: edge(poc) + self.bucket_size / 2.0,
+            vah: edge(high + 1),
+            val: edge(low),
+            buckets: volumes.iter().enumerate().map(|(i, &volume)| ProfileBucket { price: edge(i), volume }).collect(),
+        })
+    }
+}
+
+fn too_many_buckets() -> String {
+    format!("Kova sayısı {} sınırını aşıyor, kova boyutunu büyütün", MAX_BUCKETS)
+}
+
+/// Aralığın başlangıç ve bitişi (ms); seans `now`'ın UTC günüdür
+pub fn range_bounds(range: &ProfileRange, now: DateTime<Utc>) -> (i64, i64) {
+    match range {
+        ProfileRange::Session => {
+            let now_ms = now.timestamp_millis();
+            (now_ms - now_ms.rem_euclid(86_400_000), now_ms)
+        }
+        ProfileRange::Fixed { start, end } => (start.timestamp_millis(), end.unwrap_or(now).timestamp_millis()),
+        ProfileRange::Visible { start, end } => (start.timestamp_millis(), end.timestamp_millis()),
+    }
+}
+
+/// Mumlardan aralığın hacim profili (kova boyutu yoksa fiyat aralığı DEFAULT_BUCKETS'a bölünür)
+pub fn volume_profile(klines: &[Kline], spec: &VolumeProfileSpec, now: DateTime<Utc>) -> Result<VolumeProfile, String> {
+    let (start, end) = range_bounds(&spec.range, now);
+    let selected: Vec<&Kline> = klines.iter().filter(|k| k.timestamp >= start && k.timestamp <= end).collect();
+    if selected.is_empty() {
+        return Err("Profil aralığında mum yok".to_string());
+    }
+    let high = selected.iter().map(|k| k.high).fold(f64::MIN, f64::max);
+    let low = selected.iter().map(|k| k.low).fold(f64::MAX, f64::min);
+    let bucket_size = match spec.bucket_size {
+        Some(size) => size,
+        None => {
+            let range = if high > low { high - low } else { high.abs() * 0.01 };
+            range / DEFAULT_BUCKETS
+        }
+    };
+    if !(bucket_size.is_finite() && bucket_size > 0.0) {
+        return Err("Kova boyutu pozitif olmalı".to_string());
+    }
+
+    // Kovalar doldurulmadan önce: küçük kova boyutu tek mumda milyarlarca kova açabilir
+    let mut builder = VolumeProfileBuilder::new(bucket_size);
+    if (high - low) / bucket_size >= MAX_BUCKETS as f64 {
+        return Err(too_many_buckets());
+    }
+    for kline in selected {
+        builder.add_kline(kline);
+    }
+    builder.build(spec.value_area_pct)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_poc_and_value_area() {
+        // Kova 1.0: 10 -> 1, 11 -> 2, 12 -> 6, 13 -> 3, 14 -> 1 (toplam 13)
+        let mut builder = VolumeProfileBuilder::new(1.0);
+        for (price, volume) in [(10.5, 1.0), (11.2, 2.0), (12.0, 6.0), (13.9, 3.0), (14.1, 1.0)] {
+            builder.add_trade(0, price, volume);
+        }
+        let profile = builder.build(70.0).unwrap();
+        assert_eq!(profile.poc, 12.5);
+        // Hedef 9.1: 6 + 3 (üst) = 9, sonra alt 2 -> 11
+        assert_eq!((profile.val, profile.vah), (11.0, 14.0));
+        assert_eq!(profile.buckets.len(), 5);
+        assert_eq!(profile.total_volume, 13.0);
+
+        // Mum hacmi aralığındaki kovalara eşit dağıtılır; seans dışındaki mum atlanır
+        let now = DateTime::from_timestamp(86_400 + 3_600, 0).unwrap();
+        let kline = |timestamp, low: f64, high: f64, volume| Kline { timestamp, open: low, high, low, close: high, volume };
+        let klines = [kline(0, 1.0, 100.0, 1000.0), kline(86_400_000, 10.0, 11.9, 4.0), kline(86_460_000, 11.0, 11.5, 2.0)];
+        let spec = VolumeProfileSpec { bucket_size: Some(1.0), ..Default::default() };
+        let session = volume_profile(&klines, &spec, now).unwrap();
+        assert_eq!(session.buckets, vec![
+            ProfileBucket { price: 10.0, volume: 2.0 },
+            ProfileBucket { price: 11.0, volume: 4.0 },
+        ]);
+        assert_eq!((session.poc, session.start, session.end), (11.5, 86_400_000, 86_460_000));
+        assert!(volume_profile(&klines[..1], &spec, now).is_err());
+    }
+
+    #[test]
+    fn test_bucket_limit_checked_before_filling() {
+        // 0.0001 kova ile 60000-61000 aralığı 10 milyon kova olurdu
+        let now = DateTime::from_timestamp(3_600, 0).unwrap();
+        let klines = [Kline { timestamp: 0, open: 60_000.0, high: 61_000.0, low: 60_000.0, close: 60_500.0, volume: 1.0 }];
+        let spec = VolumeProfileSpec { bucket_size: Some(0.0001), ..Default::default() };
+        assert_eq!(volume_profile(&klines, &spec, now).unwrap_err(), too_many_buckets());
+
+        let spec = VolumeProfileSpec { bucket_size: Some(1.0), ..Default::default() };
+        assert_eq!(volume_profile(&klines, &spec, now).unwrap().buckets.len(), 1001);
+    }
+}
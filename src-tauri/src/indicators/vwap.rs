@@ -0,0 +1,129 @@
+// AlgoTrade OS - VWAP
+// Seans (UTC günü), haftalık, aylık ve çapalı hacim ağırlıklı ortalama fiyat
+//
+// Tipik fiyat hlc3'tür. Standart sapma hacim ağırlıklı varyansın köküdür:
+// Σ(v·tp²)/Σv - vwap². Periyot değişince birikimler sıfırlanır.
+
+use chrono::{DateTime, Datelike, Duration, Utc};
+use crate::exchange::bybit::Kline;
+use crate::models::VwapAnchor;
+use super::{Bands, Indicator};
+
+/// VWAP değeri
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VwapValue {
+    pub vwap: f64,
+    /// Hacim ağırlıklı standart sapma
+    pub std_dev: f64,
+}
+
+impl VwapValue {
+    /// VWAP ± `multiplier` x standart sapma
+    pub fn bands(&self, multiplier: f64) -> Bands {
+        Bands::around(self.vwap, self.std_dev * multiplier)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Vwap {
+    anchor: VwapAnchor,
+    period_start: Option<i64>,
+    volume: f64,
+    price_volume: f64,
+    squared_price_volume: f64,
+}
+
+impl Vwap {
+    pub fn new(anchor: VwapAnchor) -> Self {
+        Self { anchor, period_start: None, volume: 0.0, price_volume: 0.0, squared_price_volume: 0.0 }
+    }
+
+    /// Mumun ait olduğu periyodun başlangıcı (ms, çapa öncesi mumlar için None)
+    fn period_of(&self, timestamp: i64) -> Option<i64> {
+        period_start(&self.anchor, DateTime::from_timestamp_millis(timestamp)?)
+    }
+}
+
+/// `time` anının içinde olduğu VWAP periyodunun başlangıcı (ms, çapadan önceyse None)
+pub fn period_start(anchor: &VwapAnchor, time: DateTime<Utc>) -> Option<i64> {
+    let day = time.date_naive();
+    let start = match anchor {
+        VwapAnchor::Session => day,
+        VwapAnchor::Week => day - Duration::days(day.weekday().num_days_from_monday() as i64),
+        VwapAnchor::Month => day.with_day(1)?,
+        VwapAnchor::Anchored(anchor) => return (time >= *anchor).then_some(anchor.timestamp_millis()),
+    };
+    Some(start.and_hms_opt(0, 0, 0)?.and_utc().timestamp_millis())
+}
+
+impl Indicator for Vwap {
+    type Output = VwapValue;
+
+    fn update(&mut self, kline: &Kline) -> Option<VwapValue> {
+        let period = self.period_of(kline.timestamp)?;
+        if self.period_start != Some(period) {
+            *self = Self { period_start: Some(period), ..Self::new(self.anchor.clone()) };
+        }
+        let typical = (kline.high + kline.low + kline.close) / 3.0;
+        self.volume += kline.volume;
+        self.price_volume += typical * kline.volume;
+        self.squared_price_volume += typical * typical * kline.volume;
+        if self.volume <= 0.0 {
+            return None;
+        }
+        let vwap = self.price_volume / self.volume;
+        let variance = (self.squared_price_volume / self.volume - vwap * vwap).max(0.0);
+        Some(VwapValue { vwap, std_dev: variance.sqrt() })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{last_value, vwap_since};
+
+    fn candle(timestamp: i64, price: f64, volume: f64) -> Kline {
+        Kline { timestamp, open: price, high: price, low: price, close: price, volume }
+    }
+
+    #[test]
+    fn test_vwap_since() {
+        let klines = [
+            Kline { high: 12.0, low: 9.0, ..candle(0, 9.0, 1.0) },
+            Kline { high: 14.0, low: 11.0, ..candle(60, 11.0, 3.0) },
+        ];
+        assert_eq!(vwap_since(&klines, 0), Some(11.5));
+        assert_eq!(vwap_since(&klines, 60), Some(12.0));
+        assert_eq!(vwap_since(&[candle(0, 10.0, 0.0)], 0), None);
+    }
+
+    #[test]
+    fn test_vwap_anchors() {
+        const DAY: i64 = 86_400_000;
+        // 2024-01-01 Pazartesi; ikinci gün seans VWAP'ı sıfırlanır, haftalık devam eder
+        let monday = 1_704_067_200_000;
+        let klines = [candle(monday, 10.0, 1.0), candle(monday + DAY / 2, 20.0, 1.0), candle(monday + DAY, 30.0, 2.0)];
+
+        let session = last_value(Vwap::new(VwapAnchor::Session), &klines).unwrap();
+        assert_eq!(session, VwapValue { vwap: 30.0, std_dev: 0.0 });
+        let weekly = last_value(Vwap::new(VwapAnchor::Week), &klines).unwrap();
+        // (10 + 20 + 60) / 4 = 22.5; varyans (100 + 400 + 1800) / 4 - 22.5² = 68.75
+        assert_eq!(weekly.vwap, 22.5);
+        assert!((weekly.std_dev - 68.75f64.sqrt()).abs() < 1e-12);
+        assert_eq!(weekly.bands(2.0).upper, 22.5 + 2.0 * weekly.std_dev);
+
+        let day_one = &klines[..2];
+        let anchor = DateTime::from_timestamp_millis(monday + DAY / 2).unwrap();
+        assert_eq!(last_value(Vwap::new(VwapAnchor::Anchored(anchor)), day_one).unwrap().vwap, 20.0);
+        assert_eq!(vwap_since(day_one, monday + DAY / 2), Some(20.0));
+        assert!(last_value(Vwap::new(VwapAnchor::Anchored(anchor)), &klines[..1]).is_none());
+        assert_eq!(last_value(Vwap::new(VwapAnchor::Month), &klines).unwrap().vwap, 22.5);
+
+        // 2024-01-03 Çarşamba öğlen
+        let wednesday = DateTime::from_timestamp_millis(monday + 2 * DAY + DAY / 2).unwrap();
+        assert_eq!(period_start(&VwapAnchor::Session, wednesday), Some(monday + 2 * DAY));
+        assert_eq!(period_start(&VwapAnchor::Week, wednesday), Some(monday));
+        assert_eq!(period_start(&VwapAnchor::Anchored(anchor), wednesday), Some(monday + DAY / 2));
+        assert_eq!(period_start(&VwapAnchor::Anchored(wednesday), anchor), None);
+    }
+}
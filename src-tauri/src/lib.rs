@@ -9,10 +9,19 @@ pub mod commands;
 pub mod i18n;
 pub mod exchange;
 pub mod db;
+pub mod indicators;
+#[cfg(test)]
+mod testing;
 
 use commands::{
-    calculate_risk, get_settings, get_version,
+    calculate_risk, calculate_take_profits, get_risk_snapshot, get_settings, save_settings, get_version,
     list_modules, toggle_module, health_check,
+    get_module_config, set_module_config,
+    get_position_plan, set_position_plan, get_stop_moves,
+    get_asset_groups, save_asset_group, delete_asset_group, execute_basket, get_baskets,
+    get_daily_pnl, get_daily_pnl_history, override_trading_lockout,
+    get_correlation_matrix, get_drawdown_status, get_equity_curve, engage_kill_switch, reset_kill_switch, get_trade_stats,
+    get_alarms, save_alarm, set_alarm_active, delete_alarm, compute_indicator, get_volume_profile,
     set_language, get_current_language, get_available_languages,
     // Exchange commands
     connect_exchange, disconnect_exchange, get_connection_status,
@@ -36,16 +45,59 @@ pub fn run() {
         tracing::error!("Database initialization failed: {}", e);
     }
 
+    // Register modules, restore their config and activation state, start event loops
+    tauri::async_runtime::block_on(commands::init_modules());
+
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
+        .plugin(tauri_plugin_notification::init())
+        .setup(|app| {
+            // Alarm ve risk bildirimleri arayüze ve masaüstüne bu tutamaçla gider
+            commands::set_app_handle(app.handle().clone());
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             // Core commands
             calculate_risk,
+            calculate_take_profits,
+            get_risk_snapshot,
             get_settings,
+            save_settings,
             get_version,
             list_modules,
             toggle_module,
             health_check,
+            // Module config commands
+            get_module_config,
+            set_module_config,
+            // Position plan commands
+            get_position_plan,
+            set_position_plan,
+            get_stop_moves,
+            // Batch trading commands
+            get_asset_groups,
+            save_asset_group,
+            delete_asset_group,
+            execute_basket,
+            get_baskets,
+            // Daily PnL commands
+            get_daily_pnl,
+            get_daily_pnl_history,
+            override_trading_lockout,
+            get_correlation_matrix,
+            get_drawdown_status,
+            get_equity_curve,
+            engage_kill_switch,
+            reset_kill_switch,
+            get_trade_stats,
+            // Alarm commands
+            get_alarms,
+            save_alarm,
+            set_alarm_active,
+            delete_alarm,
+            // Indicator commands
+            compute_indicator,
+            get_volume_profile,
             // i18n commands
             set_language,
             get_current_language,
@@ -2,7 +2,9 @@
 // Tüm veri yapıları burada tanımlanır
 
 use serde::{Deserialize, Serialize};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use chrono_tz::Tz;
+use std::collections::HashMap;
 
 /// Standart fiyat tick verisi - tüm borsalardan gelen veri bu formata dönüştürülür
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -82,6 +84,87 @@ pub struct UserSettings {
     pub default_risk_percent: f64,
     pub max_daily_loss: f64,
     pub theme: Theme,
+    /// Her emirde uygulanan risk sınırları
+    #[serde(default)]
+    pub risk_limits: RiskLimits,
+    /// İşlem gününün sıfırlandığı IANA saat dilimi (örn: "Europe/Istanbul"; yaz saatini izler)
+    #[serde(default = "default_trading_day_timezone")]
+    pub trading_day_timezone: Tz,
+    /// Günlük kayıp kilidi geçersiz kılınmadan önce beklenecek süre (dakika)
+    #[serde(default = "default_lockout_cooldown")]
+    pub lockout_override_cooldown_minutes: u32,
+    /// Tepeden düşüş eşikleri
+    #[serde(default)]
+    pub drawdown_limits: DrawdownLimits,
+    /// Emir ya da modül model seçmediğinde kullanılan boyutlandırma modeli
+    #[serde(default)]
+    pub default_sizing: SizingModel,
+    /// Modüle özel boyutlandırma modeli (module_id -> model)
+    #[serde(default)]
+    pub module_sizing: HashMap<String, SizingModel>,
+    /// Bildirim kanalları
+    #[serde(default)]
+    pub notifications: NotificationSettings,
+}
+
+impl UserSettings {
+    /// Modülün boyutlandırma modeli (modüle özel yoksa varsayılan)
+    pub fn sizing_model(&self, module_id: Option<&str>) -> SizingModel {
+        module_id
+            .and_then(|id| self.module_sizing.get(id))
+            .unwrap_or(&self.default_sizing)
+            .clone()
+    }
+}
+
+fn default_lockout_cooldown() -> u32 {
+    60
+}
+
+fn default_trading_day_timezone() -> Tz {
+    Tz::UTC
+}
+
+/// Emir öncesi risk kapısının sınırları (yüzdeler hesap varlığına göre)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct RiskLimits {
+    /// Tek işlemin en yüksek riski (%)
+    pub max_trade_risk_pct: f64,
+    /// Açık pozisyonlar + yeni işlemin toplam riski (%)
+    pub max_open_risk_pct: f64,
+    /// Toplam pozisyon büyüklüğü / hesap varlığı
+    pub max_leverage: f64,
+    pub max_positions: u32,
+    /// Tek sembolün pozisyon büyüklüğü (%)
+    pub max_symbol_exposure_pct: f64,
+    /// Korelasyonla birleştirilmiş açık risk (%)
+    pub max_correlated_risk_pct: f64,
+    /// Portföyün 1 günlük %95 VaR'ı (%)
+    pub max_var_pct: f64,
+    /// Korelasyon için kullanılan mum aralığı
+    pub correlation_interval: String,
+    /// Korelasyon penceresindeki getiri sayısı
+    pub correlation_lookback: usize,
+    /// Korelasyon sınırını aşan emir reddedilmek yerine küçültülsün mü?
+    pub scale_correlated_orders: bool,
+}
+
+impl Default for RiskLimits {
+    fn default() -> Self {
+        Self {
+            max_trade_risk_pct: 2.0,
+            max_open_risk_pct: 6.0,
+            max_leverage: 10.0,
+            max_positions: 10,
+            max_symbol_exposure_pct: 300.0,
+            max_correlated_risk_pct: 4.0,
+            max_var_pct: 5.0,
+            correlation_interval: "60".to_string(),
+            correlation_lookback: 168,
+            scale_correlated_orders: true,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -96,31 +179,388 @@ impl Default for UserSettings {
             default_risk_percent: 1.0,
             max_daily_loss: 5.0,
             theme: Theme::Dark,
+            risk_limits: RiskLimits::default(),
+            trading_day_timezone: default_trading_day_timezone(),
+            lockout_override_cooldown_minutes: default_lockout_cooldown(),
+            drawdown_limits: DrawdownLimits::default(),
+            default_sizing: SizingModel::default(),
+            module_sizing: HashMap::new(),
+            notifications: NotificationSettings::default(),
+        }
+    }
+}
+
+/// Pozisyon boyutlandırma modeli
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "model", rename_all = "snake_case")]
+pub enum SizingModel {
+    /// Bakiyenin sabit yüzdesi stopta riske edilir
+    #[default]
+    FixedFractional,
+    /// Boyut stop mesafesi yerine ATR ile belirlenir: `multiple` ATR'lik hareket risk yüzdesine eşit olur
+    VolatilityTarget { interval: String, period: usize, multiple: f64 },
+    /// İşlem geçmişindeki kazanma oranı ve ödeme oranından Kelly oranının kesri
+    Kelly { fraction: f64 },
+    /// Sabit pozisyon değeri (hesap para biriminde)
+    FixedNotional { notional: f64 },
+    /// Sabit miktar (adet / kontrat)
+    FixedContracts { contracts: f64 },
+    /// Art arda kayıplarda risk yüzdesi her kayıp için `reduction_pct` küçülür, en az `min_factor`
+    EquityCurve { reduction_pct: f64, min_factor: f64 },
+}
+
+impl SizingModel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SizingModel::FixedFractional => "fixed_fractional",
+            SizingModel::VolatilityTarget { .. } => "volatility_target",
+            SizingModel::Kelly { .. } => "kelly",
+            SizingModel::FixedNotional { .. } => "fixed_notional",
+            SizingModel::FixedContracts { .. } => "fixed_contracts",
+            SizingModel::EquityCurve { .. } => "equity_curve",
+        }
+    }
+
+    /// Model parametrelerini doğrula
+    pub fn validate(&self) -> Result<(), String> {
+        let valid = match self {
+            SizingModel::FixedFractional => true,
+            SizingModel::VolatilityTarget { interval, period, multiple } => {
+                !interval.is_empty() && *period > 0 && *multiple > 0.0
+            }
+            SizingModel::Kelly { fraction } => *fraction > 0.0 && *fraction <= 1.0,
+            SizingModel::FixedNotional { notional } => *notional > 0.0,
+            SizingModel::FixedContracts { contracts } => *contracts > 0.0,
+            SizingModel::EquityCurve { reduction_pct, min_factor } => {
+                *reduction_pct > 0.0 && *min_factor > 0.0 && *min_factor <= 1.0
+            }
+        };
+        if valid {
+            Ok(())
+        } else {
+            Err(format!("Geçersiz boyutlandırma parametreleri: {}", self.as_str()))
+        }
+    }
+}
+
+/// Kapanan işlemlerden boyutlandırma istatistikleri
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct TradeStats {
+    pub trades: u32,
+    /// Kazanan işlem oranı (0-1)
+    pub win_rate: f64,
+    pub avg_win: f64,
+    /// Ortalama kayıp (pozitif)
+    pub avg_loss: f64,
+    /// En son işlemden geriye art arda kayıp sayısı
+    pub losing_streak: u32,
+}
+
+impl TradeStats {
+    /// Ortalama kazanç / ortalama kayıp
+    pub fn payoff_ratio(&self) -> f64 {
+        if self.avg_loss > 0.0 { self.avg_win / self.avg_loss } else { 0.0 }
+    }
+}
+
+/// Boyut hesabının bir adımı (etiket i18n anahtarı, UI'a çevrilmiş hali gider)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SizingStep {
+    pub label: String,
+    pub value: f64,
+}
+
+/// Hesap varlığının tepeden düşüş eşikleri (%) - sırasıyla uyarı, yarım boyut, acil durdurma
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct DrawdownLimits {
+    pub warn_pct: f64,
+    pub reduce_size_pct: f64,
+    pub kill_switch_pct: f64,
+}
+
+impl Default for DrawdownLimits {
+    fn default() -> Self {
+        Self {
+            warn_pct: 5.0,
+            reduce_size_pct: 10.0,
+            kill_switch_pct: 15.0,
         }
     }
 }
 
+/// Modül aktivasyon durumu (yeniden başlatmalar arasında saklanır)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ModuleActivation {
+    Inactive,
+    Active,
+    /// İşlem modülü yeniden başlatma sonrası kullanıcı onayı ve pozisyon mutabakatı bekliyor
+    NeedsConfirmation,
+}
+
+impl ModuleActivation {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ModuleActivation::Inactive => "inactive",
+            ModuleActivation::Active => "active",
+            ModuleActivation::NeedsConfirmation => "needs_confirmation",
+        }
+    }
+
+    pub fn from_code(s: &str) -> Self {
+        match s {
+            "active" => ModuleActivation::Active,
+            "needs_confirmation" => ModuleActivation::NeedsConfirmation,
+            _ => ModuleActivation::Inactive,
+        }
+    }
+}
+
+/// VWAP birikiminin sıfırlandığı an
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum VwapAnchor {
+    /// UTC günü
+    Session,
+    /// Pazartesi 00:00 UTC
+    Week,
+    Month,
+    /// Belirli bir andan itibaren (sıfırlanmaz)
+    Anchored(DateTime<Utc>),
+}
+
+/// `compute_indicator` ile hesaplanan gösterge
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "indicator", rename_all = "snake_case")]
+pub enum IndicatorSpec {
+    /// `band_multiplier` 0 ise bantlar VWAP'a eşit olur
+    Vwap {
+        anchor: VwapAnchor,
+        #[serde(default)]
+        band_multiplier: f64,
+    },
+    Sma { period: usize },
+    Ema { period: usize },
+    Rsi { period: usize },
+    Atr { period: usize },
+    Bollinger { period: usize, multiplier: f64 },
+    Macd { fast: usize, slow: usize, signal: usize },
+}
+
+/// Göstergenin bir mumdaki değeri
+///
+/// Bantlı göstergelerde `value` orta çizgidir; MACD'de `value` MACD çizgisidir.
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+pub struct IndicatorPoint {
+    /// Mumun açılış zamanı (ms)
+    pub timestamp: i64,
+    pub value: f64,
+    pub upper: Option<f64>,
+    pub lower: Option<f64>,
+    pub signal: Option<f64>,
+    pub histogram: Option<f64>,
+}
+
+/// Hacim profilinin kapsadığı zaman aralığı
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "range", rename_all = "snake_case")]
+pub enum ProfileRange {
+    /// İçinde bulunulan UTC günü
+    Session,
+    /// Sabit aralık (`end` yoksa şu ana kadar)
+    Fixed {
+        start: DateTime<Utc>,
+        #[serde(default)]
+        end: Option<DateTime<Utc>>,
+    },
+    /// Grafikte görünen aralık
+    Visible { start: DateTime<Utc>, end: DateTime<Utc> },
+}
+
+/// `get_volume_profile` isteği
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct VolumeProfileSpec {
+    #[serde(flatten)]
+    pub range: ProfileRange,
+    /// Değer alanına giren hacim yüzdesi
+    #[serde(default = "default_value_area_pct")]
+    pub value_area_pct: f64,
+    /// Fiyat kovası boyutu (yoksa aralık 100 kovaya bölünür)
+    #[serde(default)]
+    pub bucket_size: Option<f64>,
+}
+
+fn default_value_area_pct() -> f64 {
+    70.0
+}
+
+impl Default for VolumeProfileSpec {
+    fn default() -> Self {
+        Self { range: ProfileRange::Session, value_area_pct: default_value_area_pct(), bucket_size: None }
+    }
+}
+
+/// Hacim profili
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct VolumeProfile {
+    /// İlk ve son verinin zamanı (ms)
+    pub start: i64,
+    pub end: i64,
+    pub bucket_size: f64,
+    pub value_area_pct: f64,
+    pub total_volume: f64,
+    /// En yüksek hacimli kovanın orta fiyatı
+    pub poc: f64,
+    /// Değer alanının üst ve alt sınırı
+    pub vah: f64,
+    pub val: f64,
+    /// Artan fiyat sıralı kovalar
+    pub buckets: Vec<ProfileBucket>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ProfileBucket {
+    /// Kovanın alt sınırı
+    pub price: f64,
+    pub volume: f64,
+}
+
 /// Alarm tanımı
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Alarm {
     pub id: String,
     pub symbol: String,
     pub condition: AlarmCondition,
+    /// PriceAbove / PriceBelow seviyesi
     pub target_price: f64,
     pub is_active: bool,
     pub notification_type: NotificationType,
+    #[serde(default)]
+    pub mode: AlarmMode,
+    #[serde(default = "Utc::now")]
+    pub created_at: DateTime<Utc>,
+    #[serde(default)]
+    pub last_triggered_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub trigger_count: u32,
 }
 
+/// Fiyatın seviyeyi kestiği yön (seviye karşılaştırması değil, geçiş aranır)
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum AlarmCondition {
+    /// Aşağıdan yukarı kesiş
     PriceAbove,
+    /// Yukarıdan aşağı kesiş
     PriceBelow,
+    /// Seans VWAP'ının iki yönde kesişi
     CrossVwap,
+    /// Seans hacim profilinin değer alanı alt/üst sınırının iki yönde kesişi
     CrossVal,
     CrossVah,
+    /// Mum kapanışında değerlendirilen çok koşullu kural
+    Rule(AlarmRule),
+    /// Grafik çiziminin (trend çizgisi, kanal, fib) tick zamanındaki izdüşümüne göre
+    Drawing(DrawingAlert),
+}
+
+/// Kayıtlı bir çizime bağlı alarm
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DrawingAlert {
+    pub drawing_id: String,
+    #[serde(flatten)]
+    pub trigger: DrawingTrigger,
 }
 
+/// Çizim alarmının tetiklenme şekli
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "trigger", rename_all = "snake_case")]
+pub enum DrawingTrigger {
+    /// Çizgiyi (kanalda sınırlardan birini) iki yönde kesiş - trendline, ray, horizontal, parallel_channel
+    Cross,
+    /// Kanalın içinden dışına çıkış - parallel_channel
+    ExitChannel,
+    /// Fib seviyesine dokunma (örn: 0.618) - fib_retracement
+    FibLevel { level: f64 },
+}
+
+/// Seçilen aralıkta her mum kapanışında değerlendirilen kural
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AlarmRule {
+    /// Mum aralığı (Bybit interval kodu)
+    pub interval: String,
+    pub combinator: RuleCombinator,
+    pub conditions: Vec<RuleCondition>,
+    /// Kuralın art arda kaç kapanışta sağlanması gerektiği
+    #[serde(default = "default_confirmations")]
+    pub confirmations: u32,
+}
+
+fn default_confirmations() -> u32 {
+    1
+}
+
+/// Koşulların birleştirilmesi
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleCombinator {
+    /// Tüm koşullar (AND)
+    All,
+    /// Herhangi bir koşul (OR)
+    Any,
+}
+
+/// `left` ile `right` kapanan mumda karşılaştırılır (örn: kapanış VWAP'ın altında)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RuleCondition {
+    pub left: IndicatorSource,
+    pub comparison: Comparison,
+    pub right: IndicatorSource,
+}
+
+/// Kuralda kullanılan değer kaynağı
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "source", rename_all = "snake_case")]
+pub enum IndicatorSource {
+    /// Mumun kapanış fiyatı
+    Close,
+    /// Sabit değer (örn: RSI 70)
+    Value { value: f64 },
+    /// UTC seansı VWAP'ı
+    Vwap,
+    AnchoredVwap { anchor: DateTime<Utc> },
+    Sma { period: usize },
+    Rsi { period: usize },
+    /// Son mumun UTC seansının hacim profili (%70 değer alanı)
+    Poc,
+    Vah,
+    Val,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Comparison {
+    Above,
+    Below,
+    /// Önceki mumda altında/eşit, bu mumda üstünde
+    CrossesAbove,
+    CrossesBelow,
+}
+
+/// Alarm tetiklendikten sonra ne olacağı
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum AlarmMode {
+    /// Bir kez tetiklenip pasifleşir
+    #[default]
+    OneShot,
+    /// Aktif kalır, fiyat seviyeyi her yeniden kestiğinde tetiklenir
+    Rearm,
+    /// Aktif kalır, tetiklendikten sonra `seconds` boyunca kesişleri yok sayar
+    Cooldown { seconds: u64 },
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum NotificationType {
     Visual,
     Sound,
@@ -128,13 +568,623 @@ pub enum NotificationType {
     Webhook,
 }
 
+/// Bildirim kanallarının ayarları
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct NotificationSettings {
+    /// Risk uyarılarının gönderildiği kanallar
+    pub risk_warning_channels: Vec<NotificationType>,
+    /// Kanal başına dakikada en fazla bildirim
+    pub max_per_minute: u32,
+    /// Webhook kanalının hedefleri
+    pub webhooks: Vec<WebhookTarget>,
+}
+
+impl Default for NotificationSettings {
+    fn default() -> Self {
+        Self {
+            risk_warning_channels: vec![NotificationType::Visual],
+            max_per_minute: 10,
+            webhooks: Vec::new(),
+        }
+    }
+}
+
+/// Bildirimlerin POST edildiği adres
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WebhookTarget {
+    pub id: String,
+    /// Telegram için https://api.telegram.org/bot<token>/sendMessage
+    pub url: String,
+    pub format: WebhookFormat,
+    pub enabled: bool,
+}
+
+/// Webhook gövdesinin şablonu
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "format", rename_all = "snake_case")]
+pub enum WebhookFormat {
+    /// Bildirimin kendisi (JSON)
+    Generic,
+    Telegram { chat_id: String },
+    Discord,
+}
+
+/// Bildirimin kaynağı
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationKind {
+    Alarm,
+    RiskWarning,
+}
+
+/// Kanallara iletilen bildirim (arayüze "notification" olayıyla gider)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Notification {
+    pub kind: NotificationKind,
+    /// Alarm bildirimlerinde alarm id
+    pub source_id: Option<String>,
+    pub symbol: Option<String>,
+    pub title: String,
+    pub message: String,
+    /// Arayüz sesli uyarı çalsın mı?
+    pub sound: bool,
+    pub created_at: DateTime<Utc>,
+}
+
 /// Risk hesaplama sonucu
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RiskCalculation {
     pub position_size: f64,
     pub risk_amount: f64,
     pub risk_percent: f64,
+    /// Stopta fiyattan kaynaklanan kayıp (komisyon hariç)
     pub potential_loss: f64,
     pub potential_profit: f64,
     pub risk_reward_ratio: f64,
+    pub leverage: f64,
+    /// Pozisyon büyüklüğü (giriş fiyatı x miktar)
+    pub position_value: f64,
+    /// Başlangıç marjı
+    pub required_margin: f64,
+    /// Pozisyon büyüklüğünün düştüğü kademenin bakım marjı oranı
+    pub maintenance_margin_rate: f64,
+    /// Tahmini tasfiye fiyatı (izole marj)
+    pub liquidation_price: Option<f64>,
+    /// Giriş + stopta çıkış komisyonu
+    pub estimated_fees: f64,
+    /// Beklenen fonlama ödemesi (negatif = alınan)
+    pub estimated_funding: f64,
+    /// Komisyon ve ödenen fonlama dahil stopta toplam kayıp
+    pub fee_adjusted_risk: f64,
+    /// Komisyon ve fonlama sonrası net R:R
+    pub fee_adjusted_risk_reward: f64,
+    /// Tasfiye fiyatı stoptan önce geliyor (pozisyon stopa ulaşmadan tasfiye olur)
+    pub liquidation_before_stop: bool,
+    /// Spot: boyut kullanılabilir quote bakiyesiyle sınırlandı (risk tutarının altında kalır)
+    pub balance_limited: bool,
+    /// Kullanılan boyutlandırma modeli
+    pub sizing_model: String,
+    /// Boyutun nasıl hesaplandığı
+    pub derivation: Vec<SizingStep>,
+}
+
+/// Bybit risk limiti kademesi - pozisyon büyüdükçe bakım marjı oranı artar
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MarginTier {
+    /// Kademenin kapsadığı en büyük pozisyon değeri
+    pub risk_limit_value: f64,
+    pub maintenance_margin_rate: f64,
+    /// Kademeli hesaplamada düşülen bakım marjı tutarı
+    #[serde(default)]
+    pub mm_deduction: f64,
+    pub max_leverage: f64,
+}
+
+/// Pozisyon boyutu hesabına katılan kaldıraç ve işlem maliyetleri
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TradeCosts {
+    pub leverage: f64,
+    /// Giriş komisyon oranı (örn: 0.00055 = %0.055)
+    pub entry_fee_rate: f64,
+    /// Çıkış komisyon oranı (stop piyasa emriyle çıkar)
+    pub exit_fee_rate: f64,
+    /// Fonlama aralığı başına oran (pozitif: long öder)
+    pub funding_rate: f64,
+    /// Pozisyonun tutulması beklenen fonlama aralığı sayısı
+    pub funding_intervals: u32,
+    /// Sembolün risk limiti kademeleri (boşsa varsayılan bakım marjı oranı)
+    pub margin_tiers: Vec<MarginTier>,
+}
+
+impl Default for TradeCosts {
+    fn default() -> Self {
+        Self {
+            leverage: 1.0,
+            entry_fee_rate: 0.0,
+            exit_fee_rate: 0.0,
+            funding_rate: 0.0,
+            funding_intervals: 0,
+            margin_tiers: Vec::new(),
+        }
+    }
+}
+
+/// Pozisyona bağlı yönetim planı (stop-loss modülü uygular)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PositionPlan {
+    /// İz süren stop (None = sadece sabit stop / breakeven)
+    #[serde(default)]
+    pub trailing: Option<TrailingStop>,
+    /// Kademeli kâr al seviyeleri (kalan miktar runner olarak stopa / iz süren stopa kalır)
+    #[serde(default)]
+    pub take_profits: Vec<TakeProfitLevel>,
+    /// Kâr al dolumundan sonra stopun taşınacağı seviye
+    #[serde(default)]
+    pub rebalance: StopRebalance,
+    /// Süre dolduğunda yeterli kâra ulaşmamış pozisyonu kapat
+    #[serde(default)]
+    pub time_stop: Option<TimeStop>,
+    /// Volatilite giriş anına göre patladığında pozisyonu kapat
+    #[serde(default)]
+    pub volatility_stop: Option<VolatilityStop>,
+    /// Seans sonunda pozisyonu kapat
+    #[serde(default)]
+    pub session_end: Option<SessionEnd>,
+}
+
+/// Zaman stopu
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TimeStop {
+    pub limit: TimeLimit,
+    /// Süre sonunda ulaşılmış olması gereken en iyi kâr (R cinsinden)
+    #[serde(default)]
+    pub min_r: f64,
+}
+
+/// Zaman stopunun süresi (girişten itibaren)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TimeLimit {
+    Minutes { minutes: u32 },
+    /// Girişten sonra kapanan mum sayısı
+    Candles { interval: String, count: u32 },
+}
+
+/// Volatilite stopu
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct VolatilityStop {
+    pub interval: String,
+    /// ATR periyodu
+    pub period: usize,
+    /// Giriş anındaki ATR'nin katı
+    pub multiplier: f64,
+    #[serde(default)]
+    pub measure: VolatilityMeasure,
+}
+
+/// Giriş ATR'si ile karşılaştırılan ölçü
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum VolatilityMeasure {
+    /// Kapanmış mumların güncel ATR'si
+    #[default]
+    Atr,
+    /// Açık mumun gerçek aralığı (ani hareketler için)
+    Range,
+}
+
+/// Seans sonu kapanışı
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SessionEnd {
+    /// Seansın kapanış saati (UTC)
+    pub close_at: NaiveTime,
+}
+
+/// R katına göre kâr al kademesi
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TakeProfitLevel {
+    /// Giriş/stop mesafesinin katı (örn: 2.0 = 2R)
+    pub r_multiple: f64,
+    /// Pozisyonun kapatılacak yüzdesi (ilk miktara göre)
+    pub percent: f64,
+}
+
+/// Hesaplanmış kâr al emri
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TakeProfitTarget {
+    pub r_multiple: f64,
+    pub price: f64,
+    pub quantity: f64,
+}
+
+/// Kâr al dolumundan sonra stop davranışı
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum StopRebalance {
+    /// Stop değişmez
+    None,
+    /// Stop girişe (breakeven) çekilir
+    Breakeven,
+    /// Stop bir önceki kâr al seviyesine çekilir (ilk kademede girişe)
+    #[default]
+    PreviousTarget,
+}
+
+/// İz süren stop ayarı
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrailingStop {
+    pub mode: TrailingMode,
+    /// İz sürmenin başlaması için gereken kâr (R cinsinden, 0 = hemen)
+    #[serde(default)]
+    pub activate_at_r: f64,
+}
+
+/// İz süren stop yöntemi
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TrailingMode {
+    /// En iyi fiyattan sabit yüzde
+    FixedPercent { percent: f64 },
+    /// En iyi fiyattan sabit fiyat mesafesi
+    FixedDistance { distance: f64 },
+    /// En iyi fiyattan ATR katı
+    Atr { interval: String, period: usize, multiplier: f64 },
+    /// Chandelier exit: periyodun en yüksek/düşük seviyesinden ATR katı
+    Chandelier { interval: String, period: usize, multiplier: f64 },
+    /// Son N mumun dip (long) / tepe (short) seviyesi
+    Swing { interval: String, lookback: usize },
+}
+
+impl TrailingMode {
+    /// Mum verisi gerektiren yöntemin aralığı
+    pub fn interval(&self) -> Option<&str> {
+        match self {
+            TrailingMode::Atr { interval, .. }
+            | TrailingMode::Chandelier { interval, .. }
+            | TrailingMode::Swing { interval, .. } => Some(interval),
+            _ => None,
+        }
+    }
+}
+
+/// Stop hareket nedeni
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum StopMoveReason {
+    Breakeven,
+    TrailingPercent,
+    TrailingDistance,
+    TrailingAtr,
+    Chandelier,
+    Swing,
+    TakeProfit,
+}
+
+impl StopMoveReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StopMoveReason::Breakeven => "breakeven",
+            StopMoveReason::TrailingPercent => "trailing_percent",
+            StopMoveReason::TrailingDistance => "trailing_distance",
+            StopMoveReason::TrailingAtr => "trailing_atr",
+            StopMoveReason::Chandelier => "chandelier",
+            StopMoveReason::Swing => "swing",
+            StopMoveReason::TakeProfit => "take_profit",
+        }
+    }
+
+    pub fn from_code(s: &str) -> Option<Self> {
+        match s {
+            "breakeven" => Some(StopMoveReason::Breakeven),
+            "trailing_percent" => Some(StopMoveReason::TrailingPercent),
+            "trailing_distance" => Some(StopMoveReason::TrailingDistance),
+            "trailing_atr" => Some(StopMoveReason::TrailingAtr),
+            "chandelier" => Some(StopMoveReason::Chandelier),
+            "swing" => Some(StopMoveReason::Swing),
+            "take_profit" => Some(StopMoveReason::TakeProfit),
+            _ => None,
+        }
+    }
+}
+
+/// Kayıtlı stop hareketi
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StopMove {
+    pub position_id: String,
+    pub symbol: String,
+    pub from: Option<f64>,
+    pub to: f64,
+    pub reason: StopMoveReason,
+    /// Hareket anındaki piyasa fiyatı
+    pub price: f64,
+    pub moved_at: DateTime<Utc>,
+}
+
+/// Varlık grubu (sepet) - toplu işlemde birlikte alınıp satılan pariteler
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetGroup {
+    pub id: String,
+    pub name: String,
+    pub symbols: Vec<String>,
+    #[serde(default)]
+    pub allocation: AllocationMethod,
+    /// Bir bacak stop olursa diğer bacakları da kapat
+    #[serde(default)]
+    pub linked: bool,
+    #[serde(default)]
+    pub created_at: i64,
+}
+
+/// Toplam risk bütçesinin paritelere dağıtımı
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AllocationMethod {
+    /// Eşit risk
+    #[default]
+    Equal,
+    /// 24 saatlik işlem hacmiyle orantılı
+    VolumeWeighted,
+    /// 24 saatlik aralıkla (volatilite) ters orantılı
+    VolatilityWeighted,
+}
+
+impl AllocationMethod {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AllocationMethod::Equal => "equal",
+            AllocationMethod::VolumeWeighted => "volume_weighted",
+            AllocationMethod::VolatilityWeighted => "volatility_weighted",
+        }
+    }
+
+    pub fn from_code(s: &str) -> Self {
+        match s {
+            "volume_weighted" => AllocationMethod::VolumeWeighted,
+            "volatility_weighted" => AllocationMethod::VolatilityWeighted,
+            _ => AllocationMethod::Equal,
+        }
+    }
+}
+
+/// Tek birim olarak izlenen toplu işlem
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Basket {
+    pub id: String,
+    pub group_id: String,
+    pub name: String,
+    pub side: PositionSide,
+    pub linked: bool,
+    pub status: BasketStatus,
+    pub legs: Vec<BasketLeg>,
+    pub opened_at: DateTime<Utc>,
+}
+
+/// Sepet durumu
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BasketStatus {
+    Open,
+    /// Bağlı modda bir bacak kapandı, kalanlar kapatılıyor
+    Closing,
+    Closed,
+}
+
+/// Sepetin bir paritesi
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BasketLeg {
+    pub symbol: String,
+    /// Risk bütçesindeki pay (0-1)
+    pub weight: f64,
+    pub risk_amount: f64,
+    pub quantity: f64,
+    /// Emir anındaki fiyat (pozisyon açılınca ortalama giriş)
+    pub entry_price: f64,
+    pub stop_loss: f64,
+    pub status: LegStatus,
+    #[serde(default)]
+    pub order_id: Option<String>,
+    #[serde(default)]
+    pub position_id: Option<String>,
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+/// Sepet bacağı durumu
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LegStatus {
+    /// Emir gönderildi, pozisyon bekleniyor
+    Sent,
+    Open,
+    /// Kapatma emri gönderildi
+    Closing,
+    Closed,
+    /// Emir reddedildi
+    Failed,
+}
+
+/// Pozisyonun açık riski (stop tetiklenirse güncel fiyattan kaybedilecek tutar)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PositionRisk {
+    pub position_id: String,
+    pub symbol: String,
+    pub side: PositionSide,
+    pub quantity: f64,
+    pub entry_price: f64,
+    /// Son fiyat (canlı fiyat yoksa giriş)
+    pub price: f64,
+    pub stop_loss: Option<f64>,
+    pub notional: f64,
+    pub open_risk: f64,
+    /// Hesap varlığına oranı (%)
+    pub risk_percent: f64,
+}
+
+/// Baz varlık başına pozisyon büyüklüğü (kote para birimi)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetExposure {
+    pub asset: String,
+    pub long: f64,
+    pub short: f64,
+    /// long - short
+    pub net: f64,
+}
+
+/// Yön başına toplam pozisyon büyüklüğü
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SideExposure {
+    pub long: f64,
+    pub short: f64,
+    pub net: f64,
+    pub gross: f64,
+}
+
+/// Risk uyarısının türü
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum RiskWarningKind {
+    /// Toplam açık risk limite yaklaştı
+    AccountRisk,
+    /// Marjin kullanımı limite yaklaştı
+    MarginUtilization,
+    /// Hesap varlığı tepeden düşüş eşiğine ulaştı
+    Drawdown,
+}
+
+/// Limite yaklaşıldığında yayınlanan risk uyarısı
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RiskWarning {
+    pub kind: RiskWarningKind,
+    /// i18n anahtarı (warning.risk_approaching)
+    pub message_key: String,
+    /// Güncel değer (%)
+    pub value: f64,
+    /// Limit (%)
+    pub limit: f64,
+    pub raised_at: DateTime<Utc>,
+}
+
+/// Hesabın anlık risk görünümü (risk izleyici tarafından üretilir)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RiskSnapshot {
+    pub equity: f64,
+    pub available_balance: f64,
+    pub positions: Vec<PositionRisk>,
+    pub total_risk: f64,
+    /// Toplam açık riskin hesap varlığına oranı (%)
+    pub total_risk_percent: f64,
+    pub exposure_by_asset: Vec<AssetExposure>,
+    pub exposure_by_side: SideExposure,
+    /// Pozisyonlar için kilitlenen başlangıç marjini
+    pub initial_margin: f64,
+    pub maintenance_margin: f64,
+    /// Başlangıç marjininin hesap varlığına oranı (%)
+    pub margin_utilization: f64,
+    /// Şu an limite yakın olan değerler
+    pub warnings: Vec<RiskWarning>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Semboller arası getiri korelasyonları (önbellekteki kapanmış mumlardan)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CorrelationMatrix {
+    pub interval: String,
+    pub symbols: Vec<String>,
+    /// symbols ile aynı sırada; veri yetersizse None
+    pub values: Vec<Vec<Option<f64>>>,
+    /// Mum başına getirilerin standart sapması
+    pub volatilities: Vec<Option<f64>>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Borsadaki işlem gerçekleşmesi (dolum veya fonlama)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Execution {
+    pub id: String,
+    /// Dolumun ait olduğu emir (fonlamada boş)
+    #[serde(default)]
+    pub order_id: String,
+    pub symbol: String,
+    pub side: OrderSide,
+    pub price: f64,
+    pub quantity: f64,
+    /// Ödenen komisyon / fonlama (negatif = alınan)
+    pub fee: f64,
+    /// Pozisyon kapatan dolumun kâr/zararı (komisyon hariç)
+    pub realized_pnl: f64,
+    pub executed_at: DateTime<Utc>,
+}
+
+/// İşlem gününün gerçekleşen kâr/zararı
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DailyPnl {
+    pub trading_day: NaiveDate,
+    pub realized_pnl: f64,
+    pub fees: f64,
+    /// realized_pnl - fees
+    pub net_pnl: f64,
+    pub executions: u32,
+}
+
+/// Günlük kayıp limiti aşıldığında yeni girişleri engelleyen kilit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TradingLockout {
+    pub trading_day: NaiveDate,
+    pub locked_at: DateTime<Utc>,
+    /// Sonraki işlem gününün başlangıcı
+    pub until: DateTime<Utc>,
+    /// Kilit anındaki günlük kayıp (hesap varlığının %'si)
+    pub loss_percent: f64,
+    pub limit_percent: f64,
+    /// Kullanıcı bekleme süresinden sonra kilidi kaldırdıysa
+    #[serde(default)]
+    pub overridden_at: Option<DateTime<Utc>>,
+}
+
+/// Hesap varlığı eğrisindeki nokta
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EquityPoint {
+    pub equity: f64,
+    pub recorded_at: DateTime<Utc>,
+}
+
+/// Tepeden düşüşe göre uygulanan kademe
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum DrawdownTier {
+    #[default]
+    Normal,
+    /// Uyarı yayınlandı
+    Warning,
+    /// Pozisyon boyutları yarıya indirildi
+    ReducedSize,
+    /// Acil durdurma devrede
+    Halted,
+}
+
+/// Acil durdurma anahtarı - devredeyken yeni pozisyon açılamaz, elle sıfırlanır
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KillSwitch {
+    pub engaged_at: DateTime<Utc>,
+    /// Devreye girme nedeni ("drawdown" / "manual")
+    pub reason: String,
+    /// Devreye girdiği andaki düşüş (%)
+    pub drawdown_pct: f64,
+}
+
+/// Hesap varlığının tepeden düşüş durumu
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DrawdownStatus {
+    pub equity: f64,
+    pub peak: f64,
+    pub peak_at: Option<DateTime<Utc>>,
+    /// Güncel tepeden düşüş (%)
+    pub drawdown_pct: f64,
+    /// Görülen en büyük tepeden düşüş (%)
+    pub max_drawdown_pct: f64,
+    pub tier: DrawdownTier,
+    pub kill_switch: Option<KillSwitch>,
+    pub updated_at: Option<DateTime<Utc>>,
 }
@@ -0,0 +1,568 @@
+// AlgoTrade OS - Batch Trading Module
+// Toplu işlem (Basket Trading) ve varlık grubu yönetimi modülü
+//
+// Akış (roadmap/MODULE_BATCH_TRADING.md):
+// - Toplam risk bütçesi gruptaki paritelere eşit / hacim / volatilite ağırlıklı dağıtılır
+// - Tüm bacaklar emir gönderilmeden önce doğrulanır, risk kapısı bacakları birlikte onaylar
+//   (her bacak öncekilerin riskine eklenerek denetlenir), onaylanan emirler aynı anda gönderilir
+// - Sepet tek birim olarak izlenir; bağlı modda bir bacak stop olursa diğer bacaklar kapatılır
+// Bacak pozisyonları zamanlayıcıyla borsadan okunur: pozisyonu kaybolan bacak kapanmış sayılır.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use futures::future::join_all;
+use serde::Deserialize;
+use std::sync::Arc;
+use crate::core::{AppState, ScheduleSpec};
+use crate::db;
+use crate::exchange::executor::{LotSize, OrderExecutor, OrderRequest};
+use crate::models::{
+    AllocationMethod, AssetGroup, Basket, BasketLeg, BasketStatus, LegStatus, OrderSide, OrderType,
+    Position, PositionSide, SizingModel, StandardTick,
+};
+use super::traits::{TradingModule, ModuleError};
+
+/// Bacak pozisyonlarını eşleyen zamanlayıcı
+const SYNC_TIMER: &str = "sync";
+const SYNC_INTERVAL_SECS: u64 = 5;
+
+/// Emirden sonra pozisyonun borsada görünmesi için beklenen en uzun süre
+const OPEN_TIMEOUT_SECS: i64 = 60;
+
+/// Toplu işlem isteği
+#[derive(Debug, Clone, Deserialize)]
+pub struct BasketOrder {
+    pub group_id: String,
+    pub side: PositionSide,
+    /// Tüm sepet için toplam risk bütçesi (kote para birimi)
+    pub risk_amount: f64,
+    /// Her bacakta girişten stop mesafesi (%)
+    pub stop_percent: f64,
+    /// Bu sepetin boyutlandırma modeli (verilmezse modülün modeli)
+    #[serde(default)]
+    pub sizing: Option<SizingModel>,
+}
+
+/// Dağıtım ve boyutlandırma için parite verisi
+#[derive(Debug, Clone)]
+pub struct LegMarket {
+    pub symbol: String,
+    pub price: f64,
+    /// 24 saatlik işlem hacmi (kote)
+    pub turnover_24h: f64,
+    pub high_24h: f64,
+    pub low_24h: f64,
+    pub lot: LotSize,
+    /// Volatilite hedefli boyutlandırmada sembolün ATR'si
+    pub atr: Option<f64>,
+}
+
+/// Risk bütçesi ağırlıkları (toplamı 1)
+pub fn allocation_weights(method: AllocationMethod, markets: &[LegMarket]) -> Result<Vec<f64>, String> {
+    let raw: Vec<f64> = match method {
+        AllocationMethod::Equal => vec![1.0; markets.len()],
+        AllocationMethod::VolumeWeighted => markets.iter().map(|m| m.turnover_24h).collect(),
+        // Sakin paritelere daha fazla risk (24 saatlik aralığın tersi)
+        AllocationMethod::VolatilityWeighted => markets
+            .iter()
+            .map(|m| {
+                let range = (m.high_24h - m.low_24h) / m.price;
+                if range > 0.0 { 1.0 / range } else { 0.0 }
+            })
+            .collect(),
+    };
+
+    if let Some((market, _)) = markets.iter().zip(&raw).find(|(_, w)| !(**w > 0.0 && w.is_finite())) {
+        return Err(format!("{} için dağıtım verisi yok ({})", market.symbol, method.as_str()));
+    }
+    let total: f64 = raw.iter().sum();
+    if markets.is_empty() || total <= 0.0 {
+        return Err("Grupta parite yok".to_string());
+    }
+    Ok(raw.iter().map(|w| w / total).collect())
+}
+
+/// Bacakları hesapla ve doğrula - bir bacak bile geçersizse hiç emir gönderilmez
+///
+/// Risk bütçesi modeli (sabit oran, Kelly, varlık eğrisi) `order.risk_amount` içinde uygulanmış olmalı;
+/// sabit değer / miktar modelleri `scale` ile küçültülür.
+pub fn plan_legs(
+    group: &AssetGroup,
+    order: &BasketOrder,
+    markets: &[LegMarket],
+    model: &SizingModel,
+    scale: f64,
+) -> Result<Vec<BasketLeg>, String> {
+    if order.risk_amount <= 0.0 {
+        return Err("Risk bütçesi pozitif olmalı".to_string());
+    }
+    if order.stop_percent <= 0.0 || order.stop_percent >= 100.0 {
+        return Err("Stop mesafesi %0 ile %100 arasında olmalı".to_string());
+    }
+
+    let weights = allocation_weights(group.allocation, markets)?;
+    markets
+        .iter()
+        .zip(weights)
+        .map(|(market, weight)| {
+            if market.price <= 0.0 {
+                return Err(format!("{} için fiyat yok", market.symbol));
+            }
+            let distance = market.price * order.stop_percent / 100.0;
+            let risk_amount = order.risk_amount * weight;
+            let raw_quantity = match model {
+                SizingModel::FixedNotional { notional } => notional * scale * weight / market.price,
+                SizingModel::FixedContracts { contracts } => contracts * scale,
+                SizingModel::VolatilityTarget { multiple, .. } => {
+                    let atr = market.atr.filter(|a| *a > 0.0)
+                        .ok_or_else(|| format!("{} için ATR hesaplanamadı", market.symbol))?;
+                    risk_amount / (atr * multiple)
+                }
+                _ => risk_amount / distance,
+            };
+            let quantity = market.lot.round_quantity(raw_quantity);
+            if quantity <= 0.0 || quantity < market.lot.min_qty {
+                return Err(format!(
+                    "{}: miktar en küçük emir miktarının altında ({} < {})",
+                    market.symbol, quantity, market.lot.min_qty
+                ));
+            }
+            let stop_loss = market.lot.round_price(match order.side {
+                PositionSide::Long => market.price - distance,
+                PositionSide::Short => market.price + distance,
+            });
+
+            Ok(BasketLeg {
+                symbol: market.symbol.clone(),
+                weight,
+                risk_amount,
+                quantity,
+                entry_price: market.price,
+                stop_loss,
+                status: LegStatus::Sent,
+                order_id: None,
+                position_id: None,
+                error: None,
+            })
+        })
+        .collect()
+}
+
+/// Bacak emirlerini aynı anda gönder ve sepeti oluştur
+pub async fn execute_basket(
+    executor: &dyn OrderExecutor,
+    group: &AssetGroup,
+    side: PositionSide,
+    mut legs: Vec<BasketLeg>,
+    now: DateTime<Utc>,
+) -> Result<Basket, ModuleError> {
+    let order_side = match side {
+        PositionSide::Long => OrderSide::Buy,
+        PositionSide::Short => OrderSide::Sell,
+    };
+    let requests: Vec<OrderRequest> = legs
+        .iter()
+        .map(|leg| OrderRequest {
+            symbol: leg.symbol.clone(),
+            side: order_side.clone(),
+            order_type: OrderType::Market,
+            quantity: leg.quantity,
+            price: None,
+            trigger_price: None,
+            reduce_only: false,
+            stop_loss: Some(leg.stop_loss),
+            position_id: None,
+        })
+        .collect();
+
+    let results = executor.place_orders(&requests).await;
+    for (leg, result) in legs.iter_mut().zip(results) {
+        match result {
+            Ok(order_id) => leg.order_id = Some(order_id),
+            Err(e) => {
+                tracing::error!("Sepet bacağı reddedildi: {} - {}", leg.symbol, e);
+                leg.status = LegStatus::Failed;
+                leg.error = Some(e.to_string());
+            }
+        }
+    }
+
+    if legs.iter().all(|l| l.status == LegStatus::Failed) {
+        let reason = legs.iter().find_map(|l| l.error.clone()).unwrap_or_default();
+        return Err(ModuleError::ExecutionFailed(format!("Sepet emirleri gönderilemedi: {}", reason)));
+    }
+
+    // Bağlı sepet eksik bacakla tutulmaz
+    let incomplete = legs.iter().any(|l| l.status == LegStatus::Failed);
+    let status = if group.linked && incomplete {
+        tracing::warn!("Bağlı sepet {} eksik açıldı, açılan bacaklar kapatılacak", group.name);
+        BasketStatus::Closing
+    } else {
+        BasketStatus::Open
+    };
+
+    Ok(Basket {
+        id: format!("basket-{}-{:08x}", now.timestamp_millis(), rand::random::<u32>()),
+        group_id: group.id.clone(),
+        name: group.name.clone(),
+        side,
+        linked: group.linked,
+        status,
+        legs,
+        opened_at: now,
+    })
+}
+
+/// Sepeti borsadaki pozisyonlarla eşle
+///
+/// Döner: (sepet değişti mi, kapatılacak bacaklar (bacak indeksi, pozisyon))
+fn reconcile(basket: &mut Basket, positions: &[Position], now: DateTime<Utc>) -> (bool, Vec<(usize, Position)>) {
+    let mut changed = false;
+    let mut leg_lost = false;
+    let timed_out = now - basket.opened_at > Duration::seconds(OPEN_TIMEOUT_SECS);
+
+    for leg in &mut basket.legs {
+        let position = positions
+            .iter()
+            .find(|p| p.symbol == leg.symbol && p.side == basket.side);
+        match (leg.status, position) {
+            (LegStatus::Sent, Some(position)) => {
+                leg.status = LegStatus::Open;
+                leg.position_id = Some(position.id.clone());
+                leg.entry_price = position.entry_price;
+                changed = true;
+            }
+            (LegStatus::Sent, None) if timed_out => {
+                leg.status = LegStatus::Failed;
+                leg.error = Some("Pozisyon açılmadı".to_string());
+                leg_lost = true;
+                changed = true;
+            }
+            (LegStatus::Open, None) => {
+                tracing::info!("Sepet {} bacağı kapandı: {}", basket.name, leg.symbol);
+                leg.status = LegStatus::Closed;
+                leg_lost = true;
+                changed = true;
+            }
+            (LegStatus::Closing, None) => {
+                leg.status = LegStatus::Closed;
+                changed = true;
+            }
+            _ => {}
+        }
+    }
+
+    if basket.linked && leg_lost && basket.status == BasketStatus::Open {
+        tracing::warn!("Bağlı sepet {}: bir bacak kapandı, kalan bacaklar kapatılıyor", basket.name);
+        basket.status = BasketStatus::Closing;
+        changed = true;
+    }
+
+    let to_close = if basket.status == BasketStatus::Closing {
+        basket
+            .legs
+            .iter()
+            .enumerate()
+            .filter(|(_, leg)| leg.status == LegStatus::Open)
+            .filter_map(|(i, leg)| {
+                positions
+                    .iter()
+                    .find(|p| p.symbol == leg.symbol && p.side == basket.side)
+                    .map(|p| (i, p.clone()))
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    let done = basket
+        .legs
+        .iter()
+        .all(|l| matches!(l.status, LegStatus::Closed | LegStatus::Failed));
+    if done && basket.status != BasketStatus::Closed {
+        tracing::info!("Sepet {} kapandı", basket.name);
+        basket.status = BasketStatus::Closed;
+        changed = true;
+    }
+
+    (changed, to_close)
+}
+
+/// Toplu işlem modülü - sepetleri tek birim olarak izler
+pub struct BatchTradingModule {
+    is_active: bool,
+    state: Option<Arc<AppState>>,
+}
+
+impl BatchTradingModule {
+    pub fn new() -> Self {
+        Self {
+            is_active: false,
+            state: None,
+        }
+    }
+
+    /// Takip edilen sepetleri borsadaki pozisyonlarla eşle, gerekirse bacakları kapat
+    async fn sync_baskets(&self, now: DateTime<Utc>) -> Result<(), ModuleError> {
+        let state = self.state.clone().ok_or_else(|| {
+            ModuleError::InitializationFailed("State not initialized".into())
+        })?;
+
+        let tracked: Vec<Basket> = state
+            .baskets
+            .read()
+            .await
+            .values()
+            .filter(|b| b.status != BasketStatus::Closed)
+            .cloned()
+            .collect();
+        if tracked.is_empty() {
+            return Ok(());
+        }
+        let Some(executor) = state.executor().await else {
+            return Ok(()); // Bağlantı yokken eşleme yapılmaz
+        };
+
+        let positions = executor
+            .open_positions()
+            .await
+            .map_err(|e| ModuleError::ExecutionFailed(e.to_string()))?;
+
+        for mut basket in tracked {
+            let (mut changed, to_close) = reconcile(&mut basket, &positions, now);
+
+            if !to_close.is_empty() {
+                let requests: Vec<OrderRequest> = to_close
+                    .iter()
+                    .map(|(_, position)| OrderRequest::close_position(position))
+                    .collect();
+                let results = join_all(requests.iter().map(|r| executor.place_order(r))).await;
+                for ((i, position), result) in to_close.iter().zip(results) {
+                    match result {
+                        Ok(_) => {
+                            basket.legs[*i].status = LegStatus::Closing;
+                            changed = true;
+                        }
+                        Err(e) => tracing::error!("Sepet bacağı kapatılamadı: {} - {}", position.symbol, e),
+                    }
+                }
+            }
+
+            if changed {
+                if let Err(e) = db::save_basket(&basket).await {
+                    tracing::warn!("Sepet kaydedilemedi: {}", e);
+                }
+                state.baskets.write().await.insert(basket.id.clone(), basket);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Default for BatchTradingModule {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl TradingModule for BatchTradingModule {
+    fn id(&self) -> &str {
+        "batch_trading"
+    }
+
+    fn name(&self) -> &str {
+        "Batch Trading"
+    }
+
+    fn description(&self) -> &str {
+        "Toplu işlem ve varlık grubu yönetimi"
+    }
+
+    async fn initialize(&mut self, state: Arc<AppState>) -> Result<(), ModuleError> {
+        self.state = Some(state);
+        tracing::info!("BatchTrading modülü başlatıldı");
+        Ok(())
+    }
+
+    async fn shutdown(&mut self) -> Result<(), ModuleError> {
+        self.state = None;
+        tracing::info!("BatchTrading modülü durduruldu");
+        Ok(())
+    }
+
+    async fn on_price_tick(&mut self, _tick: &StandardTick) -> Result<(), ModuleError> {
+        // Bacaklar tick yerine zamanlayıcıyla eşlenir
+        Ok(())
+    }
+
+    async fn on_balance_change(&mut self, _symbol: &str, _new_balance: f64) -> Result<(), ModuleError> {
+        Ok(())
+    }
+
+    async fn on_position_opened(&mut self, _position: &Position) -> Result<(), ModuleError> {
+        Ok(())
+    }
+
+    async fn on_position_closed(&mut self, _position: &Position, _pnl: f64) -> Result<(), ModuleError> {
+        Ok(())
+    }
+
+    fn schedules(&self) -> Vec<ScheduleSpec> {
+        vec![ScheduleSpec::every(SYNC_TIMER, SYNC_INTERVAL_SECS)]
+    }
+
+    async fn on_timer(&mut self, timer_id: &str, now: DateTime<Utc>) -> Result<(), ModuleError> {
+        if !self.is_active || timer_id != SYNC_TIMER {
+            return Ok(());
+        }
+        self.sync_baskets(now).await
+    }
+
+    fn can_execute_orders(&self) -> bool {
+        true // Bu modül emir gönderebilir
+    }
+
+    fn is_active(&self) -> bool {
+        self.is_active
+    }
+
+    fn set_active(&mut self, active: bool) {
+        self.is_active = active;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::position;
+
+    fn market(symbol: &str, price: f64, turnover: f64, range: f64) -> LegMarket {
+        LegMarket {
+            symbol: symbol.into(),
+            price,
+            turnover_24h: turnover,
+            high_24h: price + range / 2.0,
+            low_24h: price - range / 2.0,
+            lot: LotSize { qty_step: 0.001, min_qty: 0.001, tick_size: 0.01 },
+            atr: Some(range / 4.0),
+        }
+    }
+
+    fn group(allocation: AllocationMethod, linked: bool) -> AssetGroup {
+        AssetGroup {
+            id: "majors".into(),
+            name: "Majors".into(),
+            symbols: vec!["BTCUSDT".into(), "ETHUSDT".into()],
+            allocation,
+            linked,
+            created_at: 0,
+        }
+    }
+
+    #[test]
+    fn test_allocation_and_leg_sizing() {
+        let markets = [market("BTCUSDT", 100.0, 300.0, 4.0), market("ETHUSDT", 50.0, 100.0, 8.0)];
+
+        assert_eq!(allocation_weights(AllocationMethod::Equal, &markets).unwrap(), vec![0.5, 0.5]);
+        assert_eq!(allocation_weights(AllocationMethod::VolumeWeighted, &markets).unwrap(), vec![0.75, 0.25]);
+        // Aralıklar %4 ve %16 -> ağırlıklar 25 : 6.25
+        assert_eq!(allocation_weights(AllocationMethod::VolatilityWeighted, &markets).unwrap(), vec![0.8, 0.2]);
+
+        let order = BasketOrder { group_id: "majors".into(), side: PositionSide::Long, risk_amount: 100.0, stop_percent: 2.0, sizing: None };
+        let fixed = SizingModel::FixedFractional;
+        let legs = plan_legs(&group(AllocationMethod::Equal, false), &order, &markets, &fixed, 1.0).unwrap();
+        // 50 risk / 2 mesafe = 25 adet, 50 risk / 1 mesafe = 50 adet
+        assert_eq!((legs[0].quantity, legs[0].stop_loss), (25.0, 98.0));
+        assert_eq!((legs[1].quantity, legs[1].stop_loss), (50.0, 49.0));
+
+        // Volatilite hedefi: 50 risk / (2 x ATR 1) = 25 adet, 50 risk / (2 x ATR 2) = 12.5 adet
+        let volatility = SizingModel::VolatilityTarget { interval: "60".into(), period: 14, multiple: 2.0 };
+        let legs = plan_legs(&group(AllocationMethod::Equal, false), &order, &markets, &volatility, 1.0).unwrap();
+        assert_eq!((legs[0].quantity, legs[1].quantity), (25.0, 12.5));
+        // Sabit değer yarım boyutta: 1000 x 0.5 x 0.5 / 100 = 2.5 adet
+        let notional = SizingModel::FixedNotional { notional: 1000.0 };
+        let legs = plan_legs(&group(AllocationMethod::Equal, false), &order, &markets, &notional, 0.5).unwrap();
+        assert_eq!((legs[0].quantity, legs[1].quantity), (2.5, 5.0));
+
+        // Hacmi olmayan parite dağıtımı durdurur
+        let thin = [market("BTCUSDT", 100.0, 300.0, 4.0), market("NEWUSDT", 1.0, 0.0, 0.1)];
+        assert!(plan_legs(&group(AllocationMethod::VolumeWeighted, false), &order, &thin, &fixed, 1.0).is_err());
+    }
+
+    #[test]
+    fn test_linked_basket_closes_remaining_legs_when_one_stops_out() {
+        let opened_at = Utc::now();
+        let order = BasketOrder { group_id: "majors".into(), side: PositionSide::Long, risk_amount: 100.0, stop_percent: 2.0, sizing: None };
+        let markets = [market("BTCUSDT", 100.0, 1.0, 4.0), market("ETHUSDT", 50.0, 1.0, 8.0)];
+        let legs = plan_legs(&group(AllocationMethod::Equal, true), &order, &markets, &SizingModel::FixedFractional, 1.0)
+            .unwrap();
+        let mut basket = Basket {
+            id: "b1".into(),
+            group_id: "majors".into(),
+            name: "Majors".into(),
+            side: PositionSide::Long,
+            linked: true,
+            status: BasketStatus::Open,
+            legs,
+            opened_at,
+        };
+
+        // İki bacak da açıldı
+        let (changed, to_close) = reconcile(&mut basket, &[position("BTCUSDT"), position("ETHUSDT")], opened_at);
+        assert!(changed && to_close.is_empty());
+        assert!(basket.legs.iter().all(|l| l.status == LegStatus::Open));
+
+        // BTC stop oldu -> ETH kapatılmalı
+        let (_, to_close) = reconcile(&mut basket, &[position("ETHUSDT")], opened_at);
+        assert_eq!(basket.status, BasketStatus::Closing);
+        assert_eq!(to_close.len(), 1);
+        assert_eq!(to_close[0].1.symbol, "ETHUSDT");
+
+        basket.legs[to_close[0].0].status = LegStatus::Closing;
+        let (_, to_close) = reconcile(&mut basket, &[], opened_at);
+        assert!(to_close.is_empty());
+        assert_eq!(basket.status, BasketStatus::Closed);
+    }
+
+    #[tokio::test]
+    async fn test_basket_legs_share_position_limit() {
+        crate::db::init_test_database();
+        let state = AppState::new();
+        state.settings.write().await.risk_limits.max_positions = 3;
+        let mock = Arc::new(crate::exchange::mock::MockExecutor::with_equity(100_000.0));
+        state.set_executor(Some(mock.clone())).await;
+        let executor = state.executor().await.unwrap();
+
+        // Her bacak tek başına sınırın altında, dördü birlikte pozisyon sayısını aşar
+        let legs: Vec<BasketLeg> = ["BTCUSDT", "ETHUSDT", "SOLUSDT", "XRPUSDT"]
+            .iter()
+            .map(|symbol| BasketLeg {
+                symbol: symbol.to_string(),
+                weight: 0.25,
+                risk_amount: 1.0,
+                quantity: 1.0,
+                entry_price: 100.0,
+                stop_loss: 99.0,
+                status: LegStatus::Sent,
+                order_id: None,
+                position_id: None,
+                error: None,
+            })
+            .collect();
+        let group = group(AllocationMethod::Equal, false);
+        let basket = execute_basket(executor.as_ref(), &group, PositionSide::Long, legs, Utc::now())
+            .await
+            .unwrap();
+
+        let failed: Vec<&str> = basket
+            .legs
+            .iter()
+            .filter(|l| l.status == LegStatus::Failed)
+            .map(|l| l.symbol.as_str())
+            .collect();
+        assert_eq!(failed, vec!["XRPUSDT"]);
+        assert!(basket.legs[3].error.as_ref().unwrap().contains("max_positions"));
+        assert_eq!(mock.placed().len(), 3);
+    }
+}
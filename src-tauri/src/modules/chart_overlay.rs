@@ -0,0 +1,84 @@
+// AlgoTrade OS - Chart Overlay Modules
+// Hesaplaması arayüzde yapılan grafik araçları (SMA, VWAP, Anchored VWAP, Strateji Oluşturucu)
+// Backend tarafında sadece aktiflik durumu ve izinleri taşınır; olay döngüsü açılmaz,
+// arayüzde "UI-only" olarak işaretlenir
+
+use async_trait::async_trait;
+use std::sync::Arc;
+use crate::core::AppState;
+use crate::models::{StandardTick, Position};
+use super::traits::{TradingModule, ModuleError, ModulePermission};
+
+/// Sadece analiz yapan, olaylara tepki vermeyen grafik modülü
+pub struct ChartOverlayModule {
+    id: &'static str,
+    name: &'static str,
+    description: &'static str,
+    is_active: bool,
+}
+
+impl ChartOverlayModule {
+    pub fn new(id: &'static str, name: &'static str, description: &'static str) -> Self {
+        Self {
+            id,
+            name,
+            description,
+            is_active: false,
+        }
+    }
+}
+
+#[async_trait]
+impl TradingModule for ChartOverlayModule {
+    fn id(&self) -> &str {
+        self.id
+    }
+
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn description(&self) -> &str {
+        self.description
+    }
+
+    async fn initialize(&mut self, _state: Arc<AppState>) -> Result<(), ModuleError> {
+        Ok(())
+    }
+
+    async fn shutdown(&mut self) -> Result<(), ModuleError> {
+        Ok(())
+    }
+
+    async fn on_price_tick(&mut self, _tick: &StandardTick) -> Result<(), ModuleError> {
+        Ok(())
+    }
+
+    async fn on_balance_change(&mut self, _symbol: &str, _new_balance: f64) -> Result<(), ModuleError> {
+        Ok(())
+    }
+
+    async fn on_position_opened(&mut self, _position: &Position) -> Result<(), ModuleError> {
+        Ok(())
+    }
+
+    async fn on_position_closed(&mut self, _position: &Position, _pnl: f64) -> Result<(), ModuleError> {
+        Ok(())
+    }
+
+    fn ui_only(&self) -> bool {
+        true
+    }
+
+    fn permissions(&self) -> Vec<ModulePermission> {
+        vec![ModulePermission::ReadOnly, ModulePermission::Analysis]
+    }
+
+    fn is_active(&self) -> bool {
+        self.is_active
+    }
+
+    fn set_active(&mut self, active: bool) {
+        self.is_active = active;
+    }
+}
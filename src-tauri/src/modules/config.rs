@@ -0,0 +1,240 @@
+// AlgoTrade OS - Module Configuration
+// Modül ayar şeması, doğrulama ve varsayılan değerler
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use crate::i18n::t;
+use super::traits::ModuleError;
+
+/// Doğrulanmış modül ayarları (alan anahtarı -> değer)
+pub type ModuleConfig = Map<String, Value>;
+
+/// Ayar alanının tipi ve sınırları
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ConfigFieldType {
+    /// Açık/kapalı anahtar
+    Bool,
+    /// Ondalıklı sayı
+    Number {
+        min: Option<f64>,
+        max: Option<f64>,
+        step: Option<f64>,
+    },
+    /// Tam sayı
+    Integer { min: Option<i64>, max: Option<i64> },
+    /// Serbest metin
+    Text,
+    /// Sabit seçenek listesi
+    Select { options: Vec<String> },
+}
+
+/// Tek bir ayar alanı - UI formu bu tanımdan oluşturulur
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigField {
+    pub key: String,
+    /// Etiket (şemada i18n anahtarı, UI'a çevrilmiş hali gider)
+    pub label: String,
+    /// Açıklama (şemada i18n anahtarı, UI'a çevrilmiş hali gider)
+    pub description: String,
+    pub field_type: ConfigFieldType,
+    pub default: Value,
+}
+
+impl ConfigField {
+    fn new(key: &str, i18n_prefix: &str, field_type: ConfigFieldType, default: Value) -> Self {
+        Self {
+            key: key.to_string(),
+            label: format!("{}.{}.label", i18n_prefix, key),
+            description: format!("{}.{}.description", i18n_prefix, key),
+            field_type,
+            default,
+        }
+    }
+
+    /// Açık/kapalı alanı
+    pub fn bool(key: &str, i18n_prefix: &str, default: bool) -> Self {
+        Self::new(key, i18n_prefix, ConfigFieldType::Bool, Value::Bool(default))
+    }
+
+    /// Sınırlı ondalıklı sayı alanı
+    pub fn number(key: &str, i18n_prefix: &str, default: f64, min: f64, max: f64, step: f64) -> Self {
+        Self::new(
+            key,
+            i18n_prefix,
+            ConfigFieldType::Number {
+                min: Some(min),
+                max: Some(max),
+                step: Some(step),
+            },
+            Value::from(default),
+        )
+    }
+
+    /// Sınırlı tam sayı alanı
+    pub fn integer(key: &str, i18n_prefix: &str, default: i64, min: i64, max: i64) -> Self {
+        Self::new(
+            key,
+            i18n_prefix,
+            ConfigFieldType::Integer {
+                min: Some(min),
+                max: Some(max),
+            },
+            Value::from(default),
+        )
+    }
+
+    /// Serbest metin alanı
+    pub fn text(key: &str, i18n_prefix: &str, default: &str) -> Self {
+        Self::new(key, i18n_prefix, ConfigFieldType::Text, Value::from(default))
+    }
+
+    /// Seçenek listesi alanı
+    pub fn select(key: &str, i18n_prefix: &str, default: &str, options: &[&str]) -> Self {
+        Self::new(
+            key,
+            i18n_prefix,
+            ConfigFieldType::Select {
+                options: options.iter().map(|o| o.to_string()).collect(),
+            },
+            Value::from(default),
+        )
+    }
+
+    /// Etiket ve açıklamayı mevcut dile çevir
+    pub fn localized(&self) -> Self {
+        Self {
+            label: t(&self.label),
+            description: t(&self.description),
+            ..self.clone()
+        }
+    }
+
+    /// Tek bir değeri alan tipine göre doğrula
+    fn check(&self, value: &Value) -> Result<(), String> {
+        match &self.field_type {
+            ConfigFieldType::Bool => {
+                if !value.is_boolean() {
+                    return Err(format!("{}: boolean bekleniyor", self.key));
+                }
+            }
+            ConfigFieldType::Number { min, max, .. } => {
+                let v = value
+                    .as_f64()
+                    .ok_or_else(|| format!("{}: sayı bekleniyor", self.key))?;
+                if min.is_some_and(|m| v < m) || max.is_some_and(|m| v > m) {
+                    return Err(format!(
+                        "{}: {} aralık dışında ({:?} - {:?})",
+                        self.key, v, min, max
+                    ));
+                }
+            }
+            ConfigFieldType::Integer { min, max } => {
+                let v = value
+                    .as_i64()
+                    .ok_or_else(|| format!("{}: tam sayı bekleniyor", self.key))?;
+                if min.is_some_and(|m| v < m) || max.is_some_and(|m| v > m) {
+                    return Err(format!(
+                        "{}: {} aralık dışında ({:?} - {:?})",
+                        self.key, v, min, max
+                    ));
+                }
+            }
+            ConfigFieldType::Text => {
+                if !value.is_string() {
+                    return Err(format!("{}: metin bekleniyor", self.key));
+                }
+            }
+            ConfigFieldType::Select { options } => {
+                let v = value
+                    .as_str()
+                    .ok_or_else(|| format!("{}: metin bekleniyor", self.key))?;
+                if !options.iter().any(|o| o == v) {
+                    return Err(format!("{}: geçersiz seçenek '{}'", self.key, v));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Şemadaki varsayılan değerler
+pub fn defaults(schema: &[ConfigField]) -> ModuleConfig {
+    schema
+        .iter()
+        .map(|f| (f.key.clone(), f.default.clone()))
+        .collect()
+}
+
+/// Ayarları şemaya göre doğrula - eksik alanlar varsayılanla doldurulur,
+/// bilinmeyen alanlar ve aralık dışı değerler reddedilir
+pub fn validate(schema: &[ConfigField], input: &Value) -> Result<ModuleConfig, ModuleError> {
+    let input = input
+        .as_object()
+        .ok_or_else(|| ModuleError::InvalidConfig("Ayarlar bir nesne olmalı".into()))?;
+
+    if let Some(unknown) = input.keys().find(|k| !schema.iter().any(|f| &f.key == *k)) {
+        return Err(ModuleError::InvalidConfig(format!("Bilinmeyen alan: {}", unknown)));
+    }
+
+    let mut config = ModuleConfig::new();
+    for field in schema {
+        let value = input.get(&field.key).unwrap_or(&field.default);
+        field.check(value).map_err(ModuleError::InvalidConfig)?;
+        config.insert(field.key.clone(), value.clone());
+    }
+    Ok(config)
+}
+
+/// Doğrulanmış ayardan ondalıklı değer oku
+pub fn get_f64(config: &ModuleConfig, key: &str, fallback: f64) -> f64 {
+    config.get(key).and_then(Value::as_f64).unwrap_or(fallback)
+}
+
+/// Doğrulanmış ayardan tam sayı oku
+pub fn get_i64(config: &ModuleConfig, key: &str, fallback: i64) -> i64 {
+    config.get(key).and_then(Value::as_i64).unwrap_or(fallback)
+}
+
+/// Doğrulanmış ayardan boolean oku
+pub fn get_bool(config: &ModuleConfig, key: &str, fallback: bool) -> bool {
+    config.get(key).and_then(Value::as_bool).unwrap_or(fallback)
+}
+
+/// Doğrulanmış ayardan metin oku
+pub fn get_str<'a>(config: &'a ModuleConfig, key: &str, fallback: &'a str) -> &'a str {
+    config.get(key).and_then(Value::as_str).unwrap_or(fallback)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn schema() -> Vec<ConfigField> {
+        vec![
+            ConfigField::bool("enabled", "test", true),
+            ConfigField::number("threshold", "test", 1.0, 0.1, 10.0, 0.1),
+            ConfigField::integer("period", "test", 14, 1, 500),
+            ConfigField::select("mode", "test", "fixed", &["fixed", "atr"]),
+        ]
+    }
+
+    #[test]
+    fn test_missing_fields_use_defaults() {
+        let config = validate(&schema(), &json!({ "threshold": 2.5 })).unwrap();
+        assert_eq!(get_f64(&config, "threshold", 0.0), 2.5);
+        assert!(get_bool(&config, "enabled", false));
+        assert_eq!(get_i64(&config, "period", 0), 14);
+        assert_eq!(get_str(&config, "mode", ""), "fixed");
+    }
+
+    #[test]
+    fn test_invalid_values_rejected() {
+        assert!(validate(&schema(), &json!({ "threshold": 50.0 })).is_err());
+        assert!(validate(&schema(), &json!({ "enabled": "yes" })).is_err());
+        assert!(validate(&schema(), &json!({ "mode": "chandelier" })).is_err());
+        assert!(validate(&schema(), &json!({ "unknown": 1 })).is_err());
+        assert!(validate(&schema(), &json!([1, 2])).is_err());
+    }
+}
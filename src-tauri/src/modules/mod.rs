@@ -2,7 +2,33 @@
 // Plugin benzeri modül sistemi - her özellik bir modül olarak çalışır
 
 pub mod traits;
+pub mod config;
+pub mod registry;
+pub mod chart_overlay;
 pub mod stop_loss;
+pub mod batch_trading;
+pub mod risk_monitor;
 pub mod risk_calculator;
+pub mod wasm_host;
 
 pub use traits::TradingModule;
+pub use registry::{ModuleEntry, ModuleInfo, ModuleRegistry};
+
+use chart_overlay::ChartOverlayModule;
+
+/// Yerleşik modülleri kaydet (kayıt sırası UI'daki sıradır)
+pub fn register_builtin_modules(registry: &ModuleRegistry) {
+    // === Grafik Göstergeleri ===
+    registry.register(ChartOverlayModule::new("sma_analyzer", "SMA Analyzer", "Simple Moving Averages"));
+    registry.register(ChartOverlayModule::new("vwap_analyzer", "VWAP Analyzer", "VWAP, VAL/VAH calculation"));
+    registry.register(ChartOverlayModule::new("anchored_vwap", "Anchored VWAP", "VWAP from a specific anchor point"));
+    // === Risk Yönetimi ===
+    registry.register(risk_monitor::RiskMonitorModule::new());
+    // === İşlem Araçları ===
+    registry.register(stop_loss::StopLossModule::new());
+    registry.register(batch_trading::BatchTradingModule::new());
+    // === Harici Eklentiler (WASM sandbox) ===
+    wasm_host::register_installed_plugins(registry, &wasm_host::plugins_dir());
+    // === Strateji Oluşturucu (En Altta Sabit) ===
+    registry.register(ChartOverlayModule::new("strategy_builder", "Strategy Builder", "Custom signals from multiple indicators"));
+}
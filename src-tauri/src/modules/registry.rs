@@ -0,0 +1,505 @@
+// AlgoTrade OS - Module Registry
+// Modül kaydı, yaşam döngüsü ve olay dağıtımı
+// Yeni bir modül eklemek için tek gereken `register_builtin_modules` içine bir `register` çağrısıdır
+
+use std::sync::{Arc, Mutex as StdMutex, OnceLock, RwLock as StdRwLock};
+use std::time::Instant;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::Value;
+use tokio::sync::Mutex;
+use crate::core::{AppEvent, AppState, Subscription};
+use crate::i18n::t;
+use crate::models::ModuleActivation;
+use super::config::{self, ConfigField, ModuleConfig};
+use super::traits::{ModuleError, ModuleHealth, ModulePermission, TradingModule};
+
+/// Art arda bu kadar hata veren modül "degraded" sayılır
+const DEGRADED_ERROR_STREAK: u32 = 3;
+
+/// Modül çalışma istatistikleri
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ModuleStats {
+    /// İşlenen toplam olay
+    pub events_processed: u64,
+    /// İşlenen fiyat tick'i
+    pub ticks_processed: u64,
+    /// Toplam hata sayısı
+    pub errors: u64,
+    /// Art arda gelen hata sayısı (başarılı olayda sıfırlanır)
+    pub consecutive_errors: u32,
+    /// Ortalama olay işleme süresi (mikrosaniye)
+    pub avg_latency_us: f64,
+    /// En uzun olay işleme süresi (mikrosaniye)
+    pub max_latency_us: u64,
+    /// Olay kuyruğunda bekleyen
+    pub pending_events: usize,
+    /// Kuyruk gecikme eşiğini aşma sayısı
+    pub lag_events: u64,
+    /// Kuyruk dolu olduğu için atlanan olay sayısı
+    pub dropped_events: u64,
+    pub is_lagging: bool,
+    pub last_event_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+    pub last_error_at: Option<DateTime<Utc>>,
+    /// initialize() başarısız olduysa false
+    pub initialized: bool,
+}
+
+/// UI'a gönderilen modül bilgisi
+#[derive(Debug, Clone, Serialize)]
+pub struct ModuleInfo {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub is_active: bool,
+    pub can_trade: bool,
+    /// Sadece arayüzde çalışır, backend olayı almaz
+    pub ui_only: bool,
+    /// Yeniden başlatma sonrası kullanıcı onayı bekliyor
+    pub needs_confirmation: bool,
+    pub permissions: Vec<ModulePermission>,
+    pub config_schema: Vec<ConfigField>,
+    pub health: ModuleHealth,
+    pub last_error: Option<String>,
+    pub stats: ModuleStats,
+}
+
+/// Kayıtlı tek bir modül
+pub struct ModuleEntry {
+    id: String,
+    can_trade: bool,
+    ui_only: bool,
+    permissions: Vec<ModulePermission>,
+    schema: Vec<ConfigField>,
+    module: Mutex<Box<dyn TradingModule>>,
+    activation: StdRwLock<ModuleActivation>,
+    stats: StdMutex<ModuleStats>,
+}
+
+impl ModuleEntry {
+    fn new(module: Box<dyn TradingModule>) -> Self {
+        Self {
+            id: module.id().to_string(),
+            can_trade: module.can_execute_orders(),
+            ui_only: module.ui_only(),
+            permissions: module.permissions(),
+            schema: module.config_schema(),
+            module: Mutex::new(module),
+            activation: StdRwLock::new(ModuleActivation::Inactive),
+            stats: StdMutex::new(ModuleStats::default()),
+        }
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Modül emir gönderebilir mi?
+    pub fn can_trade(&self) -> bool {
+        self.can_trade
+    }
+
+    /// Modülün istediği izinler
+    pub fn permissions(&self) -> &[ModulePermission] {
+        &self.permissions
+    }
+
+    pub fn schema(&self) -> &[ConfigField] {
+        &self.schema
+    }
+
+    pub fn activation(&self) -> ModuleActivation {
+        *self.activation.read().unwrap()
+    }
+
+    /// Aktivasyon durumunu değiştir (sadece Active durumunda olay alır)
+    pub async fn set_activation(&self, activation: ModuleActivation) {
+        self.module
+            .lock()
+            .await
+            .set_active(activation == ModuleActivation::Active);
+        *self.activation.write().unwrap() = activation;
+    }
+
+    /// Ayarları şemaya göre doğrula ve canlı modüle uygula
+    pub async fn apply_config(&self, input: &Value) -> Result<ModuleConfig, ModuleError> {
+        let validated = config::validate(&self.schema, input)?;
+        self.module.lock().await.apply_config(&validated)?;
+        Ok(validated)
+    }
+
+    /// Modülün mevcut ayarları
+    pub async fn get_config(&self) -> ModuleConfig {
+        self.module.lock().await.get_config()
+    }
+
+    pub fn stats(&self) -> ModuleStats {
+        self.stats.lock().unwrap().clone()
+    }
+
+    /// Modülün bildirdiği ve istatistiklerden çıkarılan sağlık durumunun kötüsü
+    pub async fn health(&self) -> ModuleHealth {
+        let stats = self.stats();
+        if !stats.initialized {
+            return ModuleHealth::Failed;
+        }
+        let reported = self.module.lock().await.health();
+        if reported != ModuleHealth::Healthy {
+            return reported;
+        }
+        if stats.consecutive_errors >= DEGRADED_ERROR_STREAK || stats.is_lagging {
+            return ModuleHealth::Degraded;
+        }
+        ModuleHealth::Healthy
+    }
+
+    /// UI için modül bilgisi
+    pub async fn info(&self) -> ModuleInfo {
+        let (name, description) = {
+            let module = self.module.lock().await;
+            (
+                translate_or(&format!("module.{}.name", self.id), module.name()),
+                translate_or(&format!("module.{}.description", self.id), module.description()),
+            )
+        };
+        let activation = self.activation();
+        let stats = self.stats();
+
+        ModuleInfo {
+            id: self.id.clone(),
+            name,
+            description,
+            is_active: activation == ModuleActivation::Active,
+            can_trade: self.can_trade,
+            ui_only: self.ui_only,
+            needs_confirmation: activation == ModuleActivation::NeedsConfirmation,
+            permissions: self.permissions.clone(),
+            config_schema: self.schema.iter().map(ConfigField::localized).collect(),
+            health: self.health().await,
+            last_error: stats.last_error.clone(),
+            stats,
+        }
+    }
+
+    /// Modülü başlat
+    async fn initialize(&self, state: Arc<AppState>) {
+        let result = {
+            let mut module = self.module.lock().await;
+            let result = module.initialize(state.clone()).await;
+            if result.is_ok() {
+                // Zamanlayıcılar borsa saatiyle tetiklenir, olay olarak geri gelir
+                for spec in module.schedules() {
+                    if let Err(e) = state.scheduler.add(&self.id, spec) {
+                        tracing::warn!("Modül {} zamanlayıcısı eklenemedi: {}", self.id, e);
+                    }
+                }
+            }
+            result
+        };
+
+        let mut stats = self.stats.lock().unwrap();
+        match result {
+            Ok(()) => stats.initialized = true,
+            Err(e) => {
+                tracing::error!("Modül {} başlatılamadı: {}", self.id, e);
+                stats.initialized = false;
+                stats.last_error = Some(e.to_string());
+                stats.last_error_at = Some(Utc::now());
+            }
+        }
+    }
+
+    /// Olayı modülün ilgili handler'ına ilet (ilgisiz olaylar için None)
+    async fn dispatch(&self, state: &AppState, event: &AppEvent) -> Option<Result<(), ModuleError>> {
+        let mut module = self.module.lock().await;
+        match event {
+            AppEvent::PriceUpdated(tick) => Some(module.on_price_tick(tick).await),
+            AppEvent::BalanceChanged { symbol, balance } => {
+                Some(module.on_balance_change(symbol, *balance).await)
+            }
+            AppEvent::PositionOpened { position_id } => {
+                let position = find_position(state, &self.id, position_id, false).await?;
+                Some(module.on_position_opened(&position).await)
+            }
+            AppEvent::PositionClosed { position_id, pnl } => {
+                let position = find_position(state, &self.id, position_id, true).await?;
+                Some(module.on_position_closed(&position, *pnl).await)
+            }
+            AppEvent::TimerFired { module_id, timer_id, fired_at } if *module_id == self.id => {
+                Some(module.on_timer(timer_id, *fired_at).await)
+            }
+            AppEvent::CandleClosed { module_id, symbol, interval, close_time } if *module_id == self.id => {
+                Some(module.on_candle_close(symbol, interval, *close_time).await)
+            }
+            _ => None,
+        }
+    }
+
+    /// Olay sonucunu istatistiklere işle
+    fn record(&self, event: &AppEvent, result: Result<(), ModuleError>, started: Instant, subscription: &Subscription) {
+        let elapsed_us = started.elapsed().as_micros() as u64;
+        let queue = subscription.metrics();
+        let mut stats = self.stats.lock().unwrap();
+
+        stats.events_processed += 1;
+        if matches!(event, AppEvent::PriceUpdated(_)) {
+            stats.ticks_processed += 1;
+        }
+        stats.avg_latency_us += (elapsed_us as f64 - stats.avg_latency_us) / stats.events_processed as f64;
+        stats.max_latency_us = stats.max_latency_us.max(elapsed_us);
+        stats.last_event_at = Some(Utc::now());
+        stats.pending_events = queue.pending_events;
+        stats.is_lagging = queue.is_lagging;
+        stats.dropped_events = queue.dropped_events;
+
+        match result {
+            Ok(()) => stats.consecutive_errors = 0,
+            Err(e) => {
+                tracing::warn!("Modül {} olay işleyemedi: {}", self.id, e);
+                stats.errors += 1;
+                stats.consecutive_errors += 1;
+                stats.last_error = Some(e.to_string());
+                stats.last_error_at = Some(Utc::now());
+            }
+        }
+    }
+}
+
+/// Modül kayıt defteri
+#[derive(Default)]
+pub struct ModuleRegistry {
+    entries: StdRwLock<Vec<Arc<ModuleEntry>>>,
+    state: OnceLock<Arc<AppState>>,
+}
+
+impl ModuleRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Modül kaydet - registry başlatıldıysa modül hemen başlatılır
+    pub fn register<M: TradingModule + 'static>(&self, module: M) {
+        self.register_boxed(Box::new(module));
+    }
+
+    /// Kutulanmış modül kaydet (dinamik yüklenen modüller için)
+    pub fn register_boxed(&self, module: Box<dyn TradingModule>) {
+        let entry = Arc::new(ModuleEntry::new(module));
+        {
+            let mut entries = self.entries.write().unwrap();
+            if entries.iter().any(|e| e.id == entry.id) {
+                tracing::warn!("Modül {} zaten kayıtlı, yeni kayıt yok sayıldı", entry.id);
+                return;
+            }
+            entries.push(entry.clone());
+        }
+        tracing::info!("Modül kaydedildi: {}", entry.id);
+
+        if let Some(state) = self.state.get() {
+            Self::start_entry(entry, state.clone());
+        }
+    }
+
+    /// Kimliğe göre modül bul
+    pub fn get(&self, module_id: &str) -> Option<Arc<ModuleEntry>> {
+        self.entries
+            .read()
+            .unwrap()
+            .iter()
+            .find(|e| e.id == module_id)
+            .cloned()
+    }
+
+    /// Kayıt sırasıyla tüm modüller
+    pub fn entries(&self) -> Vec<Arc<ModuleEntry>> {
+        self.entries.read().unwrap().clone()
+    }
+
+    /// Tüm modüllerin UI bilgisi (kayıt sırasıyla)
+    pub async fn list(&self) -> Vec<ModuleInfo> {
+        let mut infos = Vec::new();
+        for entry in self.entries() {
+            infos.push(entry.info().await);
+        }
+        infos
+    }
+
+    /// Kayıtlı aktivasyon durumlarını geri yükle
+    ///
+    /// Emir gönderebilen modüller, aktif kaydedilmiş olsalar bile pozisyon mutabakatı
+    /// yapılana kadar onay bekler.
+    pub async fn restore_activations(&self, saved: Vec<(String, ModuleActivation)>) {
+        for (module_id, activation) in saved {
+            let Some(entry) = self.get(&module_id) else {
+                continue; // Artık kayıtlı olmayan modül
+            };
+            let restored = match activation {
+                ModuleActivation::Active | ModuleActivation::NeedsConfirmation if entry.can_trade() => {
+                    tracing::warn!("İşlem modülü {} onay bekliyor", module_id);
+                    ModuleActivation::NeedsConfirmation
+                }
+                other => other,
+            };
+            entry.set_activation(restored).await;
+        }
+    }
+
+    /// Modülleri başlat ve her biri için olay döngüsü aç
+    pub fn start(&self, state: Arc<AppState>) {
+        if self.state.set(state.clone()).is_err() {
+            tracing::warn!("Modül registry zaten başlatılmış");
+            return;
+        }
+        for entry in self.entries() {
+            Self::start_entry(entry, state.clone());
+        }
+    }
+
+    /// Tek modülü başlat - her modülün kendi aboneliği olduğundan yavaş modül diğerlerini bekletmez
+    fn start_entry(entry: Arc<ModuleEntry>, state: Arc<AppState>) {
+        if entry.ui_only {
+            entry.stats.lock().unwrap().initialized = true;
+            return;
+        }
+        let subscription = state.dispatcher.subscribe(&entry.id);
+        tauri::async_runtime::spawn(async move {
+            entry.initialize(state.clone()).await;
+
+            while let Some(event) = subscription.recv().await {
+                if let AppEvent::SubscriberLagged { pending, .. } = &event {
+                    tracing::warn!("Modül {} olayların gerisinde ({} bekliyor)", entry.id, pending);
+                    entry.stats.lock().unwrap().lag_events += 1;
+                    continue;
+                }
+                if entry.activation() != ModuleActivation::Active {
+                    continue;
+                }
+
+                let started = Instant::now();
+                if let Some(result) = entry.dispatch(&state, &event).await {
+                    entry.record(&event, result, started, &subscription);
+                }
+            }
+        });
+    }
+}
+
+/// Çeviri varsa onu, yoksa modülün kendi metnini kullan
+fn translate_or(key: &str, fallback: &str) -> String {
+    let translated = t(key);
+    if translated == key {
+        fallback.to_string()
+    } else {
+        translated
+    }
+}
+
+/// Olaydaki pozisyonu uygulama durumunda bul (yoksa olay modüle iletilmez)
+///
+/// Kapanış olaylarında önce kapanan pozisyonlara bakılır; aynı kimlikle yeniden açılan
+/// pozisyon kapanan yerine geçmez.
+async fn find_position(state: &AppState, module_id: &str, position_id: &str, closed: bool) -> Option<crate::models::Position> {
+    let mut position = None;
+    if closed {
+        position = state.closed_positions.read().await.iter().find(|p| p.id == position_id).cloned();
+    }
+    if position.is_none() {
+        position = state.positions.read().await.iter().find(|p| p.id == position_id).cloned();
+    }
+    if position.is_none() {
+        tracing::warn!("Pozisyon {} bulunamadı, olay modül {} için atlandı", position_id, module_id);
+    }
+    position
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use crate::models::{Position, StandardTick};
+    use crate::testing;
+
+    /// Her tick'te hata veren test modülü
+    struct FailingModule {
+        active: bool,
+    }
+
+    #[async_trait]
+    impl TradingModule for FailingModule {
+        fn id(&self) -> &str { "failing" }
+        fn name(&self) -> &str { "Failing" }
+        fn description(&self) -> &str { "Test" }
+        async fn initialize(&mut self, _state: Arc<AppState>) -> Result<(), ModuleError> { Ok(()) }
+        async fn shutdown(&mut self) -> Result<(), ModuleError> { Ok(()) }
+        async fn on_price_tick(&mut self, _tick: &StandardTick) -> Result<(), ModuleError> {
+            Err(ModuleError::ExecutionFailed("test".into()))
+        }
+        async fn on_balance_change(&mut self, _symbol: &str, _balance: f64) -> Result<(), ModuleError> { Ok(()) }
+        async fn on_position_opened(&mut self, _position: &Position) -> Result<(), ModuleError> { Ok(()) }
+        async fn on_position_closed(&mut self, _position: &Position, _pnl: f64) -> Result<(), ModuleError> { Ok(()) }
+        fn is_active(&self) -> bool { self.active }
+        fn set_active(&mut self, active: bool) { self.active = active; }
+    }
+
+    /// Koşul sağlanana kadar yokla (en fazla 1 sn)
+    async fn wait_until(mut condition: impl FnMut() -> bool) {
+        tokio::time::timeout(std::time::Duration::from_secs(1), async {
+            while !condition() {
+                tokio::time::sleep(std::time::Duration::from_millis(1)).await;
+            }
+        })
+        .await
+        .expect("koşul zamanında sağlanmadı");
+    }
+
+    fn delivered(state: &AppState, subscriber: &str) -> u64 {
+        state.dispatcher.metrics().iter().find(|m| m.subscriber == subscriber).map_or(0, |m| m.delivered)
+    }
+
+    fn tick(symbol: &str) -> AppEvent {
+        AppEvent::PriceUpdated(testing::tick(symbol, 1.0))
+    }
+
+    #[tokio::test]
+    async fn test_only_active_modules_receive_events_and_errors_are_tracked() {
+        let state = Arc::new(AppState::new());
+        let registry = ModuleRegistry::new();
+        registry.register(FailingModule { active: false });
+        registry.register(FailingModule { active: false }); // Aynı kimlik yok sayılır
+        registry.start(state.clone());
+        assert_eq!(registry.entries().len(), 1);
+
+        let entry = registry.get("failing").unwrap();
+        state.dispatcher.publish(tick("BTCUSDT"));
+        wait_until(|| delivered(&state, "failing") == 1).await;
+        assert_eq!(entry.stats().events_processed, 0);
+
+        entry.set_activation(ModuleActivation::Active).await;
+        for symbol in ["BTCUSDT", "ETHUSDT", "SOLUSDT"] {
+            state.dispatcher.publish(tick(symbol));
+        }
+        wait_until(|| entry.stats().ticks_processed == 3).await;
+
+        let info = entry.info().await;
+        assert!(info.is_active);
+        assert_eq!(info.stats.ticks_processed, 3);
+        assert_eq!(info.stats.errors, 3);
+        assert_eq!(info.health, ModuleHealth::Degraded);
+        assert!(info.last_error.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_ui_only_modules_are_labelled_and_not_subscribed() {
+        let state = Arc::new(AppState::new());
+        let registry = ModuleRegistry::new();
+        registry.register(crate::modules::chart_overlay::ChartOverlayModule::new("sma_analyzer", "SMA", "Test"));
+        registry.start(state.clone());
+
+        let info = registry.get("sma_analyzer").unwrap().info().await;
+        assert!(info.ui_only);
+        assert!(!info.can_trade);
+        assert_eq!(info.health, ModuleHealth::Healthy);
+        assert!(state.dispatcher.metrics().iter().all(|m| m.subscriber != "sma_analyzer"));
+    }
+}
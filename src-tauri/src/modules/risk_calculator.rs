@@ -1,7 +1,96 @@
 // AlgoTrade OS - Risk Calculator Module
 // Pozisyon boyutu ve risk hesaplama modülü
 
-use crate::models::RiskCalculation;
+use crate::exchange::bybit::MarketCategory;
+use crate::exchange::executor::LotSize;
+use crate::models::{
+    MarginTier, Position, PositionSide, RiskCalculation, SizingModel, SizingStep, TakeProfitLevel, TakeProfitTarget,
+    TradeCosts, TradeStats,
+};
+
+/// Kademe bilgisi yokken kullanılan bakım marjı oranı (Bybit USDT perpetual ilk kademe)
+const DEFAULT_MAINTENANCE_MARGIN_RATE: f64 = 0.005;
+
+/// Kelly oranının güvenilir sayılması için gereken en az kapanmış işlem
+pub const KELLY_MIN_TRADES: u32 = 20;
+
+/// Boyutun neye göre belirleneceği
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SizeBasis {
+    /// Risk tutarı / stop mesafesi
+    Risk,
+    /// Risk tutarı / verilen fiyat mesafesi (volatilite hedefi)
+    Distance(f64),
+    /// Sabit pozisyon değeri (hesap para biriminde)
+    Notional(f64),
+    /// Sabit miktar
+    Quantity(f64),
+}
+
+/// Boyutlandırma modelinin kararı
+#[derive(Debug, Clone, PartialEq)]
+pub struct SizingDecision {
+    pub model: SizingModel,
+    /// Riske edilecek bakiye yüzdesi
+    pub risk_percent: f64,
+    pub basis: SizeBasis,
+    /// Kararın adımları (etiketler i18n anahtarı)
+    pub steps: Vec<SizingStep>,
+}
+
+impl SizingDecision {
+    /// Sabit oranlı boyutlandırma
+    pub fn fixed_fractional(risk_percent: f64) -> Self {
+        Self {
+            model: SizingModel::FixedFractional,
+            risk_percent,
+            basis: SizeBasis::Risk,
+            steps: vec![step("sizing.base_risk_percent", risk_percent)],
+        }
+    }
+
+    /// Boyutu bir çarpanla küçült (örn: tepeden düşüş kademesi)
+    pub fn scale(&mut self, factor: f64, label: &str) {
+        if factor == 1.0 {
+            return;
+        }
+        self.risk_percent *= factor;
+        self.basis = match self.basis {
+            SizeBasis::Notional(value) => SizeBasis::Notional(value * factor),
+            SizeBasis::Quantity(value) => SizeBasis::Quantity(value * factor),
+            other => other,
+        };
+        self.steps.push(step(label, factor));
+    }
+}
+
+fn step(label: &str, value: f64) -> SizingStep {
+    SizingStep { label: label.to_string(), value }
+}
+
+/// Fiyattaki bir birim pozisyonun hesap para birimindeki değeri
+///
+/// Lineer ve spotta birim bir coin (quote cinsinden fiyat), ters kontratta birim 1 USD (coin cinsinden 1 / fiyat).
+fn unit_value(category: MarketCategory, price: f64) -> f64 {
+    match category {
+        MarketCategory::Inverse if price > 0.0 => 1.0 / price,
+        MarketCategory::Inverse => 0.0,
+        _ => price,
+    }
+}
+
+/// Bir birimin iki fiyat arasındaki kâr/zararı (mutlak, hesap para biriminde)
+fn unit_move(category: MarketCategory, from: f64, to: f64) -> f64 {
+    (unit_value(category, from) - unit_value(category, to)).abs()
+}
+
+/// Hesap para birimindeki tutarın quote (USD) karşılığı; ters kontratta coin tutarı güncel fiyattan çevrilir
+fn to_quote(category: MarketCategory, amount: f64, price: f64) -> f64 {
+    match category {
+        MarketCategory::Inverse => amount * price,
+        _ => amount,
+    }
+}
 
 /// Risk hesaplayıcı
 pub struct RiskCalculator;
@@ -9,49 +98,139 @@ pub struct RiskCalculator;
 impl RiskCalculator {
     /// Pozisyon boyutunu hesapla
     ///
+    /// Boyut, stopta komisyonlar ve ödenecek fonlama dahil toplam kayıp risk tutarına eşit olacak
+    /// şekilde belirlenir. Yön giriş/stop ilişkisinden çıkarılır (stop girişin altındaysa long).
+    ///
+    /// Kategoriye göre:
+    /// * Linear - bakiye ve sonuçlar USDT, boyut coin adedi
+    /// * Inverse - bakiye ve sonuçlar coin, boyut USD kontrat (1 kontrat = 1 USD)
+    /// * Spot - kaldıraç/fonlama/tasfiye yok, sadece alış; boyut quote bakiyesini aşamaz
+    ///
     /// # Arguments
-    /// * `account_balance` - Hesap bakiyesi
-    /// * `risk_percent` - Risk yüzdesi (örn: 1.0 = %1)
+    /// * `category` - Market kategorisi
+    /// * `account_balance` - Hesap bakiyesi (ters kontratta coin, spotta kullanılabilir quote)
     /// * `entry_price` - Giriş fiyatı
     /// * `stop_price` - Stop-loss fiyatı
     /// * `take_profit_price` - Take-profit fiyatı (opsiyonel)
+    /// * `costs` - Kaldıraç, komisyon, fonlama ve bakım marjı kademeleri
+    /// * `sizing` - Boyutlandırma modelinin kararı (risk yüzdesi ve boyut esası)
     pub fn calculate_position_size(
+        category: MarketCategory,
         account_balance: f64,
-        risk_percent: f64,
         entry_price: f64,
         stop_price: f64,
         take_profit_price: Option<f64>,
+        costs: &TradeCosts,
+        sizing: &SizingDecision,
     ) -> RiskCalculation {
+        let is_spot = category == MarketCategory::Spot;
+        let risk_percent = sizing.risk_percent;
+        let mut derivation = sizing.steps.clone();
         // Risk edilecek miktar
         let risk_amount = account_balance * (risk_percent / 100.0);
+        let is_long = stop_price < entry_price;
+        let leverage = if is_spot { 1.0 } else { costs.leverage.max(1.0) };
+        let funding_rate = if is_spot { 0.0 } else { costs.funding_rate };
 
-        // Fiyat başına risk (entry - stop)
-        let risk_per_unit = (entry_price - stop_price).abs();
+        // Birim başına risk (entry - stop, hesap para biriminde)
+        let risk_per_unit = unit_move(category, entry_price, stop_price);
+        // Birim başına maliyetler: giriş + stopta çıkış komisyonu, ödenecek fonlama
+        let fees_per_unit = unit_value(category, entry_price) * costs.entry_fee_rate
+            + unit_value(category, stop_price) * costs.exit_fee_rate;
+        let direction = if is_long { 1.0 } else { -1.0 };
+        let funding_per_unit =
+            unit_value(category, entry_price) * funding_rate * f64::from(costs.funding_intervals) * direction;
+        let loss_per_unit = risk_per_unit + fees_per_unit + funding_per_unit.max(0.0);
 
-        // Pozisyon boyutu (lot/adet)
-        let position_size = if risk_per_unit > 0.0 {
-            risk_amount / risk_per_unit
+        // Pozisyon boyutu (lot/adet) - spotta açığa satış yok
+        let raw_size = match sizing.basis {
+            SizeBasis::Risk => risk_amount / loss_per_unit,
+            // Birim riski stop yerine verilen mesafe belirler (maliyetler yine eklenir)
+            SizeBasis::Distance(distance) => {
+                let exit = if is_long { entry_price - distance } else { entry_price + distance };
+                let unit_risk = unit_move(category, entry_price, exit) + loss_per_unit - risk_per_unit;
+                derivation.push(step("sizing.unit_risk", unit_risk));
+                if unit_risk > 0.0 { risk_amount / unit_risk } else { 0.0 }
+            }
+            SizeBasis::Notional(notional) => notional / unit_value(category, entry_price),
+            SizeBasis::Quantity(quantity) => quantity,
+        };
+        let mut position_size = if risk_per_unit > 0.0 && (is_long || !is_spot) && raw_size.is_finite() {
+            raw_size.max(0.0)
         } else {
             0.0
         };
+        // Spotta alış tutarı + komisyon kullanılabilir bakiyeyi aşamaz
+        let max_spot_size = account_balance / (entry_price * (1.0 + costs.entry_fee_rate));
+        let balance_limited = is_spot && position_size > max_spot_size;
+        if balance_limited {
+            position_size = max_spot_size;
+        }
+        derivation.push(step("sizing.risk_amount", risk_amount));
+        derivation.push(step("sizing.position_size", position_size));
 
         // Potansiyel kayıp
         let potential_loss = position_size * risk_per_unit;
+        let estimated_fees = position_size * fees_per_unit;
+        let estimated_funding = position_size * funding_per_unit;
+        let fee_adjusted_risk = position_size * loss_per_unit;
 
         // Potansiyel kar ve R:R oranı
-        let (potential_profit, risk_reward_ratio) = if let Some(tp) = take_profit_price {
-            let profit_per_unit = (tp - entry_price).abs();
+        let (potential_profit, risk_reward_ratio, fee_adjusted_risk_reward) = if let Some(tp) = take_profit_price {
+            let profit_per_unit = unit_move(category, entry_price, tp);
             let profit = position_size * profit_per_unit;
             let rr = if risk_per_unit > 0.0 {
                 profit_per_unit / risk_per_unit
             } else {
                 0.0
             };
-            (profit, rr)
+            // Hedefte çıkış komisyonu stop yerine TP fiyatından alınır
+            let net_profit_per_unit = profit_per_unit
+                - unit_value(category, entry_price) * costs.entry_fee_rate
+                - unit_value(category, tp) * costs.exit_fee_rate
+                - funding_per_unit;
+            let net_rr = if risk_per_unit > 0.0 {
+                net_profit_per_unit / loss_per_unit
+            } else {
+                0.0
+            };
+            (profit, rr, net_rr)
         } else {
-            (0.0, 0.0)
+            (0.0, 0.0, 0.0)
         };
 
+        // Marj ve tasfiye (izole marj, tek yönlü mod)
+        let position_value = position_size * unit_value(category, entry_price);
+        let required_margin = position_value / leverage;
+        let tier = Self::margin_tier(&costs.margin_tiers, position_value);
+        let maintenance_margin_rate = if is_spot {
+            0.0
+        } else {
+            tier.map_or(DEFAULT_MAINTENANCE_MARGIN_RATE, |t| t.maintenance_margin_rate)
+        };
+        let maintenance_margin = position_value * maintenance_margin_rate
+            - tier.map_or(0.0, |t| t.mm_deduction)
+            + position_value * costs.exit_fee_rate;
+        let liquidation_price = if is_spot || position_size <= 0.0 {
+            None
+        } else {
+            // Tasfiyeye kadar kaldırılabilecek birim başına zarar
+            let distance = (required_margin - maintenance_margin) / position_size;
+            match (category, is_long) {
+                (MarketCategory::Inverse, true) => Some(1.0 / (1.0 / entry_price + distance)),
+                // Ters short'un kaybı kontrat değeriyle sınırlı: düşük kaldıraçta tasfiye olmaz
+                (MarketCategory::Inverse, false) => {
+                    let inverse = 1.0 / entry_price - distance;
+                    (inverse > 0.0).then(|| 1.0 / inverse)
+                }
+                (_, true) => Some((entry_price - distance).max(0.0)),
+                (_, false) => Some(entry_price + distance),
+            }
+        };
+        let liquidation_before_stop = liquidation_price.is_some_and(|liq| {
+            if is_long { liq >= stop_price } else { liq <= stop_price }
+        });
+
         RiskCalculation {
             position_size,
             risk_amount,
@@ -59,6 +238,200 @@ impl RiskCalculator {
             potential_loss,
             potential_profit,
             risk_reward_ratio,
+            leverage,
+            position_value,
+            required_margin,
+            maintenance_margin_rate,
+            liquidation_price,
+            estimated_fees,
+            estimated_funding,
+            fee_adjusted_risk,
+            fee_adjusted_risk_reward,
+            liquidation_before_stop,
+            balance_limited,
+            sizing_model: sizing.model.as_str().to_string(),
+            derivation,
+        }
+    }
+
+    /// Boyutlandırma modelini uygula
+    ///
+    /// Yeterli veri yoksa (ATR, işlem geçmişi) sabit oranlı boyutlandırmaya düşülür ve bu adımlara yazılır.
+    ///
+    /// # Arguments
+    /// * `model` - Boyutlandırma modeli
+    /// * `risk_percent` - Temel risk yüzdesi
+    /// * `atr` - Sembolün ATR değeri (volatilite hedefi için)
+    /// * `stats` - Kapanan işlemlerin istatistikleri (Kelly ve varlık eğrisi için)
+    pub fn decide_sizing(
+        model: &SizingModel,
+        risk_percent: f64,
+        atr: Option<f64>,
+        stats: &TradeStats,
+    ) -> SizingDecision {
+        let mut decision = SizingDecision::fixed_fractional(risk_percent);
+        match model {
+            SizingModel::FixedFractional => return decision,
+            SizingModel::VolatilityTarget { multiple, .. } => match atr.filter(|a| *a > 0.0) {
+                Some(atr) => {
+                    decision.steps.push(step("sizing.atr", atr));
+                    decision.steps.push(step("sizing.atr_multiple", *multiple));
+                    decision.basis = SizeBasis::Distance(atr * multiple);
+                }
+                None => {
+                    decision.steps.push(step("sizing.atr_unavailable", 0.0));
+                    return decision;
+                }
+            },
+            SizingModel::Kelly { fraction } => {
+                decision.steps.push(step("sizing.trades", f64::from(stats.trades)));
+                if stats.trades < KELLY_MIN_TRADES || stats.avg_loss <= 0.0 {
+                    decision.steps.push(step("sizing.insufficient_history", f64::from(KELLY_MIN_TRADES)));
+                    return decision;
+                }
+                let payoff = stats.payoff_ratio();
+                // f* = W - (1 - W) / R; avantaj yoksa risk alınmaz
+                let kelly = stats.win_rate - (1.0 - stats.win_rate) / payoff;
+                decision.steps.push(step("sizing.win_rate", stats.win_rate * 100.0));
+                decision.steps.push(step("sizing.payoff_ratio", payoff));
+                decision.steps.push(step("sizing.kelly_percent", kelly * 100.0));
+                decision.steps.push(step("sizing.kelly_fraction", *fraction));
+                decision.risk_percent = (kelly * fraction * 100.0).max(0.0);
+            }
+            SizingModel::FixedNotional { notional } => {
+                decision.steps.push(step("sizing.notional", *notional));
+                decision.basis = SizeBasis::Notional(*notional);
+            }
+            SizingModel::FixedContracts { contracts } => {
+                decision.steps.push(step("sizing.contracts", *contracts));
+                decision.basis = SizeBasis::Quantity(*contracts);
+            }
+            SizingModel::EquityCurve { reduction_pct, min_factor } => {
+                let factor = (1.0 - reduction_pct / 100.0 * f64::from(stats.losing_streak)).max(*min_factor);
+                decision.steps.push(step("sizing.losing_streak", f64::from(stats.losing_streak)));
+                decision.steps.push(step("sizing.streak_factor", factor));
+                decision.risk_percent *= factor;
+            }
+        }
+        decision.model = model.clone();
+        decision.steps.push(step("sizing.risk_percent", decision.risk_percent));
+        decision
+    }
+
+    /// Kapanan işlem sonuçlarından istatistik (en yeni önce)
+    pub fn trade_stats(outcomes: &[f64]) -> TradeStats {
+        let wins: Vec<f64> = outcomes.iter().copied().filter(|pnl| *pnl > 0.0).collect();
+        let losses: Vec<f64> = outcomes.iter().filter(|pnl| **pnl < 0.0).map(|pnl| -pnl).collect();
+        let average = |values: &[f64]| {
+            if values.is_empty() { 0.0 } else { values.iter().sum::<f64>() / values.len() as f64 }
+        };
+        let decided = wins.len() + losses.len();
+        TradeStats {
+            trades: decided as u32,
+            win_rate: if decided > 0 { wins.len() as f64 / decided as f64 } else { 0.0 },
+            avg_win: average(&wins),
+            avg_loss: average(&losses),
+            losing_streak: outcomes.iter().take_while(|pnl| **pnl < 0.0).count() as u32,
+        }
+    }
+
+    /// Pozisyon değerinin düştüğü risk limiti kademesi (en büyük kademeyi aşarsa son kademe)
+    pub fn margin_tier(tiers: &[MarginTier], position_value: f64) -> Option<&MarginTier> {
+        tiers
+            .iter()
+            .find(|t| position_value <= t.risk_limit_value)
+            .or_else(|| tiers.iter().max_by(|a, b| a.risk_limit_value.total_cmp(&b.risk_limit_value)))
+    }
+
+    /// Kademeli kâr al emirlerini hesapla
+    ///
+    /// Yön giriş/stop ilişkisinden çıkarılır (stop girişin altındaysa long).
+    /// Miktarlar lot adımına aşağı yuvarlanır, en küçük emir miktarının altında kalan
+    /// kademe atlanır (miktar sonraki kademelere / runner'a kalır). Yüzdeler toplamı 100 ise son kademe
+    /// kalan miktarın tamamını alır.
+    ///
+    /// # Arguments
+    /// * `entry_price` - Giriş fiyatı
+    /// * `stop_price` - İlk stop-loss fiyatı (1R)
+    /// * `quantity` - Pozisyon miktarı
+    /// * `levels` - R katı / yüzde kademeleri
+    /// * `lot` - Sembolün miktar / fiyat adımları
+    pub fn calculate_take_profit_ladder(
+        entry_price: f64,
+        stop_price: f64,
+        quantity: f64,
+        levels: &[TakeProfitLevel],
+        lot: &LotSize,
+    ) -> Vec<TakeProfitTarget> {
+        let risk_per_unit = entry_price - stop_price;
+        if risk_per_unit == 0.0 || quantity <= 0.0 {
+            return Vec::new();
+        }
+
+        let mut levels: Vec<&TakeProfitLevel> = levels
+            .iter()
+            .filter(|l| l.r_multiple > 0.0 && l.percent > 0.0)
+            .collect();
+        levels.sort_by(|a, b| a.r_multiple.total_cmp(&b.r_multiple));
+
+        let mut targets = Vec::new();
+        let mut remaining = quantity;
+        let mut cumulative_percent = 0.0;
+        for level in levels {
+            cumulative_percent += level.percent;
+            let raw = if cumulative_percent >= 100.0 {
+                remaining
+            } else {
+                quantity * level.percent / 100.0
+            };
+            let level_quantity = lot.round_quantity(raw.min(remaining));
+            if level_quantity <= 0.0 || level_quantity < lot.min_qty {
+                continue;
+            }
+
+            remaining -= level_quantity;
+            targets.push(TakeProfitTarget {
+                r_multiple: level.r_multiple,
+                price: lot.round_price(entry_price + risk_per_unit * level.r_multiple),
+                quantity: level_quantity,
+            });
+            if cumulative_percent >= 100.0 {
+                break;
+            }
+        }
+        targets
+    }
+
+    /// `quantity` birimlik pozisyonun quote (USD) cinsinden büyüklüğü
+    ///
+    /// Lineer ve spotta fiyat x miktar; ters kontratta birim 1 USD olduğundan miktarın kendisi.
+    pub fn notional(category: MarketCategory, price: f64, quantity: f64) -> f64 {
+        to_quote(category, unit_value(category, price) * quantity, price)
+    }
+
+    /// Fiyat `price`'tan `stop`'a giderse `quantity` birimin kaybı (quote cinsinden, güncel fiyattan)
+    pub fn stop_loss_amount(category: MarketCategory, price: f64, stop: f64, quantity: f64) -> f64 {
+        to_quote(category, unit_move(category, price, stop) * quantity, price)
+    }
+
+    /// Pozisyonun açık riski: stop tetiklenirse güncel fiyattan kaybedilecek tutar (quote cinsinden)
+    ///
+    /// Stopu olmayan pozisyonun tüm büyüklüğü risk sayılır.
+    pub fn calculate_open_risk(position: &Position, price: f64) -> f64 {
+        let category = MarketCategory::for_symbol(&position.symbol);
+        match position.stop_loss {
+            Some(stop) => {
+                let at_risk = match position.side {
+                    PositionSide::Long => price > stop,
+                    PositionSide::Short => stop > price,
+                };
+                if at_risk {
+                    Self::stop_loss_amount(category, price, stop, position.quantity)
+                } else {
+                    0.0
+                }
+            }
+            None => Self::notional(category, price, position.quantity),
         }
     }
 
@@ -101,11 +474,13 @@ mod tests {
     #[test]
     fn test_position_size_calculation() {
         let result = RiskCalculator::calculate_position_size(
+            MarketCategory::Linear,
             10000.0,  // $10,000 hesap
-            1.0,      // %1 risk
             100.0,    // $100 giriş
             95.0,     // $95 stop
             Some(110.0), // $110 TP
+            &TradeCosts::default(),
+            &SizingDecision::fixed_fractional(1.0), // %1 risk
         );
 
         assert_eq!(result.risk_amount, 100.0); // $100 risk
@@ -113,6 +488,135 @@ mod tests {
         assert_eq!(result.risk_reward_ratio, 2.0); // 2:1 R:R
     }
 
+    #[test]
+    fn test_leverage_fees_and_liquidation() {
+        let tiers = vec![
+            MarginTier { risk_limit_value: 1000.0, maintenance_margin_rate: 0.005, mm_deduction: 0.0, max_leverage: 100.0 },
+            MarginTier { risk_limit_value: 5000.0, maintenance_margin_rate: 0.01, mm_deduction: 5.0, max_leverage: 50.0 },
+        ];
+        let costs = TradeCosts { leverage: 20.0, margin_tiers: tiers, ..TradeCosts::default() };
+
+        // 20x long, 2000$ ikinci kademede: tasfiye stoptan (95) önce
+        let one_percent = SizingDecision::fixed_fractional(1.0);
+        let result = RiskCalculator::calculate_position_size(
+            MarketCategory::Linear, 10000.0, 100.0, 95.0, Some(110.0), &costs, &one_percent,
+        );
+        assert_eq!(result.position_value, 2000.0);
+        assert_eq!(result.required_margin, 100.0);
+        assert_eq!(result.maintenance_margin_rate, 0.01);
+        // Mesafe: (başlangıç 100 - bakım (20 - 5)) / 20 adet = 4.25
+        assert!((result.liquidation_price.unwrap() - 95.75).abs() < 1e-9);
+        assert!(result.liquidation_before_stop);
+
+        let ten_x = TradeCosts { leverage: 10.0, ..costs.clone() };
+        let safe = RiskCalculator::calculate_position_size(
+            MarketCategory::Linear, 10000.0, 100.0, 95.0, None, &ten_x, &one_percent,
+        );
+        assert!(!safe.liquidation_before_stop);
+
+        // Komisyon dahil toplam kayıp risk tutarında kalır
+        let fees = TradeCosts { entry_fee_rate: 0.001, exit_fee_rate: 0.001, ..TradeCosts::default() };
+        let result = RiskCalculator::calculate_position_size(
+            MarketCategory::Linear, 10000.0, 100.0, 95.0, Some(110.0), &fees, &one_percent,
+        );
+        assert!((result.fee_adjusted_risk - 100.0).abs() < 1e-9);
+        assert!(result.position_size < 20.0);
+        // (10 - 0.1 - 0.11) / (5 + 0.1 + 0.095)
+        assert!((result.fee_adjusted_risk_reward - 9.79 / 5.195).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_inverse_and_spot_math() {
+        // Ters kontrat: 1 BTC bakiye, %1 risk = 0.01 BTC, kontrat başına 1/40000 - 1/50000 BTC kayıp
+        let result = RiskCalculator::calculate_position_size(
+            MarketCategory::Inverse, 1.0, 50000.0, 40000.0, Some(75000.0), &TradeCosts::default(),
+            &SizingDecision::fixed_fractional(1.0),
+        );
+        assert!((result.position_size - 2000.0).abs() < 1e-6); // 2000 USD kontrat
+        assert!((result.position_value - 0.04).abs() < 1e-12); // BTC
+        assert!((result.potential_profit - 2000.0 * (1.0 / 50000.0 - 1.0 / 75000.0)).abs() < 1e-12);
+        assert!((result.risk_reward_ratio - 4.0 / 3.0).abs() < 1e-9);
+        assert!(!result.liquidation_before_stop);
+
+        // Spot: kaldıraç yok, boyut quote bakiyesiyle sınırlı
+        let costs = TradeCosts { leverage: 10.0, ..TradeCosts::default() };
+        let result = RiskCalculator::calculate_position_size(
+            MarketCategory::Spot, 1000.0, 100.0, 98.0, None, &costs, &SizingDecision::fixed_fractional(5.0),
+        );
+        assert_eq!(result.leverage, 1.0);
+        assert!(result.balance_limited);
+        assert_eq!(result.position_size, 10.0);
+        assert_eq!(result.potential_loss, 20.0);
+        assert_eq!(result.liquidation_price, None);
+        // Spotta açığa satış yok
+        let short = RiskCalculator::calculate_position_size(
+            MarketCategory::Spot, 1000.0, 100.0, 105.0, None, &costs, &SizingDecision::fixed_fractional(1.0),
+        );
+        assert_eq!(short.position_size, 0.0);
+    }
+
+    #[test]
+    fn test_sizing_models() {
+        // Son iki işlem kayıp: 30 işlem, %50 kazanma, ödeme oranı 2
+        let mut outcomes = vec![-50.0, -50.0];
+        outcomes.extend((0..28).map(|i| if i % 2 == 0 { 100.0 } else { -50.0 }));
+        outcomes.push(0.0); // Başa baş işlem sayılmaz
+        let stats = RiskCalculator::trade_stats(&outcomes);
+        assert_eq!((stats.trades, stats.losing_streak), (30, 2));
+        assert!((stats.win_rate - 14.0 / 30.0).abs() < 1e-9);
+
+        let stats = TradeStats { trades: 30, win_rate: 0.5, avg_win: 100.0, avg_loss: 50.0, losing_streak: 2 };
+        // Kelly: 0.5 - 0.5 / 2 = %25, çeyrek Kelly = %6.25
+        let kelly = RiskCalculator::decide_sizing(&SizingModel::Kelly { fraction: 0.25 }, 1.0, None, &stats);
+        assert!((kelly.risk_percent - 6.25).abs() < 1e-9);
+        // Yetersiz geçmişte sabit orana düşülür
+        let few = TradeStats { trades: 5, ..stats.clone() };
+        let fallback = RiskCalculator::decide_sizing(&SizingModel::Kelly { fraction: 0.25 }, 1.0, None, &few);
+        assert_eq!((fallback.model, fallback.risk_percent), (SizingModel::FixedFractional, 1.0));
+
+        let curve = SizingModel::EquityCurve { reduction_pct: 25.0, min_factor: 0.3 };
+        assert_eq!(RiskCalculator::decide_sizing(&curve, 2.0, None, &stats).risk_percent, 1.0);
+
+        // Volatilite hedefi: 2 ATR (2 x 2.5 = 5) mesafe, stop daha uzak olsa da boyut 100 / 5
+        let model = SizingModel::VolatilityTarget { interval: "60".into(), period: 14, multiple: 2.0 };
+        let decision = RiskCalculator::decide_sizing(&model, 1.0, Some(2.5), &stats);
+        let result = RiskCalculator::calculate_position_size(
+            MarketCategory::Linear, 10000.0, 100.0, 90.0, None, &TradeCosts::default(), &decision,
+        );
+        assert_eq!(result.position_size, 20.0);
+        assert_eq!(result.potential_loss, 200.0);
+        assert_eq!(result.sizing_model, "volatility_target");
+
+        let mut decision = RiskCalculator::decide_sizing(&SizingModel::FixedContracts { contracts: 3.0 }, 1.0, None, &stats);
+        decision.scale(0.5, "sizing.drawdown_factor");
+        let result = RiskCalculator::calculate_position_size(
+            MarketCategory::Linear, 10000.0, 100.0, 95.0, None, &TradeCosts::default(), &decision,
+        );
+        assert_eq!(result.position_size, 1.5);
+    }
+
+    #[test]
+    fn test_take_profit_ladder() {
+        let lot = LotSize { qty_step: 0.01, min_qty: 0.05, tick_size: 0.5 };
+        let level = |r_multiple, percent| TakeProfitLevel { r_multiple, percent };
+
+        // Long: 30% @ 1R, 30% @ 2R, runner
+        let targets = RiskCalculator::calculate_take_profit_ladder(
+            100.0, 95.0, 1.0, &[level(2.0, 30.0), level(1.0, 30.0)], &lot,
+        );
+        assert_eq!(targets.len(), 2);
+        assert_eq!((targets[0].price, targets[0].quantity), (105.0, 0.3));
+        assert_eq!((targets[1].price, targets[1].quantity), (110.0, 0.3));
+
+        // Short, son kademe kalan miktarın tamamını alır; küçük kademe atlanır
+        let targets = RiskCalculator::calculate_take_profit_ladder(
+            100.0, 103.3, 0.37, &[level(1.0, 10.0), level(1.5, 50.0), level(3.0, 40.0)], &lot,
+        );
+        assert_eq!(targets.len(), 2);
+        assert_eq!((targets[0].price, targets[0].quantity), (95.0, 0.18));
+        assert_eq!((targets[1].price, targets[1].quantity), (90.0, 0.19));
+    }
+
     #[test]
     fn test_cumulative_risk() {
         let (total, percent) = RiskCalculator::calculate_cumulative_risk(
@@ -124,6 +628,31 @@ mod tests {
         assert_eq!(percent, 3.0);
     }
 
+    #[test]
+    fn test_open_risk() {
+        let mut position = Position {
+            id: "ETHUSDT-2".into(),
+            symbol: "ETHUSDT".into(),
+            side: PositionSide::Short,
+            entry_price: 2000.0,
+            quantity: 2.0,
+            stop_loss: Some(2100.0),
+            take_profit: None,
+            created_at: chrono::Utc::now(),
+        };
+        assert_eq!(RiskCalculator::calculate_open_risk(&position, 1950.0), 300.0);
+        // Kâra kilitlenmiş stop: sadece geri verilecek kâr
+        position.stop_loss = Some(1900.0);
+        assert_eq!(RiskCalculator::calculate_open_risk(&position, 1850.0), 100.0);
+        position.stop_loss = None;
+        assert_eq!(RiskCalculator::calculate_open_risk(&position, 1950.0), 3900.0);
+
+        // Ters kontrat: 200 USD kontrat, 64'ten 128'e stop = 200 / 64 - 200 / 128 coin = 1.5625 coin = 100 USD
+        let inverse = Position { symbol: "BTCUSD".into(), entry_price: 64.0, quantity: 200.0, stop_loss: Some(128.0), ..position };
+        assert_eq!(RiskCalculator::calculate_open_risk(&inverse, 64.0), 100.0);
+        assert_eq!(RiskCalculator::notional(MarketCategory::Inverse, 64.0, 200.0), 200.0);
+    }
+
     #[test]
     fn test_daily_loss_limit() {
         // Limit aşılmadı
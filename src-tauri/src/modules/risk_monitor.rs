@@ -0,0 +1,354 @@
+// AlgoTrade OS - Risk Monitor Module
+// Kümülatif risk takibi ve canlı pozisyon büyüklüğü görünümü
+//
+// Akış:
+// - Pozisyonlar ve hesap marjini zamanlayıcıyla borsadan okunur
+// - Açık risk = stop tetiklenirse güncel fiyattan kaybedilecek tutar (stopsuz pozisyonda tüm büyüklük)
+// - Toplam risk %, baz varlık / yön başına büyüklük ve marjin kullanımı AppState'e yazılır
+// - Değer limitin uyarı oranını aştığında warning.risk_approaching bir kez yayınlanır;
+//   değer eşiğin altına inince uyarı tekrar kurulur
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
+use crate::core::{AppEvent, AppState, ScheduleSpec};
+use crate::exchange::bybit::MarketCategory;
+use crate::exchange::executor::AccountBalance;
+use crate::models::{
+    AssetExposure, Position, PositionRisk, PositionSide, RiskSnapshot, RiskWarning, RiskWarningKind,
+    SideExposure, StandardTick,
+};
+use super::config::{self, ConfigField, ModuleConfig};
+use super::risk_calculator::RiskCalculator;
+use super::traits::{TradingModule, ModuleError};
+
+/// Ayar etiketleri için i18n ön eki
+const CONFIG_PREFIX: &str = "module.risk_monitor.config";
+
+/// Görünümü yenileyen zamanlayıcı
+const REFRESH_TIMER: &str = "refresh";
+const REFRESH_INTERVAL_SECS: u64 = 5;
+
+/// Yayınlanan uyarıların i18n anahtarı
+const WARNING_KEY: &str = "warning.risk_approaching";
+
+/// Sembolden ayrılan kote para birimleri (uzun olan önce denenir)
+const QUOTE_SUFFIXES: [&str; 4] = ["USDT", "USDC", "PERP", "USD"];
+
+/// Sembolün baz varlığı (BTCUSDT -> BTC, BTCUSD-26DEC25 -> BTC)
+pub fn base_asset(symbol: &str) -> &str {
+    let pair = symbol.split('-').next().unwrap_or(symbol);
+    QUOTE_SUFFIXES
+        .iter()
+        .find_map(|quote| pair.strip_suffix(quote).filter(|base| !base.is_empty()))
+        .unwrap_or(pair)
+}
+
+/// Pozisyonlar ve hesap bakiyesinden risk görünümü oluştur (uyarılar hariç)
+///
+/// `prices`: sembol -> son fiyat (eksikse giriş fiyatı kullanılır)
+pub fn build_snapshot(
+    positions: &[Position],
+    prices: &HashMap<String, f64>,
+    balance: AccountBalance,
+    now: DateTime<Utc>,
+) -> RiskSnapshot {
+    let percent_of_equity = |value: f64| {
+        if balance.equity > 0.0 {
+            value / balance.equity * 100.0
+        } else {
+            0.0
+        }
+    };
+
+    let position_risks: Vec<PositionRisk> = positions
+        .iter()
+        .map(|position| {
+            let price = prices.get(&position.symbol).copied().unwrap_or(position.entry_price);
+            let open_risk = RiskCalculator::calculate_open_risk(position, price);
+            PositionRisk {
+                position_id: position.id.clone(),
+                symbol: position.symbol.clone(),
+                side: position.side.clone(),
+                quantity: position.quantity,
+                entry_price: position.entry_price,
+                price,
+                stop_loss: position.stop_loss,
+                notional: RiskCalculator::notional(MarketCategory::for_symbol(&position.symbol), price, position.quantity),
+                open_risk,
+                risk_percent: percent_of_equity(open_risk),
+            }
+        })
+        .collect();
+
+    let (total_risk, total_risk_percent) = RiskCalculator::calculate_cumulative_risk(
+        balance.equity,
+        position_risks.iter().map(|p| p.open_risk).collect(),
+    );
+
+    let mut by_asset: BTreeMap<&str, (f64, f64)> = BTreeMap::new();
+    let mut by_side = SideExposure::default();
+    for risk in &position_risks {
+        let entry = by_asset.entry(base_asset(&risk.symbol)).or_default();
+        match risk.side {
+            PositionSide::Long => {
+                entry.0 += risk.notional;
+                by_side.long += risk.notional;
+            }
+            PositionSide::Short => {
+                entry.1 += risk.notional;
+                by_side.short += risk.notional;
+            }
+        }
+    }
+    by_side.net = by_side.long - by_side.short;
+    by_side.gross = by_side.long + by_side.short;
+
+    let mut exposure_by_asset: Vec<AssetExposure> = by_asset
+        .into_iter()
+        .map(|(asset, (long, short))| AssetExposure { asset: asset.to_string(), long, short, net: long - short })
+        .collect();
+    // En büyük brüt büyüklük önce
+    exposure_by_asset.sort_by(|a, b| (b.long + b.short).total_cmp(&(a.long + a.short)));
+
+    RiskSnapshot {
+        equity: balance.equity,
+        available_balance: balance.available,
+        positions: position_risks,
+        total_risk,
+        total_risk_percent,
+        exposure_by_asset,
+        exposure_by_side: by_side,
+        initial_margin: balance.initial_margin,
+        maintenance_margin: balance.maintenance_margin,
+        margin_utilization: percent_of_equity(balance.initial_margin),
+        warnings: Vec::new(),
+        updated_at: now,
+    }
+}
+
+/// Risk izleme modülü - hesap riskini sürekli hesaplar, limite yaklaşınca uyarır
+pub struct RiskMonitorModule {
+    is_active: bool,
+    state: Option<Arc<AppState>>,
+    /// Toplam açık riskin hesap varlığına oranı için limit (%)
+    max_account_risk_pct: f64,
+    /// En yüksek marjin kullanımı (%)
+    max_margin_utilization_pct: f64,
+    /// Limitin yüzde kaçında uyarılır
+    warn_at_pct: f64,
+    /// Şu an eşiğin üzerinde olan (yayınlanmış) uyarılar
+    raised: HashSet<RiskWarningKind>,
+}
+
+impl RiskMonitorModule {
+    pub fn new() -> Self {
+        Self {
+            is_active: false,
+            state: None,
+            max_account_risk_pct: 5.0,
+            max_margin_utilization_pct: 50.0,
+            warn_at_pct: 80.0,
+            raised: HashSet::new(),
+        }
+    }
+
+    /// Uyarı eşiğini aşan değerler
+    fn warnings(&self, snapshot: &RiskSnapshot, now: DateTime<Utc>) -> Vec<RiskWarning> {
+        [
+            (RiskWarningKind::AccountRisk, snapshot.total_risk_percent, self.max_account_risk_pct),
+            (RiskWarningKind::MarginUtilization, snapshot.margin_utilization, self.max_margin_utilization_pct),
+        ]
+        .into_iter()
+        .filter(|(_, value, limit)| *value >= limit * self.warn_at_pct / 100.0)
+        .map(|(kind, value, limit)| RiskWarning {
+            kind,
+            message_key: WARNING_KEY.to_string(),
+            value,
+            limit,
+            raised_at: now,
+        })
+        .collect()
+    }
+
+    /// Görünümü borsadan yenile, yeni aşılan eşikler için uyarı yayınla
+    async fn refresh(&mut self, now: DateTime<Utc>) -> Result<(), ModuleError> {
+        let state = self.state.clone().ok_or_else(|| {
+            ModuleError::InitializationFailed("State not initialized".into())
+        })?;
+        let Some(executor) = state.executor().await else {
+            return Ok(()); // Bağlantı yokken görünüm yenilenmez
+        };
+
+        let (positions, balance) = tokio::join!(executor.open_positions(), executor.account_balance());
+        let positions = positions.map_err(|e| ModuleError::ExecutionFailed(e.to_string()))?;
+        let balance = balance.map_err(|e| ModuleError::ExecutionFailed(e.to_string()))?;
+        let drawdown_limits = state.settings.read().await.drawdown_limits.clone();
+        state.drawdown.record(balance.equity, &drawdown_limits, now).await;
+        let prices: HashMap<String, f64> = state
+            .live_prices
+            .read()
+            .await
+            .iter()
+            .map(|(symbol, tick)| (symbol.clone(), tick.price))
+            .collect();
+
+        let mut snapshot = build_snapshot(&positions, &prices, balance, now);
+        snapshot.warnings = self.warnings(&snapshot, now);
+
+        for warning in &snapshot.warnings {
+            if !self.raised.contains(&warning.kind) {
+                tracing::warn!(
+                    "Risk limitine yaklaşıldı ({:?}): %{:.2} / %{:.2}",
+                    warning.kind, warning.value, warning.limit
+                );
+                let _ = state.dispatcher.publish(AppEvent::RiskWarning(warning.clone()));
+            }
+        }
+        self.raised = snapshot.warnings.iter().map(|w| w.kind).collect();
+
+        *state.risk_snapshot.write().await = Some(snapshot);
+        Ok(())
+    }
+}
+
+impl Default for RiskMonitorModule {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl TradingModule for RiskMonitorModule {
+    fn id(&self) -> &str {
+        "risk_monitor"
+    }
+
+    fn name(&self) -> &str {
+        "Risk Monitor"
+    }
+
+    fn description(&self) -> &str {
+        "Kümülatif risk takibi ve günlük limit kontrolü"
+    }
+
+    async fn initialize(&mut self, state: Arc<AppState>) -> Result<(), ModuleError> {
+        self.state = Some(state);
+        tracing::info!("RiskMonitor modülü başlatıldı");
+        Ok(())
+    }
+
+    async fn shutdown(&mut self) -> Result<(), ModuleError> {
+        if let Some(state) = self.state.take() {
+            *state.risk_snapshot.write().await = None;
+        }
+        self.raised.clear();
+        tracing::info!("RiskMonitor modülü durduruldu");
+        Ok(())
+    }
+
+    async fn on_price_tick(&mut self, _tick: &StandardTick) -> Result<(), ModuleError> {
+        // Görünüm tick yerine zamanlayıcıyla yenilenir
+        Ok(())
+    }
+
+    async fn on_balance_change(&mut self, _symbol: &str, _new_balance: f64) -> Result<(), ModuleError> {
+        Ok(())
+    }
+
+    async fn on_position_opened(&mut self, _position: &Position) -> Result<(), ModuleError> {
+        Ok(())
+    }
+
+    async fn on_position_closed(&mut self, _position: &Position, _pnl: f64) -> Result<(), ModuleError> {
+        Ok(())
+    }
+
+    fn schedules(&self) -> Vec<ScheduleSpec> {
+        vec![ScheduleSpec::every(REFRESH_TIMER, REFRESH_INTERVAL_SECS)]
+    }
+
+    async fn on_timer(&mut self, timer_id: &str, now: DateTime<Utc>) -> Result<(), ModuleError> {
+        if !self.is_active || timer_id != REFRESH_TIMER {
+            return Ok(());
+        }
+        self.refresh(now).await
+    }
+
+    fn config_schema(&self) -> Vec<ConfigField> {
+        vec![
+            ConfigField::number("max_account_risk_pct", CONFIG_PREFIX, 5.0, 0.1, 100.0, 0.1),
+            ConfigField::number("max_margin_utilization_pct", CONFIG_PREFIX, 50.0, 1.0, 100.0, 1.0),
+            ConfigField::number("warn_at_pct", CONFIG_PREFIX, 80.0, 10.0, 100.0, 5.0),
+        ]
+    }
+
+    fn get_config(&self) -> ModuleConfig {
+        let mut config = ModuleConfig::new();
+        config.insert("max_account_risk_pct".into(), self.max_account_risk_pct.into());
+        config.insert("max_margin_utilization_pct".into(), self.max_margin_utilization_pct.into());
+        config.insert("warn_at_pct".into(), self.warn_at_pct.into());
+        config
+    }
+
+    fn apply_config(&mut self, config: &ModuleConfig) -> Result<(), ModuleError> {
+        self.max_account_risk_pct =
+            config::get_f64(config, "max_account_risk_pct", self.max_account_risk_pct);
+        self.max_margin_utilization_pct =
+            config::get_f64(config, "max_margin_utilization_pct", self.max_margin_utilization_pct);
+        self.warn_at_pct = config::get_f64(config, "warn_at_pct", self.warn_at_pct);
+        Ok(())
+    }
+
+    fn is_active(&self) -> bool {
+        self.is_active
+    }
+
+    fn set_active(&mut self, active: bool) {
+        self.is_active = active;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing;
+
+    fn position(symbol: &str, side: PositionSide, entry: f64, quantity: f64, stop: Option<f64>) -> Position {
+        Position { side, entry_price: entry, quantity, stop_loss: stop, ..testing::position(symbol) }
+    }
+
+    #[test]
+    fn test_snapshot_and_warnings() {
+        let positions = vec![
+            position("BTCUSDT", PositionSide::Long, 100.0, 2.0, Some(95.0)),
+            position("BTCUSD", PositionSide::Short, 64.0, 200.0, Some(128.0)),
+            position("ETHUSDT", PositionSide::Long, 50.0, 4.0, None),
+        ];
+        let prices = HashMap::from([("BTCUSDT".to_string(), 105.0)]);
+        let balance = AccountBalance { equity: 1000.0, available: 600.0, initial_margin: 420.0, maintenance_margin: 40.0, unrealized_pnl: 0.0 };
+        let snapshot = build_snapshot(&positions, &prices, balance, Utc::now());
+
+        // (105 - 95) * 2 + ters kontrat 200 x (1/64 - 1/128) coin x 64 + 50 * 4 (stopsuz)
+        assert_eq!(snapshot.total_risk, 320.0);
+        assert_eq!(snapshot.total_risk_percent, 32.0);
+        assert_eq!(snapshot.margin_utilization, 42.0);
+
+        // Ters kontratın büyüklüğü USD kontrat sayısıdır
+        let btc = &snapshot.exposure_by_asset[0];
+        assert_eq!((btc.asset.as_str(), btc.long, btc.short, btc.net), ("BTC", 210.0, 200.0, 10.0));
+        assert_eq!(snapshot.exposure_by_asset[1].asset, "ETH");
+        assert_eq!(snapshot.exposure_by_side.gross, 610.0);
+        assert_eq!(snapshot.exposure_by_side.net, 210.0);
+
+        let mut module = RiskMonitorModule::new();
+        module.max_account_risk_pct = 35.0;
+        let kinds: Vec<RiskWarningKind> = module.warnings(&snapshot, Utc::now()).iter().map(|w| w.kind).collect();
+        // 32 >= 35 * 0.8, 42 >= 50 * 0.8
+        assert_eq!(kinds, vec![RiskWarningKind::AccountRisk, RiskWarningKind::MarginUtilization]);
+
+        module.max_account_risk_pct = 45.0;
+        assert_eq!(module.warnings(&snapshot, Utc::now()).len(), 1);
+    }
+}
@@ -0,0 +1,146 @@
+// AlgoTrade OS - Exit Rules
+// Pozisyon planındaki zaman, volatilite ve seans sonu çıkış kuralları
+
+use chrono::{DateTime, Duration, Utc};
+use crate::core::klines::MAX_CANDLES;
+use crate::core::scheduler::{candle_open, next_candle_close};
+use crate::exchange::bybit::Kline;
+use crate::indicators::{atr, true_range};
+use crate::models::{SessionEnd, TimeLimit, TimeStop, VolatilityMeasure, VolatilityStop};
+
+/// Kural kaynaklı çıkış nedeni
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitReason {
+    TimeStop,
+    VolatilityStop,
+    SessionEnd,
+}
+
+impl ExitReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ExitReason::TimeStop => "time_stop",
+            ExitReason::VolatilityStop => "volatility_stop",
+            ExitReason::SessionEnd => "session_end",
+        }
+    }
+}
+
+/// Zaman stopunun dolduğu an (aralık tanınmıyorsa None)
+pub fn time_stop_deadline(limit: &TimeLimit, opened_at: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    match limit {
+        TimeLimit::Minutes { minutes } => Some(opened_at + Duration::minutes(i64::from(*minutes))),
+        // Giriş mumunun kapanışı ilk mum sayılır
+        TimeLimit::Candles { interval, count } => {
+            (0..*count).try_fold(opened_at, |time, _| next_candle_close(interval, time))
+        }
+    }
+}
+
+/// Süre doldu ve pozisyonun gördüğü en iyi kâr hedefin altında mı?
+pub fn time_stop_hit(rule: &TimeStop, opened_at: DateTime<Utc>, best_r: f64, now: DateTime<Utc>) -> bool {
+    time_stop_deadline(&rule.limit, opened_at).is_some_and(|deadline| now >= deadline) && best_r < rule.min_r
+}
+
+/// `from` anının mumundan `now` anının mumuna kadar (ikisi dahil) mum sayısı
+pub fn bars_since(interval: &str, from: DateTime<Utc>, now: DateTime<Utc>) -> usize {
+    let (Some(mut open), Some(last)) = (candle_open(interval, from), candle_open(interval, now)) else {
+        return 0;
+    };
+    let mut count = 0;
+    while open <= last && count <= MAX_CANDLES {
+        count += 1;
+        match next_candle_close(interval, open) {
+            Some(next) => open = next,
+            None => break,
+        }
+    }
+    count
+}
+
+/// Giriş anındaki ATR (girişten önce kapanmış mumlardan)
+pub fn entry_atr(rule: &VolatilityStop, klines: &[Kline], opened_at: DateTime<Utc>) -> Option<f64> {
+    let open = candle_open(&rule.interval, opened_at)?.timestamp_millis();
+    let end = klines.partition_point(|k| k.timestamp < open);
+    atr(&klines[..end], rule.period)
+}
+
+/// Güncel volatilite giriş ATR'sinin katını aştı mı?
+///
+/// `klines`: kuralın aralığındaki seri (açık mum dahil, eskiden yeniye)
+pub fn volatility_hit(rule: &VolatilityStop, entry_atr: f64, klines: &[Kline], now: DateTime<Utc>) -> bool {
+    let Some(open) = candle_open(&rule.interval, now) else {
+        return false;
+    };
+    let closed = klines.partition_point(|k| k.timestamp < open.timestamp_millis());
+
+    let current = match rule.measure {
+        VolatilityMeasure::Atr => atr(&klines[..closed], rule.period),
+        VolatilityMeasure::Range => klines
+            .get(closed)
+            .map(|k| true_range(k, closed.checked_sub(1).map(|i| klines[i].close))),
+    };
+    current.is_some_and(|value| value > entry_atr * rule.multiplier)
+}
+
+/// Girişten sonraki ilk seans kapanışı geçti mi?
+pub fn session_end_hit(rule: &SessionEnd, opened_at: DateTime<Utc>, now: DateTime<Utc>) -> bool {
+    let mut close = opened_at.date_naive().and_time(rule.close_at).and_utc();
+    if close <= opened_at {
+        close += Duration::days(1);
+    }
+    now >= close
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveTime;
+
+    fn at(secs: i64) -> DateTime<Utc> {
+        DateTime::from_timestamp(secs, 0).unwrap()
+    }
+
+    fn kline(minute: i64, high: f64, low: f64) -> Kline {
+        Kline { timestamp: minute * 60_000, open: low, high, low, close: (high + low) / 2.0, volume: 1.0 }
+    }
+
+    #[test]
+    fn test_time_and_session_rules() {
+        let opened_at = at(90); // 00:01:30
+        let candles = TimeLimit::Candles { interval: "1".into(), count: 3 };
+        assert_eq!(time_stop_deadline(&candles, opened_at), Some(at(240)));
+        // 00:07 -> 01:07 arası 15 dakikalık mumlar: 00:00, 00:15, 00:30, 00:45, 01:00
+        assert_eq!(bars_since("15", at(7 * 60), at(67 * 60)), 5);
+        assert_eq!(bars_since("x", at(0), at(60)), 0);
+
+        let rule = TimeStop { limit: TimeLimit::Minutes { minutes: 5 }, min_r: 1.0 };
+        assert!(!time_stop_hit(&rule, opened_at, 0.2, at(300)));
+        assert!(time_stop_hit(&rule, opened_at, 0.2, at(390)));
+        assert!(!time_stop_hit(&rule, opened_at, 1.5, at(390)));
+
+        let session = SessionEnd { close_at: NaiveTime::from_hms_opt(0, 1, 0).unwrap() };
+        // 00:01 girişten önce geçti -> ertesi gün
+        assert!(!session_end_hit(&session, opened_at, at(3_600)));
+        assert!(session_end_hit(&session, opened_at, at(86_460)));
+    }
+
+    #[test]
+    fn test_volatility_against_entry_atr() {
+        // Girişten önce sakin mumlar (TR 2), sonra genişleyen mumlar
+        let mut klines: Vec<Kline> = (0..5).map(|m| kline(m, 101.0, 99.0)).collect();
+        let opened_at = at(5 * 60 + 10);
+        klines.push(kline(5, 104.0, 96.0)); // TR 8
+        klines.push(kline(6, 104.5, 96.5)); // Açık mum, TR 8
+
+        let atr_rule = VolatilityStop { interval: "1".into(), period: 2, multiplier: 2.0, measure: VolatilityMeasure::Atr };
+        let entry = entry_atr(&atr_rule, &klines, opened_at).unwrap();
+        assert_eq!(entry, 2.0);
+        // Kapanmış ATR: (2 + 8) / 2 = 5 > 4
+        assert!(volatility_hit(&atr_rule, entry, &klines, at(6 * 60 + 30)));
+        assert!(!volatility_hit(&VolatilityStop { multiplier: 3.0, ..atr_rule.clone() }, entry, &klines, at(6 * 60 + 30)));
+
+        let range_rule = VolatilityStop { multiplier: 3.0, measure: VolatilityMeasure::Range, ..atr_rule };
+        assert!(volatility_hit(&range_rule, entry, &klines, at(6 * 60 + 30)));
+    }
+}
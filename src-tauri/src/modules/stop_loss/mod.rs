@@ -0,0 +1,1057 @@
+// AlgoTrade OS - Smart Stop-Loss Module
+// Akıllı stop-loss ve risk yönetimi modülü
+//
+// Tetikleyiciler (roadmap/MODULE_STOP_LOSS.md):
+// - Price > Target: stopu girişe (+ komisyon payı) çek
+// - Price < Stop: pozisyonu kapat ve bekleyen kâr al emirlerini iptal et
+// Pozisyon planında iz süren stop seçildiyse stop her tick'te sadece kâr yönünde taşınır.
+// Planda kâr al kademeleri varsa reduce-only limit emirler olarak bir kez gönderilir;
+// her kademe dolduğunda stop plana göre dengelenir (girişe / önceki kademeye).
+// Plandaki çıkış kuralları (zaman stopu, volatilite stopu, seans sonu) pozisyonu kapatır.
+// Her stop hareketi nedeniyle birlikte kaydedilir (StopMoved olayı + stop_moves tablosu).
+// İzlenen pozisyonlar core::positions tarafından borsayla eşitlenir (açılış/kapanış olayları dahil).
+
+pub mod exits;
+pub mod take_profit;
+pub mod trailing;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use crate::core::{klines, AppEvent, AppState};
+use crate::db;
+use crate::exchange::bybit::Kline;
+use crate::exchange::executor::{closing_side, OrderExecutor, OrderRequest};
+use crate::models::{OrderType, StandardTick, Position, PositionPlan, PositionSide, StopMove, StopMoveReason};
+use super::risk_calculator::RiskCalculator;
+use super::traits::{TradingModule, ModuleError};
+use exits::ExitReason;
+use take_profit::{Ladder, LadderStatus};
+use super::config::{self, ConfigField, ModuleConfig};
+
+/// Ayar etiketleri için i18n ön eki
+const CONFIG_PREFIX: &str = "module.stop_loss.config";
+
+/// Başarısız borsa isteğinden sonra yeniden denemeden önce bekleme
+const RETRY_COOLDOWN: Duration = Duration::from_secs(5);
+
+/// Fiyat kâr al seviyesindeyken dolum kontrolleri arasındaki bekleme
+const FILL_CHECK_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Dolum miktarı karşılaştırmalarında kayan nokta toleransı
+const QTY_EPSILON: f64 = 1e-9;
+
+/// Pozisyon başına yapılmış işlemler - aynı işlem her tick'te tekrar gönderilmez
+#[derive(Debug)]
+struct PositionTrack {
+    symbol: String,
+    /// İlk stop seviyesi (1R stop taşındıktan sonra da bunun üzerinden hesaplanır)
+    initial_stop: Option<f64>,
+    /// İzleme başladığından beri en iyi fiyat (long: en yüksek, short: en düşük)
+    extreme: f64,
+    breakeven_done: bool,
+    close_sent: bool,
+    take_profits_cancelled: bool,
+    /// Plandan oluşturulan kâr al merdiveni
+    ladder: Option<Ladder>,
+    /// Volatilite stopu için giriş anındaki ATR
+    entry_atr: Option<f64>,
+    /// Başarısız istekten sonra bu ana kadar yeni deneme yapılmaz
+    retry_at: Option<Instant>,
+    /// Mumları yüklenmiş plan aralıkları
+    klines_loaded: HashSet<String>,
+    /// Mum yüklemesi başarısız olduysa bu ana kadar yeni deneme yapılmaz
+    klines_retry_at: Option<Instant>,
+}
+
+impl PositionTrack {
+    fn new(position: &Position) -> Self {
+        Self {
+            symbol: position.symbol.clone(),
+            initial_stop: position.stop_loss,
+            extreme: position.entry_price,
+            breakeven_done: false,
+            close_sent: false,
+            take_profits_cancelled: false,
+            ladder: None,
+            entry_atr: None,
+            retry_at: None,
+            klines_loaded: HashSet::new(),
+            klines_retry_at: None,
+        }
+    }
+}
+
+/// Tick sonrası yapılacak işlem
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum StopAction {
+    /// Stopu verilen seviyeye taşı
+    MoveStop(f64, StopMoveReason),
+    /// Plandaki kâr al kademelerini gönder
+    PlaceTakeProfits,
+    /// Fiyat kâr al seviyesinde: dolumları kontrol et, stopu dengele
+    SyncTakeProfits,
+    /// Plandaki çıkış kuralıyla pozisyonu kapat
+    Exit(ExitReason),
+    /// Pozisyonu kapat, kâr al emirlerini iptal et
+    Close,
+}
+
+/// Plan kurallarının kullandığı mum serileri
+#[derive(Default)]
+struct PlanKlines {
+    /// İz süren stop aralığındaki kapanmış mumlar
+    trailing: Vec<Kline>,
+    /// Volatilite stopu aralığındaki mumlar (açık mum dahil)
+    volatility: Vec<Kline>,
+}
+
+/// Akıllı Stop-Loss modülü
+pub struct StopLossModule {
+    is_active: bool,
+    state: Option<Arc<AppState>>,
+    /// Auto-breakeven aktif mi?
+    auto_breakeven: bool,
+    /// Breakeven için gereken R miktarı (örn: 1.0 = 1R)
+    breakeven_threshold: f64,
+    /// Breakeven stopunun girişten uzaklığı (%, komisyonları karşılar)
+    breakeven_offset_pct: f64,
+    /// İz süren stopun en küçük hareketi (fiyatın %'si, borsaya gereksiz istek atılmaz)
+    min_trail_step_pct: f64,
+    /// Pozisyon kimliği -> yapılan işlemler
+    tracks: HashMap<String, PositionTrack>,
+}
+
+impl StopLossModule {
+    pub fn new() -> Self {
+        Self {
+            is_active: false,
+            state: None,
+            auto_breakeven: true,
+            breakeven_threshold: 1.0,
+            breakeven_offset_pct: 0.1,
+            min_trail_step_pct: 0.05,
+            tracks: HashMap::new(),
+        }
+    }
+
+    /// Pozisyon için breakeven kontrolü (risk ilk stoptan hesaplanır)
+    fn check_breakeven(&self, position: &Position, initial_stop: f64, current_price: f64) -> bool {
+        let entry = position.entry_price;
+
+        // Risk miktarı (1R)
+        let risk = (entry - initial_stop).abs();
+        if risk == 0.0 {
+            return false;
+        }
+
+        match position.side {
+            PositionSide::Long => {
+                // Long pozisyon: Fiyat entry + threshold*R üzerindeyse breakeven
+                current_price >= entry + (risk * self.breakeven_threshold)
+            }
+            PositionSide::Short => {
+                // Short pozisyon: Fiyat entry - threshold*R altındaysa breakeven
+                current_price <= entry - (risk * self.breakeven_threshold)
+            }
+        }
+    }
+
+    /// Breakeven stop seviyesi (giriş + komisyon payı)
+    fn breakeven_stop(&self, position: &Position) -> f64 {
+        let offset = position.entry_price * self.breakeven_offset_pct / 100.0;
+        match position.side {
+            PositionSide::Long => position.entry_price + offset,
+            PositionSide::Short => position.entry_price - offset,
+        }
+    }
+
+    /// Pozisyon ve fiyata göre yapılacak işlem (yapılmış işlemler tekrar döndürülmez)
+    fn next_action(
+        &self,
+        position: &Position,
+        track: &PositionTrack,
+        plan: &PositionPlan,
+        klines: &PlanKlines,
+        tick: &StandardTick,
+    ) -> Option<StopAction> {
+        if track.close_sent && track.take_profits_cancelled {
+            return None;
+        }
+        if track.close_sent {
+            return Some(StopAction::Close);
+        }
+        let price = tick.price;
+
+        // Stop-loss kontrolü
+        if let Some(stop) = position.stop_loss {
+            let should_close = match position.side {
+                PositionSide::Long => price <= stop,
+                PositionSide::Short => price >= stop,
+            };
+            if should_close {
+                return Some(StopAction::Close);
+            }
+        }
+
+        // Çıkış kuralları
+        if let Some(reason) = self.exit_reason(position, track, plan, &klines.volatility, tick.timestamp) {
+            return Some(StopAction::Exit(reason));
+        }
+
+        // Kâr al merdiveni (ilk stop olmadan R hesaplanamaz)
+        if !plan.take_profits.is_empty()
+            && track.initial_stop.is_some()
+            && !track.ladder.as_ref().is_some_and(Ladder::is_placed)
+        {
+            return Some(StopAction::PlaceTakeProfits);
+        }
+        if let Some(ladder) = &track.ladder {
+            let due = ladder.next_check.is_none_or(|at| Instant::now() >= at);
+            let reached = ladder
+                .next_pending()
+                .is_some_and(|o| take_profit::reached(&position.side, o.target.price, price));
+            if due && reached {
+                return Some(StopAction::SyncTakeProfits);
+            }
+        }
+
+        let mut candidates = Vec::new();
+
+        // Breakeven kontrolü
+        if self.auto_breakeven && !track.breakeven_done {
+            if let Some(initial_stop) = track.initial_stop {
+                if self.check_breakeven(position, initial_stop, price) {
+                    candidates.push((self.breakeven_stop(position), StopMoveReason::Breakeven));
+                }
+            }
+        }
+
+        // İz süren stop
+        if let Some(trailing_stop) = &plan.trailing {
+            if self.r_progress(position, track, price) >= trailing_stop.activate_at_r {
+                if let Some(level) =
+                    trailing::candidate(&trailing_stop.mode, &position.side, track.extreme, &klines.trailing)
+                {
+                    candidates.push((level, trailing::reason(&trailing_stop.mode)));
+                }
+            }
+        }
+
+        // Mevcut stoptan daha koruyucu olan en iyi seviye
+        let min_step = price * self.min_trail_step_pct / 100.0;
+        candidates
+            .into_iter()
+            .filter(|(level, reason)| {
+                let step = if *reason == StopMoveReason::Breakeven { 0.0 } else { min_step };
+                Self::improves_stop(position, *level, price, step)
+            })
+            .reduce(|best, next| {
+                let better = match position.side {
+                    PositionSide::Long => next.0 > best.0,
+                    PositionSide::Short => next.0 < best.0,
+                };
+                if better { next } else { best }
+            })
+            .map(|(level, reason)| StopAction::MoveStop(level, reason))
+    }
+
+    /// Tetiklenen plan çıkış kuralı (ilk stop yoksa zaman stopu uygulanmaz)
+    fn exit_reason(
+        &self,
+        position: &Position,
+        track: &PositionTrack,
+        plan: &PositionPlan,
+        volatility_klines: &[Kline],
+        now: DateTime<Utc>,
+    ) -> Option<ExitReason> {
+        if let Some(rule) = &plan.session_end {
+            if exits::session_end_hit(rule, position.created_at, now) {
+                return Some(ExitReason::SessionEnd);
+            }
+        }
+        if let Some(rule) = &plan.time_stop {
+            let best_r = self.r_progress(position, track, track.extreme);
+            if exits::time_stop_hit(rule, position.created_at, best_r, now) {
+                return Some(ExitReason::TimeStop);
+            }
+        }
+        if let (Some(rule), Some(entry_atr)) = (&plan.volatility_stop, track.entry_atr) {
+            if exits::volatility_hit(rule, entry_atr, volatility_klines, now) {
+                return Some(ExitReason::VolatilityStop);
+            }
+        }
+        None
+    }
+
+    /// Seviye mevcut stoptan daha koruyucu mu (ve fiyatın doğru tarafında mı)?
+    fn improves_stop(position: &Position, level: f64, price: f64, step: f64) -> bool {
+        match position.side {
+            PositionSide::Long => level < price && position.stop_loss.is_none_or(|s| level > s + step),
+            PositionSide::Short => level > price && position.stop_loss.is_none_or(|s| level < s - step),
+        }
+    }
+
+    /// Kâr al kademelerinin dolumlarını işle, pozisyon miktarını düşür ve stopu dengele
+    ///
+    /// Gerçekleşen miktar borsadaki dolum kayıtlarından (emir kimliğine göre) okunur. Bekleyen emirlerde
+    /// olan kademe kısmen dolmuş olabilir; bekleyen emirlerden kalkan kademe tamamı gerçekleştiyse dolmuş,
+    /// iki kontrol boyunca eksik kaldıysa iptal edilmiş sayılır (dolum kaydı gecikmiş olabilir).
+    async fn sync_take_profits(
+        &self,
+        state: &AppState,
+        executor: &dyn OrderExecutor,
+        position: &Position,
+        plan: &PositionPlan,
+        track: &mut PositionTrack,
+        tick: &StandardTick,
+    ) -> Result<(), ModuleError> {
+        let Some(ladder) = track.ladder.as_mut() else {
+            return Ok(());
+        };
+        let open: HashSet<String> = executor
+            .open_orders(&position.symbol)
+            .await
+            .map_err(|e| ModuleError::ExecutionFailed(e.to_string()))?
+            .into_iter()
+            .map(|o| o.order_id)
+            .collect();
+        let since = ladder.placed_at.unwrap_or(position.created_at) - chrono::Duration::minutes(1);
+        let mut executed: HashMap<String, f64> = HashMap::new();
+        for execution in executor
+            .executions(since)
+            .await
+            .map_err(|e| ModuleError::ExecutionFailed(e.to_string()))?
+        {
+            if execution.symbol == position.symbol && !execution.order_id.is_empty() {
+                *executed.entry(execution.order_id).or_default() += execution.quantity;
+            }
+        }
+
+        let executed_before = ladder.executed();
+        let mut closed = false;
+        for order in ladder.orders.iter_mut().filter(|o| o.status == LadderStatus::Open) {
+            let Some(order_id) = order.order_id.as_ref() else {
+                continue;
+            };
+            order.executed = executed.get(order_id).copied().unwrap_or(0.0).min(order.target.quantity);
+            if open.contains(order_id) {
+                order.missing = false;
+            } else if order.executed >= order.target.quantity - QTY_EPSILON {
+                tracing::info!(
+                    "Kâr al doldu: {} {}R @ {} x {}",
+                    position.symbol,
+                    order.target.r_multiple,
+                    order.target.price,
+                    order.target.quantity
+                );
+                order.status = LadderStatus::Filled;
+                closed = true;
+            } else if order.missing {
+                tracing::warn!(
+                    "Kâr al emri dolmadan kapandı: {} {}R @ {} - gerçekleşen {} / {}",
+                    position.symbol,
+                    order.target.r_multiple,
+                    order.target.price,
+                    order.executed,
+                    order.target.quantity
+                );
+                order.status = LadderStatus::Cancelled;
+                closed = true;
+            } else {
+                order.missing = true;
+            }
+        }
+        let progressed = ladder.executed() > executed_before + QTY_EPSILON;
+        // Fiyat seviyede ama emir henüz kapanmadı - her tick'te borsaya sorma
+        ladder.next_check = (!closed).then(|| Instant::now() + FILL_CHECK_INTERVAL);
+        if !progressed && !closed {
+            return Ok(());
+        }
+
+        let position = limit_quantity(state, position, ladder.initial_quantity - ladder.executed()).await;
+        if !closed {
+            return Ok(());
+        }
+        let breakeven = self.breakeven_stop(&position);
+        let Some(stop) = take_profit::rebalanced_stop(plan.rebalance, ladder, breakeven) else {
+            return Ok(());
+        };
+        if !Self::improves_stop(&position, stop, tick.price, 0.0) {
+            return Ok(());
+        }
+
+        move_stop(state, executor, &position, stop, StopMoveReason::TakeProfit, tick).await?;
+        track.breakeven_done = true;
+        Ok(())
+    }
+
+    /// İlk stopa göre R cinsinden kâr (stop yoksa iz sürme hemen başlayabilir)
+    fn r_progress(&self, position: &Position, track: &PositionTrack, price: f64) -> f64 {
+        let Some(risk) = track.initial_stop.map(|s| (position.entry_price - s).abs()).filter(|r| *r > 0.0) else {
+            return f64::INFINITY;
+        };
+        match position.side {
+            PositionSide::Long => (price - position.entry_price) / risk,
+            PositionSide::Short => (position.entry_price - price) / risk,
+        }
+    }
+}
+
+impl Default for StopLossModule {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Planın mum gerektiren kuralları: aralık ve seride gereken en az mum
+fn required_klines(plan: &PositionPlan, position: &Position, now: DateTime<Utc>) -> Vec<(String, usize)> {
+    let mut required = Vec::new();
+    if let Some(trailing_stop) = &plan.trailing {
+        if let Some(interval) = trailing_stop.mode.interval() {
+            // Kapanmış mumlar + açık mum
+            required.push((interval.to_string(), trailing::required_bars(&trailing_stop.mode) + 1));
+        }
+    }
+    if let Some(rule) = &plan.volatility_stop {
+        // Girişten önceki ATR penceresi + girişten bu yana oluşan mumlar
+        let bars = rule.period + 1 + exits::bars_since(&rule.interval, position.created_at, now);
+        required.push((rule.interval.clone(), bars));
+    }
+    required
+}
+
+/// Planın kullandığı seriler önbellekte yetersizse borsadan doldur (aralık başına bir kez)
+async fn load_plan_klines(
+    state: &AppState,
+    position: &Position,
+    plan: &PositionPlan,
+    track: &mut PositionTrack,
+    now: DateTime<Utc>,
+) {
+    if track.klines_retry_at.is_some_and(|at| Instant::now() < at) {
+        return;
+    }
+    for (interval, required) in required_klines(plan, position, now) {
+        if track.klines_loaded.contains(&interval) {
+            continue;
+        }
+        if state.klines.count(&position.symbol, &interval) < required {
+            if let Err(e) = klines::backfill(&state.klines, &position.symbol, &interval, required).await {
+                tracing::warn!("{} {} mumları alınamadı: {}", position.symbol, interval, e);
+                track.klines_retry_at = Some(Instant::now() + RETRY_COOLDOWN);
+                continue;
+            }
+            let available = state.klines.count(&position.symbol, &interval);
+            if available < required {
+                tracing::warn!(
+                    "{} {} için {} mum gerekli, {} mevcut; kural yeterli mum oluşana kadar uygulanmaz",
+                    position.symbol, interval, required, available
+                );
+            }
+        }
+        track.klines_loaded.insert(interval);
+    }
+}
+
+/// Kâr al kademelerini reduce-only limit emir olarak gönder (gönderilmemiş olanlar)
+async fn place_take_profits(
+    executor: &dyn OrderExecutor,
+    position: &Position,
+    plan: &PositionPlan,
+    track: &mut PositionTrack,
+) -> Result<(), ModuleError> {
+    let ladder = match track.ladder.as_mut() {
+        Some(ladder) => ladder,
+        None => {
+            let lot = executor
+                .lot_size(&position.symbol)
+                .await
+                .map_err(|e| ModuleError::ExecutionFailed(e.to_string()))?;
+            let initial_stop = track.initial_stop.unwrap_or(position.entry_price);
+            let targets = RiskCalculator::calculate_take_profit_ladder(
+                position.entry_price,
+                initial_stop,
+                position.quantity,
+                &plan.take_profits,
+                &lot,
+            );
+            if targets.is_empty() {
+                tracing::warn!("Kâr al kademeleri lot adımına uymuyor: {}", position.id);
+            }
+            track.ladder.insert(Ladder::new(targets, position.quantity))
+        }
+    };
+
+    ladder.placed_at.get_or_insert_with(Utc::now);
+    for order in ladder.orders.iter_mut().filter(|o| o.order_id.is_none()) {
+        let request = OrderRequest {
+            symbol: position.symbol.clone(),
+            side: closing_side(&position.side),
+            order_type: OrderType::Limit,
+            quantity: order.target.quantity,
+            price: Some(order.target.price),
+            trigger_price: None,
+            reduce_only: true,
+            stop_loss: None,
+            position_id: Some(position.id.clone()),
+        };
+        let order_id = executor
+            .place_order(&request)
+            .await
+            .map_err(|e| ModuleError::ExecutionFailed(e.to_string()))?;
+        tracing::info!(
+            "Kâr al emri gönderildi: {} {}R @ {} x {}",
+            position.symbol,
+            order.target.r_multiple,
+            order.target.price,
+            order.target.quantity
+        );
+        order.order_id = Some(order_id);
+    }
+    Ok(())
+}
+
+/// Pozisyonu kapat ve aynı yöndeki reduce-only (kâr al) emirlerini iptal et
+async fn close_position(
+    executor: &dyn OrderExecutor,
+    position: &Position,
+    track: &mut PositionTrack,
+) -> Result<(), ModuleError> {
+    if !track.close_sent {
+        executor
+            .place_order(&OrderRequest::close_position(position))
+            .await
+            .map_err(|e| ModuleError::ExecutionFailed(e.to_string()))?;
+        track.close_sent = true;
+    }
+
+    let side = closing_side(&position.side);
+    let orders = executor
+        .open_orders(&position.symbol)
+        .await
+        .map_err(|e| ModuleError::ExecutionFailed(e.to_string()))?;
+    for order in orders.iter().filter(|o| o.reduce_only && o.side == side) {
+        executor
+            .cancel_order(&position.symbol, &order.order_id)
+            .await
+            .map_err(|e| ModuleError::ExecutionFailed(e.to_string()))?;
+    }
+    track.take_profits_cancelled = true;
+    Ok(())
+}
+
+#[async_trait]
+impl TradingModule for StopLossModule {
+    fn id(&self) -> &str {
+        "stop_loss"
+    }
+
+    fn name(&self) -> &str {
+        "Smart Stop-Loss"
+    }
+
+    fn description(&self) -> &str {
+        "Akıllı stop-loss yönetimi ve otomatik breakeven"
+    }
+
+    async fn initialize(&mut self, state: Arc<AppState>) -> Result<(), ModuleError> {
+        self.state = Some(state);
+        tracing::info!("StopLoss modülü başlatıldı");
+        Ok(())
+    }
+
+    async fn shutdown(&mut self) -> Result<(), ModuleError> {
+        self.state = None;
+        tracing::info!("StopLoss modülü durduruldu");
+        Ok(())
+    }
+
+    async fn on_price_tick(&mut self, tick: &StandardTick) -> Result<(), ModuleError> {
+        if !self.is_active {
+            return Ok(());
+        }
+
+        let state = self.state.clone().ok_or_else(|| {
+            ModuleError::InitializationFailed("State not initialized".into())
+        })?;
+
+        // Sembolün açık pozisyonları (borsa isteği sırasında kilit tutulmaz)
+        let positions: Vec<Position> = state
+            .positions
+            .read()
+            .await
+            .iter()
+            .filter(|p| p.symbol == tick.symbol)
+            .cloned()
+            .collect();
+
+        // Kapanmış pozisyonların kayıtlarını temizle
+        self.tracks
+            .retain(|id, track| track.symbol != tick.symbol || positions.iter().any(|p| &p.id == id));
+
+        let mut first_error = None;
+        for position in positions {
+            let mut track = self
+                .tracks
+                .remove(&position.id)
+                .unwrap_or_else(|| PositionTrack::new(&position));
+
+            track.extreme = match position.side {
+                PositionSide::Long => track.extreme.max(tick.price),
+                PositionSide::Short => track.extreme.min(tick.price),
+            };
+
+            let plan = state.position_plan(&position.id).await;
+            load_plan_klines(&state, &position, &plan, &mut track, tick.timestamp).await;
+            let klines = PlanKlines {
+                trailing: plan
+                    .trailing
+                    .as_ref()
+                    .and_then(|t| t.mode.interval())
+                    .map(|interval| state.klines.closed(&position.symbol, interval, tick.timestamp))
+                    .unwrap_or_default(),
+                volatility: plan
+                    .volatility_stop
+                    .as_ref()
+                    .map(|v| state.klines.get(&position.symbol, &v.interval))
+                    .unwrap_or_default(),
+            };
+            // Giriş ATR'si, girişten önceki mumlar önbellekte olduğunda bir kez hesaplanır
+            if let (Some(rule), None) = (&plan.volatility_stop, track.entry_atr) {
+                track.entry_atr = exits::entry_atr(rule, &klines.volatility, position.created_at);
+            }
+
+            let action = self.next_action(&position, &track, &plan, &klines, tick);
+            let waiting = track.retry_at.is_some_and(|at| Instant::now() < at);
+            if let (Some(action), false) = (action, waiting) {
+                let result = match state.executor().await {
+                    None => Err(ModuleError::ConnectionError("Borsa bağlantısı yok".into())),
+                    Some(executor) => match action {
+                        StopAction::MoveStop(stop, reason) => {
+                            let result = move_stop(&state, executor.as_ref(), &position, stop, reason, tick).await;
+                            if result.is_ok() && reason == StopMoveReason::Breakeven {
+                                track.breakeven_done = true;
+                            }
+                            result
+                        }
+                        StopAction::PlaceTakeProfits => {
+                            place_take_profits(executor.as_ref(), &position, &plan, &mut track).await
+                        }
+                        StopAction::SyncTakeProfits => {
+                            self.sync_take_profits(&state, executor.as_ref(), &position, &plan, &mut track, tick)
+                                .await
+                        }
+                        StopAction::Exit(reason) => {
+                            tracing::warn!(
+                                "Çıkış kuralı tetiklendi ({}): {} @ {}",
+                                reason.as_str(),
+                                position.symbol,
+                                tick.price
+                            );
+                            close_position(executor.as_ref(), &position, &mut track).await
+                        }
+                        StopAction::Close => {
+                            tracing::warn!(
+                                "Stop-loss tetiklendi: {} @ {} (stop: {:?})",
+                                position.symbol,
+                                tick.price,
+                                position.stop_loss
+                            );
+                            close_position(executor.as_ref(), &position, &mut track).await
+                        }
+                    },
+                };
+
+                match result {
+                    Ok(()) => track.retry_at = None,
+                    Err(e) => {
+                        tracing::error!("Stop işlemi başarısız ({}): {}", position.id, e);
+                        track.retry_at = Some(Instant::now() + RETRY_COOLDOWN);
+                        first_error.get_or_insert(e);
+                    }
+                }
+            }
+
+            self.tracks.insert(position.id.clone(), track);
+        }
+
+        first_error.map_or(Ok(()), Err)
+    }
+
+    async fn on_balance_change(&mut self, _symbol: &str, _new_balance: f64) -> Result<(), ModuleError> {
+        // Bu modül bakiye değişikliklerini izlemiyor
+        Ok(())
+    }
+
+    async fn on_position_opened(&mut self, position: &Position) -> Result<(), ModuleError> {
+        tracing::info!("Yeni pozisyon izlemeye alındı: {}", position.symbol);
+        self.tracks.insert(position.id.clone(), PositionTrack::new(position));
+        Ok(())
+    }
+
+    async fn on_position_closed(&mut self, position: &Position, pnl: f64) -> Result<(), ModuleError> {
+        tracing::info!(
+            "Pozisyon kapatıldı: {} | PnL: {:.2}",
+            position.symbol,
+            pnl
+        );
+        self.tracks.remove(&position.id);
+
+        // Kapanan pozisyonun planı artık geçersiz
+        if let Some(state) = &self.state {
+            if state.position_plans.write().await.remove(&position.id).is_some() {
+                if let Err(e) = db::delete_position_plan(&position.id).await {
+                    tracing::warn!("Pozisyon planı silinemedi: {}", e);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn can_execute_orders(&self) -> bool {
+        true // Bu modül emir gönderebilir
+    }
+
+    fn config_schema(&self) -> Vec<ConfigField> {
+        vec![
+            ConfigField::bool("auto_breakeven", CONFIG_PREFIX, true),
+            ConfigField::number("breakeven_threshold", CONFIG_PREFIX, 1.0, 0.1, 10.0, 0.1),
+            ConfigField::number("breakeven_offset_pct", CONFIG_PREFIX, 0.1, 0.0, 2.0, 0.01),
+            ConfigField::number("min_trail_step_pct", CONFIG_PREFIX, 0.05, 0.0, 1.0, 0.01),
+        ]
+    }
+
+    fn get_config(&self) -> ModuleConfig {
+        let mut config = ModuleConfig::new();
+        config.insert("auto_breakeven".into(), self.auto_breakeven.into());
+        config.insert("breakeven_threshold".into(), self.breakeven_threshold.into());
+        config.insert("breakeven_offset_pct".into(), self.breakeven_offset_pct.into());
+        config.insert("min_trail_step_pct".into(), self.min_trail_step_pct.into());
+        config
+    }
+
+    fn apply_config(&mut self, config: &ModuleConfig) -> Result<(), ModuleError> {
+        self.auto_breakeven = config::get_bool(config, "auto_breakeven", self.auto_breakeven);
+        self.breakeven_threshold =
+            config::get_f64(config, "breakeven_threshold", self.breakeven_threshold);
+        self.breakeven_offset_pct =
+            config::get_f64(config, "breakeven_offset_pct", self.breakeven_offset_pct);
+        self.min_trail_step_pct =
+            config::get_f64(config, "min_trail_step_pct", self.min_trail_step_pct);
+        Ok(())
+    }
+
+    fn is_active(&self) -> bool {
+        self.is_active
+    }
+
+    fn set_active(&mut self, active: bool) {
+        self.is_active = active;
+    }
+}
+
+/// Stopu borsada taşı, uygulama durumunu güncelle ve hareketi kaydet
+async fn move_stop(
+    state: &AppState,
+    executor: &dyn OrderExecutor,
+    position: &Position,
+    stop: f64,
+    reason: StopMoveReason,
+    tick: &StandardTick,
+) -> Result<(), ModuleError> {
+    tracing::info!(
+        "Stop taşınıyor ({}): {} @ {} -> {}",
+        reason.as_str(),
+        position.symbol,
+        tick.price,
+        stop
+    );
+    executor
+        .set_stop_loss(position, stop)
+        .await
+        .map_err(|e| ModuleError::ExecutionFailed(e.to_string()))?;
+
+    update_stop(state, &position.id, stop).await;
+    record_stop_move(state, StopMove {
+        position_id: position.id.clone(),
+        symbol: position.symbol.clone(),
+        from: position.stop_loss,
+        to: stop,
+        reason,
+        price: tick.price,
+        moved_at: tick.timestamp,
+    })
+    .await;
+    Ok(())
+}
+
+/// Kısmi kapanıştan sonra pozisyon miktarını kalan miktarla sınırla, güncel pozisyonu döndür
+///
+/// Pozisyon eşitlemesi miktarı borsadan zaten düşürmüş olabilir; sınırlama iki kez düşmeyi önler.
+async fn limit_quantity(state: &AppState, position: &Position, remaining: f64) -> Position {
+    let remaining = remaining.max(0.0);
+    let mut positions = state.positions.write().await;
+    match positions.iter_mut().find(|p| p.id == position.id) {
+        Some(current) => {
+            current.quantity = current.quantity.min(remaining);
+            current.clone()
+        }
+        None => Position {
+            quantity: position.quantity.min(remaining),
+            ..position.clone()
+        },
+    }
+}
+
+/// Uygulama durumundaki pozisyonun stop seviyesini güncelle
+async fn update_stop(state: &AppState, position_id: &str, stop: f64) {
+    let mut positions = state.positions.write().await;
+    if let Some(position) = positions.iter_mut().find(|p| p.id == position_id) {
+        position.stop_loss = Some(stop);
+    }
+}
+
+/// Stop hareketini yayınla ve kalıcı kayda ekle
+async fn record_stop_move(state: &AppState, stop_move: StopMove) {
+    if let Err(e) = db::save_stop_move(&stop_move).await {
+        tracing::warn!("Stop hareketi kaydedilemedi: {}", e);
+    }
+    state.dispatcher.publish(AppEvent::StopMoved(stop_move));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exchange::executor::LotSize;
+    use crate::exchange::mock::MockExecutor;
+    use crate::models::{OrderSide, StopRebalance, TakeProfitLevel, TimeLimit, TimeStop, TrailingMode, TrailingStop};
+    use crate::testing;
+
+    fn tick(price: f64) -> StandardTick {
+        testing::tick("BTCUSDT", price)
+    }
+
+    /// Açık bekleyen kapatma emri (fiyat 120, miktar 1)
+    fn add_open(executor: &MockExecutor, order_id: &str, side: OrderSide, reduce_only: bool) {
+        executor.add_open("BTCUSDT", order_id, side, 120.0, 1.0, reduce_only);
+    }
+
+    async fn setup() -> (StopLossModule, Arc<AppState>, Arc<MockExecutor>) {
+        let state = Arc::new(AppState::new());
+        state.positions.write().await.push(Position {
+            quantity: 2.0,
+            stop_loss: Some(90.0),
+            ..testing::position("BTCUSDT")
+        });
+        let executor = Arc::new(MockExecutor::default());
+        *executor.lot.lock().unwrap() = LotSize { qty_step: 0.1, min_qty: 0.1, tick_size: 0.5 };
+        state.set_executor(Some(executor.clone())).await;
+
+        let mut module = StopLossModule::new();
+        module.initialize(state.clone()).await.unwrap();
+        module.set_active(true);
+        (module, state, executor)
+    }
+
+    #[tokio::test]
+    async fn test_breakeven_moves_stop_once_with_fee_offset() {
+        let (mut module, state, executor) = setup().await;
+
+        module.on_price_tick(&tick(105.0)).await.unwrap(); // 0.5R - henüz değil
+        module.on_price_tick(&tick(110.0)).await.unwrap(); // 1R
+        module.on_price_tick(&tick(112.0)).await.unwrap();
+        module.on_price_tick(&tick(111.0)).await.unwrap();
+
+        assert_eq!(executor.calls(), vec!["stop 100.1"]);
+        assert_eq!(state.positions.read().await[0].stop_loss, Some(100.1));
+    }
+
+    #[tokio::test]
+    async fn test_trailing_stop_ratchets_and_records_reason() {
+        let (mut module, state, executor) = setup().await;
+        let subscription = state.dispatcher.subscribe("test");
+        module.auto_breakeven = false;
+        state.position_plans.write().await.insert(
+            "BTCUSDT-0".into(),
+            PositionPlan {
+                trailing: Some(TrailingStop {
+                    mode: TrailingMode::FixedDistance { distance: 5.0 },
+                    activate_at_r: 0.5,
+                }),
+                ..Default::default()
+            },
+        );
+
+        module.on_price_tick(&tick(104.0)).await.unwrap(); // 0.4R - aktif değil
+        module.on_price_tick(&tick(106.0)).await.unwrap(); // stop 101
+        module.on_price_tick(&tick(103.0)).await.unwrap(); // geri çekilme - stop gevşemez
+        module.on_price_tick(&tick(106.02)).await.unwrap(); // adım çok küçük
+        module.on_price_tick(&tick(110.0)).await.unwrap(); // stop 105
+
+        assert_eq!(executor.calls(), vec!["stop 101", "stop 105"]);
+        let moves: Vec<StopMove> = std::iter::from_fn(|| subscription.try_recv())
+            .filter_map(|e| match e {
+                AppEvent::StopMoved(m) => Some(m),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(moves.len(), 2);
+        assert_eq!((moves[1].from, moves[1].to), (Some(101.0), 105.0));
+        assert_eq!(moves[1].reason, StopMoveReason::TrailingDistance);
+    }
+
+    #[tokio::test]
+    async fn test_stop_hit_closes_position_and_cancels_take_profits_once() {
+        let (mut module, _state, executor) = setup().await;
+        add_open(&executor, "tp-1", OrderSide::Sell, true);
+        add_open(&executor, "tp-2", OrderSide::Sell, true);
+        add_open(&executor, "entry", OrderSide::Buy, false);
+
+        module.on_price_tick(&tick(89.5)).await.unwrap();
+        module.on_price_tick(&tick(89.0)).await.unwrap();
+
+        assert_eq!(executor.calls(), vec!["place Sell 2", "cancel tp-1", "cancel tp-2"]);
+    }
+
+    #[tokio::test]
+    async fn test_take_profit_ladder_fills_rebalance_stop() {
+        let (mut module, state, executor) = setup().await;
+        let subscription = state.dispatcher.subscribe("test");
+        state.position_plans.write().await.insert(
+            "BTCUSDT-0".into(),
+            PositionPlan {
+                take_profits: vec![
+                    TakeProfitLevel { r_multiple: 1.0, percent: 30.0 },
+                    TakeProfitLevel { r_multiple: 2.0, percent: 30.0 },
+                ],
+                rebalance: StopRebalance::PreviousTarget,
+                ..Default::default()
+            },
+        );
+
+        module.on_price_tick(&tick(101.0)).await.unwrap(); // Merdiven gönderilir
+        module.on_price_tick(&tick(102.0)).await.unwrap();
+        executor.fill("order-1", 0.6);
+        module.on_price_tick(&tick(110.5)).await.unwrap(); // 1R doldu -> stop girişe
+        executor.fill("order-2", 0.6);
+        module.on_price_tick(&tick(121.0)).await.unwrap(); // 2R doldu -> stop 1R seviyesine
+
+        assert_eq!(
+            executor.calls(),
+            vec!["place Sell 0.6 @ 110", "place Sell 0.6 @ 120", "stop 100.1", "stop 110"]
+        );
+        let position = state.positions.read().await[0].clone();
+        assert!((position.quantity - 0.8).abs() < 1e-9);
+        assert_eq!(position.stop_loss, Some(110.0));
+
+        let reasons: Vec<StopMoveReason> = std::iter::from_fn(|| subscription.try_recv())
+            .filter_map(|e| match e {
+                AppEvent::StopMoved(m) => Some(m.reason),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(reasons, vec![StopMoveReason::TakeProfit, StopMoveReason::TakeProfit]);
+    }
+
+    #[tokio::test]
+    async fn test_take_profit_partial_fill_and_cancel() {
+        let (mut module, state, executor) = setup().await;
+        state.position_plans.write().await.insert(
+            "BTCUSDT-0".into(),
+            PositionPlan {
+                take_profits: vec![
+                    TakeProfitLevel { r_multiple: 1.0, percent: 30.0 },
+                    TakeProfitLevel { r_multiple: 2.0, percent: 30.0 },
+                ],
+                rebalance: StopRebalance::PreviousTarget,
+                ..Default::default()
+            },
+        );
+        module.on_price_tick(&tick(101.0)).await.unwrap(); // Merdiven gönderilir
+        executor.fill("order-1", 0.2);
+        module.on_price_tick(&tick(110.5)).await.unwrap(); // Kısmi dolum: miktar düşer, stop yerinde
+        assert!((state.positions.read().await[0].quantity - 1.8).abs() < 1e-9);
+        assert_eq!(executor.calls().len(), 2);
+
+        // Emir kalanı dolmadan borsadan kalktı: bir kontrol beklenir, sonra iptal sayılır
+        executor.drop_open("order-1");
+        for _ in 0..2 {
+            module.tracks.get_mut("BTCUSDT-0").unwrap().ladder.as_mut().unwrap().next_check = None;
+            module.on_price_tick(&tick(110.5)).await.unwrap();
+        }
+        let ladder = module.tracks["BTCUSDT-0"].ladder.clone().unwrap();
+        assert_eq!(ladder.orders[0].status, LadderStatus::Cancelled);
+        assert!((state.positions.read().await[0].quantity - 1.8).abs() < 1e-9);
+        // Kısmen gerçekleşen kademe stopu girişe çeker
+        assert_eq!(executor.calls()[2], "stop 100.1");
+
+        // Sonraki kademe dolar, eşitleme miktarı zaten düşürmüşse tekrar düşülmez
+        executor.fill("order-2", 0.6);
+        state.positions.write().await[0].quantity = 1.2;
+        module.on_price_tick(&tick(121.0)).await.unwrap();
+        assert!((state.positions.read().await[0].quantity - 1.2).abs() < 1e-9);
+        assert_eq!(executor.calls().last().unwrap(), "stop 110");
+    }
+
+    #[tokio::test]
+    async fn test_time_stop_flattens_position_without_progress() {
+        let (mut module, state, executor) = setup().await;
+        state.position_plans.write().await.insert(
+            "BTCUSDT-0".into(),
+            PositionPlan {
+                time_stop: Some(TimeStop { limit: TimeLimit::Minutes { minutes: 0 }, min_r: 1.0 }),
+                ..Default::default()
+            },
+        );
+        add_open(&executor, "tp-1", OrderSide::Sell, true);
+
+        module.on_price_tick(&tick(104.0)).await.unwrap(); // 0.4R < 1R
+        module.on_price_tick(&tick(105.0)).await.unwrap();
+
+        assert_eq!(executor.calls(), vec!["place Sell 2", "cancel tp-1"]);
+    }
+
+    #[tokio::test]
+    async fn test_missing_executor_is_retried_after_cooldown() {
+        let (mut module, state, _executor) = setup().await;
+        state.set_executor(None).await;
+
+        assert!(module.on_price_tick(&tick(89.0)).await.is_err());
+        // Bekleme süresince tekrar denenmez
+        assert!(module.on_price_tick(&tick(88.0)).await.is_ok());
+    }
+
+    #[test]
+    fn test_plan_kline_requirements() {
+        let now = DateTime::from_timestamp(67 * 60, 0).unwrap();
+        let position = Position {
+            id: "p1".into(),
+            symbol: "BTCUSDT".into(),
+            side: PositionSide::Long,
+            entry_price: 100.0,
+            quantity: 1.0,
+            stop_loss: Some(95.0),
+            take_profit: None,
+            created_at: DateTime::from_timestamp(7 * 60, 0).unwrap(),
+        };
+        let plan = PositionPlan {
+            trailing: Some(TrailingStop {
+                mode: TrailingMode::Chandelier { interval: "60".into(), period: 14, multiplier: 3.0 },
+                activate_at_r: 0.0,
+            }),
+            volatility_stop: Some(crate::models::VolatilityStop {
+                interval: "15".into(),
+                period: 10,
+                multiplier: 2.0,
+                measure: Default::default(),
+            }),
+            ..Default::default()
+        };
+        // Chandelier: 15 kapanmış + açık mum; volatilite: 11 giriş öncesi + girişten beri 5 mum
+        assert_eq!(required_klines(&plan, &position, now), vec![("60".to_string(), 16), ("15".to_string(), 16)]);
+        assert!(required_klines(&PositionPlan::default(), &position, now).is_empty());
+    }
+}
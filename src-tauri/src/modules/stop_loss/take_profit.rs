@@ -0,0 +1,134 @@
+// AlgoTrade OS - Take-Profit Ladder
+// Kademeli kâr al emirlerinin takibi ve dolum sonrası stop dengelemesi
+
+use std::time::Instant;
+use chrono::{DateTime, Utc};
+use crate::models::{PositionSide, StopRebalance, TakeProfitTarget};
+
+/// Kademe emrinin durumu
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LadderStatus {
+    /// Gönderilmedi veya borsada bekliyor (kısmen dolmuş olabilir)
+    Open,
+    /// Tamamı doldu
+    Filled,
+    /// Tamamı dolmadan borsadan kalktı (iptal)
+    Cancelled,
+}
+
+/// Pozisyonun kâr al kademesi
+#[derive(Debug, Clone)]
+pub struct LadderOrder {
+    pub target: TakeProfitTarget,
+    /// Borsa emir kimliği (None = henüz gönderilmedi)
+    pub order_id: Option<String>,
+    pub status: LadderStatus,
+    /// Borsada gerçekleşen miktar
+    pub executed: f64,
+    /// Önceki kontrolde bekleyen emirlerde yoktu (dolum kaydı gecikmiş olabilir)
+    pub missing: bool,
+}
+
+impl LadderOrder {
+    /// Fiyat kademeye ulaştı ve emir borsadan kalktı mı? (stop dengelemesi için)
+    pub fn is_hit(&self) -> bool {
+        self.status != LadderStatus::Open && self.executed > 0.0
+    }
+}
+
+/// Pozisyon başına bir kez hesaplanıp gönderilen kâr al merdiveni
+#[derive(Debug, Clone)]
+pub struct Ladder {
+    pub orders: Vec<LadderOrder>,
+    /// Merdiven oluşturulduğundaki pozisyon miktarı
+    pub initial_quantity: f64,
+    /// İlk kademenin gönderildiği an (dolumlar bu andan sonra aranır)
+    pub placed_at: Option<DateTime<Utc>>,
+    /// Bu ana kadar yeni dolum kontrolü yapılmaz
+    pub next_check: Option<Instant>,
+}
+
+impl Ladder {
+    pub fn new(targets: Vec<TakeProfitTarget>, initial_quantity: f64) -> Self {
+        Self {
+            orders: targets
+                .into_iter()
+                .map(|target| LadderOrder {
+                    target,
+                    order_id: None,
+                    status: LadderStatus::Open,
+                    executed: 0.0,
+                    missing: false,
+                })
+                .collect(),
+            initial_quantity,
+            placed_at: None,
+            next_check: None,
+        }
+    }
+
+    /// Tüm kademeler borsaya gönderildi mi?
+    pub fn is_placed(&self) -> bool {
+        self.orders.iter().all(|o| o.order_id.is_some())
+    }
+
+    /// Hâlâ açık olan ilk kademe
+    pub fn next_pending(&self) -> Option<&LadderOrder> {
+        self.orders.iter().find(|o| o.status == LadderStatus::Open)
+    }
+
+    /// Kademelerde gerçekleşen toplam miktar
+    pub fn executed(&self) -> f64 {
+        self.orders.iter().map(|o| o.executed).sum()
+    }
+}
+
+/// Fiyat kâr al seviyesine ulaştı mı?
+pub fn reached(side: &PositionSide, target_price: f64, price: f64) -> bool {
+    match side {
+        PositionSide::Long => price >= target_price,
+        PositionSide::Short => price <= target_price,
+    }
+}
+
+/// Dolumlardan sonra stopun taşınacağı seviye (dolum yoksa veya dengeleme kapalıysa None)
+///
+/// `breakeven`: giriş + komisyon payı seviyesi
+pub fn rebalanced_stop(rebalance: StopRebalance, ladder: &Ladder, breakeven: f64) -> Option<f64> {
+    let last_filled = ladder.orders.iter().rposition(LadderOrder::is_hit)?;
+    match rebalance {
+        StopRebalance::None => None,
+        StopRebalance::Breakeven => Some(breakeven),
+        StopRebalance::PreviousTarget => Some(match last_filled {
+            0 => breakeven,
+            i => ladder.orders[i - 1].target.price,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rebalanced_stop_follows_previous_target() {
+        let target = |price| TakeProfitTarget { r_multiple: 1.0, price, quantity: 1.0 };
+        let mut ladder = Ladder::new(vec![target(110.0), target(120.0), target(130.0)], 3.0);
+        assert_eq!(rebalanced_stop(StopRebalance::PreviousTarget, &ladder, 100.1), None);
+
+        // Kısmen dolmuş ama hâlâ bekleyen kademe stopu taşımaz
+        ladder.orders[0].executed = 0.5;
+        assert_eq!(rebalanced_stop(StopRebalance::PreviousTarget, &ladder, 100.1), None);
+        ladder.orders[0].status = LadderStatus::Filled;
+        assert_eq!(rebalanced_stop(StopRebalance::PreviousTarget, &ladder, 100.1), Some(100.1));
+
+        // Hiç dolmadan iptal edilen kademe sayılmaz
+        ladder.orders[1].status = LadderStatus::Cancelled;
+        assert_eq!(rebalanced_stop(StopRebalance::PreviousTarget, &ladder, 100.1), Some(100.1));
+        ladder.orders[1].executed = 0.4;
+        assert_eq!(rebalanced_stop(StopRebalance::PreviousTarget, &ladder, 100.1), Some(110.0));
+        assert_eq!(rebalanced_stop(StopRebalance::Breakeven, &ladder, 100.1), Some(100.1));
+        assert_eq!(rebalanced_stop(StopRebalance::None, &ladder, 100.1), None);
+        assert_eq!(ladder.next_pending().map(|o| o.target.price), Some(130.0));
+    }
+}
@@ -0,0 +1,91 @@
+// AlgoTrade OS - Trailing Stop
+// İz süren stop yöntemlerinin seviye hesaplamaları
+
+use crate::exchange::bybit::Kline;
+use crate::indicators::{atr, highest_high, lowest_low};
+use crate::models::{PositionSide, StopMoveReason, TrailingMode};
+
+/// Yöntemin stop hareket nedeni
+pub fn reason(mode: &TrailingMode) -> StopMoveReason {
+    match mode {
+        TrailingMode::FixedPercent { .. } => StopMoveReason::TrailingPercent,
+        TrailingMode::FixedDistance { .. } => StopMoveReason::TrailingDistance,
+        TrailingMode::Atr { .. } => StopMoveReason::TrailingAtr,
+        TrailingMode::Chandelier { .. } => StopMoveReason::Chandelier,
+        TrailingMode::Swing { .. } => StopMoveReason::Swing,
+    }
+}
+
+/// Yöntemin seviye hesaplaması için gereken en az kapanmış mum
+pub fn required_bars(mode: &TrailingMode) -> usize {
+    match mode {
+        TrailingMode::FixedPercent { .. } | TrailingMode::FixedDistance { .. } => 0,
+        TrailingMode::Atr { period, .. } | TrailingMode::Chandelier { period, .. } => period + 1,
+        TrailingMode::Swing { lookback, .. } => *lookback,
+    }
+}
+
+/// Yöntemin önerdiği stop seviyesi (mum verisi yetersizse None)
+///
+/// `extreme`: pozisyon izlenmeye başladığından beri en iyi fiyat (long: en yüksek, short: en düşük)
+/// `klines`: yöntemin aralığındaki kapanmış mumlar (eskiden yeniye)
+pub fn candidate(mode: &TrailingMode, side: &PositionSide, extreme: f64, klines: &[Kline]) -> Option<f64> {
+    // Long'da stop fiyatın altında, short'ta üstünde
+    let sign = match side {
+        PositionSide::Long => 1.0,
+        PositionSide::Short => -1.0,
+    };
+
+    match mode {
+        TrailingMode::FixedPercent { percent } => Some(extreme * (1.0 - sign * percent / 100.0)),
+        TrailingMode::FixedDistance { distance } => Some(extreme - sign * distance),
+        TrailingMode::Atr { period, multiplier, .. } => {
+            Some(extreme - sign * multiplier * atr(klines, *period)?)
+        }
+        TrailingMode::Chandelier { period, multiplier, .. } => {
+            let anchor = match side {
+                PositionSide::Long => highest_high(klines, *period)?,
+                PositionSide::Short => lowest_low(klines, *period)?,
+            };
+            Some(anchor - sign * multiplier * atr(klines, *period)?)
+        }
+        TrailingMode::Swing { lookback, .. } => match side {
+            PositionSide::Long => lowest_low(klines, *lookback),
+            PositionSide::Short => highest_high(klines, *lookback),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kline(high: f64, low: f64) -> Kline {
+        Kline { timestamp: 0, open: low, high, low, close: high, volume: 0.0 }
+    }
+
+    #[test]
+    fn test_trailing_candidates() {
+        let percent = TrailingMode::FixedPercent { percent: 2.0 };
+        assert_eq!(required_bars(&percent), 0);
+        assert_eq!(required_bars(&TrailingMode::Swing { interval: "60".into(), lookback: 5 }), 5);
+        assert_eq!(candidate(&percent, &PositionSide::Long, 100.0, &[]), Some(98.0));
+        assert_eq!(candidate(&percent, &PositionSide::Short, 100.0, &[]), Some(102.0));
+
+        let distance = TrailingMode::FixedDistance { distance: 5.0 };
+        assert_eq!(candidate(&distance, &PositionSide::Short, 80.0, &[]), Some(85.0));
+
+        // Her mumun aralığı 2 ve kapanışlar boşluksuz: ATR = 2
+        let klines = vec![kline(102.0, 100.0), kline(104.0, 102.0), kline(106.0, 104.0)];
+        let chandelier = TrailingMode::Chandelier { interval: "60".into(), period: 3, multiplier: 3.0 };
+        assert_eq!(candidate(&chandelier, &PositionSide::Long, 0.0, &klines), Some(100.0));
+
+        let atr_mode = TrailingMode::Atr { interval: "60".into(), period: 3, multiplier: 1.5 };
+        assert_eq!(candidate(&atr_mode, &PositionSide::Long, 110.0, &klines), Some(107.0));
+        assert_eq!(candidate(&atr_mode, &PositionSide::Long, 110.0, &klines[..2]), None);
+
+        let swing = TrailingMode::Swing { interval: "15".into(), lookback: 2 };
+        assert_eq!(candidate(&swing, &PositionSide::Long, 0.0, &klines), Some(102.0));
+        assert_eq!(candidate(&swing, &PositionSide::Short, 0.0, &klines), Some(106.0));
+    }
+}
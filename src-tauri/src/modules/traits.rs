@@ -2,9 +2,12 @@
 // Tüm modüllerin uygulaması gereken arayüz
 
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
 use crate::models::{StandardTick, Position};
 use std::sync::Arc;
-use crate::core::AppState;
+use chrono::{DateTime, Utc};
+use crate::core::{AppState, ScheduleSpec};
+use super::config::{ConfigField, ModuleConfig};
 
 /// Her modülün uygulaması gereken temel trait
 #[async_trait]
@@ -36,11 +39,60 @@ pub trait TradingModule: Send + Sync {
     /// Pozisyon kapandığında çağrılır
     async fn on_position_closed(&mut self, position: &Position, pnl: f64) -> Result<(), ModuleError>;
 
+    /// Modülün istediği zamanlayıcılar (initialize sonrası scheduler'a kaydedilir)
+    fn schedules(&self) -> Vec<ScheduleSpec> {
+        Vec::new()
+    }
+
+    /// Zamanlayıcı tetiklendiğinde çağrılır (now: borsa saati)
+    async fn on_timer(&mut self, _timer_id: &str, _now: DateTime<Utc>) -> Result<(), ModuleError> {
+        Ok(())
+    }
+
+    /// Takip edilen mum kapandığında çağrılır
+    async fn on_candle_close(&mut self, _symbol: &str, _interval: &str, _close_time: DateTime<Utc>) -> Result<(), ModuleError> {
+        Ok(())
+    }
+
     /// Modülün emir gönderme yetkisi var mı?
     fn can_execute_orders(&self) -> bool {
         false // Varsayılan: hayır (güvenlik için)
     }
 
+    /// Hesaplaması arayüzde yapılan, backend olaylarına tepki vermeyen modül mü?
+    fn ui_only(&self) -> bool {
+        false
+    }
+
+    /// Modülün istediği izinler (UI'da gösterilir, sandbox sınırlarında uygulanır)
+    fn permissions(&self) -> Vec<ModulePermission> {
+        if self.can_execute_orders() {
+            vec![ModulePermission::ReadOnly, ModulePermission::Trading]
+        } else {
+            vec![ModulePermission::ReadOnly]
+        }
+    }
+
+    /// Modülün kendi bildirdiği sağlık durumu
+    fn health(&self) -> ModuleHealth {
+        ModuleHealth::Healthy
+    }
+
+    /// Modülün ayar şeması (UI formu bu şemadan oluşturulur)
+    fn config_schema(&self) -> Vec<ConfigField> {
+        Vec::new() // Varsayılan: ayarlanabilir alan yok
+    }
+
+    /// Modülün mevcut ayarları
+    fn get_config(&self) -> ModuleConfig {
+        ModuleConfig::new()
+    }
+
+    /// Şemaya göre doğrulanmış ayarları uygula
+    fn apply_config(&mut self, _config: &ModuleConfig) -> Result<(), ModuleError> {
+        Ok(())
+    }
+
     /// Modül aktif mi?
     fn is_active(&self) -> bool;
 
@@ -59,6 +111,8 @@ pub enum ModuleError {
     Unauthorized(String),
     /// Bağlantı hatası
     ConnectionError(String),
+    /// Geçersiz ayar
+    InvalidConfig(String),
     /// Genel hata
     Other(String),
 }
@@ -70,6 +124,7 @@ impl std::fmt::Display for ModuleError {
             ModuleError::ExecutionFailed(msg) => write!(f, "Execution failed: {}", msg),
             ModuleError::Unauthorized(msg) => write!(f, "Unauthorized: {}", msg),
             ModuleError::ConnectionError(msg) => write!(f, "Connection error: {}", msg),
+            ModuleError::InvalidConfig(msg) => write!(f, "Invalid config: {}", msg),
             ModuleError::Other(msg) => write!(f, "Error: {}", msg),
         }
     }
@@ -78,7 +133,8 @@ impl std::fmt::Display for ModuleError {
 impl std::error::Error for ModuleError {}
 
 /// Modül izinleri
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum ModulePermission {
     /// Sadece okuma (fiyat, orderbook vb.)
     ReadOnly,
@@ -89,3 +145,15 @@ pub enum ModulePermission {
     /// Tam yetki
     Full,
 }
+
+/// Modül sağlık durumu
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModuleHealth {
+    /// Sorunsuz çalışıyor
+    Healthy,
+    /// Çalışıyor ama hata veriyor veya olayların gerisinde kalıyor
+    Degraded,
+    /// Başlatılamadı veya durdu
+    Failed,
+}
@@ -0,0 +1,740 @@
+// AlgoTrade OS - WASM Plugin Host
+// Harici modülleri (eklentileri) wasmtime sandbox'ında çalıştırır
+//
+// Eklenti klasörü: <plugins>/<eklenti>/plugin.json + plugin.wasm
+//
+// ABI v1 - eklentinin dışa açması gerekenler:
+//   memory                                   doğrusal bellek
+//   atp_abi_version() -> i32                 1 döndürmeli
+//   atp_alloc(len: i32) -> i32               host'un veri yazacağı alanı ayırır
+// İsteğe bağlı (0 = başarılı, diğer değerler hata kodu; veri JSON olarak geçer):
+//   atp_init() -> i32
+//   atp_shutdown() -> i32
+//   atp_on_tick(ptr, len) -> i32             StandardTick
+//   atp_on_balance(ptr, len) -> i32          { symbol, balance }
+//   atp_on_position_opened(ptr, len) -> i32  Position
+//   atp_on_position_closed(ptr, len) -> i32  { position, pnl }
+//   atp_on_timer(ptr, len) -> i32            { timer_id, now }        (plugin.json "schedules")
+//   atp_on_candle_close(ptr, len) -> i32     { symbol, interval, close_time }
+//
+// Host'un sağladığı "atp" import'ları ve gereken izin:
+//   log(level, ptr, len)                     ReadOnly
+//   now_ms() -> i64                          ReadOnly (zamanlayıcının borsa saati)
+//   get_price(ptr, len) -> f64               ReadOnly (bilinmeyen sembol: NaN)
+//   place_order(ptr, len) -> i64             Trading
+//
+// place_order emri risk kapısından geçen yürütücüye gönderir ve sonucu JSON olarak
+// ({ "status": "placed", "order_id" } veya { "status": "rejected", "reason", "message" })
+// atp_alloc ile ayrılan alana yazar; dönüş değeri (ptr << 32) | len, yazılamazsa -1.
+//
+// plugin.json'da istenmeyen bir import'u kullanan eklenti yüklenmez. Emir izni ayrıca
+// kullanıcının modülü etkinleştirirken onayladığı (veritabanına kaydedilen) izinlere göre
+// her çağrıda denetlenir. CPU (fuel) ve bellek sınırları her çağrıda uygulanır; plugin.json'daki
+// bellek sınırı host'un üst sınırını (MAX_PLUGIN_MEMORY_MB) aşamaz.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+use serde::{Deserialize, Serialize};
+use wasmtime::{
+    Caller, Config, Engine, Instance, Linker, Memory, Module, Store, StoreLimits,
+    StoreLimitsBuilder, Trap, TypedFunc,
+};
+use crate::core::{AppState, ScheduleSpec};
+use crate::db;
+use crate::exchange::{ExecutionError, OrderRequest};
+use crate::models::{OrderSide, OrderType, Position, StandardTick};
+use super::registry::ModuleRegistry;
+use super::traits::{ModuleError, ModuleHealth, ModulePermission, TradingModule};
+
+/// Desteklenen ABI sürümü
+pub const ABI_VERSION: u32 = 1;
+
+/// Host import'larının modül adı
+const IMPORT_MODULE: &str = "atp";
+
+/// Host import'ları ve gerektirdikleri izin
+const HOST_IMPORTS: &[(&str, ModulePermission)] = &[
+    ("log", ModulePermission::ReadOnly),
+    ("now_ms", ModulePermission::ReadOnly),
+    ("get_price", ModulePermission::ReadOnly),
+    ("place_order", ModulePermission::Trading),
+];
+
+/// Eklentinin tek seferde host'a gönderebileceği en büyük veri (log, emir)
+const MAX_GUEST_PAYLOAD: usize = 64 * 1024;
+
+/// Bir eklentinin isteyebileceği en fazla doğrusal bellek (MB)
+const MAX_PLUGIN_MEMORY_MB: usize = 512;
+
+/// Eklenti kaynak sınırları
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PluginLimits {
+    /// Çağrı başına CPU bütçesi (wasmtime fuel)
+    pub fuel_per_call: u64,
+    /// Doğrusal bellek üst sınırı (MB)
+    pub max_memory_mb: usize,
+}
+
+impl Default for PluginLimits {
+    fn default() -> Self {
+        Self {
+            fuel_per_call: 10_000_000,
+            max_memory_mb: 16,
+        }
+    }
+}
+
+/// plugin.json içeriği
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginManifest {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub version: String,
+    pub abi_version: u32,
+    /// Eklentinin istediği izinler (emir izni ayrıca kullanıcı onayı gerektirir)
+    pub permissions: Vec<ModulePermission>,
+    #[serde(default)]
+    pub limits: PluginLimits,
+    /// Eklentinin zamanlayıcıları (borsa saatiyle tetiklenir)
+    #[serde(default)]
+    pub schedules: Vec<ScheduleSpec>,
+}
+
+impl PluginManifest {
+    /// İzin istenmiş mi? (Full her şeyi kapsar)
+    fn grants(&self, permission: &ModulePermission) -> bool {
+        covers(&self.permissions, permission)
+    }
+}
+
+/// İzin listesi verilen izni kapsıyor mu?
+fn covers(permissions: &[ModulePermission], permission: &ModulePermission) -> bool {
+    permissions.contains(permission) || permissions.contains(&ModulePermission::Full)
+}
+
+/// Eklentinin emir talebi (place_order import'u)
+#[derive(Debug, Deserialize)]
+struct GuestOrder {
+    symbol: String,
+    side: OrderSide,
+    order_type: OrderType,
+    quantity: f64,
+    #[serde(default)]
+    price: Option<f64>,
+    #[serde(default)]
+    trigger_price: Option<f64>,
+    #[serde(default)]
+    reduce_only: bool,
+    #[serde(default)]
+    stop_loss: Option<f64>,
+}
+
+/// Eklentiye dönen emir sonucu
+#[derive(Debug, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+enum GuestOrderResult {
+    Placed { order_id: String },
+    Rejected { reason: &'static str, message: String },
+}
+
+impl GuestOrderResult {
+    fn rejected(reason: &'static str, message: impl Into<String>) -> Self {
+        Self::Rejected { reason, message: message.into() }
+    }
+}
+
+/// Store içinde tutulan host verisi
+struct HostState {
+    module_id: String,
+    app_state: Arc<AppState>,
+    limits: StoreLimits,
+}
+
+/// Çalışan eklenti örneği
+struct PluginInstance {
+    store: Store<HostState>,
+    instance: Instance,
+    memory: Memory,
+    alloc: TypedFunc<i32, i32>,
+}
+
+/// Paylaşılan wasmtime motoru (fuel ölçümü açık)
+fn engine() -> &'static Engine {
+    static ENGINE: OnceLock<Engine> = OnceLock::new();
+    ENGINE.get_or_init(|| {
+        let mut config = Config::new();
+        config.consume_fuel(true);
+        // Emir import'u yürütücüyü bekler
+        config.async_support(true);
+        Engine::new(&config).expect("WASM motoru oluşturulamadı")
+    })
+}
+
+/// Varsayılan eklenti klasörü
+pub fn plugins_dir() -> PathBuf {
+    dirs::data_local_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("algotrade-os")
+        .join("plugins")
+}
+
+/// Klasördeki tüm eklentileri yükle ve kaydet (yüklenemeyenler loglanıp atlanır)
+pub fn register_installed_plugins(registry: &ModuleRegistry, dir: &Path) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return; // Eklenti klasörü yok
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_dir() {
+            continue;
+        }
+        match WasmModule::load_dir(&path) {
+            Ok(module) => {
+                tracing::info!("Eklenti yüklendi: {} ({:?})", module.manifest.id, path);
+                registry.register(module);
+            }
+            Err(e) => tracing::warn!("Eklenti yüklenemedi {:?}: {}", path, e),
+        }
+    }
+}
+
+/// WASM eklentisi olarak çalışan modül
+pub struct WasmModule {
+    manifest: PluginManifest,
+    module: Module,
+    instance: Option<PluginInstance>,
+    is_active: bool,
+    health: ModuleHealth,
+}
+
+impl WasmModule {
+    /// plugin.json ve plugin.wasm içeren klasörden yükle
+    pub fn load_dir(dir: &Path) -> Result<Self, ModuleError> {
+        let manifest = std::fs::read_to_string(dir.join("plugin.json"))
+            .map_err(|e| ModuleError::InitializationFailed(format!("plugin.json: {}", e)))?;
+        let manifest: PluginManifest = serde_json::from_str(&manifest)
+            .map_err(|e| ModuleError::InitializationFailed(format!("plugin.json: {}", e)))?;
+        let bytes = std::fs::read(dir.join("plugin.wasm"))
+            .map_err(|e| ModuleError::InitializationFailed(format!("plugin.wasm: {}", e)))?;
+        Self::from_bytes(manifest, &bytes)
+    }
+
+    /// Derle ve import'ları izinlere göre denetle
+    pub fn from_bytes(manifest: PluginManifest, bytes: &[u8]) -> Result<Self, ModuleError> {
+        if manifest.abi_version != ABI_VERSION {
+            return Err(ModuleError::InitializationFailed(format!(
+                "Desteklenmeyen ABI sürümü {} (beklenen {})",
+                manifest.abi_version, ABI_VERSION
+            )));
+        }
+
+        if manifest.limits.max_memory_mb == 0 || manifest.limits.max_memory_mb > MAX_PLUGIN_MEMORY_MB {
+            return Err(ModuleError::InitializationFailed(format!(
+                "Bellek sınırı 1-{} MB olmalı (istenen {})",
+                MAX_PLUGIN_MEMORY_MB, manifest.limits.max_memory_mb
+            )));
+        }
+
+        let module = Module::new(engine(), bytes)
+            .map_err(|e| ModuleError::InitializationFailed(format!("Derleme hatası: {}", e)))?;
+
+        for import in module.imports() {
+            let required = HOST_IMPORTS
+                .iter()
+                .find(|(name, _)| import.module() == IMPORT_MODULE && *name == import.name())
+                .map(|(_, permission)| permission)
+                .ok_or_else(|| {
+                    ModuleError::InitializationFailed(format!(
+                        "Bilinmeyen import: {}::{}",
+                        import.module(),
+                        import.name()
+                    ))
+                })?;
+            if !manifest.grants(required) {
+                return Err(ModuleError::Unauthorized(format!(
+                    "{} import'u {:?} izni gerektirir",
+                    import.name(),
+                    required
+                )));
+            }
+        }
+
+        Ok(Self {
+            manifest,
+            module,
+            instance: None,
+            is_active: false,
+            health: ModuleHealth::Healthy,
+        })
+    }
+
+    /// Sadece izin verilen import'ları tanımlayan linker
+    fn linker(&self) -> Result<Linker<HostState>, ModuleError> {
+        let mut linker = Linker::new(engine());
+        let link_err = |e: wasmtime::Error| ModuleError::InitializationFailed(e.to_string());
+
+        if self.manifest.grants(&ModulePermission::ReadOnly) {
+            linker
+                .func_wrap(IMPORT_MODULE, "log", |mut caller: Caller<'_, HostState>, level: i32, ptr: i32, len: i32| {
+                    let Some(message) = read_guest_string(&mut caller, ptr, len) else {
+                        return;
+                    };
+                    let id = &caller.data().module_id;
+                    match level {
+                        0 => tracing::debug!("[{}] {}", id, message),
+                        1 => tracing::info!("[{}] {}", id, message),
+                        2 => tracing::warn!("[{}] {}", id, message),
+                        _ => tracing::error!("[{}] {}", id, message),
+                    }
+                })
+                .map_err(link_err)?;
+            linker
+                .func_wrap(IMPORT_MODULE, "now_ms", |caller: Caller<'_, HostState>| {
+                    caller.data().app_state.scheduler.now().timestamp_millis()
+                })
+                .map_err(link_err)?;
+            linker
+                .func_wrap_async(IMPORT_MODULE, "get_price", |mut caller: Caller<'_, HostState>, (ptr, len): (i32, i32)| {
+                    Box::new(async move {
+                        let Some(symbol) = read_guest_string(&mut caller, ptr, len) else {
+                            return f64::NAN;
+                        };
+                        // Fiyat yazıcısı kilidi kısa süre tutar; kilit beklenir (meşgulken NaN dönülmez)
+                        let prices = caller.data().app_state.live_prices.clone();
+                        let price = prices.read().await.get(&symbol).map(|t| t.price).unwrap_or(f64::NAN);
+                        price
+                    })
+                })
+                .map_err(link_err)?;
+        }
+
+        if self.manifest.grants(&ModulePermission::Trading) {
+            linker
+                .func_wrap_async(IMPORT_MODULE, "place_order", |mut caller: Caller<'_, HostState>, (ptr, len): (i32, i32)| {
+                    Box::new(async move {
+                        let payload = read_guest_string(&mut caller, ptr, len);
+                        let (module_id, app_state) = (caller.data().module_id.clone(), caller.data().app_state.clone());
+                        let result = place_guest_order(&module_id, &app_state, payload).await;
+                        write_guest_json(&mut caller, &result).await.unwrap_or(-1)
+                    })
+                })
+                .map_err(link_err)?;
+        }
+
+        Ok(linker)
+    }
+
+    /// Eklentiyi örnekle ve ABI sürümünü doğrula
+    async fn instantiate(&self, app_state: Arc<AppState>) -> Result<PluginInstance, ModuleError> {
+        let init_err = |e: wasmtime::Error| ModuleError::InitializationFailed(e.to_string());
+        let memory_bytes = self.manifest.limits.max_memory_mb.checked_mul(1024 * 1024).ok_or_else(|| {
+            ModuleError::InitializationFailed(format!("Bellek sınırı çok büyük: {} MB", self.manifest.limits.max_memory_mb))
+        })?;
+        let limits = StoreLimitsBuilder::new()
+            .memory_size(memory_bytes)
+            .instances(1)
+            .memories(1)
+            .tables(1)
+            .build();
+        let mut store = Store::new(
+            engine(),
+            HostState {
+                module_id: self.manifest.id.clone(),
+                app_state,
+                limits,
+            },
+        );
+        store.limiter(|host| &mut host.limits);
+        store.set_fuel(self.manifest.limits.fuel_per_call).map_err(init_err)?;
+
+        let instance = self.linker()?.instantiate_async(&mut store, &self.module).await.map_err(init_err)?;
+        let memory = instance
+            .get_memory(&mut store, "memory")
+            .ok_or_else(|| ModuleError::InitializationFailed("memory dışa açılmamış".into()))?;
+        let alloc = instance
+            .get_typed_func::<i32, i32>(&mut store, "atp_alloc")
+            .map_err(init_err)?;
+        let version = instance
+            .get_typed_func::<(), i32>(&mut store, "atp_abi_version")
+            .map_err(init_err)?
+            .call_async(&mut store, ())
+            .await
+            .map_err(init_err)?;
+        if version != ABI_VERSION as i32 {
+            return Err(ModuleError::InitializationFailed(format!(
+                "Eklenti ABI sürümü {} (beklenen {})",
+                version, ABI_VERSION
+            )));
+        }
+
+        Ok(PluginInstance { store, instance, memory, alloc })
+    }
+
+    /// Çağrı hatasını ModuleError'a çevir (CPU limiti ayrı raporlanır)
+    fn call_error(&mut self, export: &str, e: wasmtime::Error) -> ModuleError {
+        if e.downcast_ref::<Trap>() == Some(&Trap::OutOfFuel) {
+            self.health = ModuleHealth::Degraded;
+            return ModuleError::ExecutionFailed(format!("{}: CPU limiti aşıldı", export));
+        }
+        ModuleError::ExecutionFailed(format!("{}: {}", export, e))
+    }
+
+    /// Argümansız dışa açılmış fonksiyonu çağır (yoksa başarılı say)
+    async fn call_unit(&mut self, export: &str) -> Result<(), ModuleError> {
+        let fuel = self.manifest.limits.fuel_per_call;
+        let Some(plugin) = self.instance.as_mut() else {
+            return Ok(());
+        };
+        let Ok(func) = plugin.instance.get_typed_func::<(), i32>(&mut plugin.store, export) else {
+            return Ok(());
+        };
+        let result = match plugin.store.set_fuel(fuel) {
+            Ok(()) => func.call_async(&mut plugin.store, ()).await,
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok(status) => check_status(export, status),
+            Err(e) => Err(self.call_error(export, e)),
+        }
+    }
+
+    /// JSON veriyi eklenti belleğine yazıp handler'ı çağır (handler yoksa başarılı say)
+    async fn call_with<T: Serialize>(&mut self, export: &str, payload: &T) -> Result<(), ModuleError> {
+        let fuel = self.manifest.limits.fuel_per_call;
+        let Some(plugin) = self.instance.as_mut() else {
+            return Ok(());
+        };
+        let Ok(func) = plugin.instance.get_typed_func::<(i32, i32), i32>(&mut plugin.store, export) else {
+            return Ok(());
+        };
+        let bytes = serde_json::to_vec(payload)
+            .map_err(|e| ModuleError::ExecutionFailed(e.to_string()))?;
+        let len = bytes.len() as i32;
+
+        let result = async {
+            plugin.store.set_fuel(fuel)?;
+            let ptr = plugin.alloc.call_async(&mut plugin.store, len).await?;
+            plugin.memory.write(&mut plugin.store, ptr as usize, &bytes)?;
+            func.call_async(&mut plugin.store, (ptr, len)).await
+        }
+        .await;
+        match result {
+            Ok(status) => check_status(export, status),
+            Err(e) => Err(self.call_error(export, e)),
+        }
+    }
+}
+
+/// Eklenti dönüş kodunu kontrol et
+fn check_status(export: &str, status: i32) -> Result<(), ModuleError> {
+    if status == 0 {
+        Ok(())
+    } else {
+        Err(ModuleError::ExecutionFailed(format!("{} hata kodu {}", export, status)))
+    }
+}
+
+/// Eklentinin emrini kullanıcı onayına ve risk kapısına tabi olarak borsaya gönder
+async fn place_guest_order(module_id: &str, app_state: &AppState, payload: Option<String>) -> GuestOrderResult {
+    let Some(order) = payload.and_then(|p| serde_json::from_str::<GuestOrder>(&p).ok()) else {
+        return GuestOrderResult::rejected("invalid_request", "Emir JSON'u okunamadı");
+    };
+    if !(order.quantity.is_finite() && order.quantity > 0.0) {
+        return GuestOrderResult::rejected("invalid_request", "Geçersiz miktar");
+    }
+    match db::get_module_grants(module_id).await {
+        Ok(granted) if covers(&granted, &ModulePermission::Trading) => {}
+        Ok(_) => return GuestOrderResult::rejected("unauthorized", "Emir izni kullanıcı tarafından onaylanmadı"),
+        Err(e) => return GuestOrderResult::rejected("unauthorized", e),
+    }
+    let Some(executor) = app_state.executor().await else {
+        return GuestOrderResult::rejected("not_connected", ExecutionError::NotConnected.to_string());
+    };
+
+    let request = OrderRequest {
+        symbol: order.symbol,
+        side: order.side,
+        order_type: order.order_type,
+        quantity: order.quantity,
+        price: order.price,
+        trigger_price: order.trigger_price,
+        reduce_only: order.reduce_only,
+        stop_loss: order.stop_loss,
+        position_id: None,
+    };
+    match executor.place_order(&request).await {
+        Ok(order_id) => {
+            tracing::info!("[{}] Emir gönderildi: {} {:?} {} ({})", module_id, request.symbol, request.side, request.quantity, order_id);
+            GuestOrderResult::Placed { order_id }
+        }
+        Err(e) => {
+            tracing::warn!("[{}] Emir gönderilemedi: {}", module_id, e);
+            let reason = match e {
+                ExecutionError::NotConnected => "not_connected",
+                ExecutionError::RiskRejected(_) => "risk_rejected",
+                ExecutionError::Exchange(_) => "exchange_error",
+            };
+            GuestOrderResult::rejected(reason, e.to_string())
+        }
+    }
+}
+
+/// Değeri JSON olarak eklenti belleğine yaz, (ptr << 32) | len döndür
+async fn write_guest_json<T: Serialize>(caller: &mut Caller<'_, HostState>, value: &T) -> Option<i64> {
+    let bytes = serde_json::to_vec(value).ok()?;
+    let len = i32::try_from(bytes.len()).ok()?;
+    let alloc = caller.get_export("atp_alloc")?.into_func()?.typed::<i32, i32>(&caller).ok()?;
+    let ptr = alloc.call_async(&mut *caller, len).await.ok()?;
+    let memory = caller.get_export("memory")?.into_memory()?;
+    memory.write(&mut *caller, usize::try_from(ptr).ok()?, &bytes).ok()?;
+    Some((i64::from(ptr) << 32) | i64::from(len))
+}
+
+/// Eklenti belleğinden UTF-8 metin oku (sınır dışı veya çok büyükse None)
+fn read_guest_string(caller: &mut Caller<'_, HostState>, ptr: i32, len: i32) -> Option<String> {
+    let (ptr, len) = (usize::try_from(ptr).ok()?, usize::try_from(len).ok()?);
+    if len > MAX_GUEST_PAYLOAD {
+        return None;
+    }
+    let memory = caller.get_export("memory")?.into_memory()?;
+    let bytes = memory.data(&caller).get(ptr..ptr.checked_add(len)?)?;
+    String::from_utf8(bytes.to_vec()).ok()
+}
+
+#[async_trait]
+impl TradingModule for WasmModule {
+    fn id(&self) -> &str {
+        &self.manifest.id
+    }
+
+    fn name(&self) -> &str {
+        &self.manifest.name
+    }
+
+    fn description(&self) -> &str {
+        &self.manifest.description
+    }
+
+    async fn initialize(&mut self, state: Arc<AppState>) -> Result<(), ModuleError> {
+        self.instance = Some(self.instantiate(state).await?);
+        self.health = ModuleHealth::Healthy;
+        self.call_unit("atp_init").await
+    }
+
+    async fn shutdown(&mut self) -> Result<(), ModuleError> {
+        let result = self.call_unit("atp_shutdown").await;
+        self.instance = None;
+        result
+    }
+
+    async fn on_price_tick(&mut self, tick: &StandardTick) -> Result<(), ModuleError> {
+        self.call_with("atp_on_tick", tick).await
+    }
+
+    async fn on_balance_change(&mut self, symbol: &str, new_balance: f64) -> Result<(), ModuleError> {
+        self.call_with(
+            "atp_on_balance",
+            &serde_json::json!({ "symbol": symbol, "balance": new_balance }),
+        )
+        .await
+    }
+
+    async fn on_position_opened(&mut self, position: &Position) -> Result<(), ModuleError> {
+        self.call_with("atp_on_position_opened", position).await
+    }
+
+    async fn on_position_closed(&mut self, position: &Position, pnl: f64) -> Result<(), ModuleError> {
+        self.call_with(
+            "atp_on_position_closed",
+            &serde_json::json!({ "position": position, "pnl": pnl }),
+        )
+        .await
+    }
+
+    fn schedules(&self) -> Vec<ScheduleSpec> {
+        self.manifest.schedules.clone()
+    }
+
+    async fn on_timer(&mut self, timer_id: &str, now: DateTime<Utc>) -> Result<(), ModuleError> {
+        self.call_with("atp_on_timer", &serde_json::json!({ "timer_id": timer_id, "now": now })).await
+    }
+
+    async fn on_candle_close(&mut self, symbol: &str, interval: &str, close_time: DateTime<Utc>) -> Result<(), ModuleError> {
+        self.call_with(
+            "atp_on_candle_close",
+            &serde_json::json!({ "symbol": symbol, "interval": interval, "close_time": close_time }),
+        )
+        .await
+    }
+
+    fn can_execute_orders(&self) -> bool {
+        self.manifest.grants(&ModulePermission::Trading)
+    }
+
+    fn permissions(&self) -> Vec<ModulePermission> {
+        self.manifest.permissions.clone()
+    }
+
+    fn health(&self) -> ModuleHealth {
+        if self.instance.is_none() {
+            return ModuleHealth::Failed;
+        }
+        self.health
+    }
+
+    fn is_active(&self) -> bool {
+        self.is_active
+    }
+
+    fn set_active(&mut self, active: bool) {
+        self.is_active = active;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::tick;
+
+    fn manifest(permissions: Vec<ModulePermission>) -> PluginManifest {
+        PluginManifest {
+            id: "test_plugin".into(),
+            name: "Test Plugin".into(),
+            description: String::new(),
+            version: "0.1.0".into(),
+            abi_version: ABI_VERSION,
+            permissions,
+            limits: PluginLimits { fuel_per_call: 100_000, max_memory_mb: 1 },
+            schedules: Vec::new(),
+        }
+    }
+
+    /// ABI iskeleti + test edilen gövde
+    fn plugin(imports: &str, body: &str) -> String {
+        format!(
+            r#"(module
+                {imports}
+                (memory (export "memory") 1)
+                (global $heap (mut i32) (i32.const 1024))
+                (func (export "atp_abi_version") (result i32) (i32.const 1))
+                (func (export "atp_alloc") (param $len i32) (result i32)
+                    (local $ptr i32)
+                    (local.set $ptr (global.get $heap))
+                    (global.set $heap (i32.add (global.get $heap) (local.get $len)))
+                    (local.get $ptr))
+                {body})"#
+        )
+    }
+
+    #[test]
+    fn test_trading_import_requires_permission() {
+        let wat = plugin(
+            r#"(import "atp" "place_order" (func $place (param i32 i32) (result i32)))"#,
+            "",
+        );
+        let denied = WasmModule::from_bytes(manifest(vec![ModulePermission::ReadOnly]), wat.as_bytes());
+        assert!(matches!(denied, Err(ModuleError::Unauthorized(_))));
+
+        let allowed = WasmModule::from_bytes(
+            manifest(vec![ModulePermission::ReadOnly, ModulePermission::Trading]),
+            wat.as_bytes(),
+        );
+        assert!(allowed.unwrap().can_execute_orders());
+
+        // plugin.json'daki bellek sınırı host'un üst sınırını aşamaz
+        let mut huge = manifest(vec![ModulePermission::ReadOnly]);
+        huge.limits.max_memory_mb = usize::MAX;
+        assert!(matches!(WasmModule::from_bytes(huge, plugin("", "").as_bytes()), Err(ModuleError::InitializationFailed(_))));
+    }
+
+    #[tokio::test]
+    async fn test_cpu_and_memory_limits() {
+        let wat = plugin(
+            "",
+            r#"(func (export "atp_on_tick") (param i32 i32) (result i32)
+                   (loop $spin (br $spin))
+                   (i32.const 0))
+               (func (export "atp_on_balance") (param i32 i32) (result i32)
+                   ;; 1 MB sınırı: 64 sayfa büyütme başarısız olmalı (-1)
+                   (memory.grow (i32.const 64)))"#,
+        );
+        let mut module = WasmModule::from_bytes(manifest(vec![ModulePermission::ReadOnly]), wat.as_bytes()).unwrap();
+        module.initialize(Arc::new(AppState::new())).await.unwrap();
+
+        let err = module.on_price_tick(&tick("BTCUSDT", 100.0)).await.unwrap_err();
+        assert!(err.to_string().contains("CPU"));
+        assert_eq!(TradingModule::health(&module), ModuleHealth::Degraded);
+
+        let err = module.on_balance_change("USDT", 10.0).await.unwrap_err();
+        assert!(err.to_string().contains("-1"));
+    }
+
+    /// Eklentinin 2048 adresine sakladığı place_order sonucunu oku
+    fn stored_result(module: &mut WasmModule) -> serde_json::Value {
+        let plugin = module.instance.as_mut().unwrap();
+        let data = plugin.memory.data(&plugin.store);
+        let packed = i64::from_le_bytes(data[2048..2056].try_into().unwrap());
+        let (ptr, len) = ((packed >> 32) as usize, (packed & 0xffff_ffff) as usize);
+        serde_json::from_slice(&data[ptr..ptr + len]).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_place_order_needs_grant_and_goes_through_executor() {
+        db::init_test_database();
+        let order = r#"{"symbol":"BTCUSDT","side":"Buy","order_type":"Market","quantity":0.5,"stop_loss":99}"#;
+        let wat = plugin(
+            r#"(import "atp" "get_price" (func $price (param i32 i32) (result f64)))
+               (import "atp" "place_order" (func $place (param i32 i32) (result i64)))"#,
+            &format!(
+                r#"(data (i32.const 0) "BTCUSDT")
+                   (data (i32.const 16) "{}")
+                   (func (export "atp_on_tick") (param i32 i32) (result i32)
+                       ;; Fiyat 50'nin üzerindeyse al, sonucu 2048'e sakla
+                       (if (result i32) (f64.gt (call $price (i32.const 0) (i32.const 7)) (f64.const 50))
+                           (then
+                               (i64.store (i32.const 2048) (call $place (i32.const 16) (i32.const {})))
+                               (i32.const 0))
+                           (else (i32.const 1))))"#,
+                order.replace('"', "\\\""),
+                order.len()
+            ),
+        );
+        let state = Arc::new(AppState::new());
+        let mut module = WasmModule::from_bytes(
+            manifest(vec![ModulePermission::ReadOnly, ModulePermission::Trading]),
+            wat.as_bytes(),
+        )
+        .unwrap();
+        module.initialize(state.clone()).await.unwrap();
+
+        // Fiyat henüz bilinmiyor (NaN) - emir yok
+        assert!(module.on_price_tick(&tick("BTCUSDT", 100.0)).await.is_err());
+        state.update_price(tick("BTCUSDT", 100.0)).await;
+
+        // plugin.json izni yetmez, kullanıcı onayı gerekir
+        module.on_price_tick(&tick("BTCUSDT", 100.0)).await.unwrap();
+        assert_eq!(stored_result(&mut module)["reason"], "unauthorized");
+
+        db::save_module_grants("test_plugin", &[ModulePermission::ReadOnly, ModulePermission::Trading]).await.unwrap();
+        module.on_price_tick(&tick("BTCUSDT", 100.0)).await.unwrap();
+        assert_eq!(stored_result(&mut module)["reason"], "not_connected");
+
+        let executor = Arc::new(crate::exchange::mock::MockExecutor::with_equity(10_000.0));
+        state.set_executor(Some(executor.clone())).await;
+        module.on_price_tick(&tick("BTCUSDT", 100.0)).await.unwrap();
+        let result = stored_result(&mut module);
+        assert_eq!(result["status"], "placed");
+        assert_eq!(result["order_id"], "order-1");
+        let placed = executor.placed();
+        assert_eq!(placed.len(), 1);
+        assert_eq!(placed[0].side, OrderSide::Buy);
+        assert_eq!(placed[0].quantity, 0.5);
+        assert_eq!(placed[0].stop_loss, Some(99.0));
+    }
+}
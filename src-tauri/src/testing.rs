@@ -0,0 +1,31 @@
+// AlgoTrade OS - Test Support
+// Testlerde ortak kullanılan pozisyon ve tick kurucuları
+
+use chrono::Utc;
+use crate::models::{Exchange, Position, PositionSide, StandardTick};
+
+/// `symbol` için 100'den açılmış 1 birimlik, stopsuz long pozisyon (id: "<symbol>-0").
+/// Farklı alanlar `Position { side, ..position("BTCUSDT") }` ile verilir.
+pub fn position(symbol: &str) -> Position {
+    Position {
+        id: format!("{}-0", symbol),
+        symbol: symbol.into(),
+        side: PositionSide::Long,
+        entry_price: 100.0,
+        quantity: 1.0,
+        stop_loss: None,
+        take_profit: None,
+        created_at: Utc::now(),
+    }
+}
+
+/// Şimdiki zamanlı, 1 hacimli Bybit tick'i
+pub fn tick(symbol: &str, price: f64) -> StandardTick {
+    StandardTick {
+        symbol: symbol.into(),
+        price,
+        volume: 1.0,
+        timestamp: Utc::now(),
+        exchange: Exchange::Bybit,
+    }
+}
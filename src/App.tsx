@@ -11,6 +11,7 @@ import { AnchoredVwapSettings, AnchoredVwapConfig, getDefaultAnchoredVwapConfigs
 import { StrategyBuilder, Strategy, getDefaultStrategies } from "./components/StrategyBuilder";
 import { ColorProvider } from "./components/ColorSettings";
 import { ResizablePanel } from "./components/ResizablePanel";
+import { NotificationToasts } from "./components/NotificationToasts";
 
 // Types
 interface RiskCalculation {
@@ -20,6 +21,54 @@ interface RiskCalculation {
   potential_loss: number;
   potential_profit: number;
   risk_reward_ratio: number;
+  leverage: number;
+  position_value: number;
+  required_margin: number;
+  maintenance_margin_rate: number;
+  liquidation_price: number | null;
+  estimated_fees: number;
+  estimated_funding: number;
+  fee_adjusted_risk: number;
+  fee_adjusted_risk_reward: number;
+  liquidation_before_stop: boolean;
+  balance_limited: boolean;
+  sizing_model: string;
+  derivation: Array<{ label: string; value: number }>;
+}
+
+type SizingModelName =
+  | "fixed_fractional"
+  | "volatility_target"
+  | "kelly"
+  | "fixed_notional"
+  | "fixed_contracts"
+  | "equity_curve";
+
+// Default parameter for each sizing model (shown in the single parameter input)
+const sizingDefaults: Record<SizingModelName, number> = {
+  fixed_fractional: 0,
+  volatility_target: 2,
+  kelly: 0.25,
+  fixed_notional: 1000,
+  fixed_contracts: 1,
+  equity_curve: 25,
+};
+
+function buildSizingModel(model: SizingModelName, param: number) {
+  switch (model) {
+    case "volatility_target":
+      return { model, interval: "60", period: 14, multiple: param };
+    case "kelly":
+      return { model, fraction: param };
+    case "fixed_notional":
+      return { model, notional: param };
+    case "fixed_contracts":
+      return { model, contracts: param };
+    case "equity_curve":
+      return { model, reduction_pct: param, min_factor: 0.25 };
+    default:
+      return { model };
+  }
 }
 
 interface ModuleInfo {
@@ -28,6 +77,8 @@ interface ModuleInfo {
   description: string;
   is_active: boolean;
   can_trade: boolean;
+  ui_only: boolean;
+  needs_confirmation: boolean;
 }
 
 interface ConnectionState {
@@ -63,6 +114,7 @@ function App() {
   const { t } = useTranslation();
   const [version, setVersion] = useState("");
   const [modules, setModules] = useState<ModuleInfo[]>([]);
+  const [moduleError, setModuleError] = useState<{ moduleId: string; message: string } | null>(null);
   const [showApiSettings, setShowApiSettings] = useState(false);
   const [connectionState, setConnectionState] = useState<ConnectionState>({
     is_connected: false,
@@ -80,6 +132,9 @@ function App() {
   const [entryPrice, setEntryPrice] = useState(100);
   const [stopPrice, setStopPrice] = useState(95);
   const [takeProfitPrice, setTakeProfitPrice] = useState(110);
+  const [leverage, setLeverage] = useState(1);
+  const [sizingModel, setSizingModel] = useState<SizingModelName>("fixed_fractional");
+  const [sizingParam, setSizingParam] = useState(0);
   const [riskResult, setRiskResult] = useState<RiskCalculation | null>(null);
 
   // Active tab for right panel
@@ -154,13 +209,22 @@ function App() {
         entry_price: entryPrice,
         stop_price: stopPrice,
         take_profit_price: takeProfitPrice,
+        category: selectedCategory,
+        symbol: selectedSymbol,
+        leverage,
+        sizing: buildSizingModel(sizingModel, sizingParam),
       },
     });
     setRiskResult(result);
   }
 
   async function toggleModule(moduleId: string, active: boolean) {
-    await invoke("toggle_module", { moduleId, active });
+    setModuleError(null);
+    try {
+      await invoke("toggle_module", { moduleId, active });
+    } catch (e) {
+      setModuleError({ moduleId, message: String(e) });
+    }
     const updatedModules = await invoke<ModuleInfo[]>("list_modules");
     setModules(updatedModules);
   }
@@ -189,6 +253,7 @@ function App() {
   return (
     <ColorProvider>
     <div className="h-screen bg-dark-950 text-white flex flex-col overflow-hidden">
+      <NotificationToasts />
       {/* Header */}
       <header className="bg-dark-900 border-b border-dark-700 px-6 py-3">
         <div className="flex items-center justify-between">
@@ -402,6 +467,16 @@ function App() {
                               {t("moduleStore.tradingAccess")}
                             </span>
                           )}
+                          {module.ui_only && (
+                            <span className="inline-block mt-2 text-xs px-2 py-0.5 bg-dark-700 text-dark-300 rounded">
+                              {t("moduleStore.uiOnly")}
+                            </span>
+                          )}
+                          {module.needs_confirmation && (
+                            <span className="inline-block mt-2 ml-1 text-xs px-2 py-0.5 bg-amber-900/30 text-amber-400 rounded">
+                              {t("moduleStore.needsConfirmation")}
+                            </span>
+                          )}
                         </div>
                         <button
                           onClick={() => toggleModule(module.id, !module.is_active)}
@@ -415,6 +490,12 @@ function App() {
                         </button>
                       </div>
 
+                      {moduleError?.moduleId === module.id && (
+                        <div className="mt-2 p-2 bg-danger-900/20 border border-danger-700 rounded-lg text-danger-400 text-xs">
+                          {moduleError.message}
+                        </div>
+                      )}
+
                       {/* VWAP Settings - show when vwap_analyzer is active */}
                       {module.id === "vwap_analyzer" && (
                         <VwapSettings
@@ -520,6 +601,53 @@ function App() {
                       className="w-full bg-dark-800 border border-dark-600 rounded-lg px-3 py-2 text-sm text-white focus:outline-none focus:border-primary-500"
                     />
                   </div>
+                  <div>
+                    <label className="block text-xs text-dark-400 mb-1">
+                      {t("riskCalculator.leverage")} (x)
+                    </label>
+                    <input
+                      type="number"
+                      step="1"
+                      min="1"
+                      value={leverage}
+                      onChange={(e) => setLeverage(Number(e.target.value))}
+                      className="w-full bg-dark-800 border border-dark-600 rounded-lg px-3 py-2 text-sm text-white focus:outline-none focus:border-primary-500"
+                    />
+                  </div>
+                  <div>
+                    <label className="block text-xs text-dark-400 mb-1">
+                      {t("riskCalculator.sizingModel")}
+                    </label>
+                    <select
+                      value={sizingModel}
+                      onChange={(e) => {
+                        const model = e.target.value as SizingModelName;
+                        setSizingModel(model);
+                        setSizingParam(sizingDefaults[model]);
+                      }}
+                      className="w-full bg-dark-800 border border-dark-600 rounded-lg px-3 py-2 text-sm text-white focus:outline-none focus:border-primary-500"
+                    >
+                      {(Object.keys(sizingDefaults) as SizingModelName[]).map((model) => (
+                        <option key={model} value={model}>
+                          {t(`riskCalculator.sizingModels.${model}.name`)}
+                        </option>
+                      ))}
+                    </select>
+                  </div>
+                  {sizingModel !== "fixed_fractional" && (
+                    <div>
+                      <label className="block text-xs text-dark-400 mb-1">
+                        {t(`riskCalculator.sizingModels.${sizingModel}.param`)}
+                      </label>
+                      <input
+                        type="number"
+                        step="0.01"
+                        value={sizingParam}
+                        onChange={(e) => setSizingParam(Number(e.target.value))}
+                        className="w-full bg-dark-800 border border-dark-600 rounded-lg px-3 py-2 text-sm text-white focus:outline-none focus:border-primary-500"
+                      />
+                    </div>
+                  )}
                 </div>
 
                 <button
@@ -538,7 +666,8 @@ function App() {
                       <div className="flex justify-between">
                         <span className="text-dark-400">{t("riskCalculator.results.positionSize")}:</span>
                         <span className="text-white font-medium">
-                          {riskResult.position_size.toFixed(4)} {t("riskCalculator.results.units")}
+                          {riskResult.position_size.toFixed(4)}{" "}
+                          {t(selectedCategory === "inverse" ? "riskCalculator.results.contracts" : "riskCalculator.results.units")}
                         </span>
                       </div>
                       <div className="flex justify-between">
@@ -569,6 +698,61 @@ function App() {
                           1:{riskResult.risk_reward_ratio.toFixed(2)}
                         </span>
                       </div>
+                      <div className="flex justify-between pt-2 border-t border-dark-700">
+                        <span className="text-dark-400">{t("riskCalculator.results.requiredMargin")}:</span>
+                        <span className="text-white font-medium">
+                          ${riskResult.required_margin.toFixed(2)}
+                        </span>
+                      </div>
+                      <div className="flex justify-between">
+                        <span className="text-dark-400">{t("riskCalculator.results.liquidationPrice")}:</span>
+                        <span
+                          className={`font-medium ${
+                            riskResult.liquidation_before_stop ? "text-danger-400" : "text-white"
+                          }`}
+                        >
+                          {riskResult.liquidation_price !== null ? `$${riskResult.liquidation_price.toFixed(2)}` : "-"}
+                        </span>
+                      </div>
+                      <div className="flex justify-between">
+                        <span className="text-dark-400">{t("riskCalculator.results.estimatedFees")}:</span>
+                        <span className="text-danger-400 font-medium">
+                          ${(riskResult.estimated_fees + riskResult.estimated_funding).toFixed(2)}
+                        </span>
+                      </div>
+                      <div className="flex justify-between">
+                        <span className="text-dark-400">{t("riskCalculator.results.feeAdjustedRisk")}:</span>
+                        <span className="text-danger-400 font-medium">
+                          ${riskResult.fee_adjusted_risk.toFixed(2)}
+                        </span>
+                      </div>
+                      <div className="flex justify-between">
+                        <span className="text-dark-400">{t("riskCalculator.results.feeAdjustedRiskReward")}:</span>
+                        <span className="text-white font-medium">
+                          1:{riskResult.fee_adjusted_risk_reward.toFixed(2)}
+                        </span>
+                      </div>
+                      {riskResult.derivation.length > 0 && (
+                        <div className="pt-2 border-t border-dark-700 space-y-1">
+                          <div className="text-dark-400">{t("riskCalculator.results.derivation")}:</div>
+                          {riskResult.derivation.map((step, i) => (
+                            <div key={i} className="flex justify-between text-dark-300">
+                              <span>{step.label}</span>
+                              <span>{Number(step.value.toFixed(4))}</span>
+                            </div>
+                          ))}
+                        </div>
+                      )}
+                      {riskResult.balance_limited && (
+                        <div className="pt-2 text-yellow-400">
+                          {t("riskCalculator.results.balanceLimited")}
+                        </div>
+                      )}
+                      {riskResult.liquidation_before_stop && (
+                        <div className="pt-2 text-danger-400">
+                          {t("riskCalculator.results.liquidationBeforeStop")}
+                        </div>
+                      )}
                     </div>
                   </div>
                 )}
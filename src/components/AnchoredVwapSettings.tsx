@@ -338,12 +338,13 @@ export function calculateAnchoredVwap(
 
   const values: AnchoredVwapDataPoint[] = [];
   let sumTPV = 0;  // Cumulative sum of (Typical Price * Volume)
+  let sumTP2V = 0; // Cumulative sum of (Typical Price^2 * Volume)
   let sumVolume = 0;
-  let sumSquaredDiff = 0;
 
   for (const kline of relevantKlines) {
     const typicalPrice = (kline.high + kline.low + kline.close) / 3;
     sumTPV += typicalPrice * kline.volume;
+    sumTP2V += typicalPrice * typicalPrice * kline.volume;
     sumVolume += kline.volume;
 
     if (sumVolume === 0) continue;
@@ -354,8 +355,8 @@ export function calculateAnchoredVwap(
     let lowerBand: number | undefined;
 
     if (config.showBands) {
-      sumSquaredDiff += Math.pow(typicalPrice - vwap, 2) * kline.volume;
-      const variance = sumSquaredDiff / sumVolume;
+      // Volume-weighted variance, same formula as the backend indicators (indicators/vwap.rs)
+      const variance = Math.max(sumTP2V / sumVolume - vwap * vwap, 0);
       const stdDev = Math.sqrt(variance);
       upperBand = vwap + stdDev * config.bandMultiplier;
       lowerBand = vwap - stdDev * config.bandMultiplier;
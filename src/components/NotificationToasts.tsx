@@ -0,0 +1,85 @@
+import { useEffect, useState } from "react";
+import { listen } from "@tauri-apps/api/event";
+
+// Backend "notification" event payload (models::Notification)
+export interface AppNotification {
+  kind: "alarm" | "risk_warning";
+  source_id: string | null;
+  symbol: string | null;
+  title: string;
+  message: string;
+  sound: boolean;
+  created_at: string;
+}
+
+interface Toast extends AppNotification {
+  key: number;
+}
+
+const TOAST_DURATION_MS = 8000;
+const MAX_TOASTS = 5;
+
+// Short two-tone beep for the Sound channel
+function playAlertSound() {
+  try {
+    const ctx = new AudioContext();
+    [880, 660].forEach((frequency, i) => {
+      const osc = ctx.createOscillator();
+      const gain = ctx.createGain();
+      osc.frequency.value = frequency;
+      gain.gain.value = 0.15;
+      osc.connect(gain);
+      gain.connect(ctx.destination);
+      osc.start(ctx.currentTime + i * 0.18);
+      osc.stop(ctx.currentTime + i * 0.18 + 0.15);
+    });
+    setTimeout(() => ctx.close(), 600);
+  } catch (e) {
+    console.error("Alert sound failed:", e);
+  }
+}
+
+export function NotificationToasts() {
+  const [toasts, setToasts] = useState<Toast[]>([]);
+
+  useEffect(() => {
+    let counter = 0;
+    const unlisten = listen<AppNotification>("notification", (event) => {
+      const toast = { ...event.payload, key: ++counter };
+      if (toast.sound) playAlertSound();
+      setToasts((prev) => [...prev, toast].slice(-MAX_TOASTS));
+      setTimeout(() => {
+        setToasts((prev) => prev.filter((t) => t.key !== toast.key));
+      }, TOAST_DURATION_MS);
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  if (toasts.length === 0) return null;
+
+  return (
+    <div className="fixed bottom-4 right-4 z-50 flex flex-col gap-2 w-80">
+      {toasts.map((toast) => (
+        <div
+          key={toast.key}
+          className={`rounded-lg border px-4 py-3 shadow-lg bg-dark-900 ${
+            toast.kind === "risk_warning" ? "border-red-500" : "border-primary-500"
+          }`}
+        >
+          <div className="flex items-start justify-between gap-2">
+            <span className="text-sm font-semibold text-white">{toast.title}</span>
+            <button
+              onClick={() => setToasts((prev) => prev.filter((t) => t.key !== toast.key))}
+              className="text-dark-400 hover:text-white text-xs"
+            >
+              ✕
+            </button>
+          </div>
+          <p className="text-xs text-dark-300 mt-1">{toast.message}</p>
+        </div>
+      ))}
+    </div>
+  );
+}